@@ -37,6 +37,7 @@ pub use crate::gpyrpc::*;
 use crate::service::capi::{kclvm_service_call_with_length, kclvm_service_new};
 use crate::service::service_impl::KclvmServiceImpl;
 use anyhow::Result;
+pub use kclvm_runner::plugin::{NativePlugin, PluginRegistry};
 use std::ffi::{c_char, CString};
 
 pub type API = KclvmServiceImpl;
@@ -22,7 +22,10 @@ fn c_char_to_vec(args: *const c_char, args_len: usize) -> Vec<u8> {
 /// Create an instance of kclvm_service and return its pointer
 #[no_mangle]
 pub extern "C-unwind" fn kclvm_service_new(plugin_agent: u64) -> *mut kclvm_service {
-    let serv = kclvm_service { plugin_agent };
+    let serv = kclvm_service {
+        plugin_agent,
+        ..Default::default()
+    };
     Box::into_raw(Box::new(serv))
 }
 
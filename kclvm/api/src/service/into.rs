@@ -1,11 +1,12 @@
 use crate::gpyrpc::{
-    CliConfig, Error, KeyValuePair, LoadSettingsFilesResult, Message, Position, Scope, ScopeIndex,
-    Symbol, SymbolIndex,
+    CliConfig, Error, ExecError, KeyValuePair, LoadSettingsFilesResult, Message, Position,
+    RelatedInfo, Scope, ScopeIndex, SourceRange, StackFrame, Symbol, SymbolIndex,
 };
 use crate::service::ty::kcl_ty_to_pb_ty;
 use kclvm_config::settings::SettingsFile;
 use kclvm_error::Diagnostic;
 use kclvm_loader::{ScopeInfo, SymbolInfo};
+use kclvm_runner::error;
 use kclvm_sema::core::{scope::ScopeRef, symbol::SymbolRef};
 
 pub(crate) trait IntoLoadSettingsFiles {
@@ -17,6 +18,10 @@ pub(crate) trait IntoError {
     fn into_error(self) -> Error;
 }
 
+pub(crate) trait IntoExecError {
+    fn into_exec_error(self) -> ExecError;
+}
+
 pub(crate) trait IntoSymbolIndex {
     fn into_symbol_index(self) -> SymbolIndex;
 }
@@ -90,6 +95,48 @@ impl IntoError for Diagnostic {
     }
 }
 
+impl IntoExecError for error::ExecError {
+    fn into_exec_error(self) -> ExecError {
+        ExecError {
+            kind: self.kind,
+            code: self.code,
+            message: self.message,
+            range: self.range.map(into_source_range),
+            related: self
+                .related
+                .into_iter()
+                .map(|r| RelatedInfo {
+                    message: r.message,
+                    range: Some(into_source_range(r.range)),
+                })
+                .collect(),
+            backtrace: self
+                .backtrace
+                .into_iter()
+                .map(|f| StackFrame {
+                    func: f.func,
+                    pos: Some(into_position(f.pos)),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn into_source_range(range: error::SourceRange) -> SourceRange {
+    SourceRange {
+        start: Some(into_position(range.start)),
+        end: Some(into_position(range.end)),
+    }
+}
+
+fn into_position(pos: error::SourcePos) -> Position {
+    Position {
+        filename: pos.filename,
+        line: pos.line as i64,
+        column: pos.column as i64,
+    }
+}
+
 impl IntoSymbolIndex for SymbolRef {
     fn into_symbol_index(self) -> SymbolIndex {
         let (index, generation) = self.get_id().into_raw_parts();
@@ -22,6 +22,7 @@ use kclvm_query::query::{get_full_schema_type, get_full_schema_type_under_path};
 use kclvm_query::selector::{list_variables, ListOptions};
 use kclvm_query::GetSchemaOption;
 use kclvm_runner::exec_program;
+use kclvm_runner::plugin::{NativePlugin, PluginRegistry};
 #[cfg(feature = "llvm")]
 use kclvm_runner::{build_program, exec_artifact};
 use kclvm_sema::core::global_state::GlobalState;
@@ -44,6 +45,8 @@ use super::util::{transform_exec_para, transform_str_para};
 #[derive(Debug, Clone, Default)]
 pub struct KclvmServiceImpl {
     pub plugin_agent: u64,
+    /// The native plugins registered by the embedding application.
+    pub plugins: PluginRegistry,
 }
 
 impl From<&kclvm_query::selector::Variable> for Variable {
@@ -66,6 +69,28 @@ impl From<&kclvm_query::selector::Variable> for Variable {
 }
 
 impl KclvmServiceImpl {
+    /// Register the native plugin which can be imported by `import kcl_plugin.<name>`
+    /// in the executed programs, the plugin calls are type-checked by the declared
+    /// function signatures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_api::service::service_impl::KclvmServiceImpl;
+    /// use kclvm_api::NativePlugin;
+    /// let mut serv = KclvmServiceImpl::default();
+    /// serv.register_plugin(NativePlugin::new("hello").function(
+    ///     "say_hello",
+    ///     &[("name", "str")],
+    ///     "str",
+    ///     |_, args, _| Ok(format!("hello {}", args.arg_i_str(0, None).unwrap_or_default()).into()),
+    /// ));
+    /// ```
+    pub fn register_plugin(&mut self, plugin: NativePlugin) -> &mut Self {
+        self.plugins.register(plugin);
+        self
+    }
+
     /// Ping KclvmService, return the same value as the parameter
     ///
     /// # Examples
@@ -253,6 +278,7 @@ impl KclvmServiceImpl {
                     k_code_list: parse_args.sources.clone(),
                     package_maps,
                     load_plugins: true,
+                    plugin_decl_codes: self.plugins.decl_codes(),
                     ..Default::default()
                 }),
                 resolve_ast: args.resolve_ast,
@@ -480,7 +506,7 @@ impl KclvmServiceImpl {
     /// ```
     pub fn exec_program(&self, args: &ExecProgramArgs) -> anyhow::Result<ExecProgramResult> {
         // transform args to json
-        let exec_args = transform_exec_para(&Some(args.clone()), self.plugin_agent)?;
+        let exec_args = transform_exec_para(&Some(args.clone()), self.plugin_agent, &self.plugins)?;
         let sess = ParseSessionRef::default();
         let result = exec_program(sess, &exec_args)?;
 
@@ -489,6 +515,12 @@ impl KclvmServiceImpl {
             yaml_result: result.yaml_result,
             log_message: result.log_message,
             err_message: result.err_message,
+            provenance_result: result.provenance_result,
+            errors: result
+                .errors
+                .into_iter()
+                .map(|e| e.into_exec_error())
+                .collect(),
         })
     }
 
@@ -515,7 +547,7 @@ impl KclvmServiceImpl {
     /// ```
     #[cfg(feature = "llvm")]
     pub fn build_program(&self, args: &BuildProgramArgs) -> anyhow::Result<BuildProgramResult> {
-        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent, &self.plugins)?;
         let artifact = build_program(
             ParseSessionRef::default(),
             &exec_args,
@@ -557,13 +589,19 @@ impl KclvmServiceImpl {
     /// ```
     #[cfg(feature = "llvm")]
     pub fn exec_artifact(&self, args: &ExecArtifactArgs) -> anyhow::Result<ExecProgramResult> {
-        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent, &self.plugins)?;
         let result = exec_artifact(&args.path, &exec_args)?;
         Ok(ExecProgramResult {
             json_result: result.json_result,
             yaml_result: result.yaml_result,
             log_message: result.log_message,
             err_message: result.err_message,
+            provenance_result: result.provenance_result,
+            errors: result
+                .errors
+                .into_iter()
+                .map(|e| e.into_exec_error())
+                .collect(),
         })
     }
 
@@ -645,7 +683,7 @@ impl KclvmServiceImpl {
         args: &GetSchemaTypeMappingArgs,
     ) -> anyhow::Result<GetSchemaTypeMappingResult> {
         let mut type_mapping = HashMap::new();
-        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent, &self.plugins)?;
         for (k, schema_ty) in get_full_schema_type(
             Some(&args.schema_name),
             CompilationOptions {
@@ -709,7 +747,7 @@ impl KclvmServiceImpl {
         args: &GetSchemaTypeMappingArgs,
     ) -> anyhow::Result<GetSchemaTypeMappingUnderPathResult> {
         let mut type_mapping = HashMap::new();
-        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent, &self.plugins)?;
         for (k, schema_tys) in get_full_schema_type_under_path(
             Some(&args.schema_name),
             CompilationOptions {
@@ -1057,7 +1095,7 @@ impl KclvmServiceImpl {
     /// ```
     pub fn test(&self, args: &TestArgs) -> anyhow::Result<TestResult> {
         let mut result = TestResult::default();
-        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent, &self.plugins)?;
         let opts = testing::TestOptions {
             exec_args,
             run_regexp: args.run_regexp.clone(),
@@ -1,6 +1,7 @@
 use crate::gpyrpc::{Decorator, Example, KclType};
+use kclvm_ast::ast::TypeConstraint;
 use kclvm_primitives::IndexSet;
-use kclvm_sema::ty::{DictType, SchemaType, Type};
+use kclvm_sema::ty::{range_bound_value, DictType, SchemaType, Type};
 use std::collections::HashMap;
 
 /// Convert the kcl sematic type to the kcl protobuf type.
@@ -23,10 +24,34 @@ pub(crate) fn kcl_ty_to_pb_ty(ty: &Type) -> KclType {
             ..Default::default()
         },
         kclvm_sema::ty::TypeKind::Schema(schema_ty) => kcl_schema_ty_to_pb_ty(schema_ty),
-        _ => KclType {
-            r#type: ty.ty_str(),
+        kclvm_sema::ty::TypeKind::Enum(enum_ty) => KclType {
+            r#type: "enum".to_string(),
+            enum_name: enum_ty.name.clone(),
+            enum_values: enum_ty
+                .members
+                .values()
+                .map(|member| member.ty.into_type_annotation_str())
+                .collect(),
+            pkg_path: enum_ty.pkgpath.clone(),
+            description: enum_ty.doc.clone(),
             ..Default::default()
         },
+        _ => {
+            let mut pb_ty = KclType {
+                r#type: ty.kind_ty_str(),
+                ..Default::default()
+            };
+            // Export the value constraint of the refined type.
+            match &ty.constraint {
+                Some(TypeConstraint::Range(range)) => {
+                    pb_ty.minimum = range.min.as_ref().map(range_bound_value);
+                    pb_ty.maximum = range.max.as_ref().map(range_bound_value);
+                }
+                Some(TypeConstraint::Pattern(pattern)) => pb_ty.pattern = pattern.clone(),
+                None => {}
+            }
+            pb_ty
+        }
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::gpyrpc::ExecProgramArgs;
+use kclvm_runner::plugin::PluginRegistry;
 
 /// Transform the str with zero value into [`Option<String>`]
 #[inline]
@@ -14,6 +15,7 @@ pub(crate) fn transform_str_para(para: &str) -> Option<String> {
 pub(crate) fn transform_exec_para(
     exec_args: &Option<ExecProgramArgs>,
     plugin_agent: u64,
+    plugins: &PluginRegistry,
 ) -> anyhow::Result<kclvm_runner::ExecProgramArgs> {
     let mut args = match exec_args {
         Some(exec_args) => {
@@ -23,5 +25,6 @@ pub(crate) fn transform_exec_para(
         None => kclvm_runner::ExecProgramArgs::default(),
     };
     args.plugin_agent = plugin_agent;
+    args.plugins = plugins.clone();
     Ok(args)
 }
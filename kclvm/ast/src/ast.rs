@@ -36,7 +36,7 @@
 use kclvm_utils::path::PathPrefix;
 use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
@@ -416,6 +416,9 @@ pub struct Program {
     pub pkgs_not_imported: HashMap<String, Vec<String>>,
     pub modules: HashMap<String, Arc<RwLock<Module>>>,
     pub modules_not_imported: HashMap<String, Arc<RwLock<Module>>>,
+    /// Plugin pkgpath e.g., `kcl_plugin.hello` and its declaration module file names,
+    /// the declaration modules are stored in `modules`.
+    pub plugin_decls: HashMap<String, Vec<String>>,
 }
 
 impl Program {
@@ -498,6 +501,7 @@ impl Program {
             .pkgs
             .get(pkg_name)
             .or(self.pkgs_not_imported.get(pkg_name))
+            .or(self.plugin_decls.get(pkg_name))
         {
             for module_name in module_names {
                 if let Some(module) = self.get_module_ref(module_name) {
@@ -507,6 +511,24 @@ impl Program {
         }
         result
     }
+
+    /// Get the names in the export statements of the package, `None` denotes
+    /// the package has no export statements and all the members are exported.
+    pub fn get_pkg_exports(&self, pkgpath: &str) -> Option<HashSet<String>> {
+        let mut exports: Option<HashSet<String>> = None;
+        for module in self.get_modules_for_pkg(pkgpath) {
+            if let Ok(module) = module.read() {
+                for stmt in &module.body {
+                    if let Stmt::Export(export_stmt) = &stmt.node {
+                        exports
+                            .get_or_insert_with(HashSet::new)
+                            .extend(export_stmt.names.iter().map(|name| name.node.clone()));
+                    }
+                }
+            }
+        }
+        exports
+    }
 }
 
 /// Module is an abstract syntax tree for a single KCL file.
@@ -560,6 +582,8 @@ pub enum Stmt {
     SchemaAttr(SchemaAttr),
     Schema(SchemaStmt),
     Rule(RuleStmt),
+    Enum(EnumStmt),
+    Export(ExportStmt),
 }
 
 /// TypeAliasStmt represents a type alias statement, e.g.
@@ -672,6 +696,17 @@ pub struct ImportStmt {
     pub pkg_name: String,
 }
 
+/// ExportStmt represents an export list of the package members which can be
+/// accessed by other packages, all the members are exported when the package
+/// has no export statements, e.g.
+/// ```kcl
+/// export Person, make_person
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExportStmt {
+    pub names: Vec<Node<String>>,
+}
+
 /// SchemaStmt, e.g.
 /// ```kcl
 /// schema BaseSchema:
@@ -687,11 +722,15 @@ pub struct ImportStmt {
 ///
 /// protocol ProtocolExample:
 ///     attr: int
+///
+/// schema GenericExample[T][]:
+///     items: [T]
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct SchemaStmt {
     pub doc: Option<NodeRef<String>>,
     pub name: NodeRef<String>,
+    pub type_params: Vec<NodeRef<String>>,
     pub parent_name: Option<NodeRef<Identifier>>,
     pub for_host_name: Option<NodeRef<Identifier>>,
     pub is_mixin: bool,
@@ -787,6 +826,7 @@ pub struct SchemaIndexSignature {
 /// schema SchemaAttrExample:
 ///      x: int
 ///      y: str
+///      final z: str = y + "-z"
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct SchemaAttr {
@@ -795,6 +835,9 @@ pub struct SchemaAttr {
     pub op: Option<AugOp>,
     pub value: Option<NodeRef<Expr>>,
     pub is_optional: bool,
+    /// Final attributes are computed by the schema and can't be overridden
+    /// by the configs and the sub schemas.
+    pub is_final: bool,
     pub decorators: Vec<NodeRef<CallExpr>>,
 
     pub ty: NodeRef<Type>,
@@ -824,6 +867,36 @@ pub struct RuleStmt {
     pub for_host_name: Option<NodeRef<Identifier>>,
 }
 
+/// EnumStmt represents a named set of literal members, e.g.
+/// ```kcl
+/// enum Protocol:
+///     """The network protocol."""
+///     TCP = "tcp"
+///     """Transmission control protocol."""
+///     UDP = "udp"
+///     """User datagram protocol."""
+///     SCTP
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EnumStmt {
+    pub doc: Option<NodeRef<String>>,
+    pub name: NodeRef<String>,
+    pub members: Vec<NodeRef<EnumMember>>,
+}
+
+/// EnumMember represents a member of the enum statement, the member
+/// value is the member name string when the value is omitted, e.g.
+/// ```kcl
+/// TCP = "tcp"
+/// """Transmission control protocol."""
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EnumMember {
+    pub name: NodeRef<String>,
+    pub value: Option<NodeRef<Expr>>,
+    pub doc: Option<NodeRef<String>>,
+}
+
 /// A expression
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
@@ -848,6 +921,7 @@ pub enum Expr {
     Config(ConfigExpr),
     Check(CheckExpr),
     Lambda(LambdaExpr),
+    Match(MatchExpr),
     Subscript(Subscript),
     Keyword(Keyword),
     Arguments(Arguments),
@@ -884,6 +958,7 @@ impl Expr {
             Expr::Config(_) => "ConfigExpression",
             Expr::Check(_) => "CheckExpression",
             Expr::Lambda(_) => "LambdaExpression",
+            Expr::Match(_) => "MatchExpression",
             Expr::Subscript(_) => "SubscriptExpression",
             Expr::Keyword(_) => "KeywordExpression",
             Expr::Arguments(_) => "ArgumentsExpression",
@@ -1247,14 +1322,180 @@ pub struct CheckExpr {
 ///     z = 2 * x
 ///     z + y
 /// }
+/// lambda[T] x: T -> T {
+///     x
+/// }
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct LambdaExpr {
+    pub type_params: Vec<NodeRef<String>>,
     pub args: Option<NodeRef<Arguments>>,
     pub body: Vec<NodeRef<Stmt>>,
     pub return_ty: Option<NodeRef<Type>>,
 }
 
+/// MatchExpr, e.g.
+/// ```kcl
+/// match value {
+///     "tcp" | "udp": "network"
+///     Person {name: name} if name: name
+///     [first, *rest]: first
+///     {"kind": kind, **rest}: kind
+///     _: "unknown"
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MatchExpr {
+    pub subject: NodeRef<Expr>,
+    pub cases: Vec<NodeRef<MatchCase>>,
+}
+
+/// MatchCase, e.g.
+/// ```kcl
+/// [first, *rest] if first > 0: first
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MatchCase {
+    pub pattern: NodeRef<Pattern>,
+    pub guard: Option<NodeRef<Expr>>,
+    pub body: NodeRef<Expr>,
+}
+
+/// Pattern in the match case, e.g.
+/// ```kcl
+/// _
+/// "tcp"
+/// Protocol.TCP
+/// name
+/// Person {name: name}
+/// [first, *rest]
+/// {"kind": kind, **rest}
+/// "tcp" | "udp"
+/// [_, _] as pair
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "value")]
+pub enum Pattern {
+    /// The wildcard pattern `_` matches any value.
+    Wildcard,
+    /// The value pattern matches a literal or a dotted name value e.g., `"tcp"`, `-1` and `Protocol.TCP`.
+    Value(NodeRef<Expr>),
+    /// The capture pattern matches any value and binds it to the name.
+    Capture(NodeRef<Identifier>),
+    /// The schema pattern matches the schema instance and its attributes e.g., `Person {name: name}`.
+    Schema(SchemaPattern),
+    /// The list pattern matches the list and its items e.g., `[first, *rest]`.
+    List(ListPattern),
+    /// The dict pattern matches the dict and its entries e.g., `{"kind": kind, **rest}`.
+    Dict(DictPattern),
+    /// The or pattern matches if any alternative matches e.g., `"tcp" | "udp"`.
+    Or(Vec<NodeRef<Pattern>>),
+    /// The as pattern binds the value matched by the pattern to the name e.g., `[_, _] as pair`.
+    As(AsPattern),
+}
+
+impl Pattern {
+    /// Get all the capture variable names bound by the pattern.
+    pub fn get_capture_names(&self) -> Vec<String> {
+        let mut names = vec![];
+        self.collect_capture_names(&mut names);
+        names
+    }
+
+    fn collect_capture_names(&self, names: &mut Vec<String>) {
+        let capture_names = match self {
+            Pattern::Wildcard | Pattern::Value(_) => vec![],
+            Pattern::Capture(name) => vec![name],
+            Pattern::Schema(schema_pattern) => {
+                for entry in &schema_pattern.entries {
+                    entry.node.value.node.collect_capture_names(names);
+                }
+                vec![]
+            }
+            Pattern::List(list_pattern) => {
+                for elt in &list_pattern.elts {
+                    elt.node.collect_capture_names(names);
+                }
+                list_pattern.rest.iter().collect()
+            }
+            Pattern::Dict(dict_pattern) => {
+                for entry in &dict_pattern.entries {
+                    entry.node.value.node.collect_capture_names(names);
+                }
+                dict_pattern.rest.iter().collect()
+            }
+            Pattern::Or(patterns) => {
+                for pattern in patterns {
+                    pattern.node.collect_capture_names(names);
+                }
+                vec![]
+            }
+            Pattern::As(as_pattern) => {
+                as_pattern.pattern.node.collect_capture_names(names);
+                vec![&as_pattern.name]
+            }
+        };
+        for name in capture_names {
+            let name = name.node.get_name();
+            if name != "_" {
+                names.push(name);
+            }
+        }
+    }
+}
+
+/// SchemaPattern, e.g.
+/// ```kcl
+/// Person {name: name, age: _}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SchemaPattern {
+    pub name: NodeRef<Identifier>,
+    pub entries: Vec<NodeRef<PatternEntry>>,
+}
+
+/// ListPattern, e.g.
+/// ```kcl
+/// [first, second, *rest]
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ListPattern {
+    pub elts: Vec<NodeRef<Pattern>>,
+    /// The rest items binding e.g., `rest` in `*rest`, `*_` denotes the rest items are ignored.
+    pub rest: Option<NodeRef<Identifier>>,
+}
+
+/// DictPattern, e.g.
+/// ```kcl
+/// {"kind": kind, name: "app", **rest}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DictPattern {
+    pub entries: Vec<NodeRef<PatternEntry>>,
+    /// The rest entries binding e.g., `rest` in `**rest`.
+    pub rest: Option<NodeRef<Identifier>>,
+}
+
+/// PatternEntry denotes the key and value pattern in the dict and schema pattern, e.g.
+/// ```kcl
+/// name: "app"
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PatternEntry {
+    pub key: NodeRef<String>,
+    pub value: NodeRef<Pattern>,
+}
+
+/// AsPattern, e.g.
+/// ```kcl
+/// Person {} as person
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AsPattern {
+    pub pattern: NodeRef<Pattern>,
+    pub name: NodeRef<Identifier>,
+}
+
 /// Subscript, e.g.
 /// ```kcl
 /// a[0]
@@ -1831,6 +2072,8 @@ pub enum Type {
     Union(UnionType),
     Literal(LiteralType),
     Function(FunctionType),
+    Generic(GenericType),
+    Refined(RefinedType),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -1839,6 +2082,71 @@ pub struct FunctionType {
     pub ret_ty: Option<NodeRef<Type>>,
 }
 
+/// GenericType denotes an instantiation of a generic schema, e.g.
+/// ```kcl
+/// page: Page[int]
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GenericType {
+    pub name: Identifier,
+    pub type_args: Vec<NodeRef<Type>>,
+}
+
+/// RefinedType denotes a basic type refined by a value constraint, e.g.
+/// ```kcl
+/// port: int(1..65535)
+/// name: str(r"^[a-z0-9-]+$")
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RefinedType {
+    pub base: BasicType,
+    pub constraint: TypeConstraint,
+}
+
+/// TypeConstraint denotes the value constraint of the refined type.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "value")]
+pub enum TypeConstraint {
+    /// The inclusive number range e.g., `1..65535`, `1..` and `..65535`.
+    Range(RangeConstraint),
+    /// The regular expression pattern of the string value.
+    Pattern(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RangeConstraint {
+    pub min: Option<NumberLitValue>,
+    pub max: Option<NumberLitValue>,
+}
+
+impl ToString for TypeConstraint {
+    /// The constraint string is also used by the runtime type checking,
+    /// e.g., `1..65535` and `"^[a-z0-9-]+$"`.
+    fn to_string(&self) -> String {
+        fn number_str(value: &Option<NumberLitValue>) -> String {
+            match value {
+                Some(NumberLitValue::Int(v)) => v.to_string(),
+                Some(NumberLitValue::Float(v)) => {
+                    let mut float_str = v.to_string();
+                    if !float_str.contains('.') {
+                        float_str.push_str(".0");
+                    }
+                    float_str
+                }
+                None => "".to_string(),
+            }
+        }
+        match self {
+            TypeConstraint::Range(range) => {
+                format!("{}..{}", number_str(&range.min), number_str(&range.max))
+            }
+            TypeConstraint::Pattern(pattern) => {
+                format!("\"{}\"", pattern.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum BasicType {
     Bool,
@@ -1967,6 +2275,23 @@ impl ToString for Type {
                         to_str(&ret.node, w);
                     }
                 }
+                Type::Generic(v) => {
+                    w.push_str(&v.name.get_name());
+                    w.push('[');
+                    for (i, arg) in v.type_args.iter().enumerate() {
+                        if i > 0 {
+                            w.push_str(", ");
+                        }
+                        to_str(&arg.node, w);
+                    }
+                    w.push(']');
+                }
+                Type::Refined(v) => {
+                    to_str(&Type::Basic(v.base.clone()), w);
+                    w.push('(');
+                    w.push_str(&v.constraint.to_string());
+                    w.push(')');
+                }
             }
         }
 
@@ -260,6 +260,7 @@ fn gen_schema_stmt(count: i32) -> Vec<NodeRef<ast::Stmt>> {
         schema_stmts.push(node_ref!(ast::Stmt::Schema(SchemaStmt {
             doc: Some(node_ref!("".to_string())),
             name: node_ref!("schema_stmt_".to_string() + &c.to_string()),
+            type_params: vec![],
             parent_name: None,
             for_host_name: None,
             is_mixin: false,
@@ -77,6 +77,8 @@ pub trait TypedResultWalker<'ctx>: Sized {
     fn walk_import_stmt(&self, import_stmt: &'ctx ast::ImportStmt) -> Self::Result;
     fn walk_schema_stmt(&self, schema_stmt: &'ctx ast::SchemaStmt) -> Self::Result;
     fn walk_rule_stmt(&self, rule_stmt: &'ctx ast::RuleStmt) -> Self::Result;
+    fn walk_enum_stmt(&self, enum_stmt: &'ctx ast::EnumStmt) -> Self::Result;
+    fn walk_export_stmt(&self, export_stmt: &'ctx ast::ExportStmt) -> Self::Result;
 
     /*
      * Expr
@@ -106,6 +108,7 @@ pub trait TypedResultWalker<'ctx>: Sized {
     fn walk_config_expr(&self, config_expr: &'ctx ast::ConfigExpr) -> Self::Result;
     fn walk_check_expr(&self, check_expr: &'ctx ast::CheckExpr) -> Self::Result;
     fn walk_lambda_expr(&self, lambda_expr: &'ctx ast::LambdaExpr) -> Self::Result;
+    fn walk_match_expr(&self, match_expr: &'ctx ast::MatchExpr) -> Self::Result;
     fn walk_keyword(&self, keyword: &'ctx ast::Keyword) -> Self::Result;
     fn walk_arguments(&self, arguments: &'ctx ast::Arguments) -> Self::Result;
     fn walk_compare(&self, compare: &'ctx ast::Compare) -> Self::Result;
@@ -153,6 +156,8 @@ pub trait MutSelfTypedResultWalker<'ctx>: Sized {
             ast::Stmt::SchemaAttr(schema_attr) => self.walk_schema_attr(schema_attr),
             ast::Stmt::Schema(schema_stmt) => self.walk_schema_stmt(schema_stmt),
             ast::Stmt::Rule(rule_stmt) => self.walk_rule_stmt(rule_stmt),
+            ast::Stmt::Enum(enum_stmt) => self.walk_enum_stmt(enum_stmt),
+            ast::Stmt::Export(export_stmt) => self.walk_export_stmt(export_stmt),
         }
     }
     fn walk_expr_stmt(&mut self, expr_stmt: &'ctx ast::ExprStmt) -> Self::Result;
@@ -168,6 +173,8 @@ pub trait MutSelfTypedResultWalker<'ctx>: Sized {
     fn walk_import_stmt(&mut self, import_stmt: &'ctx ast::ImportStmt) -> Self::Result;
     fn walk_schema_stmt(&mut self, schema_stmt: &'ctx ast::SchemaStmt) -> Self::Result;
     fn walk_rule_stmt(&mut self, rule_stmt: &'ctx ast::RuleStmt) -> Self::Result;
+    fn walk_enum_stmt(&mut self, enum_stmt: &'ctx ast::EnumStmt) -> Self::Result;
+    fn walk_export_stmt(&mut self, export_stmt: &'ctx ast::ExportStmt) -> Self::Result;
 
     /*
      * Expr
@@ -199,6 +206,7 @@ pub trait MutSelfTypedResultWalker<'ctx>: Sized {
             ast::Expr::Config(config_expr) => self.walk_config_expr(config_expr),
             ast::Expr::Check(check) => self.walk_check_expr(check),
             ast::Expr::Lambda(lambda) => self.walk_lambda_expr(lambda),
+            ast::Expr::Match(match_expr) => self.walk_match_expr(match_expr),
             ast::Expr::Subscript(subscript) => self.walk_subscript(subscript),
             ast::Expr::Keyword(keyword) => self.walk_keyword(keyword),
             ast::Expr::Arguments(arguments) => self.walk_arguments(arguments),
@@ -241,6 +249,7 @@ pub trait MutSelfTypedResultWalker<'ctx>: Sized {
     fn walk_config_expr(&mut self, config_expr: &'ctx ast::ConfigExpr) -> Self::Result;
     fn walk_check_expr(&mut self, check_expr: &'ctx ast::CheckExpr) -> Self::Result;
     fn walk_lambda_expr(&mut self, lambda_expr: &'ctx ast::LambdaExpr) -> Self::Result;
+    fn walk_match_expr(&mut self, match_expr: &'ctx ast::MatchExpr) -> Self::Result;
     fn walk_keyword(&mut self, keyword: &'ctx ast::Keyword) -> Self::Result;
     fn walk_arguments(&mut self, arguments: &'ctx ast::Arguments) -> Self::Result;
     fn walk_compare(&mut self, compare: &'ctx ast::Compare) -> Self::Result;
@@ -299,6 +308,9 @@ pub trait MutSelfMutWalker<'ctx> {
     fn walk_import_stmt(&mut self, _import_stmt: &'ctx mut ast::ImportStmt) {
         // Nothing to do
     }
+    fn walk_export_stmt(&mut self, _export_stmt: &'ctx mut ast::ExportStmt) {
+        // Nothing to do
+    }
     fn walk_schema_attr(&mut self, schema_attr: &'ctx mut ast::SchemaAttr) {
         walk_list_mut!(self, walk_call_expr, schema_attr.decorators);
         walk_if_mut!(self, walk_expr, schema_attr.value);
@@ -327,6 +339,13 @@ pub trait MutSelfMutWalker<'ctx> {
                     .iter_mut()
                     .for_each(|ty| self.walk_type(&mut ty.node));
             }
+            ast::Type::Generic(generic_ty) => {
+                self.walk_identifier(&mut generic_ty.name);
+                generic_ty
+                    .type_args
+                    .iter_mut()
+                    .for_each(|ty| self.walk_type(&mut ty.node));
+            }
             _ => {}
         }
     }
@@ -350,6 +369,11 @@ pub trait MutSelfMutWalker<'ctx> {
         walk_if_mut!(self, walk_arguments, rule_stmt.args);
         walk_if_mut!(self, walk_identifier, rule_stmt.for_host_name);
     }
+    fn walk_enum_stmt(&mut self, enum_stmt: &'ctx mut ast::EnumStmt) {
+        for member in enum_stmt.members.iter_mut() {
+            walk_if_mut!(self, walk_expr, member.node.value);
+        }
+    }
     fn walk_quant_expr(&mut self, quant_expr: &'ctx mut ast::QuantExpr) {
         self.walk_expr(&mut quant_expr.target.node);
         walk_list_mut!(self, walk_identifier, quant_expr.variables);
@@ -447,6 +471,48 @@ pub trait MutSelfMutWalker<'ctx> {
         walk_list_mut!(self, walk_stmt, lambda_expr.body);
         walk_if_mut!(self, walk_type, lambda_expr.return_ty);
     }
+    fn walk_match_expr(&mut self, match_expr: &'ctx mut ast::MatchExpr) {
+        self.walk_expr(&mut match_expr.subject.node);
+        for case in match_expr.cases.iter_mut() {
+            self.walk_pattern(&mut case.node.pattern.node);
+            walk_if_mut!(self, walk_expr, case.node.guard);
+            self.walk_expr(&mut case.node.body.node);
+        }
+    }
+    fn walk_pattern(&mut self, pattern: &'ctx mut ast::Pattern) {
+        match pattern {
+            ast::Pattern::Wildcard => {}
+            ast::Pattern::Value(value) => self.walk_expr(&mut value.node),
+            ast::Pattern::Capture(name) => self.walk_identifier(&mut name.node),
+            ast::Pattern::Schema(schema_pattern) => {
+                self.walk_identifier(&mut schema_pattern.name.node);
+                for entry in schema_pattern.entries.iter_mut() {
+                    self.walk_pattern(&mut entry.node.value.node);
+                }
+            }
+            ast::Pattern::List(list_pattern) => {
+                for elt in list_pattern.elts.iter_mut() {
+                    self.walk_pattern(&mut elt.node);
+                }
+                walk_if_mut!(self, walk_identifier, list_pattern.rest);
+            }
+            ast::Pattern::Dict(dict_pattern) => {
+                for entry in dict_pattern.entries.iter_mut() {
+                    self.walk_pattern(&mut entry.node.value.node);
+                }
+                walk_if_mut!(self, walk_identifier, dict_pattern.rest);
+            }
+            ast::Pattern::Or(patterns) => {
+                for pattern in patterns.iter_mut() {
+                    self.walk_pattern(&mut pattern.node);
+                }
+            }
+            ast::Pattern::As(as_pattern) => {
+                self.walk_pattern(&mut as_pattern.pattern.node);
+                self.walk_identifier(&mut as_pattern.name.node);
+            }
+        }
+    }
     fn walk_keyword(&mut self, keyword: &'ctx mut ast::Keyword) {
         self.walk_identifier(&mut keyword.arg.node);
         if let Some(v) = keyword.value.as_deref_mut() {
@@ -524,6 +590,8 @@ pub trait MutSelfMutWalker<'ctx> {
             ast::Stmt::SchemaAttr(schema_attr) => self.walk_schema_attr(schema_attr),
             ast::Stmt::Schema(schema_stmt) => self.walk_schema_stmt(schema_stmt),
             ast::Stmt::Rule(rule_stmt) => self.walk_rule_stmt(rule_stmt),
+            ast::Stmt::Enum(enum_stmt) => self.walk_enum_stmt(enum_stmt),
+            ast::Stmt::Export(export_stmt) => self.walk_export_stmt(export_stmt),
         }
     }
     fn walk_expr(&mut self, expr: &'ctx mut ast::Expr) {
@@ -552,6 +620,7 @@ pub trait MutSelfMutWalker<'ctx> {
             ast::Expr::Config(config_expr) => self.walk_config_expr(config_expr),
             ast::Expr::Check(check) => self.walk_check_expr(check),
             ast::Expr::Lambda(lambda) => self.walk_lambda_expr(lambda),
+            ast::Expr::Match(match_expr) => self.walk_match_expr(match_expr),
             ast::Expr::Subscript(subscript) => self.walk_subscript(subscript),
             ast::Expr::Keyword(keyword) => self.walk_keyword(keyword),
             ast::Expr::Arguments(arguments) => self.walk_arguments(arguments),
@@ -604,6 +673,9 @@ pub trait Walker<'ctx>: TypedResultWalker<'ctx> {
     fn walk_import_stmt(&mut self, import_stmt: &'ctx ast::ImportStmt) {
         walk_import_stmt(self, import_stmt);
     }
+    fn walk_export_stmt(&mut self, export_stmt: &'ctx ast::ExportStmt) {
+        walk_export_stmt(self, export_stmt);
+    }
     fn walk_schema_attr(&mut self, schema_attr: &'ctx ast::SchemaAttr) {
         walk_schema_attr(self, schema_attr);
     }
@@ -613,6 +685,9 @@ pub trait Walker<'ctx>: TypedResultWalker<'ctx> {
     fn walk_rule_stmt(&mut self, rule_stmt: &'ctx ast::RuleStmt) {
         walk_rule_stmt(self, rule_stmt);
     }
+    fn walk_enum_stmt(&mut self, enum_stmt: &'ctx ast::EnumStmt) {
+        walk_enum_stmt(self, enum_stmt);
+    }
     fn walk_quant_expr(&mut self, quant_expr: &'ctx ast::QuantExpr) {
         walk_quant_expr(self, quant_expr);
     }
@@ -670,6 +745,12 @@ pub trait Walker<'ctx>: TypedResultWalker<'ctx> {
     fn walk_lambda_expr(&mut self, lambda_expr: &'ctx ast::LambdaExpr) {
         walk_lambda_expr(self, lambda_expr);
     }
+    fn walk_match_expr(&mut self, match_expr: &'ctx ast::MatchExpr) {
+        walk_match_expr(self, match_expr);
+    }
+    fn walk_pattern(&mut self, pattern: &'ctx ast::Pattern) {
+        walk_pattern(self, pattern);
+    }
     fn walk_keyword(&mut self, keyword: &'ctx ast::Keyword) {
         walk_keyword(self, keyword);
     }
@@ -741,6 +822,7 @@ pub fn walk_expr<'ctx, V: Walker<'ctx>>(walker: &mut V, expr: &'ctx ast::Expr) {
         ast::Expr::Config(config_expr) => walker.walk_config_expr(config_expr),
         ast::Expr::Check(check) => walker.walk_check_expr(check),
         ast::Expr::Lambda(lambda) => walker.walk_lambda_expr(lambda),
+        ast::Expr::Match(match_expr) => walker.walk_match_expr(match_expr),
         ast::Expr::Subscript(subscript) => walker.walk_subscript(subscript),
         ast::Expr::Keyword(keyword) => walker.walk_keyword(keyword),
         ast::Expr::Arguments(arguments) => walker.walk_arguments(arguments),
@@ -769,6 +851,8 @@ pub fn walk_stmt<'ctx, V: Walker<'ctx>>(walker: &mut V, stmt: &'ctx ast::Stmt) {
         ast::Stmt::SchemaAttr(schema_attr) => walker.walk_schema_attr(schema_attr),
         ast::Stmt::Schema(schema_stmt) => walker.walk_schema_stmt(schema_stmt),
         ast::Stmt::Rule(rule_stmt) => walker.walk_rule_stmt(rule_stmt),
+        ast::Stmt::Enum(enum_stmt) => walker.walk_enum_stmt(enum_stmt),
+        ast::Stmt::Export(export_stmt) => walker.walk_export_stmt(export_stmt),
     }
 }
 
@@ -823,6 +907,11 @@ pub fn walk_import_stmt<'ctx, V: Walker<'ctx>>(walker: &mut V, import_stmt: &'ct
     let _ = import_stmt;
 }
 
+pub fn walk_export_stmt<'ctx, V: Walker<'ctx>>(walker: &mut V, export_stmt: &'ctx ast::ExportStmt) {
+    let _ = walker;
+    let _ = export_stmt;
+}
+
 pub fn walk_schema_attr<'ctx, V: Walker<'ctx>>(walker: &mut V, schema_attr: &'ctx ast::SchemaAttr) {
     walk_list!(walker, walk_call_expr, schema_attr.decorators);
     walk_if!(walker, walk_expr, schema_attr.value);
@@ -849,6 +938,12 @@ pub fn walk_rule_stmt<'ctx, V: Walker<'ctx>>(walker: &mut V, rule_stmt: &'ctx as
     walk_if!(walker, walk_identifier, rule_stmt.for_host_name);
 }
 
+pub fn walk_enum_stmt<'ctx, V: Walker<'ctx>>(walker: &mut V, enum_stmt: &'ctx ast::EnumStmt) {
+    for member in &enum_stmt.members {
+        walk_if!(walker, walk_expr, member.node.value);
+    }
+}
+
 pub fn walk_quant_expr<'ctx, V: Walker<'ctx>>(walker: &mut V, quant_expr: &'ctx ast::QuantExpr) {
     walker.walk_expr(&quant_expr.target.node);
     walk_list!(walker, walk_identifier, quant_expr.variables);
@@ -980,6 +1075,44 @@ pub fn walk_lambda_expr<'ctx, V: Walker<'ctx>>(walker: &mut V, lambda_expr: &'ct
     walk_list!(walker, walk_stmt, lambda_expr.body);
 }
 
+pub fn walk_match_expr<'ctx, V: Walker<'ctx>>(walker: &mut V, match_expr: &'ctx ast::MatchExpr) {
+    walker.walk_expr(&match_expr.subject.node);
+    for case in &match_expr.cases {
+        walker.walk_pattern(&case.node.pattern.node);
+        walk_if!(walker, walk_expr, case.node.guard);
+        walker.walk_expr(&case.node.body.node);
+    }
+}
+
+pub fn walk_pattern<'ctx, V: Walker<'ctx>>(walker: &mut V, pattern: &'ctx ast::Pattern) {
+    match pattern {
+        ast::Pattern::Wildcard => {}
+        ast::Pattern::Value(value) => walker.walk_expr(&value.node),
+        ast::Pattern::Capture(name) => walker.walk_identifier(&name.node),
+        ast::Pattern::Schema(schema_pattern) => {
+            walker.walk_identifier(&schema_pattern.name.node);
+            for entry in &schema_pattern.entries {
+                walker.walk_pattern(&entry.node.value.node);
+            }
+        }
+        ast::Pattern::List(list_pattern) => {
+            walk_list!(walker, walk_pattern, list_pattern.elts);
+            walk_if!(walker, walk_identifier, list_pattern.rest);
+        }
+        ast::Pattern::Dict(dict_pattern) => {
+            for entry in &dict_pattern.entries {
+                walker.walk_pattern(&entry.node.value.node);
+            }
+            walk_if!(walker, walk_identifier, dict_pattern.rest);
+        }
+        ast::Pattern::Or(patterns) => walk_list!(walker, walk_pattern, patterns),
+        ast::Pattern::As(as_pattern) => {
+            walker.walk_pattern(&as_pattern.pattern.node);
+            walker.walk_identifier(&as_pattern.name.node);
+        }
+    }
+}
+
 pub fn walk_keyword<'ctx, V: Walker<'ctx>>(walker: &mut V, keyword: &'ctx ast::Keyword) {
     walker.walk_identifier(&keyword.arg.node);
     match &keyword.value {
@@ -1101,6 +1234,9 @@ pub trait MutSelfWalker {
     fn walk_import_stmt(&mut self, _import_stmt: &ast::ImportStmt) {
         // Nothing to do
     }
+    fn walk_export_stmt(&mut self, _export_stmt: &ast::ExportStmt) {
+        // Nothing to do
+    }
     fn walk_schema_attr(&mut self, schema_attr: &ast::SchemaAttr) {
         walk_list!(self, walk_call_expr, schema_attr.decorators);
         walk_if!(self, walk_expr, schema_attr.value);
@@ -1125,6 +1261,11 @@ pub trait MutSelfWalker {
         walk_if!(self, walk_arguments, rule_stmt.args);
         walk_if!(self, walk_identifier, rule_stmt.for_host_name);
     }
+    fn walk_enum_stmt(&mut self, enum_stmt: &ast::EnumStmt) {
+        for member in &enum_stmt.members {
+            walk_if!(self, walk_expr, member.node.value);
+        }
+    }
     fn walk_quant_expr(&mut self, quant_expr: &ast::QuantExpr) {
         self.walk_expr(&quant_expr.target.node);
         walk_list!(self, walk_identifier, quant_expr.variables);
@@ -1218,6 +1359,42 @@ pub trait MutSelfWalker {
         walk_if!(self, walk_arguments, lambda_expr.args);
         walk_list!(self, walk_stmt, lambda_expr.body);
     }
+    fn walk_match_expr(&mut self, match_expr: &ast::MatchExpr) {
+        self.walk_expr(&match_expr.subject.node);
+        for case in &match_expr.cases {
+            self.walk_pattern(&case.node.pattern.node);
+            walk_if!(self, walk_expr, case.node.guard);
+            self.walk_expr(&case.node.body.node);
+        }
+    }
+    fn walk_pattern(&mut self, pattern: &ast::Pattern) {
+        match pattern {
+            ast::Pattern::Wildcard => {}
+            ast::Pattern::Value(value) => self.walk_expr(&value.node),
+            ast::Pattern::Capture(name) => self.walk_identifier(&name.node),
+            ast::Pattern::Schema(schema_pattern) => {
+                self.walk_identifier(&schema_pattern.name.node);
+                for entry in &schema_pattern.entries {
+                    self.walk_pattern(&entry.node.value.node);
+                }
+            }
+            ast::Pattern::List(list_pattern) => {
+                walk_list!(self, walk_pattern, list_pattern.elts);
+                walk_if!(self, walk_identifier, list_pattern.rest);
+            }
+            ast::Pattern::Dict(dict_pattern) => {
+                for entry in &dict_pattern.entries {
+                    self.walk_pattern(&entry.node.value.node);
+                }
+                walk_if!(self, walk_identifier, dict_pattern.rest);
+            }
+            ast::Pattern::Or(patterns) => walk_list!(self, walk_pattern, patterns),
+            ast::Pattern::As(as_pattern) => {
+                self.walk_pattern(&as_pattern.pattern.node);
+                self.walk_identifier(&as_pattern.name.node);
+            }
+        }
+    }
     fn walk_keyword(&mut self, keyword: &ast::Keyword) {
         self.walk_identifier(&keyword.arg.node);
         if let Some(v) = &keyword.value {
@@ -1288,6 +1465,8 @@ pub trait MutSelfWalker {
             ast::Stmt::SchemaAttr(schema_attr) => self.walk_schema_attr(schema_attr),
             ast::Stmt::Schema(schema_stmt) => self.walk_schema_stmt(schema_stmt),
             ast::Stmt::Rule(rule_stmt) => self.walk_rule_stmt(rule_stmt),
+            ast::Stmt::Enum(enum_stmt) => self.walk_enum_stmt(enum_stmt),
+            ast::Stmt::Export(export_stmt) => self.walk_export_stmt(export_stmt),
         }
     }
     fn walk_expr(&mut self, expr: &ast::Expr) {
@@ -1316,6 +1495,7 @@ pub trait MutSelfWalker {
             ast::Expr::Config(config_expr) => self.walk_config_expr(config_expr),
             ast::Expr::Check(check) => self.walk_check_expr(check),
             ast::Expr::Lambda(lambda) => self.walk_lambda_expr(lambda),
+            ast::Expr::Match(match_expr) => self.walk_match_expr(match_expr),
             ast::Expr::Subscript(subscript) => self.walk_subscript(subscript),
             ast::Expr::Keyword(keyword) => self.walk_keyword(keyword),
             ast::Expr::Arguments(arguments) => self.walk_arguments(arguments),
@@ -160,6 +160,16 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
         self.write_newline_without_fill();
     }
 
+    fn walk_export_stmt(&mut self, export_stmt: &'ctx ast::ExportStmt) -> Self::Result {
+        self.write("export ");
+        interleave!(
+            || self.write(", "),
+            |name: &ast::Node<String>| self.write(&name.node),
+            export_stmt.names
+        );
+        self.write_newline_without_fill();
+    }
+
     fn walk_schema_stmt(&mut self, schema_stmt: &'ctx ast::SchemaStmt) -> Self::Result {
         interleave!(
             || self.write_newline(),
@@ -181,10 +191,14 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
             self.write("schema ");
         }
         self.write(&schema_stmt.name.node);
+        self.write_type_params(&schema_stmt.type_params);
+        // The type parameters are always followed by the arguments brackets.
         if let Some(args) = &schema_stmt.args {
             self.write("[");
             self.walk_arguments(&args.node);
             self.write("]");
+        } else if !schema_stmt.type_params.is_empty() {
+            self.write("[]");
         }
         if let Some(parent_name) = &schema_stmt.parent_name {
             self.write("(");
@@ -323,6 +337,37 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
         self.write_indentation(Indentation::Dedent);
     }
 
+    fn walk_enum_stmt(&mut self, enum_stmt: &'ctx ast::EnumStmt) -> Self::Result {
+        self.write("enum ");
+        self.write(&enum_stmt.name.node);
+        self.write_token(TokenKind::Colon);
+        // Enum Stmt indent
+        self.write_indentation(Indentation::IndentWithNewline);
+        if let Some(doc) = &enum_stmt.doc {
+            self.write(&doc.node);
+            self.write_newline();
+        }
+        interleave!(
+            || self.write_newline(),
+            |member: &ast::NodeRef<ast::EnumMember>| {
+                self.write_comments_before_node(&member);
+                self.write(&member.node.name.node);
+                if let Some(value) = &member.node.value {
+                    self.write(" = ");
+                    self.expr(value);
+                }
+                if let Some(doc) = &member.node.doc {
+                    self.write_newline();
+                    self.write(&doc.node);
+                }
+            },
+            enum_stmt.members
+        );
+        self.write_newline_without_fill();
+        // Enum Stmt dedent
+        self.write_indentation(Indentation::Dedent);
+    }
+
     fn walk_quant_expr(&mut self, quant_expr: &'ctx ast::QuantExpr) -> Self::Result {
         let in_one_line = self.last_ast_line > 0 && quant_expr.test.line == self.last_ast_line;
         let quant_op_string: String = quant_expr.op.clone().into();
@@ -363,6 +408,9 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
         if !schema_attr.decorators.is_empty() {
             self.write_newline();
         }
+        if schema_attr.is_final {
+            self.write("final ");
+        }
         // A schema string attribute needs quote.
         if !schema_attr.is_ident_attr() {
             self.write(&format!("{:?}", schema_attr.name.node));
@@ -707,6 +755,7 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
 
     fn walk_lambda_expr(&mut self, lambda_expr: &'ctx ast::LambdaExpr) -> Self::Result {
         self.write("lambda");
+        self.write_type_params(&lambda_expr.type_params);
         if let Some(args) = &lambda_expr.args {
             self.write_space();
             self.walk_arguments(&args.node);
@@ -730,6 +779,29 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
         self.write_token(TokenKind::CloseDelim(DelimToken::Brace));
     }
 
+    fn walk_match_expr(&mut self, match_expr: &'ctx ast::MatchExpr) -> Self::Result {
+        self.write("match ");
+        self.expr(&match_expr.subject);
+        self.write(" {");
+        self.write_indentation(Indentation::IndentWithNewline);
+        interleave!(
+            || self.write_newline(),
+            |case: &ast::NodeRef<ast::MatchCase>| {
+                self.write_comments_before_node(&case);
+                self.write_pattern(&case.node.pattern.node);
+                if let Some(guard) = &case.node.guard {
+                    self.write(" if ");
+                    self.expr(guard);
+                }
+                self.write(": ");
+                self.expr(&case.node.body);
+            },
+            match_expr.cases
+        );
+        self.write_indentation(Indentation::DedentWithNewline);
+        self.write("}");
+    }
+
     fn walk_keyword(&mut self, keyword: &'ctx ast::Keyword) -> Self::Result {
         self.walk_identifier(&keyword.arg.node);
         if let Some(value) = &keyword.value {
@@ -954,6 +1026,99 @@ impl<'p> Printer<'p> {
             self.write(&attr.node);
         };
     }
+
+    fn write_pattern(&mut self, pattern: &ast::Pattern) {
+        match pattern {
+            ast::Pattern::Wildcard => self.write("_"),
+            ast::Pattern::Value(value) => self.expr(value),
+            ast::Pattern::Capture(name) => self.walk_identifier(&name.node),
+            ast::Pattern::Schema(schema_pattern) => {
+                self.walk_identifier(&schema_pattern.name.node);
+                self.write(" {");
+                interleave!(
+                    || self.write(COMMA_WHITESPACE),
+                    |entry: &ast::NodeRef<ast::PatternEntry>| {
+                        self.write(&entry.node.key.node);
+                        self.write(": ");
+                        self.write_pattern(&entry.node.value.node);
+                    },
+                    schema_pattern.entries
+                );
+                self.write("}");
+            }
+            ast::Pattern::List(list_pattern) => {
+                self.write("[");
+                interleave!(
+                    || self.write(COMMA_WHITESPACE),
+                    |elt: &ast::NodeRef<ast::Pattern>| self.write_pattern(&elt.node),
+                    list_pattern.elts
+                );
+                if let Some(rest) = &list_pattern.rest {
+                    if !list_pattern.elts.is_empty() {
+                        self.write(COMMA_WHITESPACE);
+                    }
+                    self.write("*");
+                    self.walk_identifier(&rest.node);
+                }
+                self.write("]");
+            }
+            ast::Pattern::Dict(dict_pattern) => {
+                self.write("{");
+                interleave!(
+                    || self.write(COMMA_WHITESPACE),
+                    |entry: &ast::NodeRef<ast::PatternEntry>| {
+                        self.write(&format!("{:?}", entry.node.key.node));
+                        self.write(": ");
+                        self.write_pattern(&entry.node.value.node);
+                    },
+                    dict_pattern.entries
+                );
+                if let Some(rest) = &dict_pattern.rest {
+                    if !dict_pattern.entries.is_empty() {
+                        self.write(COMMA_WHITESPACE);
+                    }
+                    self.write("**");
+                    self.walk_identifier(&rest.node);
+                }
+                self.write("}");
+            }
+            ast::Pattern::Or(patterns) => {
+                interleave!(
+                    || self.write(" | "),
+                    |pattern: &ast::NodeRef<ast::Pattern>| {
+                        // The as pattern in the or pattern needs parentheses.
+                        if let ast::Pattern::As(_) = &pattern.node {
+                            self.write("(");
+                            self.write_pattern(&pattern.node);
+                            self.write(")");
+                        } else {
+                            self.write_pattern(&pattern.node);
+                        }
+                    },
+                    patterns
+                );
+            }
+            ast::Pattern::As(as_pattern) => {
+                self.write_pattern(&as_pattern.pattern.node);
+                self.write(" as ");
+                self.walk_identifier(&as_pattern.name.node);
+            }
+        }
+    }
+
+    fn write_type_params(&mut self, type_params: &[ast::NodeRef<String>]) {
+        if !type_params.is_empty() {
+            self.write("[");
+            self.write(
+                &type_params
+                    .iter()
+                    .map(|param| param.node.clone())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            );
+            self.write("]");
+        }
+    }
 }
 
 impl<'p> Printer<'p> {
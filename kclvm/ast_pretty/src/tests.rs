@@ -9,6 +9,10 @@ const FILE_OUTPUT_SUFFIX: &str = ".output";
 const TEST_CASES: &[&str] = &[
     "arguments",
     "empty",
+    "enum",
+    "export",
+    "final",
+    "generic",
     "if_stmt",
     "import",
     "unary",
@@ -18,6 +22,7 @@ const TEST_CASES: &[&str] = &[
     "index_sign",
     "joined_str",
     "lambda",
+    "match",
     "orelse",
     "quant",
     "rule",
@@ -29,6 +29,8 @@ pub fn main(args: &[&str]) -> Result<()> {
         }
         #[cfg(not(target_arch = "wasm32"))]
         Some(("server", _)) => kclvm_api::service::jsonrpc::start_stdio_server(),
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(("debug", _)) => kclvm_runner::debugger::start_stdio_server(),
         _ => Ok(()),
     }
 }
@@ -43,7 +45,9 @@ pub fn app() -> Command {
             Command::new("run")
             .about("run")
             .arg(arg!([input] ... "Specify the input files to run").num_args(0..))
-            .arg(arg!(output: -o --output <output> "Specify the YAML output file path"))
+            .arg(arg!(output: -o --output <output> "Specify the output file path, or the output directory in the split mode"))
+            .arg(arg!(format: --format <format> "Specify the output format").value_parser(["yaml", "json", "toml", "ndjson"]))
+            .arg(arg!(split: --split "Write each top-level key or each yaml stream document to its own file in the output directory").requires("output"))
             .arg(arg!(setting: -Y --setting <setting> ... "Specify the input setting file").num_args(1..))
             .arg(arg!(verbose: -v --verbose "Print test information verbosely").action(ArgAction::Count))
             .arg(arg!(disable_none: -n --disable_none "Disable dumping None values"))
@@ -52,6 +56,13 @@ pub fn app() -> Command {
             .arg(arg!(sort_keys: -k --sort_keys "Sort result keys"))
             .arg(arg!(show_hidden: -H --show_hidden "Display hidden attributes"))
             .arg(arg!(fast_eval: -K --fast_eval "Use the fast evaluation mode"))
+            .arg(arg!(provenance: --provenance "Annotate the YAML output with the source position comments of each field"))
+            .arg(arg!(incremental: --incremental "Cache the parsed files, the package values and the whole program result, and reuse them when their inputs are unchanged"))
+            .arg(arg!(parallel: --parallel "Evaluate the independent imported packages in parallel"))
+            .arg(arg!(hermetic: --hermetic "Run in the hermetic mode where the nondeterministic built-in functions use the injected values or fail"))
+            .arg(arg!(hermetic_clock: --hermetic_clock <hermetic_clock> "Specify the fixed clock in seconds since the Unix epoch in the hermetic mode").value_parser(clap::value_parser!(i64)).requires("hermetic"))
+            .arg(arg!(hermetic_seed: --hermetic_seed <hermetic_seed> "Specify the random seed in the hermetic mode").value_parser(clap::value_parser!(u64)).requires("hermetic"))
+            .arg(arg!(hermetic_env: --hermetic_env <hermetic_env> ... "Specify the environment variable snapshot in the hermetic mode e.g., KEY=VALUE").num_args(1..).requires("hermetic"))
             .arg(arg!(arguments: -D --argument <arguments> ... "Specify the top-level argument").num_args(1..))
             .arg(arg!(path_selector: -S --path_selector <path_selector> ... "Specify the path selector").num_args(1..))
             .arg(arg!(overrides: -O --overrides <overrides> ... "Specify the configuration override path and value").num_args(1..))
@@ -60,5 +71,6 @@ pub fn app() -> Command {
             .arg(arg!(package_map: -E --external <package_map> ... "Mapping of package name and path where the package is located").num_args(1..)),
         )
     .subcommand(Command::new("server").about("Start a rpc server for APIs"))
+    .subcommand(Command::new("debug").about("Start a debug adapter protocol server over stdio"))
     .subcommand(Command::new("version").about("Show the KCL version"))
 }
@@ -1,20 +1,69 @@
 use anyhow::Result;
 use clap::ArgMatches;
 use kclvm_error::StringError;
-use kclvm_parser::ParseSession;
-use kclvm_runner::exec_program;
-use std::io::Write;
+use kclvm_parser::{ParseSession, ParseSessionRef};
+use kclvm_runner::{
+    exec_program, exec_program_to_writer, ExecProgramArgs, ExecProgramResult, OutputFormat,
+};
+use kclvm_runtime::HermeticConfig;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::settings::must_build_settings;
+use crate::util::{bool_from_matches, hashmaps_from_matches};
 
 /// Run the KCL run command.
 pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
     // Config settings building
     let settings = must_build_settings(matches);
     let output = settings.output();
+    let split = bool_from_matches(matches, "split").unwrap_or_default();
+    let provenance = bool_from_matches(matches, "provenance").unwrap_or_default();
+    let mut args: ExecProgramArgs = settings.try_into()?;
+    args.provenance = provenance;
+    args.incremental = bool_from_matches(matches, "incremental").unwrap_or_default();
+    args.parallel = bool_from_matches(matches, "parallel").unwrap_or_default();
+    args.hermetic = HermeticConfig {
+        enabled: bool_from_matches(matches, "hermetic").unwrap_or_default(),
+        clock: matches.get_one::<i64>("hermetic_clock").copied(),
+        random_seed: matches.get_one::<u64>("hermetic_seed").copied(),
+        env: hashmaps_from_matches(matches, "hermetic_env")
+            .transpose()?
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+    };
+    // Only plan the result format used by the output.
+    if args.format == OutputFormat::Yaml && !split {
+        args.disable_json_result = true;
+    } else {
+        args.disable_yaml_result = true;
+    }
     let sess = Arc::new(ParseSession::default());
-    match exec_program(sess.clone(), &settings.try_into()?) {
+    // Stream the large output into the output file as it is planned.
+    let stream_output = match output.as_deref() {
+        Some(o) if !split && !provenance && is_streamable(args.format) => Some(o),
+        _ => None,
+    };
+    let result = match stream_output {
+        Some(o) => {
+            // Write into a temp file in the same directory and replace the output
+            // file only on success, which keeps the previous output on failure.
+            let temp_output = temp_output_path(o);
+            let result = stream_to_file(sess.clone(), &args, &temp_output);
+            match &result {
+                Ok(result) if result.err_message.is_empty() => std::fs::rename(&temp_output, o)?,
+                _ => {
+                    let _ = std::fs::remove_file(&temp_output);
+                }
+            }
+            result
+        }
+        None => exec_program(sess.clone(), &args),
+    };
+    match result {
         Ok(result) => {
             // Output log message
             if !result.log_message.is_empty() {
@@ -27,12 +76,26 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
                 }
                 sess.0.emit_stashed_diagnostics_and_abort()?;
             }
-            if !result.yaml_result.is_empty() {
-                match output {
-                    Some(o) => std::fs::write(o, result.yaml_result)?,
-                    // [`println!`] is not a good way to output content to stdout,
-                    // using [`writeln`] can be better to redirect the output.
-                    None => writeln!(writer, "{}", result.yaml_result)?,
+            if stream_output.is_some() {
+                // The result has been written into the output file.
+            } else if split {
+                // The output directory is required by the split mode.
+                if let Some(dir) = output {
+                    result.split(args.format, dir)?;
+                }
+            } else {
+                let content = if provenance && args.format == OutputFormat::Yaml {
+                    result.annotate_yaml()?
+                } else {
+                    result.format(args.format)?
+                };
+                if !content.is_empty() {
+                    match output {
+                        Some(o) => std::fs::write(o, content)?,
+                        // [`println!`] is not a good way to output content to stdout,
+                        // using [`writeln`] can be better to redirect the output.
+                        None => writeln!(writer, "{}", content)?,
+                    }
                 }
             }
         }
@@ -46,3 +109,30 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
     }
     Ok(())
 }
+
+/// Execute the program and write the result into the file as it is planned.
+fn stream_to_file(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    path: &Path,
+) -> Result<ExecProgramResult> {
+    let mut file = BufWriter::new(File::create(path)?);
+    let result = exec_program_to_writer(sess, args, &mut file)?;
+    file.flush()?;
+    Ok(result)
+}
+
+/// Get the temp file path in the directory of the output file.
+fn temp_output_path(output: &str) -> PathBuf {
+    let path = Path::new(output);
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+/// Whether the output format can be written as the result is planned.
+fn is_streamable(format: OutputFormat) -> bool {
+    matches!(format, OutputFormat::Yaml | OutputFormat::Ndjson)
+}
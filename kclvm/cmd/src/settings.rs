@@ -45,6 +45,7 @@ pub(crate) fn build_settings(matches: &ArgMatches) -> Result<SettingsPathBuf> {
         Some(SettingsFile {
             kcl_cli_configs: Some(Config {
                 output: matches.get_one::<String>("output").map(|v| v.to_string()),
+                format: matches.get_one::<String>("format").map(|v| v.to_string()),
                 overrides: strings_from_matches(matches, "overrides"),
                 path_selector: strings_from_matches(matches, "path_selector"),
                 strict_range_check: bool_from_matches(matches, "strict_range_check"),
@@ -536,6 +536,7 @@ impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
         &self,
         functions: &[FunctionValue<'ctx>],
         attr_functions: &HashMap<String, Vec<FunctionValue<'ctx>>>,
+        bases: &[Self::Value],
         runtime_type: &str,
     ) -> Self::Value {
         if functions.is_empty() {
@@ -566,6 +567,7 @@ impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
         for attr in attr_functions.keys() {
             self.dict_insert_override_item(attr_map, attr, self.undefined_value())
         }
+        let bases = self.list_values(bases);
         self.builder
             .build_call(
                 self.lookup_function(&ApiFunc::kclvm_value_schema_function.name()),
@@ -574,6 +576,7 @@ impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
                     schema_body_fn_ptr.into(),
                     check_block_fn_ptr.into(),
                     attr_map.into(),
+                    bases.into(),
                     runtime_type_native_str.into(),
                 ],
                 runtime_type,
@@ -1485,6 +1488,9 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     ) -> Result<(), LLVMString> {
         let triple = inkwell::targets::TargetMachine::get_default_triple();
         let target = inkwell::targets::Target::from_triple(&triple)?;
+        // Verify the generated IR e.g., the variables dominate all their uses in the debug build.
+        #[cfg(debug_assertions)]
+        module.verify()?;
         // Convert LLVM module to ll file.
         module.print_to_file(path)?;
         let buf = MemoryBuffer::create_from_file(path)?;
@@ -42,6 +42,8 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         utils::reset_target_vars(self);
         match &stmt.node {
             ast::Stmt::TypeAlias(type_alias) => self.walk_type_alias_stmt(type_alias),
+            ast::Stmt::Enum(enum_stmt) => self.walk_enum_stmt(enum_stmt),
+            ast::Stmt::Export(export_stmt) => self.walk_export_stmt(export_stmt),
             ast::Stmt::Expr(expr_stmt) => self.walk_expr_stmt(expr_stmt),
             ast::Stmt::Unification(unification_stmt) => {
                 self.walk_unification_stmt(unification_stmt)
@@ -143,6 +145,16 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         self.ok_result()
     }
 
+    fn walk_enum_stmt(&self, _enum_stmt: &'ctx ast::EnumStmt) -> Self::Result {
+        // Nothing to do, because all enum members have been replaced at compile time
+        self.ok_result()
+    }
+
+    fn walk_export_stmt(&self, _export_stmt: &'ctx ast::ExportStmt) -> Self::Result {
+        // Nothing to do, because the export list has been checked at compile time
+        self.ok_result()
+    }
+
     fn walk_assign_stmt(&self, assign_stmt: &'ctx ast::AssignStmt) -> Self::Result {
         check_backtrack_stop!(self);
         self.local_vars.borrow_mut().clear();
@@ -493,6 +505,33 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         add_variable(value::SCHEMA_KWARGS, kwargs);
         add_variable(value::SCHEMA_RUNTIME_TYPE, self.string_value(&runtime_type));
         self.walk_arguments(&schema_stmt.args, args, kwargs);
+        // Final attributes check
+        let final_attrs: Vec<&str> = schema_stmt
+            .body
+            .iter()
+            .filter_map(|stmt| match &stmt.node {
+                ast::Stmt::SchemaAttr(schema_attr) if schema_attr.is_final => {
+                    Some(schema_attr.name.node.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        if !final_attrs.is_empty() {
+            let final_attrs_value = self.list_value();
+            for attr in final_attrs {
+                self.list_append(final_attrs_value, self.string_value(attr));
+            }
+            self.build_void_call(
+                &ApiFunc::kclvm_schema_final_check.name(),
+                &[
+                    self.current_runtime_ctx_ptr(),
+                    schema_config,
+                    schema_config_meta,
+                    final_attrs_value,
+                    self.native_global_string_value(schema_name),
+                ],
+            );
+        }
         let schema = value::SchemaType::new(
             schema_name,
             schema_pkgpath,
@@ -978,9 +1017,20 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 }
             }
         }
+        // The parent schema and mixin functions to check the `is` operator.
+        let bases: Vec<BasicValueEnum<'ctx>> = schema_stmt
+            .parent_name
+            .iter()
+            .chain(schema_stmt.mixins.iter())
+            .map(|name| {
+                self.walk_identifier_with_ctx(&name.node, &ast::ExprContext::Load, None)
+                    .expect(kcl_error::COMPILE_ERROR_MSG)
+            })
+            .collect();
         let function = self.struct_function_value(
             &[function, check_function],
             &place_holder_map,
+            &bases,
             &runtime_type,
         );
         self.leave_scope();
@@ -1242,8 +1292,12 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             self.builder.position_at_end(func_before_block);
             self.pop_function();
         }
-        let function =
-            self.struct_function_value(&[function, check_function], &HashMap::new(), &runtime_type);
+        let function = self.struct_function_value(
+            &[function, check_function],
+            &HashMap::new(),
+            &[],
+            &runtime_type,
+        );
         self.leave_scope();
         self.pop_function();
         self.schema_stack.borrow_mut().pop();
@@ -1289,6 +1343,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             ast::Expr::Config(config_expr) => self.walk_config_expr(config_expr),
             ast::Expr::Check(check) => self.walk_check_expr(check),
             ast::Expr::Lambda(lambda) => self.walk_lambda_expr(lambda),
+            ast::Expr::Match(match_expr) => self.walk_match_expr(match_expr),
             ast::Expr::Subscript(subscript) => self.walk_subscript(subscript),
             ast::Expr::Keyword(keyword) => self.walk_keyword(keyword),
             ast::Expr::Arguments(..) => self.ok_result(),
@@ -2208,6 +2263,61 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         Ok(function)
     }
 
+    fn walk_match_expr(&self, match_expr: &'ctx ast::MatchExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        let subject = self
+            .walk_expr(&match_expr.subject)
+            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let end_block = self.append_block("");
+        let tpe = self.value_ptr_type();
+        let mut incoming = vec![];
+        for case in &match_expr.cases {
+            // Enter the case scope for the pattern capture variables.
+            self.enter_scope();
+            let fail_block = self.append_block("");
+            self.walk_pattern(&case.node.pattern, subject, fail_block)
+                .expect(kcl_error::COMPILE_ERROR_MSG);
+            if let Some(guard) = &case.node.guard {
+                let guard_value = self.walk_expr(guard).expect(kcl_error::COMPILE_ERROR_MSG);
+                let is_truth = self.value_is_truthy(guard_value);
+                let body_block = self.append_block("");
+                self.cond_br(is_truth, body_block, fail_block);
+                self.builder.position_at_end(body_block);
+            }
+            let value = self
+                .walk_expr(&case.node.body)
+                .expect(kcl_error::COMPILE_ERROR_MSG);
+            let body_block = self.append_block("");
+            self.br(body_block);
+            self.builder.position_at_end(body_block);
+            let ptr = self.builder.build_alloca(tpe, "");
+            self.builder.build_store(ptr, value);
+            let value = self.builder.build_load(ptr, "");
+            self.br(end_block);
+            incoming.push((value, body_block));
+            self.leave_scope();
+            self.builder.position_at_end(fail_block);
+        }
+        // No match case is found.
+        self.build_void_call(
+            &ApiFunc::kclvm_value_match_failed.name(),
+            &[self.current_runtime_ctx_ptr(), subject],
+        );
+        let failed_value = self.undefined_value();
+        let failed_block = self.append_block("");
+        self.br(failed_block);
+        self.builder.position_at_end(failed_block);
+        self.br(end_block);
+        incoming.push((failed_value, failed_block));
+        self.builder.position_at_end(end_block);
+        let phi = self.builder.build_phi(tpe, "");
+        for (value, block) in &incoming {
+            phi.add_incoming(&[(value, *block)]);
+        }
+        self.local_vars.borrow_mut().clear();
+        Ok(phi.as_basic_value())
+    }
+
     fn walk_keyword(&self, _keyword: &'ctx ast::Keyword) -> Self::Result {
         // Nothing to do
         self.ok_result()
@@ -3043,4 +3153,159 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         }
         Ok(config_value)
     }
+
+    /// Match the value with the pattern and store the capture variables, jump to
+    /// the `fail_block` when the value is not matched.
+    pub(crate) fn walk_pattern(
+        &self,
+        pattern: &'ctx NodeRef<ast::Pattern>,
+        value: BasicValueEnum<'ctx>,
+        fail_block: BasicBlock<'ctx>,
+    ) -> CompileResult<'ctx> {
+        match &pattern.node {
+            ast::Pattern::Wildcard => {}
+            ast::Pattern::Value(expr) => {
+                let pattern_value = self.walk_expr(expr)?;
+                let is_equal = self.cmp_equal_to(value, pattern_value);
+                let is_truth = self.value_is_truthy(is_equal);
+                self.pattern_cond_br(is_truth, fail_block);
+            }
+            ast::Pattern::Capture(name) => self.store_pattern_capture(name, value)?,
+            ast::Pattern::Schema(schema_pattern) => {
+                let schema_type = self.walk_identifier(&schema_pattern.name.node)?;
+                let is_instance = self.is(value, schema_type);
+                let is_truth = self.value_is_truthy(is_instance);
+                self.pattern_cond_br(is_truth, fail_block);
+                self.walk_pattern_entries(&schema_pattern.entries, value, fail_block)?;
+            }
+            ast::Pattern::List(list_pattern) => {
+                let is_matched = self
+                    .build_call(
+                        &ApiFunc::kclvm_value_match_sequence.name(),
+                        &[
+                            value,
+                            self.native_int_value(list_pattern.elts.len() as i32),
+                            self.native_i8_value(list_pattern.rest.is_some() as i8),
+                        ],
+                    )
+                    .into_int_value();
+                let is_matched = self.builder.build_int_compare(
+                    IntPredicate::NE,
+                    is_matched,
+                    self.native_i8_zero(),
+                    "",
+                );
+                self.pattern_cond_br(is_matched.into(), fail_block);
+                for (i, elt) in list_pattern.elts.iter().enumerate() {
+                    let item = self.value_subscript(value, self.int_value(i as i64));
+                    self.walk_pattern(elt, item, fail_block)?;
+                }
+                if let Some(rest) = &list_pattern.rest {
+                    let rest_value = self.list_slice(
+                        value,
+                        self.int_value(list_pattern.elts.len() as i64),
+                        self.none_value(),
+                        self.none_value(),
+                    );
+                    self.store_pattern_capture(rest, rest_value)?;
+                }
+            }
+            ast::Pattern::Dict(dict_pattern) => {
+                let keys = self.pattern_keys(&dict_pattern.entries);
+                self.walk_pattern_entries(&dict_pattern.entries, value, fail_block)?;
+                if let Some(rest) = &dict_pattern.rest {
+                    let rest_value = self.build_call(
+                        &ApiFunc::kclvm_value_match_rest_mapping.name(),
+                        &[self.current_runtime_ctx_ptr(), value, keys],
+                    );
+                    self.store_pattern_capture(rest, rest_value)?;
+                }
+            }
+            ast::Pattern::Or(alternatives) => {
+                let matched_block = self.append_block("");
+                for alternative in alternatives {
+                    let next_block = self.append_block("");
+                    self.walk_pattern(alternative, value, next_block)?;
+                    self.br(matched_block);
+                    self.builder.position_at_end(next_block);
+                }
+                self.br(fail_block);
+                self.builder.position_at_end(matched_block);
+            }
+            ast::Pattern::As(as_pattern) => {
+                self.walk_pattern(&as_pattern.pattern, value, fail_block)?;
+                self.store_pattern_capture(&as_pattern.name, value)?;
+            }
+        }
+        self.ok_result()
+    }
+
+    /// Match the dict or schema value with the pattern entries, all the entry keys
+    /// must be in the value.
+    fn walk_pattern_entries(
+        &self,
+        entries: &'ctx [NodeRef<ast::PatternEntry>],
+        value: BasicValueEnum<'ctx>,
+        fail_block: BasicBlock<'ctx>,
+    ) -> CompileResult<'ctx> {
+        let keys = self.pattern_keys(entries);
+        let is_matched = self
+            .build_call(&ApiFunc::kclvm_value_match_mapping.name(), &[value, keys])
+            .into_int_value();
+        let is_matched =
+            self.builder
+                .build_int_compare(IntPredicate::NE, is_matched, self.native_i8_zero(), "");
+        self.pattern_cond_br(is_matched.into(), fail_block);
+        for entry in entries {
+            let key = self.string_value(&entry.node.key.node);
+            let entry_value = self.dict_get(value, key);
+            self.walk_pattern(&entry.node.value, entry_value, fail_block)?;
+        }
+        self.ok_result()
+    }
+
+    /// Get the key list value of the pattern entries.
+    fn pattern_keys(&self, entries: &'ctx [NodeRef<ast::PatternEntry>]) -> BasicValueEnum<'ctx> {
+        let keys: Vec<BasicValueEnum<'ctx>> = entries
+            .iter()
+            .map(|entry| self.string_value(&entry.node.key.node))
+            .collect();
+        self.list_values(&keys)
+    }
+
+    /// Jump to the next block when the pattern condition is true, otherwise jump
+    /// to the `fail_block`.
+    fn pattern_cond_br(&self, cond: BasicValueEnum<'ctx>, fail_block: BasicBlock<'ctx>) {
+        let next_block = self.append_block("");
+        self.cond_br(cond, next_block, fail_block);
+        self.builder.position_at_end(next_block);
+    }
+
+    /// Store the pattern capture variable in the current match case scope.
+    fn store_pattern_capture(
+        &self,
+        name: &'ctx NodeRef<ast::Identifier>,
+        value: BasicValueEnum<'ctx>,
+    ) -> CompileResult<'ctx> {
+        let var_name = name.node.get_name();
+        if var_name != "_" {
+            self.local_vars.borrow_mut().insert(var_name.clone());
+            if !self.store_variable_in_current_scope(&var_name, value) {
+                // Allocate the capture variable in the function entry block, which dominates
+                // all the alternatives of the or pattern that bind the same name.
+                let cur_bb = self.builder.get_insert_block().unwrap();
+                let func = cur_bb.get_parent().unwrap();
+                let entry_bb = func.get_first_basic_block().unwrap();
+                match entry_bb.get_first_instruction() {
+                    Some(inst) => self.builder.position_before(&inst),
+                    None => self.builder.position_at_end(entry_bb),
+                };
+                let var = self.builder.build_alloca(self.value_ptr_type(), &var_name);
+                self.add_variable(&var_name, var);
+                self.builder.position_at_end(cur_bb);
+                self.builder.build_store(var, value);
+            }
+        }
+        self.ok_result()
+    }
 }
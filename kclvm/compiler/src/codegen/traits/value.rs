@@ -35,6 +35,7 @@ pub trait ValueMethods: BackendTypes {
         &self,
         functions: &[Self::Function],
         attr_functions: &HashMap<String, Vec<Self::Function>>,
+        bases: &[Self::Value],
         runtime_type: &str,
     ) -> Self::Value;
     /// Construct a builtin function value using the function name.
@@ -18,6 +18,7 @@ const LOCK_SUFFIX: &str = ".lock";
 const DEFAULT_CACHE_DIR: &str = ".kclvm/cache";
 const CACHE_INFO_FILENAME: &str = "info";
 const KCL_SUFFIX_PATTERN: &str = "*.k";
+const JSON_SUFFIX: &str = ".json";
 pub const KCL_CACHE_PATH_ENV_VAR: &str = "KCL_CACHE_PATH";
 
 pub type CacheInfo = Vec<u8>;
@@ -137,6 +138,55 @@ where
     Ok(())
 }
 
+/// Load the data cached by the content key, `kind` is the cache kind e.g., `ast`.
+/// Returns `None` when the cache does not exist or is not well formed.
+pub fn load_content_cache<T>(root: &str, kind: &str, key: &str) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    let filename = get_content_cache_filename(root, kind, key);
+    let file = File::open(filename).ok()?;
+    serde_json::from_reader(std::io::BufReader::new(file)).ok()
+}
+
+/// Save the data cached by the content key, `kind` is the cache kind e.g., `ast`.
+pub fn save_content_cache<T>(root: &str, kind: &str, key: &str, data: &T) -> Result<()>
+where
+    T: Serialize,
+{
+    let cache_dir = Path::new(&get_cache_dir(root, None)).join(kind);
+    create_dir_all(&cache_dir)?;
+    let dst_filename = get_content_cache_filename(root, kind, key);
+    let tmp_filename = temp_file(&cache_dir.display().to_string(), key);
+    let mut lock_file = open_lock_file(&format!("{}{}", dst_filename, LOCK_SUFFIX))?;
+    lock_file.lock()?;
+    let file = File::create(&tmp_filename)?;
+    serde_json::to_writer(std::io::BufWriter::new(file), data)?;
+    std::fs::rename(&tmp_filename, &dst_filename)?;
+    lock_file.unlock()?;
+    Ok(())
+}
+
+/// Get the md5 hex digest of the content parts, which is used as the content cache key.
+pub fn content_hash(parts: &[&[u8]]) -> String {
+    let mut md5 = Md5::new();
+    for part in parts {
+        md5.input(part);
+        // Separate the parts to avoid the same digest of different part lists.
+        md5.input([0u8]);
+    }
+    md5.result().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[inline]
+fn get_content_cache_filename(root: &str, kind: &str, key: &str) -> String {
+    Path::new(&get_cache_dir(root, None))
+        .join(kind)
+        .join(format!("{}{}", key, JSON_SUFFIX))
+        .display()
+        .to_string()
+}
+
 #[inline]
 fn get_cache_dir(root: &str, cache_dir: Option<&str>) -> String {
     let cache_dir = cache_dir.unwrap_or(DEFAULT_CACHE_DIR);
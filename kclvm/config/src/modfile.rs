@@ -21,6 +21,7 @@ pub const KCL_FILE_SUFFIX: &str = ".k";
 pub const KCL_FILE_EXTENSION: &str = "k";
 pub const KCL_MOD_PATH_ENV: &str = "${KCL_MOD}";
 pub const KCL_PKG_PATH: &str = "KCL_PKG_PATH";
+pub const KCL_PLUGIN_PATH: &str = "KCL_PLUGIN_PATH";
 pub const DEFAULT_KCL_HOME: &str = ".kcl";
 pub const DEFAULT_KPM_SUBDIR: &str = "kpm";
 
@@ -30,6 +31,7 @@ pub struct ModFile {
     pub package: Option<Package>,
     pub profile: Option<Profile>,
     pub dependencies: Option<Dependencies>,
+    pub plugins: Option<Plugins>,
 }
 
 /// ModLockFile is kcl package file 'kc.mod.lock'.
@@ -71,8 +73,14 @@ pub struct Profile {
     pub overrides: Option<Vec<String>>,
     /// A list of additional options for the KCL compiler.
     pub options: Option<Vec<String>>,
+    /// A list of paths to find the plugin declaration files e.g., `kcl_plugin.hello`
+    /// is declared in the file `<plugin_path>/hello.k`.
+    pub plugin_paths: Option<Vec<String>>,
 }
 
+/// A map of plugin names to their WebAssembly module specifications.
+pub type Plugins = HashMap<String, PluginSource>;
+
 /// A map of package names to their respective dependency specifications.
 pub type Dependencies = HashMap<String, Dependency>;
 pub type LockDependencies = HashMap<String, LockDependency>;
@@ -174,11 +182,47 @@ pub struct LocalSource {
     pub path: String,
 }
 
+/// Defines a WebAssembly plugin imported by `import kcl_plugin.<name>`, e.g.,
+/// `hello = { path = "plugins/hello.wasm" }` in the plugins section of 'kcl.mod'.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PluginSource {
+    /// The path to the WebAssembly module file.
+    pub path: String,
+    /// An optional path to the KCL declaration file of the plugin functions,
+    /// default is the module file path with the '.k' extension.
+    pub decl: Option<String>,
+}
+
+impl PluginSource {
+    /// Get the declaration file path of the plugin if it exists.
+    pub fn get_decl_path(&self) -> Option<String> {
+        match &self.decl {
+            Some(decl) => Some(decl.clone()),
+            None => {
+                let path = Path::new(&self.path).with_extension(KCL_FILE_EXTENSION);
+                if path.is_file() {
+                    Some(path.display().to_string())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
 impl ModFile {
     #[inline]
     pub fn get_entries(&self) -> Option<Vec<String>> {
         self.profile.as_ref().map(|p| p.entries.clone()).flatten()
     }
+
+    #[inline]
+    pub fn get_plugin_paths(&self) -> Option<Vec<String>> {
+        self.profile
+            .as_ref()
+            .map(|p| p.plugin_paths.clone())
+            .flatten()
+    }
 }
 
 /// Load kcl mod file from path
@@ -212,6 +256,57 @@ pub fn get_vendor_home() -> String {
     }
 }
 
+/// Get the paths holding the plugin declaration files.
+/// From the environment variable KCL_PLUGIN_PATH, which is a list of paths
+/// joined by the platform path separator e.g., `:` on Unix.
+pub fn get_plugin_paths() -> Vec<String> {
+    match env::var_os(KCL_PLUGIN_PATH) {
+        Some(paths) => env::split_paths(&paths)
+            .map(|path| path.display().to_string().adjust_canonicalization())
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Get the plugins in the 'kcl.mod' file of the package root, whose paths
+/// are joined with the package root. An empty map is returned when the
+/// 'kcl.mod' file does not exist or is invalid.
+pub fn get_mod_plugins<P: AsRef<Path>>(root: P) -> Plugins {
+    let root = root.as_ref();
+    match load_mod_file(root) {
+        Ok(mod_file) => mod_file
+            .plugins
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, plugin)| {
+                let join = |path: &str| root.join(path).display().to_string();
+                let plugin = PluginSource {
+                    path: join(&plugin.path),
+                    decl: plugin.decl.as_deref().map(join),
+                };
+                (name, plugin)
+            })
+            .collect(),
+        Err(_) => Plugins::default(),
+    }
+}
+
+/// Get the plugin declaration paths in the 'kcl.mod' file of the package root,
+/// which are joined with the package root. An empty list is returned when the
+/// 'kcl.mod' file does not exist or is invalid.
+pub fn get_mod_plugin_paths<P: AsRef<Path>>(root: P) -> Vec<String> {
+    let root = root.as_ref();
+    match load_mod_file(root) {
+        Ok(mod_file) => mod_file
+            .get_plugin_paths()
+            .unwrap_or_default()
+            .iter()
+            .map(|path| root.join(path).display().to_string())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
 /// Create a '.kcl/kpm' folder in the user's root directory,
 /// returning the folder path in [Option::Some] if it already exists.
 ///
@@ -385,5 +480,44 @@ mod modfile_test {
                 path: "../pkg".to_string(),
             }))
         );
+        assert_eq!(
+            kcl_mod.plugins.as_ref().unwrap().get("hello"),
+            Some(&PluginSource {
+                path: "plugins/hello.wasm".to_string(),
+                decl: Some("plugins/hello.k".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_mod_plugins() {
+        let plugins = get_mod_plugins(TEST_ROOT);
+        let plugin = plugins.get("hello").unwrap();
+        assert_eq!(
+            plugin.path,
+            Path::new(TEST_ROOT)
+                .join("plugins/hello.wasm")
+                .display()
+                .to_string()
+        );
+        assert_eq!(
+            plugin.get_decl_path(),
+            Some(
+                Path::new(TEST_ROOT)
+                    .join("plugins/hello.k")
+                    .display()
+                    .to_string()
+            )
+        );
+        assert!(get_mod_plugins("./src/testdata/a").is_empty());
+    }
+
+    #[test]
+    fn test_get_mod_plugin_paths() {
+        assert_eq!(
+            get_mod_plugin_paths(TEST_ROOT),
+            vec![Path::new(TEST_ROOT).join("decls").display().to_string()]
+        );
+        assert!(get_mod_plugin_paths("./src/testdata/a").is_empty());
     }
 }
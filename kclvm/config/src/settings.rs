@@ -53,6 +53,8 @@ pub struct Config {
     pub files: Option<Vec<String>>,
     pub file: Option<Vec<String>>,
     pub output: Option<String>,
+    /// The output format e.g., yaml, json, toml and ndjson.
+    pub format: Option<String>,
     pub overrides: Option<Vec<String>>,
     pub path_selector: Option<Vec<String>>,
     pub strict_range_check: Option<bool>,
@@ -77,6 +79,7 @@ impl SettingsFile {
                 file: Some(vec![]),
                 files: Some(vec![]),
                 output: None,
+                format: None,
                 overrides: Some(vec![]),
                 path_selector: Some(vec![]),
                 strict_range_check: Some(false),
@@ -379,6 +382,7 @@ pub fn merge_settings(settings: &[SettingsFile]) -> SettingsFile {
                 set_if!(result_kcl_cli_configs, files, kcl_cli_configs);
                 set_if!(result_kcl_cli_configs, file, kcl_cli_configs);
                 set_if!(result_kcl_cli_configs, output, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, format, kcl_cli_configs);
                 set_if!(result_kcl_cli_configs, overrides, kcl_cli_configs);
                 set_if!(result_kcl_cli_configs, path_selector, kcl_cli_configs);
                 set_if!(result_kcl_cli_configs, strict_range_check, kcl_cli_configs);
@@ -9,5 +9,9 @@ pkg1 = "oci_tag1"
 pkg2 = { oci = "oci://ghcr.io/kcl-lang/helloworld", tag = "0.1.1" }
 pkg3 = { path = "../pkg"}
 
+[plugins]
+hello = { path = "plugins/hello.wasm", decl = "plugins/hello.k" }
+
 [profile]
 entries = ["main.k"]
+plugin_paths = ["decls"]
@@ -8,7 +8,10 @@ use std::{
 };
 
 use crate::{
-    cache::{load_pkg_cache, save_pkg_cache, CacheOption},
+    cache::{
+        content_hash, load_content_cache, load_pkg_cache, save_content_cache, save_pkg_cache,
+        CacheOption,
+    },
     modfile::{get_vendor_home, KCL_PKG_PATH},
 };
 
@@ -76,3 +79,23 @@ fn test_pkg_cache() {
         Some("test_data".to_string())
     )
 }
+
+#[test]
+fn test_content_cache() {
+    let root = env::temp_dir()
+        .join("kcl_content_cache_test")
+        .display()
+        .to_string();
+    let key = content_hash(&[b"main.k", b"a = 1"]);
+    assert_eq!(key.len(), 32);
+    assert_ne!(key, content_hash(&[b"main.ka", b" = 1"]));
+    save_content_cache(&root, "test_kind", &key, &vec!["test_data".to_string()]).unwrap();
+    assert_eq!(
+        load_content_cache::<Vec<String>>(&root, "test_kind", &key),
+        Some(vec!["test_data".to_string()])
+    );
+    assert_eq!(
+        load_content_cache::<Vec<String>>(&root, "test_kind", &content_hash(&[b"b = 1"])),
+        None
+    );
+}
@@ -52,6 +52,8 @@ register_errors! {
     E2D34: ErrorKind::IllegalInheritError, include_str!("./error_codes/E2D34.md"),
     // E3XXX Runtime Errors
     E3M38: ErrorKind::EvaluationError, include_str!("./error_codes/E2D34.md"),
+    E3M39: ErrorKind::RecursionError, include_str!("./error_codes/E3M39.md"),
+    E3M40: ErrorKind::ResourceLimitError, include_str!("./error_codes/E3M40.md"),
 }
 
 // Error messages for WXXXX errors. Each message should start and end with a
@@ -101,6 +103,7 @@ pub enum ErrorKind {
     IllegalAttributeError,
     IllegalParameterError,
     RecursionError,
+    ResourceLimitError,
     PlanError,
     CannotAddMembers,
     CompileError,
@@ -22,7 +22,7 @@ use compiler_base_session::{Session, SessionDiagnostic};
 use compiler_base_span::{span::new_byte_pos, Span};
 use diagnostic::Range;
 use kclvm_primitives::{DefaultHashBuilder, IndexSet};
-use kclvm_runtime::PanicInfo;
+use kclvm_runtime::{PanicInfo, RuntimeErrorType};
 use std::{any::Any, sync::Arc};
 use thiserror::Error;
 
@@ -310,6 +310,15 @@ impl From<PanicInfo> for Diagnostic {
             )
         };
 
+        diag.code = match panic_info.err_type_code {
+            code if code == RuntimeErrorType::RecursionError as i32 => {
+                Some(DiagnosticId::Error(ErrorKind::RecursionError))
+            }
+            code if code == RuntimeErrorType::ResourceLimitExceeded as i32 => {
+                Some(DiagnosticId::Error(ErrorKind::ResourceLimitError))
+            }
+            _ => None,
+        };
         if panic_info.kcl_config_meta_file.is_empty() {
             return diag;
         }
@@ -0,0 +1,53 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use std::sync::{Arc, RwLock};
+
+use kclvm_ast::ast;
+
+use crate::{Evaluator, PackageResult};
+
+/// PackageCache caches the evaluation results of the imported packages between
+/// runs. The cache keys the results of a package path e.g., by the digests of
+/// the package and its transitive dependencies, and checks the external inputs
+/// recorded in the results when loading them.
+pub trait PackageCache {
+    /// Load the cached result of the package.
+    fn load(&self, pkgpath: &str) -> Option<PackageResult>;
+    /// Save the evaluation result of the package.
+    fn save(&self, pkgpath: &str, result: &PackageResult);
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Set the package cache which reuses the evaluated package values on the import.
+    #[inline]
+    pub fn set_package_cache(&self, cache: &'ctx dyn PackageCache) {
+        *self.package_cache.borrow_mut() = Some(cache);
+    }
+
+    /// Compile the imported package modules in the current package scope. The preloaded
+    /// or cached package result is installed if any, otherwise the package is evaluated
+    /// and its result is saved into the package cache.
+    pub(crate) fn compile_package(&self, pkgpath: &str, modules: &[Arc<RwLock<ast::Module>>]) {
+        let cache = *self.package_cache.borrow();
+        if let Some(package) = self.take_preloaded_package(pkgpath) {
+            self.install_package(modules, &package);
+            if let Some(cache) = cache {
+                cache.save(pkgpath, &package);
+            }
+            return;
+        }
+        match cache {
+            Some(cache) => match cache.load(pkgpath) {
+                Some(package) => self.install_package(modules, &package),
+                None => {
+                    if let Some(package) = self.compile_package_result(modules) {
+                        cache.save(pkgpath, &package);
+                    }
+                }
+            },
+            None => {
+                self.compile_ast_modules(modules);
+            }
+        }
+    }
+}
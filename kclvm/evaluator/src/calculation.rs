@@ -125,12 +125,12 @@ impl<'ctx> Evaluator<'ctx> {
     /// lhs is rhs
     #[inline]
     pub(crate) fn is(&self, lhs: ValueRef, rhs: ValueRef) -> ValueRef {
-        (lhs == rhs).into()
+        lhs.is(&self.runtime_ctx.borrow(), &rhs).into()
     }
     /// lhs is not rhs
     #[inline]
     pub(crate) fn is_not(&self, lhs: ValueRef, rhs: ValueRef) -> ValueRef {
-        (lhs != rhs).into()
+        lhs.is_not(&self.runtime_ctx.borrow(), &rhs).into()
     }
     /// lhs in rhs
     #[inline]
@@ -305,6 +305,9 @@ impl<'ctx> Evaluator<'ctx> {
         if p.is_config() {
             let mut dict: DictValue = Default::default();
             dict.values.insert(key.to_string(), v.clone());
+            if let Some(provenance) = self.current_provenance(op.symbol()) {
+                dict.provenance.insert(key.to_string(), provenance);
+            }
             dict.ops.insert(key.to_string(), op);
             if let Some(index) = insert_index {
                 dict.insert_indexs.insert(key.to_string(), index);
@@ -14,6 +14,15 @@ use crate::{
     EvalContext, Evaluator, LambdaOrSchemaEvalContext,
 };
 
+/// CallFrame is a call in the KCL call stack, which records the callee frame and
+/// the caller position.
+pub(crate) struct CallFrame<'ctx> {
+    frame: Rc<Frame>,
+    filename: &'ctx str,
+    line: i32,
+    col: i32,
+}
+
 impl<'ctx> Evaluator<'ctx> {
     /// Current package path
     #[inline]
@@ -39,6 +48,7 @@ impl<'ctx> Evaluator<'ctx> {
     /// Update current runtime context kcl filename and line
     #[inline]
     pub(crate) fn update_ctx_panic_info<T>(&self, node: &'ctx ast::Node<T>) {
+        *self.current_filename.borrow_mut() = &node.filename;
         let mut ctx = self.runtime_ctx.borrow_mut();
         ctx.panic_info.kcl_file = node.filename.clone();
         ctx.panic_info.kcl_line = node.line as i32;
@@ -257,24 +267,95 @@ impl<'ctx> Evaluator<'ctx> {
         }))
     }
 
-    pub(crate) fn push_backtrace(&self, frame: &Frame) {
-        let ctx = &mut self.runtime_ctx.borrow_mut();
-        if ctx.cfg.debug_mode {
-            let backtrace_frame = BacktraceFrame::from_panic_info(&ctx.panic_info);
-            ctx.backtrace.push(backtrace_frame);
-            ctx.panic_info.kcl_func = frame.proxy.get_name();
+    /// Record a loop iteration step and check the step and time limits.
+    #[inline]
+    pub(crate) fn check_loop_step(&self) {
+        self.runtime_ctx.borrow_mut().check_step();
+    }
+
+    /// Get the display name of the backtrace frame, the lambda is named with its
+    /// definition position e.g., `lambda@main.k:1`.
+    pub(crate) fn frame_name(&self, frame: &Frame) -> String {
+        match &frame.proxy {
+            Proxy::Lambda(lambda) => match lambda.ctx.node.body.first() {
+                Some(stmt) => format!("lambda@{}:{}", stmt.filename, stmt.line),
+                None => format!("{}.lambda", frame.pkgpath),
+            },
+            proxy => format!("{}.{}", frame.pkgpath, proxy.get_name()),
         }
     }
 
-    pub(crate) fn pop_backtrace(&self) {
+    pub(crate) fn push_backtrace(&self, frame: &Rc<Frame>) {
+        self.enter_profile_frame(|| self.frame_name(frame));
+        self.debug_call_enter(frame);
         let ctx = &mut self.runtime_ctx.borrow_mut();
-        if ctx.cfg.debug_mode {
-            if let Some(backtrace_frame) = ctx.backtrace.pop() {
-                ctx.panic_info.kcl_func = backtrace_frame.func;
-                ctx.panic_info.kcl_line = backtrace_frame.line;
-                ctx.panic_info.kcl_file = backtrace_frame.file;
+        // Check the call depth, step and time limits.
+        ctx.enter_call();
+        // Only record the callee and the caller position, the readable backtrace
+        // frames are built when a runtime panic occurs.
+        self.call_stack.borrow_mut().push(CallFrame {
+            frame: frame.clone(),
+            filename: *self.current_filename.borrow(),
+            line: ctx.panic_info.kcl_line,
+            col: ctx.panic_info.kcl_col,
+        });
+    }
+
+    /// Pop the call pushed by `push_backtrace`, which is not called when a runtime
+    /// panic unwinds, and the call stack is restored by `unwind_backtrace`.
+    pub(crate) fn pop_backtrace(&self) {
+        if let Some(call) = self.pop_call() {
+            let ctx = &mut self.runtime_ctx.borrow_mut();
+            if ctx.cfg.debug_mode {
+                ctx.panic_info.kcl_line = call.line;
+                ctx.panic_info.kcl_file = call.filename.to_string();
+                *self.current_filename.borrow_mut() = call.filename;
+            }
+        }
+    }
+
+    fn pop_call(&self) -> Option<CallFrame<'ctx>> {
+        self.exit_profile_frame();
+        self.debug_call_exit();
+        self.runtime_ctx.borrow_mut().exit_call();
+        self.call_stack.borrow_mut().pop()
+    }
+
+    /// Get the current depth of the KCL call stack.
+    #[inline]
+    pub(crate) fn backtrace_depth(&self) -> usize {
+        self.call_stack.borrow().len()
+    }
+
+    /// Pop the calls above the `depth` left by a caught runtime panic.
+    pub(crate) fn unwind_backtrace(&self, depth: usize) {
+        while self.backtrace_depth() > depth {
+            self.pop_call();
+        }
+    }
+
+    /// Record the KCL call stack of the runtime panic as the readable backtrace
+    /// frames in the runtime context and unwind the call stack.
+    pub fn record_panic_backtrace(&self) {
+        {
+            let call_stack = self.call_stack.borrow();
+            let ctx = &mut self.runtime_ctx.borrow_mut();
+            // Each call is made in the function of the previous call.
+            let mut func = ctx.panic_info.kcl_func.clone();
+            let mut backtrace = Vec::with_capacity(call_stack.len());
+            for call in call_stack.iter() {
+                backtrace.push(BacktraceFrame {
+                    file: call.filename.to_string(),
+                    func,
+                    col: call.col,
+                    line: call.line,
+                });
+                func = call.frame.proxy.get_name();
             }
+            ctx.panic_info.kcl_func = func;
+            ctx.backtrace = backtrace;
         }
+        self.unwind_backtrace(0);
     }
 
     #[inline]
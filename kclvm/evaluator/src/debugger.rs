@@ -0,0 +1,128 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use anyhow::{anyhow, Result};
+use kclvm_ast::ast;
+use kclvm_ast::walker::TypedResultWalker;
+use kclvm_runtime::ValueRef;
+
+use crate::proxy::Frame;
+use crate::{Evaluator, GLOBAL_LEVEL, INNER_LEVEL};
+
+/// DebugHook is called by the evaluator before each statement and around each
+/// lambda, schema and rule call, which can block the evaluation to inspect the
+/// scope variables and the call stack e.g., a debug adapter. The hook outlives
+/// the evaluator, so the expressions it owns can be evaluated with
+/// [Evaluator::eval_debug_expr].
+pub trait DebugHook {
+    /// Called before evaluating the statement.
+    fn on_stmt<'ctx>(&'ctx self, evaluator: &Evaluator<'ctx>, stmt: &ast::Node<ast::Stmt>);
+    /// Called when entering a lambda, schema or rule call with the frame name.
+    fn on_call_enter(&self, name: &str);
+    /// Called when leaving a lambda, schema or rule call.
+    fn on_call_exit(&self);
+}
+
+/// The variables in a scope of the current evaluation position.
+#[derive(Debug, Clone)]
+pub struct DebugScope {
+    /// The scope name e.g., `Locals`, `Globals` and `Schema`.
+    pub name: String,
+    pub variables: Vec<(String, ValueRef)>,
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Set the debug hook which is called during the evaluation.
+    #[inline]
+    pub fn set_debug_hook(&self, hook: &'ctx dyn DebugHook) {
+        *self.debug_hook.borrow_mut() = Some(hook);
+    }
+
+    /// Get the variables of the local, global and schema scopes at the current
+    /// evaluation position, inner local variables shadow the outer ones.
+    pub fn debug_scopes(&self) -> Vec<DebugScope> {
+        let mut locals: Vec<(String, ValueRef)> = vec![];
+        let mut globals = vec![];
+        {
+            let pkg_scopes = self.pkg_scopes.borrow();
+            if let Some(scopes) = pkg_scopes.get(&self.current_pkgpath()) {
+                for scope in scopes.iter().skip(INNER_LEVEL).rev() {
+                    for (name, value) in &scope.variables {
+                        if !locals.iter().any(|(n, _)| n == name) {
+                            locals.push((name.clone(), value.clone()));
+                        }
+                    }
+                }
+                if let Some(scope) = scopes.get(GLOBAL_LEVEL) {
+                    globals = scope
+                        .variables
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.clone()))
+                        .collect();
+                }
+            }
+        }
+        let mut debug_scopes = vec![
+            DebugScope {
+                name: "Locals".to_string(),
+                variables: locals,
+            },
+            DebugScope {
+                name: "Globals".to_string(),
+                variables: globals,
+            },
+        ];
+        if let Some(ctx) = self.schema_stack.borrow().last() {
+            let value = ctx.value();
+            if value.is_config() {
+                debug_scopes.push(DebugScope {
+                    name: "Schema".to_string(),
+                    variables: value
+                        .as_dict_ref()
+                        .values
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.clone()))
+                        .collect(),
+                });
+            }
+        }
+        debug_scopes
+    }
+
+    /// Evaluate the expression at the current evaluation position e.g., a breakpoint
+    /// condition or a watch expression. The runtime error is returned as an error
+    /// instead of aborting the evaluation.
+    pub fn eval_debug_expr(&self, expr: &'ctx ast::Node<ast::Expr>) -> Result<ValueRef> {
+        let depth = self.backtrace_depth();
+        let call_depth = self.runtime_ctx.borrow().call_depth();
+        let result = std::panic::catch_unwind(|| self.walk_expr(expr));
+        self.unwind_backtrace(depth);
+        self.runtime_ctx.borrow_mut().unwind_calls(call_depth);
+        match result {
+            Ok(result) => result,
+            Err(err) => Err(anyhow!(kclvm_error::err_to_str(err))),
+        }
+    }
+
+    #[inline]
+    fn get_debug_hook(&self) -> Option<&'ctx dyn DebugHook> {
+        *self.debug_hook.borrow()
+    }
+
+    pub(crate) fn debug_stmt(&self, stmt: &ast::Node<ast::Stmt>) {
+        if let Some(hook) = self.get_debug_hook() {
+            hook.on_stmt(self, stmt);
+        }
+    }
+
+    pub(crate) fn debug_call_enter(&self, frame: &Frame) {
+        if let Some(hook) = self.get_debug_hook() {
+            hook.on_call_enter(&self.frame_name(frame));
+        }
+    }
+
+    pub(crate) fn debug_call_exit(&self) {
+        if let Some(hook) = self.get_debug_hook() {
+            hook.on_call_exit();
+        }
+    }
+}
@@ -101,8 +101,6 @@ impl<'ctx> Evaluator<'ctx> {
         // Change the backtrace metadata: filename, line, etc.
         self.push_backtrace(&frame);
         defer! {
-            // Recover the backtrace metadata: filename, line, etc.
-            self.pop_backtrace();
             // Recover the package path scope.
             self.pop_pkgpath();
         }
@@ -132,6 +130,8 @@ impl<'ctx> Evaluator<'ctx> {
             // The built-in lazy eval semantics prevent invoking
             Proxy::Global(_) => self.undefined_value(),
         };
+        // Recover the backtrace metadata: filename, line, etc.
+        self.pop_backtrace();
         value
     }
 }
@@ -3,14 +3,19 @@
 #[cfg(test)]
 mod tests;
 
+mod cache;
 mod calculation;
 mod context;
+mod debugger;
 mod error;
 mod func;
 #[macro_use]
 mod lazy;
 mod module;
 mod node;
+mod parallel;
+mod profile;
+mod provenance;
 mod proxy;
 mod rule;
 mod runtime;
@@ -22,11 +27,15 @@ mod value;
 
 extern crate kclvm_error;
 
+pub use cache::PackageCache;
+pub use debugger::{DebugHook, DebugScope};
 use func::FunctionEvalContextRef;
 use generational_arena::{Arena, Index};
 use kclvm_primitives::IndexMap;
-use kclvm_runtime::val_plan::KCL_PRIVATE_VAR_PREFIX;
+use kclvm_runtime::val_plan::{PlanFormat, KCL_PRIVATE_VAR_PREFIX};
 use lazy::{BacktrackMeta, LazyEvalScope};
+pub use parallel::{package_import_graph, ContextFactory, PackageResult};
+pub use profile::{ProfileEntry, ProfileFormat, Profiler};
 use proxy::{Frame, Proxy};
 use rule::RuleEvalContextRef;
 use schema::SchemaEvalContextRef;
@@ -35,12 +44,14 @@ use std::collections::{HashMap, HashSet};
 use std::panic::RefUnwindSafe;
 use std::rc::Rc;
 use std::str;
+use std::sync::Arc;
 use std::{cell::RefCell, panic::UnwindSafe};
 
 use crate::error as kcl_error;
 use anyhow::Result;
+use context::CallFrame;
 use kclvm_ast::ast::{self, AstIndex};
-use kclvm_runtime::{Context, ValueRef};
+use kclvm_runtime::{Context, Provenance, ValueRef};
 
 /// SCALAR_KEY denotes the temp scalar key for the global variable json plan process.
 const SCALAR_KEY: &str = "";
@@ -91,6 +102,22 @@ pub struct Evaluator<'ctx> {
     pub backtrack_meta: RefCell<Vec<BacktrackMeta>>,
     /// Current AST id for the evaluator walker.
     pub ast_id: RefCell<AstIndex>,
+    /// The filename of the current evaluating node.
+    pub(crate) current_filename: RefCell<&'ctx str>,
+    /// The KCL call stack of the functions, schemas and rules.
+    pub(crate) call_stack: RefCell<Vec<CallFrame<'ctx>>>,
+    /// The evaluation profiler, which is enabled in the profiling mode.
+    pub profiler: RefCell<Option<Profiler>>,
+    /// The debug hook called during the evaluation, which is set in the debug mode.
+    pub debug_hook: RefCell<Option<&'ctx dyn DebugHook>>,
+    /// The current source position for the provenance of config keys.
+    pub provenance_pos: RefCell<Provenance>,
+    /// The provenance of the main package global variables.
+    pub global_provenance: RefCell<IndexMap<String, Provenance>>,
+    /// The imported package results evaluated in parallel, which are installed on the import.
+    pub(crate) preloaded_packages: RefCell<HashMap<String, Arc<PackageResult>>>,
+    /// The package cache to reuse the evaluated package values between runs.
+    pub package_cache: RefCell<Option<&'ctx dyn PackageCache>>,
 }
 
 #[derive(Clone)]
@@ -155,7 +182,15 @@ impl<'ctx> Evaluator<'ctx> {
             local_vars: RefCell::new(Default::default()),
             backtrack_meta: RefCell::new(Default::default()),
             ast_id: RefCell::new(AstIndex::default()),
+            current_filename: RefCell::new(""),
+            call_stack: RefCell::new(vec![]),
             ctx_stack: RefCell::new(Default::default()),
+            profiler: RefCell::new(None),
+            debug_hook: RefCell::new(None),
+            provenance_pos: RefCell::new(Default::default()),
+            global_provenance: RefCell::new(Default::default()),
+            preloaded_packages: RefCell::new(Default::default()),
+            package_cache: RefCell::new(None),
         }
     }
 
@@ -167,6 +202,20 @@ impl<'ctx> Evaluator<'ctx> {
         Ok(self.plan_globals_to_string())
     }
 
+    /// Evaluate the program and write the planned result in the format into the
+    /// writer, which does not build the JSON and YAML result strings.
+    pub fn run_to_writer(
+        self: &Evaluator<'ctx>,
+        format: PlanFormat,
+        writer: &mut dyn std::io::Write,
+    ) -> Result<()> {
+        let modules = self.program.get_modules_for_pkg(kclvm_ast::MAIN_PKG);
+        self.init_scope(kclvm_ast::MAIN_PKG);
+        self.compile_ast_modules(&modules);
+        let value = self.plan_globals_value();
+        self.plan_value_to_writer(&value, format, writer)
+    }
+
     /// Evaluate the program with the function mode and return the JSON and YAML result,
     /// which means treating the files in the entire main package as a function run to
     /// return the result of the function run, rather than a dictionary composed of each
@@ -182,7 +231,13 @@ impl<'ctx> Evaluator<'ctx> {
     }
 
     /// Plan globals to a planed json and yaml string.
+    #[inline]
     pub(crate) fn plan_globals_to_string(&self) -> (String, String) {
+        self.plan_value(&self.plan_globals_value())
+    }
+
+    /// Merge the global scalars and variables into the value to plan.
+    pub(crate) fn plan_globals_value(&self) -> ValueRef {
         let current_pkgpath = self.current_pkgpath();
         let pkg_scopes = &self.pkg_scopes.borrow();
         let scopes = pkg_scopes
@@ -196,7 +251,7 @@ impl<'ctx> Evaluator<'ctx> {
         let mut global_dict = self.dict_value();
         // Plan empty dict result.
         if scalars.is_empty() && globals.is_empty() {
-            return self.plan_value(&global_dict);
+            return global_dict;
         }
         // Deal scalars
         for scalar in scalars.iter() {
@@ -213,10 +268,9 @@ impl<'ctx> Evaluator<'ctx> {
             self.dict_insert_merge_value(&mut value_dict, name.as_str(), value);
             self.dict_insert_merge_value(&mut global_dict, SCALAR_KEY, &value_dict);
         }
-        // Plan result to JSON and YAML string.
         match global_dict.dict_get_value(SCALAR_KEY) {
-            Some(value) => self.plan_value(&value),
-            None => self.plan_value(&self.dict_value()),
+            Some(value) => value,
+            None => self.dict_value(),
         }
     }
 
@@ -237,6 +291,23 @@ impl<'ctx> Evaluator<'ctx> {
         ctx.yaml_result = yaml_string.clone();
         (json_string, yaml_string)
     }
+
+    /// Plan the value and write the result in the format into the writer.
+    pub fn plan_value_to_writer(
+        &self,
+        value: &ValueRef,
+        format: PlanFormat,
+        writer: &mut dyn std::io::Write,
+    ) -> Result<()> {
+        let mut ctx = self.runtime_ctx.borrow_mut();
+        let value = match ctx.buffer.custom_manifests_output.clone() {
+            Some(output) => ValueRef::from_yaml_stream(&mut ctx, &output).unwrap(),
+            None => value.clone(),
+        };
+        value.plan_to_writer(&ctx, format, writer)?;
+        writer.flush()?;
+        Ok(())
+    }
 }
 
 impl UnwindSafe for Evaluator<'_> {}
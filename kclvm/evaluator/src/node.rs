@@ -40,8 +40,16 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         backtrack_break_here!(self, stmt);
         self.update_ctx_panic_info(stmt);
         self.update_ast_id(stmt);
+        let provenance_pos = self.saved_provenance_pos();
+        defer! {
+            self.restore_provenance_pos(provenance_pos);
+        }
+        self.update_provenance_stmt_pos(stmt);
+        self.debug_stmt(stmt);
         let value = match &stmt.node {
             ast::Stmt::TypeAlias(type_alias) => self.walk_type_alias_stmt(type_alias),
+            ast::Stmt::Enum(enum_stmt) => self.walk_enum_stmt(enum_stmt),
+            ast::Stmt::Export(export_stmt) => self.walk_export_stmt(export_stmt),
             ast::Stmt::Expr(expr_stmt) => self.walk_expr_stmt(expr_stmt),
             ast::Stmt::Unification(unification_stmt) => {
                 self.walk_unification_stmt(unification_stmt)
@@ -98,6 +106,16 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         self.ok_result()
     }
 
+    fn walk_enum_stmt(&self, _enum_stmt: &'ctx ast::EnumStmt) -> Self::Result {
+        // Nothing to do, because all enum members have been replaced at compile time
+        self.ok_result()
+    }
+
+    fn walk_export_stmt(&self, _export_stmt: &'ctx ast::ExportStmt) -> Self::Result {
+        // Nothing to do, because the export list has been checked at compile time
+        self.ok_result()
+    }
+
     fn walk_assign_stmt(&self, assign_stmt: &'ctx ast::AssignStmt) -> Self::Result {
         self.clear_local_vars();
         // Set target vars.
@@ -243,7 +261,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
                         m
                     })
                     .collect();
-                self.compile_ast_modules(&modules);
+                self.compile_package(&import_stmt.path.node, &modules);
                 self.pop_pkgpath();
             }
         }
@@ -273,6 +291,21 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         if !self.store_variable(name, function.clone()) {
             self.add_variable(name, function.clone());
         }
+        // Record the parent schema and mixins to check the `is` operator.
+        let bases = schema_stmt
+            .parent_name
+            .iter()
+            .chain(schema_stmt.mixins.iter())
+            .filter_map(|name| {
+                self.walk_identifier_with_ctx(&name.node, &ast::ExprContext::Load, None)
+                    .ok()
+                    .and_then(|func| func.try_get_runtime_type())
+            })
+            .collect();
+        self.runtime_ctx
+            .borrow_mut()
+            .schema_bases
+            .insert(runtime_type.clone(), bases);
         self.schemas.borrow_mut().insert(runtime_type, index);
         Ok(function)
     }
@@ -330,6 +363,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
             ast::Expr::Config(config_expr) => self.walk_config_expr(config_expr),
             ast::Expr::Check(check) => self.walk_check_expr(check),
             ast::Expr::Lambda(lambda) => self.walk_lambda_expr(lambda),
+            ast::Expr::Match(match_expr) => self.walk_match_expr(match_expr),
             ast::Expr::Subscript(subscript) => self.walk_subscript(subscript),
             ast::Expr::Keyword(keyword) => self.walk_keyword(keyword),
             ast::Expr::Arguments(..) => self.ok_result(),
@@ -372,6 +406,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         // Start block
         while let Some((next_value, key, value)) = iter_value.next_with_key_value(&iter_host_value)
         {
+            self.check_loop_step();
             // Next value block
             let variables = &quant_expr.variables;
             for v in variables {
@@ -875,7 +910,6 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
                 self.push_pkgpath(&frame.pkgpath);
                 self.push_backtrace(&frame);
                 defer! {
-                    self.pop_backtrace();
                     self.pop_pkgpath();
                 }
                 let value = (schema.body)(
@@ -884,12 +918,12 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
                     &list_value,
                     &dict_value,
                 );
+                self.pop_backtrace();
                 value
             } else if let Proxy::Rule(rule) = &frame.proxy {
                 self.push_pkgpath(&frame.pkgpath);
                 self.push_backtrace(&frame);
                 defer! {
-                    self.pop_backtrace();
                     self.pop_pkgpath();
                 }
                 let value = (rule.body)(
@@ -898,6 +932,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
                     &list_value,
                     &dict_value,
                 );
+                self.pop_backtrace();
                 value
             } else {
                 self.undefined_value()
@@ -978,6 +1013,32 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         Ok(self.proxy_function_value(index))
     }
 
+    fn walk_match_expr(&self, match_expr: &'ctx ast::MatchExpr) -> Self::Result {
+        let subject = self.walk_expr(&match_expr.subject)?;
+        for case in &match_expr.cases {
+            // Enter the case scope for the pattern capture variables.
+            self.enter_scope();
+            defer! {
+                self.leave_scope();
+                self.clear_local_vars();
+            }
+            if !self.walk_pattern(&case.node.pattern, &subject)? {
+                continue;
+            }
+            if let Some(guard) = &case.node.guard {
+                let value = self.walk_expr(guard)?;
+                if !self.value_is_truthy(&value) {
+                    continue;
+                }
+            }
+            return self.walk_expr(&case.node.body);
+        }
+        panic!(
+            "no match case found for the value {}",
+            subject.to_json_string()
+        )
+    }
+
     fn walk_keyword(&self, _keyword: &'ctx ast::Keyword) -> Self::Result {
         // Nothing to do
         self.ok_result()
@@ -1133,7 +1194,9 @@ impl<'ctx> Evaluator<'ctx> {
         let mut result = self.ok_result();
         for stmt in stmts {
             if !matches!(&stmt.node, ast::Stmt::Import(..)) {
+                self.enter_profile_frame(|| self.profile_stmt_name(stmt));
                 result = self.walk_stmt(stmt);
+                self.exit_profile_frame();
             }
         }
         result
@@ -1171,7 +1234,9 @@ impl<'ctx> Evaluator<'ctx> {
                             .expect(&format!("module {:?} not found in program", module));
                         if let Some(stmt) = module.body.get(setter.stmt) {
                             self.push_backtrack_meta(setter);
+                            self.enter_profile_frame(|| self.profile_stmt_name(stmt));
                             self.walk_stmt(stmt).expect(INTERNAL_ERROR_MSG);
+                            self.exit_profile_frame();
                             self.pop_backtrack_meta();
                         }
                     }
@@ -1229,6 +1294,7 @@ impl<'ctx> Evaluator<'ctx> {
             // Global variables
             if self.scope_level() == GLOBAL_LEVEL {
                 self.add_or_update_global_variable(name, right_value.clone(), true);
+                self.record_global_provenance(name);
             // Lambda local variables.
             } else if self.is_in_lambda() {
                 let value = right_value.clone();
@@ -1525,6 +1591,7 @@ impl<'ctx> Evaluator<'ctx> {
 
         while let Some((next_value, key, value)) = iter_value.next_with_key_value(&iter_host_value)
         {
+            self.check_loop_step();
             for v in targets {
                 self.add_local_var(&v.node.names[0].node)
             }
@@ -1570,6 +1637,7 @@ impl<'ctx> Evaluator<'ctx> {
             }
             let next_gen_index = gen_index + 1;
             if next_gen_index >= generators.len() {
+                self.runtime_ctx.borrow_mut().alloc_values(1);
                 match comp_type {
                     ast::CompType::List => {
                         let item = self.walk_expr(elt).expect(kcl_error::RUNTIME_ERROR_MSG);
@@ -1607,7 +1675,122 @@ impl<'ctx> Evaluator<'ctx> {
         }
     }
 
+    /// Match the value with the pattern and store the capture variables, returns
+    /// whether the value is matched.
+    pub(crate) fn walk_pattern(
+        &self,
+        pattern: &'ctx NodeRef<ast::Pattern>,
+        value: &ValueRef,
+    ) -> anyhow::Result<bool> {
+        let matched = match &pattern.node {
+            ast::Pattern::Wildcard => true,
+            ast::Pattern::Value(expr) => {
+                let pattern_value = self.walk_expr(expr)?;
+                value.cmp_equal(&pattern_value)
+            }
+            ast::Pattern::Capture(name) => {
+                self.store_pattern_capture(name, value.clone())?;
+                true
+            }
+            ast::Pattern::Schema(schema_pattern) => {
+                let schema_type = self.walk_identifier(&schema_pattern.name.node)?;
+                if !value.is(&self.runtime_ctx.borrow(), &schema_type) {
+                    return Ok(false);
+                }
+                for entry in &schema_pattern.entries {
+                    match value.dict_get_value(&entry.node.key.node) {
+                        Some(attr_value) => {
+                            if !self.walk_pattern(&entry.node.value, &attr_value)? {
+                                return Ok(false);
+                            }
+                        }
+                        None => return Ok(false),
+                    }
+                }
+                true
+            }
+            ast::Pattern::List(list_pattern) => {
+                if !value.match_sequence(list_pattern.elts.len(), list_pattern.rest.is_some()) {
+                    return Ok(false);
+                }
+                for (i, elt) in list_pattern.elts.iter().enumerate() {
+                    let item = value
+                        .list_get(i as isize)
+                        .expect(kcl_error::INTERNAL_ERROR_MSG);
+                    if !self.walk_pattern(elt, &item)? {
+                        return Ok(false);
+                    }
+                }
+                if let Some(rest) = &list_pattern.rest {
+                    let rest_value = value.list_slice(
+                        &ValueRef::int(list_pattern.elts.len() as i64),
+                        &ValueRef::none(),
+                        &ValueRef::none(),
+                    );
+                    self.store_pattern_capture(rest, rest_value)?;
+                }
+                true
+            }
+            ast::Pattern::Dict(dict_pattern) => {
+                let keys: Vec<String> = dict_pattern
+                    .entries
+                    .iter()
+                    .map(|entry| entry.node.key.node.clone())
+                    .collect();
+                if !value.match_mapping(&keys) {
+                    return Ok(false);
+                }
+                for entry in &dict_pattern.entries {
+                    let entry_value = value
+                        .dict_get_value(&entry.node.key.node)
+                        .expect(kcl_error::INTERNAL_ERROR_MSG);
+                    if !self.walk_pattern(&entry.node.value, &entry_value)? {
+                        return Ok(false);
+                    }
+                }
+                if let Some(rest) = &dict_pattern.rest {
+                    self.store_pattern_capture(rest, value.match_rest_mapping(&keys))?;
+                }
+                true
+            }
+            ast::Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    if self.walk_pattern(alternative, value)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            ast::Pattern::As(as_pattern) => {
+                if !self.walk_pattern(&as_pattern.pattern, value)? {
+                    return Ok(false);
+                }
+                self.store_pattern_capture(&as_pattern.name, value.clone())?;
+                true
+            }
+        };
+        Ok(matched)
+    }
+
+    /// Store the pattern capture variable in the current match case scope.
+    fn store_pattern_capture(
+        &self,
+        name: &'ctx NodeRef<ast::Identifier>,
+        value: ValueRef,
+    ) -> anyhow::Result<()> {
+        let var_name = name.node.get_name();
+        if var_name != "_" {
+            self.add_local_var(&var_name);
+            self.walk_identifier_with_ctx(&name.node, &ast::ExprContext::Store, Some(value))?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn walk_config_entries(&self, items: &'ctx [NodeRef<ConfigEntry>]) -> EvalResult {
+        let provenance_pos = self.saved_provenance_pos();
+        defer! {
+            self.restore_provenance_pos(provenance_pos);
+        }
         let mut config_value = self.dict_value();
         for item in items {
             let value = self.walk_expr(&item.node.value)?;
@@ -1646,6 +1829,7 @@ impl<'ctx> Evaluator<'ctx> {
                     Some(name) if !self.is_local_var(name) => self.string_value(name),
                     _ => self.walk_expr(key_node)?,
                 };
+                self.update_provenance_pos(key_node);
                 self.dict_insert(
                     &mut config_value,
                     &key.as_str(),
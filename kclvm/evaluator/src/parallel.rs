@@ -0,0 +1,509 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The package level parallel scheduler. Runtime values are reference counted
+//! and can not be shared between threads, thus each imported package is
+//! evaluated by a standalone evaluator in a worker thread and its global values
+//! are copied into the thread-safe [SendValue]s. The main evaluator installs
+//! the package results at the import statements in the import order, which
+//! makes the merged result the same as the serial evaluation.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use kclvm_ast::ast;
+use kclvm_ast::walker::TypedResultWalker;
+use kclvm_primitives::IndexMap;
+use kclvm_runtime::{
+    ConfigEntryOperationKind, Context, DictValue, EvalInputs, ListValue, Provenance, SchemaValue,
+    Value, ValueRef, PKG_PATH_PREFIX,
+};
+use kclvm_sema::{builtin, plugin};
+use serde::{Deserialize, Serialize};
+
+use crate::{Evaluator, GLOBAL_LEVEL};
+
+/// SendValue is a thread-safe copy of a runtime data value. Functions and
+/// types are not data values and can not be copied.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) enum SendValue {
+    Undefined,
+    None,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Unit(f64, i64, String),
+    Str(String),
+    List(Vec<SendValue>),
+    Dict(Box<SendDict>),
+    Schema(Box<SendSchema>),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct SendDict {
+    values: Vec<(String, SendValue)>,
+    ops: IndexMap<String, ConfigEntryOperationKind>,
+    insert_indexs: IndexMap<String, i32>,
+    attr_map: IndexMap<String, String>,
+    potential_schema: Option<String>,
+    provenance: IndexMap<String, Provenance>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct SendSchema {
+    name: String,
+    pkgpath: String,
+    config: SendDict,
+    config_keys: Vec<String>,
+    config_meta: SendValue,
+    optional_mapping: SendValue,
+    args: SendValue,
+    kwargs: SendValue,
+}
+
+impl SendValue {
+    /// Copy the runtime value, returns `None` when the value contains functions.
+    pub(crate) fn from_value(value: &ValueRef) -> Option<Self> {
+        Some(match &*value.rc.borrow() {
+            Value::undefined => SendValue::Undefined,
+            Value::none => SendValue::None,
+            Value::bool_value(v) => SendValue::Bool(*v),
+            Value::int_value(v) => SendValue::Int(*v),
+            Value::float_value(v) => SendValue::Float(*v),
+            Value::unit_value(v, raw, unit) => SendValue::Unit(*v, *raw, unit.clone()),
+            Value::str_value(v) => SendValue::Str(v.clone()),
+            Value::list_value(list) => SendValue::List(
+                list.values
+                    .iter()
+                    .map(SendValue::from_value)
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            Value::dict_value(dict) => SendValue::Dict(Box::new(SendDict::from_dict(dict)?)),
+            Value::schema_value(schema) => SendValue::Schema(Box::new(SendSchema {
+                name: schema.name.clone(),
+                pkgpath: schema.pkgpath.clone(),
+                config: SendDict::from_dict(&schema.config)?,
+                config_keys: schema.config_keys.clone(),
+                config_meta: SendValue::from_value(&schema.config_meta)?,
+                optional_mapping: SendValue::from_value(&schema.optional_mapping)?,
+                args: SendValue::from_value(&schema.args)?,
+                kwargs: SendValue::from_value(&schema.kwargs)?,
+            })),
+            Value::func_value(_) => return None,
+        })
+    }
+
+    /// Create a new runtime value from the copy.
+    pub(crate) fn to_value(&self) -> ValueRef {
+        let value = match self {
+            SendValue::Undefined => Value::undefined,
+            SendValue::None => Value::none,
+            SendValue::Bool(v) => Value::bool_value(*v),
+            SendValue::Int(v) => Value::int_value(*v),
+            SendValue::Float(v) => Value::float_value(*v),
+            SendValue::Unit(v, raw, unit) => Value::unit_value(*v, *raw, unit.clone()),
+            SendValue::Str(v) => Value::str_value(v.clone()),
+            SendValue::List(values) => Value::list_value(Box::new(ListValue {
+                values: values.iter().map(SendValue::to_value).collect(),
+            })),
+            SendValue::Dict(dict) => Value::dict_value(Box::new(dict.to_dict())),
+            SendValue::Schema(schema) => Value::schema_value(Box::new(SchemaValue {
+                name: schema.name.clone(),
+                pkgpath: schema.pkgpath.clone(),
+                config: Box::new(schema.config.to_dict()),
+                config_keys: schema.config_keys.clone(),
+                config_meta: schema.config_meta.to_value(),
+                optional_mapping: schema.optional_mapping.to_value(),
+                args: schema.args.to_value(),
+                kwargs: schema.kwargs.to_value(),
+            })),
+        };
+        ValueRef {
+            rc: Rc::new(RefCell::new(value)),
+        }
+    }
+}
+
+impl SendDict {
+    fn from_dict(dict: &DictValue) -> Option<Self> {
+        Some(SendDict {
+            values: dict
+                .values
+                .iter()
+                .map(|(k, v)| Some((k.clone(), SendValue::from_value(v)?)))
+                .collect::<Option<Vec<_>>>()?,
+            ops: dict.ops.clone(),
+            insert_indexs: dict.insert_indexs.clone(),
+            attr_map: dict.attr_map.clone(),
+            potential_schema: dict.potential_schema.clone(),
+            provenance: dict.provenance.clone(),
+        })
+    }
+
+    fn to_dict(&self) -> DictValue {
+        DictValue {
+            values: self
+                .values
+                .iter()
+                .map(|(k, v)| (k.clone(), v.to_value()))
+                .collect(),
+            ops: self.ops.clone(),
+            insert_indexs: self.insert_indexs.clone(),
+            attr_map: self.attr_map.clone(),
+            potential_schema: self.potential_schema.clone(),
+            provenance: self.provenance.clone(),
+        }
+    }
+}
+
+/// PackageResult is the evaluation result of an imported package, which is evaluated
+/// in a worker thread or saved into the package cache.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PackageResult {
+    /// The global variables of the package in the definition order, except the schema,
+    /// rule and lambda definitions which are defined again on the installation.
+    globals: Vec<(String, SendValue)>,
+    /// The global lambda definitions of the package.
+    lambdas: HashSet<String>,
+    /// The schema instances created by the package, keyed by the schema runtime type.
+    instances: Vec<(String, Vec<SendValue>)>,
+    /// The print messages of the package without the messages of its dependencies.
+    log_message: String,
+    /// The external inputs read by the package.
+    inputs: EvalInputs,
+}
+
+impl PackageResult {
+    /// The external inputs read by the package and its dependencies.
+    #[inline]
+    pub fn inputs(&self) -> &EvalInputs {
+        &self.inputs
+    }
+}
+
+/// The context factory of the worker evaluators.
+pub type ContextFactory<'a> = dyn Fn() -> Context + Sync + 'a;
+
+impl Evaluator<'_> {
+    /// Evaluate the independent imported packages of the program in parallel with at
+    /// most `threads` worker threads before running the program. Each worker evaluator
+    /// uses a new runtime context created by `new_ctx`, which should be configured
+    /// the same as the context of this evaluator.
+    ///
+    /// The packages are scheduled in the waves of the package import graph, and the
+    /// packages which can not be evaluated in parallel e.g., the packages whose data
+    /// globals contain functions, are still evaluated serially when they are imported.
+    ///
+    /// The worker evaluators are read-only and can not call plugins, the packages with
+    /// side effects e.g., `file.write` fail in the workers before the side effects take
+    /// place and are evaluated serially, thus the side effects happen exactly once.
+    pub fn preload_packages(&self, threads: usize, new_ctx: &ContextFactory) {
+        // The profiler and the debugger are bound to the evaluator in the current thread.
+        if threads < 2 || self.profiler.borrow().is_some() || self.debug_hook.borrow().is_some() {
+            return;
+        }
+        let graph = package_import_graph(self.program);
+        let mut preloaded: HashMap<String, Arc<PackageResult>> = HashMap::new();
+        let mut done: HashSet<&String> = HashSet::new();
+        while done.len() < graph.len() {
+            let wave: Vec<&String> = graph
+                .iter()
+                .filter(|(pkgpath, deps)| {
+                    !done.contains(pkgpath) && deps.iter().all(|dep| done.contains(dep))
+                })
+                .map(|(pkgpath, _)| pkgpath)
+                .collect();
+            // Cyclic imports are reported by the serial evaluation.
+            if wave.is_empty() {
+                break;
+            }
+            let results = self.eval_packages_in_parallel(&wave, &preloaded, threads, new_ctx);
+            for (pkgpath, result) in wave.iter().zip(results) {
+                if let Some(result) = result {
+                    preloaded.insert(pkgpath.to_string(), Arc::new(result));
+                }
+                done.insert(*pkgpath);
+            }
+        }
+        *self.preloaded_packages.borrow_mut() = preloaded;
+    }
+
+    fn eval_packages_in_parallel(
+        &self,
+        pkgpaths: &[&String],
+        preloaded: &HashMap<String, Arc<PackageResult>>,
+        threads: usize,
+        new_ctx: &ContextFactory,
+    ) -> Vec<Option<PackageResult>> {
+        let program = self.program;
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<PackageResult>>> =
+            Mutex::new((0..pkgpaths.len()).map(|_| None).collect());
+        std::thread::scope(|s| {
+            for _ in 0..threads.min(pkgpaths.len()) {
+                s.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    if index >= pkgpaths.len() {
+                        break;
+                    }
+                    let result = eval_package(program, pkgpaths[index], preloaded, new_ctx);
+                    results.lock().unwrap_or_else(PoisonError::into_inner)[index] = result;
+                });
+            }
+        });
+        results.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Evaluate the package in the worker evaluator and copy its results.
+    fn eval_package_result(&self, pkgpath: &str) -> Option<PackageResult> {
+        let modules = self.program.get_modules_for_pkg(pkgpath);
+        let pkgpath = format!("{}{}", PKG_PATH_PREFIX, pkgpath);
+        self.push_pkgpath(&pkgpath);
+        self.init_scope(&pkgpath);
+        let result = self.compile_package_result(&modules);
+        self.pop_pkgpath();
+        result
+    }
+
+    /// Evaluate the package modules in the current package scope and copy its results.
+    /// The package is still evaluated when it returns `None` for the results which can
+    /// not be copied.
+    pub(crate) fn compile_package_result(
+        &self,
+        modules: &[Arc<RwLock<ast::Module>>],
+    ) -> Option<PackageResult> {
+        for module in modules {
+            let module = module.read().expect("Failed to acquire module lock");
+            self.predefine_global_vars(&module);
+        }
+        for module in modules {
+            let module = module.read().expect("Failed to acquire module lock");
+            self.compile_module_import_and_types(&module);
+        }
+        // All the dependencies are evaluated on the import statements, thus the
+        // following outputs are only produced by the package itself.
+        let (log_start, instance_lens) = {
+            let ctx = self.runtime_ctx.borrow();
+            let instance_lens: HashMap<(String, String), usize> = ctx
+                .instances
+                .iter()
+                .flat_map(|(ty, pkg_instances)| {
+                    pkg_instances
+                        .iter()
+                        .map(move |(pkg, values)| ((ty.clone(), pkg.clone()), values.len()))
+                })
+                .collect();
+            (ctx.log_message.len(), instance_lens)
+        };
+        for module in modules {
+            let module = module.read().expect("Failed to acquire module lock");
+            self.walk_module(&module)
+                .expect(crate::error::RUNTIME_ERROR_MSG);
+        }
+        let pkgpath = self.current_pkgpath();
+        let ctx = self.runtime_ctx.borrow();
+        // The manifests output is global and can not be merged.
+        if ctx.buffer.custom_manifests_output.is_some() {
+            return None;
+        }
+        let pkg_scopes = self.pkg_scopes.borrow();
+        let globals = pkg_scopes
+            .get(&pkgpath)?
+            .get(GLOBAL_LEVEL)?
+            .variables
+            .iter();
+        let (types, lambdas) = package_definitions(modules);
+        let mut instances = vec![];
+        for (ty, pkg_instances) in &ctx.instances {
+            let mut values = vec![];
+            for (pkg, pkg_values) in pkg_instances {
+                let start = instance_lens
+                    .get(&(ty.clone(), pkg.clone()))
+                    .cloned()
+                    .unwrap_or_default();
+                for value in &pkg_values[start..] {
+                    values.push(SendValue::from_value(value)?);
+                }
+            }
+            if !values.is_empty() {
+                instances.push((ty.clone(), values));
+            }
+        }
+        let mut globals_without_definitions = vec![];
+        for (name, value) in globals {
+            // The definitions are functions which can not be copied.
+            if value.is_func() && (types.contains(name) || lambdas.contains(name)) {
+                continue;
+            }
+            globals_without_definitions.push((name.clone(), SendValue::from_value(value)?));
+        }
+        Some(PackageResult {
+            globals: globals_without_definitions,
+            lambdas,
+            instances,
+            log_message: ctx.log_message[log_start..].to_string(),
+            inputs: ctx.inputs.clone(),
+        })
+    }
+
+    /// Take the preloaded result of the package.
+    #[inline]
+    pub(crate) fn take_preloaded_package(&self, pkgpath: &str) -> Option<Arc<PackageResult>> {
+        self.preloaded_packages.borrow_mut().remove(pkgpath)
+    }
+
+    /// Install the preloaded package result into the current package scope. The
+    /// imports, types and lambdas of the package are still compiled in the current
+    /// evaluator to keep the same import order and schema types as the serial evaluation.
+    pub(crate) fn install_package(
+        &self,
+        modules: &[Arc<RwLock<ast::Module>>],
+        package: &PackageResult,
+    ) {
+        for module in modules {
+            let module = module.read().expect("Failed to acquire module lock");
+            self.compile_module_import_and_types(&module);
+        }
+        let pkgpath = self.current_pkgpath();
+        for module in modules {
+            let module = module.read().expect("Failed to acquire module lock");
+            for stmt in &module.body {
+                if let ast::Stmt::Assign(assign_stmt) = &stmt.node {
+                    if !matches!(assign_stmt.value.node, ast::Expr::Lambda(_)) {
+                        continue;
+                    }
+                    for target in &assign_stmt.targets {
+                        let name = target.node.get_name();
+                        if !target.node.paths.is_empty() || !package.lambdas.contains(name) {
+                            continue;
+                        }
+                        let value = self
+                            .walk_expr(&assign_stmt.value)
+                            .expect(crate::error::RUNTIME_ERROR_MSG);
+                        self.add_variable(name, value.clone());
+                        if let Some(scope) = self.lazy_scopes.borrow_mut().get_mut(&pkgpath) {
+                            scope.cache.insert(name.to_string(), value);
+                        }
+                    }
+                }
+            }
+        }
+        for (name, value) in &package.globals {
+            let value = value.to_value();
+            self.add_variable(name, value.clone());
+            if let Some(scope) = self.lazy_scopes.borrow_mut().get_mut(&pkgpath) {
+                scope.cache.insert(name.clone(), value);
+            }
+        }
+        let instance_pkgpath = self.last_pkgpath();
+        let mut ctx = self.runtime_ctx.borrow_mut();
+        for (ty, values) in &package.instances {
+            ctx.instances
+                .entry(ty.clone())
+                .or_default()
+                .entry(instance_pkgpath.clone())
+                .or_default()
+                .extend(values.iter().map(SendValue::to_value));
+        }
+        ctx.log_message.push_str(&package.log_message);
+        let inputs = &package.inputs;
+        for path in &inputs.files {
+            ctx.record_file_input(path);
+        }
+        for pattern in &inputs.globs {
+            ctx.record_glob_input(pattern);
+        }
+        for key in &inputs.envs {
+            ctx.record_env_input(key);
+        }
+        if inputs.uncacheable {
+            ctx.mark_uncacheable();
+        }
+    }
+}
+
+/// Evaluate the package in a new evaluator, returns `None` when the evaluation
+/// fails or the result can not be copied.
+fn eval_package(
+    program: &ast::Program,
+    pkgpath: &str,
+    preloaded: &HashMap<String, Arc<PackageResult>>,
+    new_ctx: &ContextFactory,
+) -> Option<PackageResult> {
+    let mut ctx = new_ctx();
+    // Deny the side effects in the worker, which would be repeated when the package
+    // falls back to the serial evaluation.
+    ctx.cfg.capabilities.read_only = true;
+    ctx.cfg.capabilities.deny_plugins = true;
+    let ctx = Rc::new(RefCell::new(ctx));
+    let evaluator = Evaluator::new_with_runtime_ctx(program, ctx.clone());
+    *evaluator.preloaded_packages.borrow_mut() = preloaded.clone();
+    let result = std::panic::catch_unwind(|| evaluator.eval_package_result(pkgpath))
+        .ok()
+        .flatten();
+    ctx.borrow().gc();
+    result
+}
+
+/// Get the schema and rule names and the global lambda names defined in the package modules.
+fn package_definitions(modules: &[Arc<RwLock<ast::Module>>]) -> (HashSet<String>, HashSet<String>) {
+    let mut types = HashSet::new();
+    let mut lambdas = HashSet::new();
+    for module in modules {
+        let module = module.read().expect("Failed to acquire module lock");
+        for stmt in &module.body {
+            match &stmt.node {
+                ast::Stmt::Schema(schema_stmt) => {
+                    types.insert(schema_stmt.name.node.clone());
+                }
+                ast::Stmt::Rule(rule_stmt) => {
+                    types.insert(rule_stmt.name.node.clone());
+                }
+                ast::Stmt::Assign(assign_stmt)
+                    if matches!(assign_stmt.value.node, ast::Expr::Lambda(_)) =>
+                {
+                    for target in &assign_stmt.targets {
+                        if target.node.paths.is_empty() {
+                            lambdas.insert(target.node.get_name().to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    (types, lambdas)
+}
+
+/// Get the user package dependencies of each imported package in the program.
+pub fn package_import_graph(program: &ast::Program) -> IndexMap<String, Vec<String>> {
+    let mut pkgpaths: Vec<&String> = program
+        .pkgs
+        .keys()
+        .filter(|pkgpath| *pkgpath != kclvm_ast::MAIN_PKG)
+        .collect();
+    pkgpaths.sort();
+    let mut graph = IndexMap::default();
+    for pkgpath in pkgpaths {
+        let mut deps = vec![];
+        for module in program.get_modules_for_pkg(pkgpath) {
+            let module = module.read().expect("Failed to acquire module lock");
+            for stmt in &module.body {
+                if let ast::Stmt::Import(import_stmt) = &stmt.node {
+                    let path = &import_stmt.path.node;
+                    if !builtin::STANDARD_SYSTEM_MODULES.contains(&path.as_str())
+                        && !path.starts_with(plugin::PLUGIN_MODULE_PREFIX)
+                        && program.pkgs.contains_key(path)
+                        && path != pkgpath
+                    {
+                        deps.push(path.clone());
+                    }
+                }
+            }
+        }
+        graph.insert(pkgpath.clone(), deps);
+    }
+    graph
+}
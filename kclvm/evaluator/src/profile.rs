@@ -0,0 +1,215 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use std::time::{Duration, Instant};
+
+use kclvm_ast::ast;
+use kclvm_primitives::IndexMap;
+use kclvm_runtime::ValueRef;
+
+use crate::Evaluator;
+
+/// The output format of the profiling result.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ProfileFormat {
+    /// The folded stack format which can be consumed by the flamegraph tools
+    /// e.g., `flamegraph.pl` and `inferno-flamegraph`.
+    Folded,
+    /// The Chrome trace event JSON format which can be opened in `chrome://tracing`
+    /// or Perfetto.
+    ChromeTrace,
+}
+
+impl ProfileFormat {
+    /// Get the profile format from the output file path, the Chrome trace format
+    /// is used for the `.json` file and the folded stack format for others.
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".json") {
+            ProfileFormat::ChromeTrace
+        } else {
+            ProfileFormat::Folded
+        }
+    }
+}
+
+/// The profiling statistics of a schema, lambda, rule or top-level statement.
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct ProfileEntry {
+    /// The call count.
+    pub calls: u64,
+    /// The time including the nested calls.
+    pub inclusive: Duration,
+    /// The time excluding the nested calls.
+    pub exclusive: Duration,
+    /// The allocated value count including the nested calls.
+    pub values: u64,
+}
+
+#[derive(Debug)]
+struct OpenFrame {
+    name: String,
+    start: Instant,
+    children: Duration,
+    values: u64,
+}
+
+#[derive(Debug)]
+struct TraceEvent {
+    name: String,
+    ts: Duration,
+    dur: Duration,
+    values: u64,
+}
+
+/// Profiler records the time and allocated value count of each schema
+/// instantiation, lambda call, rule and top-level statement.
+#[derive(Debug)]
+pub struct Profiler {
+    start: Instant,
+    stack: Vec<OpenFrame>,
+    /// The profiling statistics keyed by the frame name.
+    pub entries: IndexMap<String, ProfileEntry>,
+    /// The exclusive time keyed by the folded stack.
+    stacks: IndexMap<String, Duration>,
+    events: Vec<TraceEvent>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            stack: vec![],
+            entries: Default::default(),
+            stacks: Default::default(),
+            events: vec![],
+        }
+    }
+
+    /// Open a profiling frame with the name and the current allocated value count.
+    pub fn enter(&mut self, name: String, values: u64) {
+        self.stack.push(OpenFrame {
+            name,
+            start: Instant::now(),
+            children: Duration::ZERO,
+            values,
+        });
+    }
+
+    /// Close the last profiling frame with the current allocated value count.
+    pub fn exit(&mut self, values: u64) {
+        let frame = match self.stack.pop() {
+            Some(frame) => frame,
+            None => return,
+        };
+        let elapsed = frame.start.elapsed();
+        let exclusive = elapsed.saturating_sub(frame.children);
+        let values = values.saturating_sub(frame.values);
+        // Recursive calls are counted in the inclusive time of the outermost call only.
+        let recursive = self.stack.iter().any(|f| f.name == frame.name);
+        let folded = self
+            .stack
+            .iter()
+            .map(|f| f.name.as_str())
+            .chain(std::iter::once(frame.name.as_str()))
+            .collect::<Vec<&str>>()
+            .join(";");
+        *self.stacks.entry(folded).or_default() += exclusive;
+        if let Some(parent) = self.stack.last_mut() {
+            parent.children += elapsed;
+        }
+        let entry = self.entries.entry(frame.name.clone()).or_default();
+        entry.calls += 1;
+        entry.exclusive += exclusive;
+        if !recursive {
+            entry.inclusive += elapsed;
+            entry.values += values;
+        }
+        self.events.push(TraceEvent {
+            name: frame.name,
+            ts: frame.start.saturating_duration_since(self.start),
+            dur: elapsed,
+            values,
+        });
+    }
+
+    /// Output the profiling result in the format.
+    pub fn output(&self, format: ProfileFormat) -> String {
+        match format {
+            ProfileFormat::Folded => self.to_folded(),
+            ProfileFormat::ChromeTrace => self.to_chrome_trace(),
+        }
+    }
+
+    /// Output the folded stacks with the exclusive time in microseconds, one
+    /// stack per line e.g., `main.k:1;__main__.Person 12`.
+    pub fn to_folded(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, dur)| format!("{} {}\n", stack, dur.as_micros()))
+            .collect()
+    }
+
+    /// Output the Chrome trace event JSON with the complete events.
+    pub fn to_chrome_trace(&self) -> String {
+        let events = ValueRef::list(None);
+        for event in &self.events {
+            let args = ValueRef::dict(Some(&[("values", &ValueRef::int(event.values as i64))]));
+            let event = ValueRef::dict(Some(&[
+                ("name", &ValueRef::str(&event.name)),
+                ("cat", &ValueRef::str("kcl")),
+                ("ph", &ValueRef::str("X")),
+                ("ts", &ValueRef::int(event.ts.as_micros() as i64)),
+                ("dur", &ValueRef::int(event.dur.as_micros() as i64)),
+                ("pid", &ValueRef::int(1)),
+                ("tid", &ValueRef::int(1)),
+                ("args", &args),
+            ]));
+            events.clone().list_append(&event);
+        }
+        ValueRef::dict(Some(&[
+            ("traceEvents", &events),
+            ("displayTimeUnit", &ValueRef::str("ms")),
+        ]))
+        .to_json_string()
+    }
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Enable the profiling mode, the result can be got through [Evaluator::take_profiler]
+    /// after the evaluation.
+    #[inline]
+    pub fn enable_profiler(&self) {
+        *self.profiler.borrow_mut() = Some(Profiler::new());
+    }
+
+    /// Take the profiler and disable the profiling mode.
+    #[inline]
+    pub fn take_profiler(&self) -> Option<Profiler> {
+        self.profiler.borrow_mut().take()
+    }
+
+    pub(crate) fn enter_profile_frame(&self, name: impl FnOnce() -> String) {
+        if let Some(profiler) = self.profiler.borrow_mut().as_mut() {
+            let values = self.runtime_ctx.borrow().resource_usage.values;
+            profiler.enter(name(), values);
+        }
+    }
+
+    pub(crate) fn exit_profile_frame(&self) {
+        if let Some(profiler) = self.profiler.borrow_mut().as_mut() {
+            let values = self.runtime_ctx.borrow().resource_usage.values;
+            profiler.exit(values);
+        }
+    }
+
+    /// Get the profiling frame name of the top-level statement e.g., `main.k:1`.
+    #[inline]
+    pub(crate) fn profile_stmt_name(&self, stmt: &ast::Node<ast::Stmt>) -> String {
+        format!("{}:{}", stmt.filename, stmt.line)
+    }
+}
@@ -0,0 +1,154 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use kclvm_ast::ast;
+use kclvm_primitives::IndexMap;
+use kclvm_runtime::val_plan::KCL_PRIVATE_VAR_PREFIX;
+use kclvm_runtime::{Provenance, ValueRef};
+
+use crate::{Evaluator, GLOBAL_LEVEL};
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Update the current source position with the statement, which is recorded
+    /// as the provenance of the keys set by the statement.
+    pub(crate) fn update_provenance_stmt_pos(&self, stmt: &ast::Node<ast::Stmt>) {
+        if self.runtime_ctx.borrow().cfg.provenance {
+            let op = match &stmt.node {
+                ast::Stmt::Assign(_) => "=",
+                ast::Stmt::Unification(_) => ":",
+                ast::Stmt::AugAssign(aug_assign_stmt) => aug_assign_stmt.op.symbol(),
+                _ => "",
+            };
+            self.set_provenance_pos(stmt, op);
+        }
+    }
+
+    /// Update the current source position with the config entry key.
+    pub(crate) fn update_provenance_pos<T>(&self, node: &ast::Node<T>) {
+        if self.runtime_ctx.borrow().cfg.provenance {
+            self.set_provenance_pos(node, "");
+        }
+    }
+
+    fn set_provenance_pos<T>(&self, node: &ast::Node<T>, op: &str) {
+        *self.provenance_pos.borrow_mut() = Provenance {
+            filename: node.filename.clone(),
+            line: node.line,
+            column: node.column,
+            op: op.to_string(),
+        };
+    }
+
+    /// Get the current source position to be restored after evaluating a nested
+    /// statement or config, returns `None` when the provenance mode is disabled.
+    pub(crate) fn saved_provenance_pos(&self) -> Option<Provenance> {
+        if self.runtime_ctx.borrow().cfg.provenance {
+            Some(self.provenance_pos.borrow().clone())
+        } else {
+            None
+        }
+    }
+
+    /// Restore the source position saved by [Evaluator::saved_provenance_pos].
+    pub(crate) fn restore_provenance_pos(&self, pos: Option<Provenance>) {
+        if let Some(pos) = pos {
+            *self.provenance_pos.borrow_mut() = pos;
+        }
+    }
+
+    /// Get the provenance at the current source position with the operator symbol,
+    /// returns `None` when the provenance mode is disabled.
+    pub(crate) fn current_provenance(&self, op: &str) -> Option<Provenance> {
+        if self.runtime_ctx.borrow().cfg.provenance {
+            let mut provenance = self.provenance_pos.borrow().clone();
+            provenance.op = op.to_string();
+            Some(provenance)
+        } else {
+            None
+        }
+    }
+
+    /// Record the provenance of the main package global variable.
+    pub(crate) fn record_global_provenance(&self, name: &str) {
+        if self.runtime_ctx.borrow().cfg.provenance && self.current_pkgpath() == kclvm_ast::MAIN_PKG
+        {
+            let provenance = self.provenance_pos.borrow().clone();
+            self.global_provenance
+                .borrow_mut()
+                .insert(name.to_string(), provenance);
+        }
+    }
+
+    /// Get the provenance map of the output fields after the evaluation, which is
+    /// keyed by the field path e.g., `app.spec.containers[0].image`. It is empty
+    /// when the provenance mode is disabled.
+    pub fn provenance_map(&self) -> IndexMap<String, Provenance> {
+        let mut provenance_map = IndexMap::default();
+        if !self.runtime_ctx.borrow().cfg.provenance {
+            return provenance_map;
+        }
+        let show_hidden = self.runtime_ctx.borrow().plan_opts.show_hidden;
+        let globals: Vec<(String, ValueRef)> = {
+            let pkg_scopes = self.pkg_scopes.borrow();
+            match pkg_scopes
+                .get(kclvm_ast::MAIN_PKG)
+                .and_then(|scopes| scopes.get(GLOBAL_LEVEL))
+            {
+                Some(scope) => scope
+                    .variables
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect(),
+                None => vec![],
+            }
+        };
+        let global_provenance = self.global_provenance.borrow();
+        for (name, value) in &globals {
+            if !is_output_field(name, value, show_hidden) {
+                continue;
+            }
+            if let Some(provenance) = global_provenance.get(name) {
+                provenance_map.insert(name.clone(), provenance.clone());
+            }
+            collect_provenance(value, name, show_hidden, &mut provenance_map);
+        }
+        provenance_map
+    }
+}
+
+/// Whether the field is in the output, private fields, functions and
+/// undefined values are not in the output.
+fn is_output_field(name: &str, value: &ValueRef, show_hidden: bool) -> bool {
+    (show_hidden || !name.starts_with(KCL_PRIVATE_VAR_PREFIX))
+        && !value.is_func()
+        && !value.is_undefined()
+}
+
+fn collect_provenance(
+    value: &ValueRef,
+    path: &str,
+    show_hidden: bool,
+    provenance_map: &mut IndexMap<String, Provenance>,
+) {
+    if value.is_list() {
+        for (i, item) in value.as_list_ref().values.iter().enumerate() {
+            collect_provenance(
+                item,
+                &format!("{}[{}]", path, i),
+                show_hidden,
+                provenance_map,
+            );
+        }
+    } else if value.is_config() {
+        let dict = value.as_dict_ref();
+        for (key, item) in &dict.values {
+            if !is_output_field(key, item, show_hidden) {
+                continue;
+            }
+            let path = format!("{}.{}", path, key);
+            if let Some(provenance) = dict.provenance.get(key) {
+                provenance_map.insert(path.clone(), provenance.clone());
+            }
+            collect_provenance(item, &path, show_hidden, provenance_map);
+        }
+    }
+}
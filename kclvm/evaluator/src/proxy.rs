@@ -57,13 +57,13 @@ pub(crate) fn call_schema_body(
             s.push_pkgpath(&frame.pkgpath);
             s.push_backtrace(&frame);
             defer! {
-                s.pop_backtrace();
                 s.pop_pkgpath();
             }
             {
                 schema.ctx.borrow_mut().set_info_with_schema(&ctx.borrow())
             }
             let value = (schema.body)(s, &schema.ctx, args, kwargs);
+            s.pop_backtrace();
             value
         } else {
             ctx.borrow().value.clone()
@@ -93,13 +93,13 @@ pub(crate) fn call_schema_body_from_rule(
             s.push_pkgpath(&frame.pkgpath);
             s.push_backtrace(&frame);
             defer! {
-                s.pop_backtrace();
                 s.pop_pkgpath();
             }
             {
                 schema.ctx.borrow_mut().set_info_with_rule(&ctx.borrow())
             }
             let value = (schema.body)(s, &schema.ctx, args, kwargs);
+            s.pop_backtrace();
             value
         } else {
             ctx.borrow().value.clone()
@@ -129,13 +129,13 @@ pub(crate) fn call_schema_check(
             s.push_pkgpath(&frame.pkgpath);
             s.push_backtrace(&frame);
             defer! {
-                s.pop_backtrace();
                 s.pop_pkgpath();
             }
             if let Some(ctx) = ctx {
                 schema.ctx.borrow_mut().set_info_with_schema(&ctx.borrow())
             }
             (schema.check)(s, &schema.ctx, schema_value, args, kwargs);
+            s.pop_backtrace();
         }
     }
 }
@@ -153,10 +153,10 @@ pub(crate) fn call_rule_check(s: &Evaluator, func: &ValueRef, args: &ValueRef, k
             s.push_pkgpath(&frame.pkgpath);
             s.push_backtrace(&frame);
             defer! {
-                s.pop_backtrace();
                 s.pop_pkgpath();
             }
             (rule.check)(s, &rule.ctx, args, kwargs);
+            s.pop_backtrace();
         }
     }
 }
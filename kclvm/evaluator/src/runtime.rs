@@ -5,8 +5,8 @@ use std::{
 };
 
 use kclvm_runtime::{
-    get_call_arg, is_runtime_catch_function, kclvm_plugin_invoke, ptr_as_ref, Context,
-    SchemaTypeFunc, UnsafeWrapper, ValueRef,
+    get_call_arg, get_higher_order_builtin, is_runtime_catch_function, kclvm_plugin_invoke,
+    ptr_as_ref, Context, SchemaTypeFunc, UnsafeWrapper, ValueRef,
 };
 
 use crate::Evaluator;
@@ -25,6 +25,12 @@ pub fn invoke_function(
         if is_runtime_catch_function(fn_ptr) {
             let value = runtime_catch(s, args, kwargs);
             return value;
+        } else if let Some(builtin) = get_higher_order_builtin(fn_ptr) {
+            // Builtin functions such as `map` and `filter` call KCL functions,
+            // which must be invoked by the evaluator instead of the runtime.
+            return builtin(args, kwargs, &mut |func, args| {
+                invoke_function_with_args(s, func, args)
+            });
         } else {
             let ctx: &mut Context = &mut s.runtime_ctx.borrow_mut();
             unsafe {
@@ -47,6 +53,18 @@ pub fn invoke_function(
     }
 }
 
+/// Invoke user defined functions, builtin functions or plugin functions with
+/// positional arguments.
+pub fn invoke_function_with_args(s: &Evaluator, func: &ValueRef, args: &[ValueRef]) -> ValueRef {
+    let mut list_value = ValueRef::list(Some(&args.iter().collect::<Vec<&ValueRef>>()));
+    let dict_value = ValueRef::dict(None);
+    if let Some(proxy) = func.try_get_proxy() {
+        s.invoke_proxy_function(proxy, &list_value, &dict_value)
+    } else {
+        invoke_function(s, func, &mut list_value, &dict_value)
+    }
+}
+
 /// Executes the provided function and catches any potential runtime errors.
 /// Returns undefined if execution is successful, otherwise returns an error
 /// message in case of a runtime panic.
@@ -59,9 +77,13 @@ pub fn runtime_catch(s: &Evaluator, args: &ValueRef, kwargs: &ValueRef) -> Value
                 s.invoke_proxy_function(proxy, &args, &kwargs);
             }
         });
+        let depth = s.backtrace_depth();
+        let call_depth = s.runtime_ctx.borrow().call_depth();
         let result = catch_unwind(AssertUnwindSafe(|| unsafe {
             (wrapper.get())();
         }));
+        s.unwind_backtrace(depth);
+        s.runtime_ctx.borrow_mut().unwind_calls(call_depth);
         return match result {
             Ok(_) => ValueRef::undefined(),
             Err(err) => ValueRef::str(&kclvm_error::err_to_str(err)),
@@ -464,6 +464,8 @@ pub(crate) fn schema_body(
     }
     // Evaluate arguments and keyword arguments and store values to local variables.
     s.walk_arguments(&ctx.borrow().node.args, args, kwargs);
+    // Final attributes check
+    check_schema_final_attr(s, &ctx.borrow());
     // Eval schema body and record schema instances.
     {
         let schema_pkgpath = &s.current_pkgpath();
@@ -624,10 +626,10 @@ pub(crate) fn schema_check(
                     s.push_pkgpath(&frame.pkgpath);
                     s.push_backtrace(&frame);
                     defer! {
-                        s.pop_backtrace();
                         s.pop_pkgpath();
                     }
                     (schema.check)(s, &schema.ctx, schema_value, args, kwargs);
+                    s.pop_backtrace();
                 }
             }
         }
@@ -740,6 +742,29 @@ fn update_schema_relaxed_attr(
     }
 }
 
+fn check_schema_final_attr(s: &Evaluator, ctx: &SchemaEvalContext) {
+    let final_attrs: Vec<String> = ctx
+        .node
+        .body
+        .iter()
+        .filter_map(|stmt| match &stmt.node {
+            ast::Stmt::SchemaAttr(schema_attr) if schema_attr.is_final => {
+                Some(schema_attr.name.node.clone())
+            }
+            _ => None,
+        })
+        .collect();
+    let runtime_ctx = &mut s.runtime_ctx.borrow_mut();
+    if !final_attrs.is_empty() && !runtime_ctx.cfg.disable_schema_check {
+        ctx.config.schema_check_attr_final(
+            runtime_ctx,
+            &ctx.config_meta,
+            &final_attrs,
+            &ctx.node.name.node,
+        );
+    }
+}
+
 fn check_schema_optional_attr(s: &Evaluator, schema_value: &ValueRef) {
     if is_top_level_schema_instance(s) {
         schema_value.schema_check_attr_optional(&mut s.runtime_ctx.borrow_mut(), true);
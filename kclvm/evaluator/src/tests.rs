@@ -1,4 +1,4 @@
-use crate::Evaluator;
+use crate::{Evaluator, PackageCache, PackageResult};
 use kclvm_ast::MAIN_PKG;
 use kclvm_loader::{load_packages, LoadPackageOptions};
 use kclvm_parser::LoadProgramOptions;
@@ -463,6 +463,25 @@ config: Config {
 	provider_values.attr1 = "foobar"
 }
 "#}
+evaluator_snapshot! {schema_3, r#"
+schema Person:
+    name: str
+
+schema NameMixin:
+    title: str = "Mr."
+
+schema Student(Person):
+    mixin [NameMixin]
+    grade: int = 1
+
+_student = Student {name = "Alice"}
+_person = Person {name = "Bob"}
+is_student = _student is Student
+is_person = _student is Person
+is_named = _student is NameMixin
+person_is_student = _person is Student
+student_is_not_person = _student is not Person
+"#}
 evaluator_snapshot! {lazy_scope_0, r#"
 b = a + c
 a = 1
@@ -497,6 +516,16 @@ evaluator_snapshot! {list_comp1, r#"
 a = [ x for x in "你好"]
 "#}
 
+evaluator_snapshot! {builtin_call_higher_order_0, r#"
+_inc = lambda x: int {
+    x + 1
+}
+a = map(_inc, [1, 2, 3])
+b = filter(lambda x { x > 1 }, a)
+c = reduce(lambda acc, x { acc + x }, b, 1)
+d = sort_by(["bb", "a", "ccc"], len, reverse=True)
+"#}
+
 #[test]
 fn test_if_stmt_setters() {
     let p = load_packages(&LoadPackageOptions {
@@ -525,6 +554,7 @@ fn test_if_stmt_setters() {
 }
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::thread;
@@ -619,3 +649,72 @@ sum = testing.add(1, 1)
     let evaluator = Evaluator::new_with_runtime_ctx(&p.program, context_with_plugin());
     insta::assert_snapshot!(format!("{}", evaluator.run().unwrap().1));
 }
+
+#[test]
+fn test_preload_packages() {
+    let p = load_packages(&LoadPackageOptions {
+        paths: vec!["src/testdata/parallel/main.k".to_string()],
+        load_builtin: false,
+        ..Default::default()
+    })
+    .unwrap();
+    let serial = Evaluator::new(&p.program).run().unwrap();
+    let evaluator = Evaluator::new(&p.program);
+    evaluator.preload_packages(2, &Context::new);
+    // The packages defining schemas and lambdas are preloaded rather than evaluated again.
+    {
+        let preloaded = evaluator.preloaded_packages.borrow();
+        assert!(preloaded.contains_key("b"));
+        assert!(preloaded.contains_key("d"));
+    }
+    assert_eq!(evaluator.run().unwrap(), serial);
+    assert!(evaluator.preloaded_packages.borrow().is_empty());
+}
+
+#[derive(Default)]
+struct MemoryPackageCache {
+    results: RefCell<HashMap<String, PackageResult>>,
+    loaded: RefCell<Vec<String>>,
+}
+
+impl PackageCache for MemoryPackageCache {
+    fn load(&self, pkgpath: &str) -> Option<PackageResult> {
+        let result = self.results.borrow().get(pkgpath).cloned();
+        if result.is_some() {
+            self.loaded.borrow_mut().push(pkgpath.to_string());
+        }
+        result
+    }
+
+    fn save(&self, pkgpath: &str, result: &PackageResult) {
+        self.results
+            .borrow_mut()
+            .insert(pkgpath.to_string(), result.clone());
+    }
+}
+
+#[test]
+fn test_package_cache() {
+    let p = load_packages(&LoadPackageOptions {
+        paths: vec!["src/testdata/parallel/main.k".to_string()],
+        load_builtin: false,
+        ..Default::default()
+    })
+    .unwrap();
+    let serial = Evaluator::new(&p.program).run().unwrap();
+    let cache = MemoryPackageCache::default();
+    let evaluator = Evaluator::new(&p.program);
+    evaluator.set_package_cache(&cache);
+    assert_eq!(evaluator.run().unwrap(), serial);
+    assert!(cache.loaded.borrow().is_empty());
+    assert!(cache.results.borrow().contains_key("b"));
+    assert!(cache.results.borrow().contains_key("d"));
+    // The cached package values are installed rather than evaluated again.
+    let evaluator = Evaluator::new(&p.program);
+    evaluator.set_package_cache(&cache);
+    assert_eq!(evaluator.run().unwrap(), serial);
+    assert_eq!(
+        *cache.loaded.borrow(),
+        vec!["b".to_string(), "d".to_string()]
+    );
+}
@@ -40,7 +40,6 @@ pub fn resolve_schema(s: &Evaluator, schema: &ValueRef, keys: &[String]) -> Valu
             s.push_pkgpath(&frame.pkgpath);
             s.push_backtrace(&frame);
             defer! {
-                s.pop_backtrace();
                 s.pop_pkgpath();
             }
             let value = (caller.body)(
@@ -49,6 +48,7 @@ pub fn resolve_schema(s: &Evaluator, schema: &ValueRef, keys: &[String]) -> Valu
                 &schema_value.args,
                 &schema_value.kwargs,
             );
+            s.pop_backtrace();
             value
         } else {
             schema.clone()
@@ -30,6 +30,13 @@ fn do_union(
         for (k, v) in &delta.insert_indexs {
             obj.insert_indexs.insert(k.clone(), *v);
         }
+        // Update provenance map, undefined values do not change the original value
+        // and its provenance.
+        for (k, v) in &delta.provenance {
+            if !matches!(delta.values.get(k), Some(value) if value.is_undefined()) {
+                obj.provenance.insert(k.clone(), v.clone());
+            }
+        }
         // Update values
         for (k, v) in &delta.values {
             let operation = if let Some(op) = delta.ops.get(k) {
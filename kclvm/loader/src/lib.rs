@@ -108,6 +108,7 @@ pub enum ScopeKind {
     Config,
     Check,
     Callable,
+    MatchCase,
 }
 
 /// load_package provides users with the ability to parse kcl program and sematic model
@@ -257,6 +258,7 @@ impl From<LocalSymbolScopeKind> for ScopeKind {
             LocalSymbolScopeKind::Config => ScopeKind::Config,
             LocalSymbolScopeKind::Check => ScopeKind::Check,
             LocalSymbolScopeKind::Callable => ScopeKind::Callable,
+            LocalSymbolScopeKind::MatchCase => ScopeKind::MatchCase,
         }
     }
 }
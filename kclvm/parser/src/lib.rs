@@ -19,7 +19,11 @@ use compiler_base_span::span::new_byte_pos;
 use file_graph::{toposort, Pkg, PkgFile, PkgFileGraph, PkgMap};
 use kclvm_ast::ast::Module;
 use kclvm_ast::{ast, MAIN_PKG};
-use kclvm_config::modfile::{get_vendor_home, KCL_FILE_EXTENSION, KCL_FILE_SUFFIX, KCL_MOD_FILE};
+use kclvm_config::cache::{content_hash, load_content_cache, save_content_cache};
+use kclvm_config::modfile::{
+    get_mod_plugin_paths, get_mod_plugins, get_plugin_paths, get_vendor_home, KCL_FILE_EXTENSION,
+    KCL_FILE_SUFFIX, KCL_MOD_FILE,
+};
 use kclvm_error::diagnostic::{Errors, Range};
 use kclvm_error::{ErrorKind, Message, Position, Style};
 use kclvm_primitives::IndexMap;
@@ -37,6 +41,9 @@ use std::sync::{Arc, RwLock};
 
 use kclvm_span::create_session_globals_then;
 
+/// The cache kind of the parsed AST modules.
+const AST_CACHE_KIND: &str = "ast";
+
 #[derive(Default, Debug)]
 /// [`PkgInfo`] is some basic information about a kcl package.
 pub(crate) struct PkgInfo {
@@ -202,6 +209,43 @@ pub fn parse_file_with_session(
     Ok(m)
 }
 
+/// Parse a KCL file to the AST module with the persistent AST cache in the cache
+/// directory, which is keyed by the file name and the source content hash.
+pub fn parse_file_with_cache(
+    sess: ParseSessionRef,
+    cache_dir: &str,
+    filename: &str,
+    code: Option<String>,
+) -> Result<ast::Module> {
+    let src = match code {
+        Some(src) => src,
+        None => match std::fs::read_to_string(filename) {
+            Ok(src) => src,
+            Err(err) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to load KCL file '{filename}'. Because '{err}'"
+                ));
+            }
+        },
+    };
+    let key = content_hash(&[filename.as_bytes(), src.as_bytes()]);
+    if let Some(m) = load_content_cache::<ast::Module>(cache_dir, AST_CACHE_KIND, &key) {
+        // Store the file source for the diagnostics of the later compile stages.
+        sess.0
+            .sm
+            .new_source_file(PathBuf::from(filename).into(), src);
+        return Ok(m);
+    }
+    let diagnostic_count = sess.1.read().diagnostics.len();
+    let m = parse_file_with_session(sess.clone(), filename, Some(src))?;
+    // Modules with parse errors are not cached to report the errors in the next run.
+    if sess.1.read().diagnostics.len() == diagnostic_count {
+        // The cache is an optimization, thus the cache writing error is ignored.
+        let _ = save_content_cache(cache_dir, AST_CACHE_KIND, &key, &m);
+    }
+    Ok(m)
+}
+
 /// Parse a KCL file to the AST module with the parse session and the global session
 #[inline]
 pub fn parse_file_with_global_session(
@@ -262,6 +306,17 @@ pub struct LoadProgramOptions {
     pub load_packages: bool,
     /// Whether to load plugins
     pub load_plugins: bool,
+    /// The paths to find the plugin declaration files e.g., `kcl_plugin.hello`
+    /// is declared in the file `<plugin_path>/hello.k`, default is from the
+    /// environment variable `KCL_PLUGIN_PATH`. The `plugin_paths` in the
+    /// 'kcl.mod' file of the program root are searched before them.
+    pub plugin_paths: Vec<String>,
+    /// The plugin declaration code keyed by the plugin pkgpath e.g., `kcl_plugin.hello`,
+    /// which is used before the declaration files in the plugin paths.
+    pub plugin_decl_codes: HashMap<String, String>,
+    /// The root directory of the persistent AST cache. When it is set, the parsed
+    /// modules are cached by the file name and source content hash across runs.
+    pub cache_dir: Option<String>,
 }
 
 impl Default for LoadProgramOptions {
@@ -274,6 +329,9 @@ impl Default for LoadProgramOptions {
             mode: ParseMode::ParseComments,
             load_packages: true,
             load_plugins: false,
+            plugin_paths: get_plugin_paths(),
+            plugin_decl_codes: Default::default(),
+            cache_dir: None,
         }
     }
 }
@@ -428,6 +486,13 @@ fn is_plugin_pkg(pkgpath: &str) -> bool {
     pkgpath.starts_with(PLUGIN_MODULE_PREFIX)
 }
 
+fn is_mod_plugin_pkg(pkg_root: &str, pkgpath: &str) -> bool {
+    match pkgpath.strip_prefix(PLUGIN_MODULE_PREFIX) {
+        Some(name) => get_mod_plugins(pkg_root).contains_key(name),
+        None => false,
+    }
+}
+
 fn is_builtin_pkg(pkgpath: &str) -> bool {
     let system_modules = kclvm_sema::builtin::system_module::STANDARD_SYSTEM_MODULES;
     system_modules.contains(&pkgpath)
@@ -447,7 +512,8 @@ fn find_packages(
 
     // plugin pkgs
     if is_plugin_pkg(pkg_path) {
-        if !opts.load_plugins {
+        // The plugins in the 'kcl.mod' file are always loaded.
+        if !opts.load_plugins && !is_mod_plugin_pkg(pkg_root, pkg_path) {
             sess.1.write().add_error(
                 ErrorKind::CannotFindModule,
                 &[Message {
@@ -700,7 +766,15 @@ pub fn parse_file(
         }
         .cloned(),
     };
-    let m = parse_file_with_session(sess.clone(), file.get_path().to_str().unwrap(), src)?;
+    let m = match &opts.cache_dir {
+        Some(cache_dir) => parse_file_with_cache(
+            sess.clone(),
+            cache_dir,
+            file.get_path().to_str().unwrap(),
+            src,
+        )?,
+        None => parse_file_with_session(sess.clone(), file.get_path().to_str().unwrap(), src)?,
+    };
     let deps = get_deps(&file, &m, pkgs, pkgmap, opts, sess)?;
     let dep_files = deps.keys().map(|f| f.clone()).collect();
     pkgmap.extend(deps.clone());
@@ -1009,12 +1083,14 @@ pub fn parse_program(
             }
         }
     }
+    let plugin_decls = load_plugin_decls(sess.clone(), &workdir, &mut modules, opts)?;
     let program = ast::Program {
         root: workdir,
         pkgs,
         pkgs_not_imported: HashMap::new(),
         modules,
         modules_not_imported: HashMap::new(),
+        plugin_decls,
     };
 
     Ok(LoadProgramResult {
@@ -1024,6 +1100,86 @@ pub fn parse_program(
     })
 }
 
+/// Load the declaration modules of the plugins imported by the program modules
+/// from the declaration codes, the 'kcl.mod' file of the program root or the plugin
+/// paths in the 'kcl.mod' file and the options, and store them into the program modules.
+fn load_plugin_decls(
+    sess: ParseSessionRef,
+    root: &str,
+    modules: &mut HashMap<String, Arc<RwLock<Module>>>,
+    opts: &LoadProgramOptions,
+) -> Result<HashMap<String, Vec<String>>> {
+    let mut plugin_decls = HashMap::new();
+    let mod_plugins = get_mod_plugins(root);
+    // The plugin paths in the 'kcl.mod' file are searched before the option paths.
+    let plugin_paths: Vec<String> = get_mod_plugin_paths(root)
+        .into_iter()
+        .chain(opts.plugin_paths.iter().cloned())
+        .collect();
+    if mod_plugins.is_empty()
+        && (!opts.load_plugins || (plugin_paths.is_empty() && opts.plugin_decl_codes.is_empty()))
+    {
+        return Ok(plugin_decls);
+    }
+    let mut plugin_pkgpaths: Vec<String> = vec![];
+    for module in modules.values() {
+        let module = module
+            .read()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire module lock: {e}"))?;
+        for stmt in &module.body {
+            if let ast::Stmt::Import(import_stmt) = &stmt.node {
+                let pkgpath = &import_stmt.path.node;
+                if is_plugin_pkg(pkgpath) && !plugin_pkgpaths.contains(pkgpath) {
+                    plugin_pkgpaths.push(pkgpath.to_string());
+                }
+            }
+        }
+    }
+    for pkgpath in plugin_pkgpaths {
+        let (filename, code) = match opts.plugin_decl_codes.get(&pkgpath) {
+            Some(code) => (
+                format!("{}{}", pkgpath, KCL_FILE_SUFFIX),
+                Some(code.clone()),
+            ),
+            None => match pkgpath
+                .strip_prefix(PLUGIN_MODULE_PREFIX)
+                .and_then(|name| mod_plugins.get(name))
+            {
+                Some(plugin) => match plugin.get_decl_path() {
+                    Some(filename) => (filename.adjust_canonicalization(), None),
+                    None => continue,
+                },
+                None => match find_plugin_decl_file(&pkgpath, &plugin_paths) {
+                    Some(filename) => (filename, None),
+                    None => continue,
+                },
+            },
+        };
+        let m = parse_file_with_session(sess.clone(), &filename, code)?;
+        modules.insert(filename.clone(), Arc::new(RwLock::new(m)));
+        plugin_decls.insert(pkgpath, vec![filename]);
+    }
+    Ok(plugin_decls)
+}
+
+/// Find the declaration file of the plugin pkgpath in the plugin paths
+/// e.g., `kcl_plugin.hello` -> `<plugin_path>/hello.k`.
+fn find_plugin_decl_file(pkgpath: &str, plugin_paths: &[String]) -> Option<String> {
+    let name = pkgpath.strip_prefix(PLUGIN_MODULE_PREFIX)?;
+    plugin_paths.iter().find_map(|plugin_path| {
+        let mut path = PathBuf::from(plugin_path);
+        for s in name.split('.') {
+            path.push(s);
+        }
+        path.set_extension(KCL_FILE_EXTENSION);
+        if path.is_file() {
+            Some(path.display().to_string().adjust_canonicalization())
+        } else {
+            None
+        }
+    })
+}
+
 /// If there are too many files in the directory, it will affect the performance of lsp. Set a maximum number of files
 const MAX_SCAN_FILES: usize = 1000;
 
@@ -620,6 +620,9 @@ impl<'a> Parser<'a> {
                 // lambda expression
                 else if self.token.is_keyword(kw::Lambda) {
                     self.parse_lambda_expr()
+                // match expression, `match` is still a valid identifier in other expressions.
+                } else if self.token.is_keyword(kw::Match) && self.is_match_expr_start() {
+                    self.parse_match_expr()
                 // quant expression
                 } else if self.token.is_keyword(kw::Any)
                     || self.token.is_keyword(kw::All)
@@ -1940,7 +1943,8 @@ impl<'a> Parser<'a> {
     }
 
     /// Syntax:
-    /// lambda_expr: LAMBDA [arguments] [RIGHT_ARROW type]
+    /// lambda_expr: LAMBDA [type_params] [arguments] [RIGHT_ARROW type]
+    ///   e.g., `lambda[T] x: T -> T { x }`
     ///   LEFT_BRACE
     ///     [expr_stmt | NEWLINE _INDENT schema_init_stmt+ _DEDENT]
     ///   RIGHT_BRACE
@@ -1948,6 +1952,7 @@ impl<'a> Parser<'a> {
         let token = self.token;
         self.bump_keyword(kw::Lambda);
 
+        let type_params = self.parse_type_params();
         let mut args = None;
         let mut return_ty = None;
 
@@ -2008,6 +2013,7 @@ impl<'a> Parser<'a> {
 
         Box::new(Node::node(
             Expr::Lambda(LambdaExpr {
+                type_params,
                 args,
                 return_ty,
                 body: stmt_list,
@@ -2016,6 +2022,364 @@ impl<'a> Parser<'a> {
         ))
     }
 
+    /// Whether the `match` keyword starts a match expression, the match subject
+    /// must be an identifier or a literal e.g., `match value {` and `match "s" {`.
+    fn is_match_expr_start(&mut self) -> bool {
+        match self.cursor.peek() {
+            Some(token) => match token.kind {
+                TokenKind::Literal(_) => true,
+                TokenKind::Ident(_) => ![
+                    kw::In,
+                    kw::If,
+                    kw::Elif,
+                    kw::Else,
+                    kw::For,
+                    kw::Is,
+                    kw::Not,
+                    kw::And,
+                    kw::Or,
+                    kw::As,
+                ]
+                .iter()
+                .any(|keyword| token.is_keyword(*keyword)),
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Syntax:
+    /// match_expr: MATCH match_subject LEFT_BRACE [NEWLINE _INDENT] (match_case [COMMA | NEWLINE])* [_DEDENT] RIGHT_BRACE
+    /// match_subject: identifier | string | number | constant
+    fn parse_match_expr(&mut self) -> NodeRef<Expr> {
+        let token = self.token;
+        self.bump_keyword(kw::Match);
+
+        let subject = if self.token.is_keyword(kw::None)
+            || self.token.is_keyword(kw::Undefined)
+            || self.token.is_keyword(kw::True)
+            || self.token.is_keyword(kw::False)
+            || matches!(self.token.kind, TokenKind::Literal(_))
+        {
+            self.parse_operand_expr()
+        } else {
+            self.parse_identifier_expr()
+        };
+
+        self.bump_token(TokenKind::OpenDelim(DelimToken::Brace));
+
+        let mut cases = Vec::new();
+        loop {
+            self.skip_newlines();
+            self.clean_all_indentations();
+            if matches!(
+                self.token.kind,
+                TokenKind::CloseDelim(DelimToken::Brace) | TokenKind::Eof
+            ) {
+                break;
+            }
+            let marker = self.mark();
+            cases.push(self.parse_match_case());
+            if let TokenKind::Comma = self.token.kind {
+                self.bump();
+            }
+            self.drop(marker);
+        }
+
+        self.bump_token(TokenKind::CloseDelim(DelimToken::Brace));
+
+        Box::new(Node::node(
+            Expr::Match(MatchExpr { subject, cases }),
+            self.sess.struct_token_loc(token, self.prev_token),
+        ))
+    }
+
+    /// Syntax:
+    /// match_case: pattern [IF expr] COLON expr
+    fn parse_match_case(&mut self) -> NodeRef<MatchCase> {
+        let token = self.token;
+        let pattern = self.parse_pattern();
+
+        let guard = if self.token.is_keyword(kw::If) {
+            self.bump();
+            Some(self.parse_expr())
+        } else {
+            None
+        };
+
+        self.bump_token(TokenKind::Colon);
+        let body = self.parse_expr();
+
+        Box::new(Node::node(
+            MatchCase {
+                pattern,
+                guard,
+                body,
+            },
+            self.sess.struct_token_loc(token, self.prev_token),
+        ))
+    }
+
+    /// Syntax:
+    /// pattern: or_pattern [AS NAME]
+    /// or_pattern: closed_pattern (BIT_OR closed_pattern)*
+    pub(crate) fn parse_pattern(&mut self) -> NodeRef<Pattern> {
+        let token = self.token;
+        let mut patterns = vec![self.parse_closed_pattern()];
+        while let TokenKind::BinOp(BinOpToken::Or) = self.token.kind {
+            self.bump();
+            patterns.push(self.parse_closed_pattern());
+        }
+        let pattern = if patterns.len() == 1 {
+            patterns.pop().unwrap()
+        } else {
+            Box::new(Node::node(
+                Pattern::Or(patterns),
+                self.sess.struct_token_loc(token, self.prev_token),
+            ))
+        };
+        if self.token.is_keyword(kw::As) {
+            self.bump();
+            let name = self.parse_pattern_name();
+            Box::new(Node::node(
+                Pattern::As(AsPattern { pattern, name }),
+                self.sess.struct_token_loc(token, self.prev_token),
+            ))
+        } else {
+            pattern
+        }
+    }
+
+    /// Syntax:
+    /// closed_pattern: wildcard_pattern | value_pattern | capture_pattern | schema_pattern
+    ///     | list_pattern | dict_pattern | LEFT_PARENTHESES pattern RIGHT_PARENTHESES
+    /// wildcard_pattern: "_"
+    /// value_pattern: string | [MINUS] number | constant | NAME (DOT NAME)+
+    /// capture_pattern: NAME
+    fn parse_closed_pattern(&mut self) -> NodeRef<Pattern> {
+        let token = self.token;
+        let pattern = match self.token.kind {
+            TokenKind::Ident(_) => {
+                if self.token.is_keyword(kw::None)
+                    || self.token.is_keyword(kw::Undefined)
+                    || self.token.is_keyword(kw::True)
+                    || self.token.is_keyword(kw::False)
+                {
+                    Pattern::Value(self.parse_operand_expr())
+                } else {
+                    let name = self.parse_identifier();
+                    if let TokenKind::OpenDelim(DelimToken::Brace) = self.token.kind {
+                        return self.parse_schema_pattern(name, token);
+                    } else if name.node.names.len() > 1 {
+                        let pos = name.pos();
+                        Pattern::Value(Box::new(Node::node_with_pos(
+                            Expr::Identifier(name.node),
+                            pos,
+                        )))
+                    } else if name.node.get_name() == "_" {
+                        Pattern::Wildcard
+                    } else {
+                        Pattern::Capture(name)
+                    }
+                }
+            }
+            TokenKind::Literal(_) => Pattern::Value(self.parse_operand_expr()),
+            TokenKind::BinOp(BinOpToken::Minus) => Pattern::Value(self.parse_unary_expr()),
+            TokenKind::OpenDelim(DelimToken::Bracket) => return self.parse_list_pattern(),
+            TokenKind::OpenDelim(DelimToken::Brace) => return self.parse_dict_pattern(),
+            TokenKind::OpenDelim(DelimToken::Paren) => {
+                self.bump();
+                let pattern = self.parse_pattern();
+                self.bump_token(TokenKind::CloseDelim(DelimToken::Paren));
+                return pattern;
+            }
+            _ => {
+                self.sess.struct_token_error(
+                    &[
+                        TokenKind::ident_value(),
+                        TokenKind::literal_value(),
+                        TokenKind::OpenDelim(DelimToken::Paren).into(),
+                        TokenKind::OpenDelim(DelimToken::Bracket).into(),
+                        TokenKind::OpenDelim(DelimToken::Brace).into(),
+                    ],
+                    self.token,
+                );
+                Pattern::Wildcard
+            }
+        };
+        Box::new(Node::node(
+            pattern,
+            self.sess.struct_token_loc(token, self.prev_token),
+        ))
+    }
+
+    /// Syntax:
+    /// schema_pattern: identifier LEFT_BRACE [pattern_entry (COMMA pattern_entry)* [COMMA]] RIGHT_BRACE
+    fn parse_schema_pattern(
+        &mut self,
+        name: NodeRef<Identifier>,
+        token: token::Token,
+    ) -> NodeRef<Pattern> {
+        let (entries, _) = self.parse_pattern_entries(false);
+        Box::new(Node::node(
+            Pattern::Schema(SchemaPattern { name, entries }),
+            self.sess.struct_token_loc(token, self.prev_token),
+        ))
+    }
+
+    /// Syntax:
+    /// list_pattern: LEFT_BRACKETS [pattern (COMMA pattern)* [COMMA MULTIPLY NAME] [COMMA]] RIGHT_BRACKETS
+    fn parse_list_pattern(&mut self) -> NodeRef<Pattern> {
+        let token = self.token;
+        self.bump_token(TokenKind::OpenDelim(DelimToken::Bracket));
+        let mut elts = Vec::new();
+        let mut rest = None;
+        loop {
+            self.skip_newlines();
+            self.clean_all_indentations();
+            if matches!(
+                self.token.kind,
+                TokenKind::CloseDelim(DelimToken::Bracket) | TokenKind::Eof
+            ) {
+                break;
+            }
+            let marker = self.mark();
+            if let TokenKind::BinOp(BinOpToken::Star) = self.token.kind {
+                self.bump();
+                if rest.is_some() {
+                    self.sess.struct_span_error(
+                        "multiple rest items in the list pattern",
+                        self.prev_token.span,
+                    );
+                }
+                rest = Some(self.parse_pattern_name());
+            } else {
+                if rest.is_some() {
+                    self.sess.struct_span_error(
+                        "the rest items must be the last one in the list pattern",
+                        self.token.span,
+                    );
+                }
+                elts.push(self.parse_pattern());
+            }
+            self.skip_newlines();
+            self.clean_all_indentations();
+            if let TokenKind::Comma = self.token.kind {
+                self.bump();
+            }
+            self.drop(marker);
+        }
+        self.bump_token(TokenKind::CloseDelim(DelimToken::Bracket));
+        Box::new(Node::node(
+            Pattern::List(ListPattern { elts, rest }),
+            self.sess.struct_token_loc(token, self.prev_token),
+        ))
+    }
+
+    /// Syntax:
+    /// dict_pattern: LEFT_BRACE [pattern_entry (COMMA pattern_entry)* [COMMA POWER NAME] [COMMA]] RIGHT_BRACE
+    fn parse_dict_pattern(&mut self) -> NodeRef<Pattern> {
+        let token = self.token;
+        let (entries, rest) = self.parse_pattern_entries(true);
+        Box::new(Node::node(
+            Pattern::Dict(DictPattern { entries, rest }),
+            self.sess.struct_token_loc(token, self.prev_token),
+        ))
+    }
+
+    /// Syntax:
+    /// pattern_entries: LEFT_BRACE [pattern_entry (COMMA pattern_entry)* [COMMA POWER NAME] [COMMA]] RIGHT_BRACE
+    /// pattern_entry: (NAME | string) COLON pattern
+    fn parse_pattern_entries(
+        &mut self,
+        allow_rest: bool,
+    ) -> (Vec<NodeRef<PatternEntry>>, Option<NodeRef<Identifier>>) {
+        self.bump_token(TokenKind::OpenDelim(DelimToken::Brace));
+        let mut entries = Vec::new();
+        let mut rest = None;
+        loop {
+            self.skip_newlines();
+            self.clean_all_indentations();
+            if matches!(
+                self.token.kind,
+                TokenKind::CloseDelim(DelimToken::Brace) | TokenKind::Eof
+            ) {
+                break;
+            }
+            let marker = self.mark();
+            let token = self.token;
+            if let TokenKind::BinOp(BinOpToken::StarStar) = self.token.kind {
+                self.bump();
+                if !allow_rest {
+                    self.sess.struct_span_error(
+                        "the rest entries are not allowed in the schema pattern",
+                        self.prev_token.span,
+                    );
+                } else if rest.is_some() {
+                    self.sess.struct_span_error(
+                        "multiple rest entries in the pattern",
+                        self.prev_token.span,
+                    );
+                }
+                rest = Some(self.parse_pattern_name());
+            } else {
+                let key = match self.token.kind {
+                    TokenKind::Literal(lit) if matches!(lit.kind, token::LitKind::Str { .. }) => {
+                        self.parse_string_attribute()
+                    }
+                    _ => match self.token.ident() {
+                        Some(ident) => {
+                            self.bump();
+                            Some(node_ref!(ident.as_str(), self.token_span_pos(token, token)))
+                        }
+                        None => None,
+                    },
+                };
+                match key {
+                    Some(key) => {
+                        if rest.is_some() {
+                            self.sess.struct_span_error(
+                                "the rest entries must be the last one in the pattern",
+                                token.span,
+                            );
+                        }
+                        self.bump_token(TokenKind::Colon);
+                        let value = self.parse_pattern();
+                        entries.push(Box::new(Node::node(
+                            PatternEntry { key, value },
+                            self.sess.struct_token_loc(token, self.prev_token),
+                        )));
+                    }
+                    None => {
+                        self.sess.struct_token_error(
+                            &[TokenKind::ident_value(), TokenKind::literal_value()],
+                            self.token,
+                        );
+                    }
+                }
+            }
+            self.skip_newlines();
+            self.clean_all_indentations();
+            if let TokenKind::Comma = self.token.kind {
+                self.bump();
+            }
+            self.drop(marker);
+        }
+        self.bump_token(TokenKind::CloseDelim(DelimToken::Brace));
+        (entries, rest)
+    }
+
+    /// Parse the name binding in the pattern, which must be an ordinary identifier.
+    fn parse_pattern_name(&mut self) -> NodeRef<Identifier> {
+        let token = self.token;
+        let name = self.parse_identifier();
+        if name.node.names.len() > 1 {
+            self.sess
+                .struct_span_error("expected an ordinary identifier", token.span);
+        }
+        name
+    }
+
     /// Syntax:
     /// paren_expr: LEFT_PARENTHESES expr RIGHT_PARENTHESES
     fn parse_paren_expr(&mut self) -> NodeRef<Expr> {
@@ -2276,7 +2640,7 @@ impl<'a> Parser<'a> {
 
     /// Syntax:
     /// number: DEC_NUMBER [multiplier] | HEX_NUMBER | BIN_NUMBER | OCT_NUMBER | FLOAT_NUMBER
-    fn parse_num_expr(&mut self, lk: token::Lit) -> NodeRef<Expr> {
+    pub(crate) fn parse_num_expr(&mut self, lk: token::Lit) -> NodeRef<Expr> {
         let token = self.token;
 
         let (binary_suffix, value) = match lk.kind {
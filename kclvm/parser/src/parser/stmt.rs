@@ -18,7 +18,7 @@ impl<'a> Parser<'a> {
     /// Syntax:
     /// statement: simple_stmt | compound_stmt
     /// simple_stmt: (assign_stmt | expr_stmt | assert_stmt | import_stmt | type_alias_stmt) NEWLINE
-    /// compound_stmt: if_stmt | schema_stmt
+    /// compound_stmt: if_stmt | schema_stmt | rule_stmt | enum_stmt
     pub(crate) fn parse_stmt(&mut self) -> Option<NodeRef<Stmt>> {
         // skip new lines
         if matches!(self.token.kind, TokenKind::Newline) {
@@ -65,6 +65,13 @@ impl<'a> Parser<'a> {
             return Some(self.parse_if_stmt());
         }
 
+        // enum Name: ..., `enum` is still a valid identifier in other statements.
+        if self.token.is_keyword(kw::Enum)
+            && matches!(self.cursor.peek(), Some(token) if matches!(token.kind, TokenKind::Ident(_)))
+        {
+            return Some(self.parse_enum_stmt());
+        }
+
         // @decorators
         let decorators = if matches!(self.token.kind, TokenKind::At) {
             Some(self.parse_decorators())
@@ -87,7 +94,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Syntax:
-    /// simple_stmt: (assign_stmt | unification_stmt | expr_stmt | assert_stmt | import_stmt | type_alias_stmt) NEWLINE
+    /// simple_stmt: (assign_stmt | unification_stmt | expr_stmt | assert_stmt | import_stmt | export_stmt | type_alias_stmt) NEWLINE
     fn parse_simple_stmt(&mut self) -> Option<NodeRef<Stmt>> {
         // skip new lines
         if matches!(self.token.kind, TokenKind::Newline) {
@@ -104,6 +111,13 @@ impl<'a> Parser<'a> {
             return Some(self.parse_import_stmt());
         }
 
+        // export ..., `export` is still a valid identifier in other statements.
+        if self.token.is_keyword(kw::Export)
+            && matches!(self.cursor.peek(), Some(token) if matches!(token.kind, TokenKind::Ident(_)))
+        {
+            return Some(self.parse_export_stmt());
+        }
+
         // type ...
         if self.token.is_keyword(kw::Type) {
             return Some(self.parse_type_alias_stmt());
@@ -289,6 +303,7 @@ impl<'a> Parser<'a> {
                             op: Some(aug_op),
                             value: Some(value),
                             is_optional: false,
+                            is_final: false,
                             decorators: Vec::new(),
                         }),
                         self.token_span_pos(token, self.prev_token)
@@ -337,6 +352,7 @@ impl<'a> Parser<'a> {
                             op: None,
                             value: None,
                             is_optional: false,
+                            is_final: false,
                             decorators: Vec::new(),
                         }),
                         self.token_span_pos(token, stmt_end_token)
@@ -497,6 +513,37 @@ impl<'a> Parser<'a> {
         t
     }
 
+    /// Syntax:
+    /// export_stmt: EXPORT NAME (COMMA NAME)*
+    fn parse_export_stmt(&mut self) -> NodeRef<Stmt> {
+        let token = self.token;
+        self.bump_keyword(kw::Export);
+
+        let mut names = vec![];
+        loop {
+            let ident = self.parse_identifier().node;
+            match ident.names.len() {
+                1 => names.push(ident.names[0].clone()),
+                _ => self
+                    .sess
+                    .struct_span_error("Invalid export name", self.prev_token.span),
+            }
+            if !matches!(self.token.kind, TokenKind::Comma) {
+                break;
+            }
+            self.bump_token(TokenKind::Comma);
+        }
+
+        let t = node_ref!(
+            Stmt::Export(ExportStmt { names }),
+            self.token_span_pos(token, self.prev_token)
+        );
+
+        self.skip_newlines();
+
+        t
+    }
+
     /// Syntax:
     /// type_alias_stmt: "type" NAME ASSIGN type
     fn parse_type_alias_stmt(&mut self) -> NodeRef<Stmt> {
@@ -682,9 +729,13 @@ impl<'a> Parser<'a> {
 
     /// Syntax:
     /// schema_stmt: [decorators] (SCHEMA|MIXIN|PROTOCOL) NAME
-    ///   [LEFT_BRACKETS [schema_arguments] RIGHT_BRACKETS]
+    ///   [[type_params] LEFT_BRACKETS [schema_arguments] RIGHT_BRACKETS]
     ///   [LEFT_PARENTHESES identifier (COMMA identifier)* RIGHT_PARENTHESES]
     ///   [for_host] COLON NEWLINE [schema_body]
+    ///
+    /// A single brackets after the schema name are always the schema arguments, thus
+    /// the type parameters must be followed by the schema arguments brackets, which
+    /// can be empty e.g., `schema Page[T][]` and `schema Page[T][size: int]`.
     fn parse_schema_stmt(&mut self, decorators: Option<Vec<NodeRef<CallExpr>>>) -> NodeRef<Stmt> {
         let token = self.token;
 
@@ -727,6 +778,13 @@ impl<'a> Parser<'a> {
             is_protocol = true;
         }
 
+        // schema Name[T, ...][args...]
+        let type_params = if self.is_followed_by_brackets() {
+            self.parse_type_params()
+        } else {
+            vec![]
+        };
+
         // schema Name[args...]
         let args = if let TokenKind::OpenDelim(DelimToken::Bracket) = self.token.kind {
             self.parse_parameters(
@@ -782,6 +840,7 @@ impl<'a> Parser<'a> {
                 Stmt::Schema(SchemaStmt {
                     doc: body.doc,
                     name,
+                    type_params,
                     parent_name,
                     for_host_name,
                     is_mixin,
@@ -801,6 +860,7 @@ impl<'a> Parser<'a> {
                 Stmt::Schema(SchemaStmt {
                     doc: None,
                     name,
+                    type_params,
                     parent_name,
                     for_host_name,
                     is_mixin,
@@ -852,6 +912,60 @@ impl<'a> Parser<'a> {
         decorators
     }
 
+    /// Syntax:
+    /// type_params: LEFT_BRACKETS NAME (COMMA NAME)* RIGHT_BRACKETS
+    ///
+    /// The type parameters use the same brackets as the type arguments e.g., the
+    /// generic schema `schema Page[T][]` is used as the type `Page[int]`.
+    pub(crate) fn parse_type_params(&mut self) -> Vec<NodeRef<String>> {
+        let mut type_params = vec![];
+        if self.token.kind != TokenKind::OpenDelim(DelimToken::Bracket) {
+            return type_params;
+        }
+        self.bump_token(TokenKind::OpenDelim(DelimToken::Bracket));
+        // Parse all the type parameters until the parameters list end ']'
+        while self.token.kind != TokenKind::CloseDelim(DelimToken::Bracket) && self.peek_has_next()
+        {
+            let name = self.parse_identifier();
+            let pos = name.pos();
+            type_params.push(node_ref!(name.node.get_names().join("."), pos));
+            if let TokenKind::Comma = self.token.kind {
+                self.bump_token(TokenKind::Comma);
+            } else {
+                break;
+            }
+        }
+        self.bump_token(TokenKind::CloseDelim(DelimToken::Bracket));
+        type_params
+    }
+
+    /// Whether the brackets at the current token are followed by another brackets
+    /// on the same line e.g., the schema type parameters `[T]` in `schema Page[T][]`.
+    fn is_followed_by_brackets(&self) -> bool {
+        if self.token.kind != TokenKind::OpenDelim(DelimToken::Bracket) {
+            return false;
+        }
+        let stream = &self.cursor.stream;
+        let mut depth = 1;
+        for (i, token) in stream.iter().enumerate().skip(self.cursor.index()) {
+            match token.kind {
+                TokenKind::OpenDelim(DelimToken::Bracket) => depth += 1,
+                TokenKind::CloseDelim(DelimToken::Bracket) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return matches!(
+                            stream.get(i + 1).map(|token| token.kind),
+                            Some(TokenKind::OpenDelim(DelimToken::Bracket))
+                        );
+                    }
+                }
+                TokenKind::Newline | TokenKind::Eof => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
     /// Syntax:
     /// schema_arguments: schema_argument (COMMA schema_argument)*
     /// schema_argument: NAME [COLON type] [ASSIGN test]
@@ -1032,6 +1146,16 @@ impl<'a> Parser<'a> {
                 ));
                 continue;
             }
+            // final schema_attribute_stmt
+            else if self.is_final_schema_attribute() {
+                let token = self.token;
+                let attr = self.parse_schema_attribute();
+                body_body.push(node_ref!(
+                    Stmt::SchemaAttr(attr),
+                    self.token_span_pos(token, self.prev_token)
+                ));
+                continue;
+            }
             if let Some(peek) = self.cursor.peek() {
                 if let TokenKind::Question = peek.kind {
                     let token = self.token;
@@ -1100,6 +1224,7 @@ impl<'a> Parser<'a> {
                                     op: Some(AugOp::Assign),
                                     value: Some(assign.value),
                                     is_optional: false,
+                                    is_final: false,
                                     decorators: Vec::new(),
                                 }),
                                 x.pos()
@@ -1139,6 +1264,7 @@ impl<'a> Parser<'a> {
                 end_line: 0,
                 end_column: 0,
             }),
+            type_params: vec![],
             parent_name: None,
             for_host_name: None,
             is_mixin: false,
@@ -1217,7 +1343,7 @@ impl<'a> Parser<'a> {
 
     /// Syntax:
     /// schema_attribute_stmt: attribute_stmt NEWLINE
-    /// attribute_stmt: [decorators] (identifier | string) [QUESTION] COLON type [(ASSIGN|COMP_OR) test]
+    /// attribute_stmt: [decorators] [FINAL] (identifier | string) [QUESTION] COLON type [(ASSIGN|COMP_OR) test]
     fn parse_schema_attribute(&mut self) -> SchemaAttr {
         let doc = "".to_string();
 
@@ -1230,6 +1356,14 @@ impl<'a> Parser<'a> {
             Vec::new()
         };
 
+        // Parse the final attribute modifier `final`
+        let is_final = if self.is_final_schema_attribute() {
+            self.bump_keyword(kw::Final);
+            true
+        } else {
+            false
+        };
+
         // Parse schema identifier-like or string-like attributes
         let name = if let Some(name) = self.parse_string_attribute() {
             name
@@ -1277,10 +1411,21 @@ impl<'a> Parser<'a> {
             op,
             value,
             is_optional,
+            is_final,
             decorators,
         }
     }
 
+    /// Whether the current token is the `final` modifier of a schema attribute,
+    /// `final` is still a valid attribute name e.g., `final: bool = True`.
+    fn is_final_schema_attribute(&self) -> bool {
+        self.token.is_keyword(kw::Final)
+            && matches!(
+                self.cursor.peek(),
+                Some(token) if matches!(token.kind, TokenKind::Ident(_)) || token.is_string_lit()
+            )
+    }
+
     /// Syntax:
     /// schema_index_signature:
     ///   LEFT_BRACKETS [NAME COLON] [ELLIPSIS] basic_type RIGHT_BRACKETS
@@ -1540,6 +1685,88 @@ impl<'a> Parser<'a> {
         )
     }
 
+    /// Syntax:
+    /// enum_stmt: ENUM identifier COLON NEWLINE _INDENT [string NEWLINE] enum_member+ _DEDENT
+    /// enum_member: NAME [ASSIGN test] NEWLINE [string NEWLINE]
+    fn parse_enum_stmt(&mut self) -> NodeRef<Stmt> {
+        let token = self.token;
+        self.bump_keyword(kw::Enum);
+
+        let name_expr = self.parse_identifier_expr();
+        let name_pos = name_expr.pos();
+        let name = expr_as!(name_expr, Expr::Identifier).unwrap();
+        let name = node_ref!(name.get_names().join("."), name_pos);
+
+        self.bump_token(TokenKind::Colon);
+        self.skip_newlines();
+        self.bump_token(TokenKind::Indent(VALID_SPACES_LENGTH));
+
+        // doc string
+        let body_doc = self.parse_doc();
+
+        let mut members = vec![];
+        self.validate_dedent();
+        while self.token.kind != TokenKind::Dedent(VALID_SPACES_LENGTH) {
+            let marker = self.mark();
+            if matches!(self.token.kind, TokenKind::Eof) {
+                self.sess
+                    .struct_token_error(&[TokenKind::Newline.into()], self.token);
+                break;
+            }
+
+            if let Some(member) = self.parse_enum_member() {
+                members.push(member);
+            }
+
+            self.drop(marker);
+        }
+        self.validate_dedent();
+        self.bump_token(TokenKind::Dedent(VALID_SPACES_LENGTH));
+
+        let pos = self.token_span_pos(token, self.prev_token);
+
+        node_ref!(
+            Stmt::Enum(EnumStmt {
+                doc: body_doc,
+                name,
+                members,
+            }),
+            pos
+        )
+    }
+
+    /// Syntax:
+    /// enum_member: NAME [ASSIGN test] NEWLINE [string NEWLINE]
+    fn parse_enum_member(&mut self) -> Option<NodeRef<EnumMember>> {
+        let token = self.token;
+        let name = match self.token.ident() {
+            Some(ident) => {
+                self.bump();
+                node_ref!(ident.as_str(), self.token_span_pos(token, token))
+            }
+            None => {
+                self.sess
+                    .struct_token_error(&[TokenKind::ident_value()], self.token);
+                self.bump();
+                return None;
+            }
+        };
+
+        let value = if let TokenKind::Assign = self.token.kind {
+            self.bump();
+            Some(self.parse_expr())
+        } else {
+            None
+        };
+        let pos = self.token_span_pos(token, self.prev_token);
+
+        self.skip_newlines();
+        // The member doc string follows the member.
+        let doc = self.parse_doc();
+
+        Some(node_ref!(EnumMember { name, value, doc }, pos))
+    }
+
     pub(crate) fn parse_string_attribute(&mut self) -> Option<NodeRef<String>> {
         match self.token.kind {
             TokenKind::Literal(lit) => {
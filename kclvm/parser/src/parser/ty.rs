@@ -4,7 +4,7 @@ use super::Parser;
 
 use kclvm_ast::ast::{Expr, Node, NodeRef, Type};
 use kclvm_ast::token;
-use kclvm_ast::token::{BinOpToken, DelimToken, TokenKind};
+use kclvm_ast::token::{BinOpToken, DelimToken, Token, TokenKind};
 use kclvm_ast::{ast, expr_as};
 use kclvm_span::symbol::{kw, sym};
 
@@ -12,10 +12,12 @@ impl<'a> Parser<'a> {
     /// Syntax:
     ///
     /// type: type_element (OR type_element)*
-    /// type_element: schema_type | function_type | basic_type | compound_type | literal_type
+    /// type_element: schema_type | function_type | basic_type | refined_type | compound_type | literal_type
     /// function_type: LEFT_PARENTHESES [type_element (COMMA type_element)*] RIGHT_PARENTHESES [RIGHT_ARROW type_element]
-    /// schema_type: identifier
+    /// schema_type: identifier [LEFT_BRACKETS type (COMMA type)* RIGHT_BRACKETS]
     /// basic_type: STRING_TYPE | INT_TYPE | FLOAT_TYPE | BOOL_TYPE | ANY_TYPE
+    /// refined_type: (INT_TYPE | FLOAT_TYPE) LEFT_PARENTHESES [number] DOT DOT [number] RIGHT_PARENTHESES
+    ///     | STRING_TYPE LEFT_PARENTHESES string RIGHT_PARENTHESES
     /// compound_type: list_type | dict_type
     /// list_type: LEFT_BRACKETS (type)? RIGHT_BRACKETS
     /// dict_type: LEFT_BRACE (type)? COLON (type)? RIGHT_BRACE
@@ -82,33 +84,43 @@ impl<'a> Parser<'a> {
                 self.sess.struct_token_loc(token, self.prev_token),
             ));
         } else if self.token.is_keyword(sym::int) {
-            let t = Type::Basic(ast::BasicType::Int);
             self.bump_keyword(sym::int);
-            return Box::new(Node::node(
-                t,
-                self.sess.struct_token_loc(token, self.prev_token),
-            ));
+            return self.parse_basic_type_constraint(token, ast::BasicType::Int);
         } else if self.token.is_keyword(sym::float) {
-            let t = Type::Basic(ast::BasicType::Float);
             self.bump_keyword(sym::float);
-            return Box::new(Node::node(
-                t,
-                self.sess.struct_token_loc(token, self.prev_token),
-            ));
+            return self.parse_basic_type_constraint(token, ast::BasicType::Float);
         } else if self.token.is_keyword(sym::str) {
-            let t = Type::Basic(ast::BasicType::Str);
             self.bump_keyword(sym::str);
-            return Box::new(Node::node(
-                t,
-                self.sess.struct_token_loc(token, self.prev_token),
-            ));
+            return self.parse_basic_type_constraint(token, ast::BasicType::Str);
         }
 
         // named type
         if let TokenKind::Ident(_) = self.token.kind {
             let ident = self.parse_identifier_expr();
             let ident = expr_as!(ident, Expr::Identifier).unwrap();
-            let t = Type::Named(ident);
+            // Generic schema type arguments e.g., `Page[int]`
+            let t = if let TokenKind::OpenDelim(DelimToken::Bracket) = self.token.kind {
+                self.bump_token(TokenKind::OpenDelim(DelimToken::Bracket));
+                let mut type_args = vec![];
+                // Parse all the type arguments until the arguments list end ']'
+                while self.token.kind != TokenKind::CloseDelim(DelimToken::Bracket)
+                    && self.peek_has_next()
+                {
+                    type_args.push(self.parse_type_annotation());
+                    if let TokenKind::Comma = self.token.kind {
+                        self.bump_token(TokenKind::Comma);
+                    } else {
+                        break;
+                    }
+                }
+                self.bump_token(TokenKind::CloseDelim(DelimToken::Bracket));
+                Type::Generic(ast::GenericType {
+                    name: ident,
+                    type_args,
+                })
+            } else {
+                Type::Named(ident)
+            };
             return Box::new(Node::node(
                 t,
                 self.sess.struct_token_loc(token, self.prev_token),
@@ -288,4 +300,98 @@ impl<'a> Parser<'a> {
             self.sess.struct_token_loc(token, self.prev_token),
         ))
     }
+
+    /// Parse the optional value constraint of the basic type e.g., `int(1..65535)`
+    /// and `str(r"^[a-z0-9-]+$")`, the basic type keyword has been bumped.
+    fn parse_basic_type_constraint(&mut self, token: Token, base: ast::BasicType) -> NodeRef<Type> {
+        let t = if let TokenKind::OpenDelim(DelimToken::Paren) = self.token.kind {
+            self.bump_token(TokenKind::OpenDelim(DelimToken::Paren));
+            let constraint = match base {
+                ast::BasicType::Str => {
+                    ast::TypeConstraint::Pattern(self.parse_pattern_constraint())
+                }
+                _ => ast::TypeConstraint::Range(self.parse_range_constraint()),
+            };
+            self.bump_token(TokenKind::CloseDelim(DelimToken::Paren));
+            Type::Refined(ast::RefinedType { base, constraint })
+        } else {
+            Type::Basic(base)
+        };
+        Box::new(Node::node(
+            t,
+            self.sess.struct_token_loc(token, self.prev_token),
+        ))
+    }
+
+    /// Syntax:
+    ///
+    /// range: [number] DOT DOT [number]
+    fn parse_range_constraint(&mut self) -> ast::RangeConstraint {
+        let min = self.parse_range_bound();
+        self.bump_token(TokenKind::Dot);
+        self.bump_token(TokenKind::Dot);
+        let max = self.parse_range_bound();
+        ast::RangeConstraint { min, max }
+    }
+
+    /// Syntax:
+    ///
+    /// number: [MINUS] (INT | FLOAT)
+    fn parse_range_bound(&mut self) -> Option<ast::NumberLitValue> {
+        let token = self.token;
+        let is_negative = if let TokenKind::BinOp(BinOpToken::Minus) = self.token.kind {
+            self.bump();
+            true
+        } else {
+            false
+        };
+        if let TokenKind::Literal(lit) = self.token.kind {
+            if let token::LitKind::Integer | token::LitKind::Float = lit.kind {
+                let number_lit = self.parse_num_expr(lit);
+                if let Expr::NumberLit(number_lit) = number_lit.node {
+                    if number_lit.binary_suffix.is_some() {
+                        self.sess.struct_span_error(
+                            "the number multiplier is not allowed in the range constraint",
+                            token.span,
+                        );
+                    }
+                    return Some(match number_lit.value {
+                        ast::NumberLitValue::Int(v) if is_negative => ast::NumberLitValue::Int(-v),
+                        ast::NumberLitValue::Float(v) if is_negative => {
+                            ast::NumberLitValue::Float(-v)
+                        }
+                        value => value,
+                    });
+                }
+            }
+        }
+        if is_negative {
+            self.sess.struct_token_error(
+                &[token::LitKind::Integer.into(), token::LitKind::Float.into()],
+                self.token,
+            );
+        }
+        None
+    }
+
+    /// Syntax:
+    ///
+    /// pattern: string
+    fn parse_pattern_constraint(&mut self) -> String {
+        if let TokenKind::Literal(lit) = self.token.kind {
+            if let token::LitKind::Str { .. } = lit.kind {
+                self.bump();
+                return lit.symbol.as_str();
+            }
+        }
+        self.sess.struct_token_error(
+            &[token::LitKind::Str {
+                is_long_string: false,
+                is_raw: false,
+            }
+            .into()],
+            self.token,
+        );
+        "".to_string()
+    }
 }
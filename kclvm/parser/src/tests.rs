@@ -196,6 +196,46 @@ pub fn test_parse_file_invalid() {
     }
 }
 
+#[test]
+fn test_parse_type_params() {
+    let module = parse_file_force_errors(
+        "test.k",
+        Some(
+            "schema Person[name]:\n    n: str = name\n\nschema Page[T][]:\n    items: [T]\n\nschema Pair[K, V][key: K]:\n    value: V\n\nf = lambda[T] x: T -> T {\n    x\n}\n"
+                .to_string(),
+        ),
+    )
+    .unwrap();
+    let schema_params = |i: usize| match &module.body[i].node {
+        ast::Stmt::Schema(schema_stmt) => (
+            schema_stmt
+                .type_params
+                .iter()
+                .map(|param| param.node.clone())
+                .collect::<Vec<String>>(),
+            schema_stmt.args.as_ref().unwrap().node.args.len(),
+        ),
+        _ => unreachable!(),
+    };
+    // A single brackets are the schema arguments.
+    assert_eq!(schema_params(0), (vec![], 1));
+    assert_eq!(schema_params(1), (vec!["T".to_string()], 0));
+    assert_eq!(
+        schema_params(2),
+        (vec!["K".to_string(), "V".to_string()], 1)
+    );
+    match &module.body[3].node {
+        ast::Stmt::Assign(assign_stmt) => match &assign_stmt.value.node {
+            ast::Expr::Lambda(lambda_expr) => {
+                assert_eq!(lambda_expr.type_params[0].node, "T");
+                assert_eq!(lambda_expr.args.as_ref().unwrap().node.args.len(), 1);
+            }
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
 pub fn test_vendor_home() {
     let vendor = &PathBuf::from(".")
         .join("testdata")
@@ -94,6 +94,7 @@ parse_expr_snapshot!(
     _a
 }"####
 );
+parse_expr_snapshot!(match_expr_0, r####"match x {1: "a", _: "b"}"####);
 parse_expr_snapshot!(
     config_expr_0,
     r####"{
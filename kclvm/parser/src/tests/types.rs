@@ -30,3 +30,10 @@ parse_type_node_snapshot!(type_str_0, r####"int"####);
 parse_type_node_snapshot!(type_str_1, r####"  int    "####);
 parse_type_node_snapshot!(type_str_2, r####"bool | True |  int  | str|str"####);
 parse_type_node_snapshot!(type_str_3, r####"[ [{str: float}] | int]"####);
+
+parse_type_snapshot!(refined_type_0, r####"int(1..65535)"####);
+parse_type_snapshot!(refined_type_1, r####"str("^[a-z]+$")"####);
+parse_type_node_snapshot!(
+    type_str_4,
+    r####"int(1..65535) | float(-0.5..) | str(r"^[a-z0-9-]+$")"####
+);
@@ -120,6 +120,12 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
     fn walk_import_stmt(&mut self, _import_stmt: &'ctx mut ast::ImportStmt) {
         // Nothing to do
     }
+    fn walk_export_stmt(&mut self, export_stmt: &'ctx mut ast::ExportStmt) {
+        for name in export_stmt.names.iter_mut() {
+            name.line += self.line_offset as u64;
+            name.end_line += self.line_offset as u64;
+        }
+    }
     fn walk_schema_attr(&mut self, schema_attr: &'ctx mut ast::SchemaAttr) {
         schema_attr.name.line += self.line_offset as u64;
         schema_attr.name.end_line += self.line_offset as u64;
@@ -174,6 +180,14 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
                     self.walk_type(&mut ty.node)
                 });
             }
+            ast::Type::Generic(generic_ty) => {
+                self.walk_identifier(&mut generic_ty.name);
+                generic_ty.type_args.iter_mut().for_each(|ty| {
+                    ty.line += self.line_offset as u64;
+                    ty.end_line += self.line_offset as u64;
+                    self.walk_type(&mut ty.node)
+                });
+            }
             _ => {}
         }
     }
@@ -181,6 +195,11 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
         schema_stmt.name.line += self.line_offset as u64;
         schema_stmt.name.end_line += self.line_offset as u64;
 
+        for type_param in schema_stmt.type_params.iter_mut() {
+            type_param.line += self.line_offset as u64;
+            type_param.end_line += self.line_offset as u64;
+        }
+
         match schema_stmt.parent_name.as_deref_mut() {
             Some(v) => {
                 v.line += self.line_offset as u64;
@@ -283,6 +302,22 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
         walk_if_mut!(self, walk_arguments, rule_stmt.args);
         walk_if_mut!(self, walk_identifier, rule_stmt.for_host_name);
     }
+    fn walk_enum_stmt(&mut self, enum_stmt: &'ctx mut ast::EnumStmt) {
+        enum_stmt.name.line += self.line_offset as u64;
+        enum_stmt.name.end_line += self.line_offset as u64;
+
+        for member in enum_stmt.members.iter_mut() {
+            member.line += self.line_offset as u64;
+            member.end_line += self.line_offset as u64;
+            member.node.name.line += self.line_offset as u64;
+            member.node.name.end_line += self.line_offset as u64;
+            if let Some(value) = member.node.value.as_deref_mut() {
+                value.line += self.line_offset as u64;
+                value.end_line += self.line_offset as u64;
+                self.walk_expr(&mut value.node);
+            }
+        }
+    }
     fn walk_quant_expr(&mut self, quant_expr: &'ctx mut ast::QuantExpr) {
         quant_expr.target.line += self.line_offset as u64;
         quant_expr.target.end_line += self.line_offset as u64;
@@ -590,6 +625,11 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
         walk_if_mut!(self, walk_expr, check_expr.msg);
     }
     fn walk_lambda_expr(&mut self, lambda_expr: &'ctx mut ast::LambdaExpr) {
+        for type_param in lambda_expr.type_params.iter_mut() {
+            type_param.line += self.line_offset as u64;
+            type_param.end_line += self.line_offset as u64;
+        }
+
         match lambda_expr.args.as_deref_mut() {
             Some(v) => {
                 v.line += self.line_offset as u64;
@@ -615,6 +655,123 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
         walk_list_mut!(self, walk_stmt, lambda_expr.body);
         walk_if_mut!(self, walk_type, lambda_expr.return_ty);
     }
+    fn walk_match_expr(&mut self, match_expr: &'ctx mut ast::MatchExpr) {
+        match_expr.subject.line += self.line_offset as u64;
+        match_expr.subject.end_line += self.line_offset as u64;
+
+        for case in match_expr.cases.iter_mut() {
+            case.line += self.line_offset as u64;
+            case.end_line += self.line_offset as u64;
+
+            case.node.pattern.line += self.line_offset as u64;
+            case.node.pattern.end_line += self.line_offset as u64;
+
+            if let Some(v) = case.node.guard.as_deref_mut() {
+                v.line += self.line_offset as u64;
+                v.end_line += self.line_offset as u64;
+            }
+
+            case.node.body.line += self.line_offset as u64;
+            case.node.body.end_line += self.line_offset as u64;
+        }
+
+        self.walk_expr(&mut match_expr.subject.node);
+        for case in match_expr.cases.iter_mut() {
+            self.walk_pattern(&mut case.node.pattern.node);
+            walk_if_mut!(self, walk_expr, case.node.guard);
+            self.walk_expr(&mut case.node.body.node);
+        }
+    }
+    fn walk_pattern(&mut self, pattern: &'ctx mut ast::Pattern) {
+        match pattern {
+            ast::Pattern::Wildcard => {}
+            ast::Pattern::Value(value) => {
+                value.line += self.line_offset as u64;
+                value.end_line += self.line_offset as u64;
+
+                self.walk_expr(&mut value.node);
+            }
+            ast::Pattern::Capture(name) => {
+                name.line += self.line_offset as u64;
+                name.end_line += self.line_offset as u64;
+
+                self.walk_identifier(&mut name.node);
+            }
+            ast::Pattern::Schema(schema_pattern) => {
+                schema_pattern.name.line += self.line_offset as u64;
+                schema_pattern.name.end_line += self.line_offset as u64;
+
+                for entry in schema_pattern.entries.iter_mut() {
+                    entry.line += self.line_offset as u64;
+                    entry.end_line += self.line_offset as u64;
+                    entry.node.key.line += self.line_offset as u64;
+                    entry.node.key.end_line += self.line_offset as u64;
+                    entry.node.value.line += self.line_offset as u64;
+                    entry.node.value.end_line += self.line_offset as u64;
+                }
+
+                self.walk_identifier(&mut schema_pattern.name.node);
+                for entry in schema_pattern.entries.iter_mut() {
+                    self.walk_pattern(&mut entry.node.value.node);
+                }
+            }
+            ast::Pattern::List(list_pattern) => {
+                for elt in list_pattern.elts.iter_mut() {
+                    elt.line += self.line_offset as u64;
+                    elt.end_line += self.line_offset as u64;
+                }
+
+                if let Some(v) = list_pattern.rest.as_deref_mut() {
+                    v.line += self.line_offset as u64;
+                    v.end_line += self.line_offset as u64;
+                }
+
+                for elt in list_pattern.elts.iter_mut() {
+                    self.walk_pattern(&mut elt.node);
+                }
+                walk_if_mut!(self, walk_identifier, list_pattern.rest);
+            }
+            ast::Pattern::Dict(dict_pattern) => {
+                for entry in dict_pattern.entries.iter_mut() {
+                    entry.line += self.line_offset as u64;
+                    entry.end_line += self.line_offset as u64;
+                    entry.node.key.line += self.line_offset as u64;
+                    entry.node.key.end_line += self.line_offset as u64;
+                    entry.node.value.line += self.line_offset as u64;
+                    entry.node.value.end_line += self.line_offset as u64;
+                }
+
+                if let Some(v) = dict_pattern.rest.as_deref_mut() {
+                    v.line += self.line_offset as u64;
+                    v.end_line += self.line_offset as u64;
+                }
+
+                for entry in dict_pattern.entries.iter_mut() {
+                    self.walk_pattern(&mut entry.node.value.node);
+                }
+                walk_if_mut!(self, walk_identifier, dict_pattern.rest);
+            }
+            ast::Pattern::Or(patterns) => {
+                for pattern in patterns.iter_mut() {
+                    pattern.line += self.line_offset as u64;
+                    pattern.end_line += self.line_offset as u64;
+                }
+
+                for pattern in patterns.iter_mut() {
+                    self.walk_pattern(&mut pattern.node);
+                }
+            }
+            ast::Pattern::As(as_pattern) => {
+                as_pattern.pattern.line += self.line_offset as u64;
+                as_pattern.pattern.end_line += self.line_offset as u64;
+                as_pattern.name.line += self.line_offset as u64;
+                as_pattern.name.end_line += self.line_offset as u64;
+
+                self.walk_pattern(&mut as_pattern.pattern.node);
+                self.walk_identifier(&mut as_pattern.name.node);
+            }
+        }
+    }
     fn walk_keyword(&mut self, keyword: &'ctx mut ast::Keyword) {
         keyword.arg.line += self.line_offset as u64;
         keyword.arg.end_line += self.line_offset as u64;
@@ -718,6 +875,8 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
             ast::Stmt::SchemaAttr(schema_attr) => self.walk_schema_attr(schema_attr),
             ast::Stmt::Schema(schema_stmt) => self.walk_schema_stmt(schema_stmt),
             ast::Stmt::Rule(rule_stmt) => self.walk_rule_stmt(rule_stmt),
+            ast::Stmt::Enum(enum_stmt) => self.walk_enum_stmt(enum_stmt),
+            ast::Stmt::Export(export_stmt) => self.walk_export_stmt(export_stmt),
         }
     }
     fn walk_expr(&mut self, expr: &'ctx mut ast::Expr) {
@@ -746,6 +905,7 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
             ast::Expr::Config(config_expr) => self.walk_config_expr(config_expr),
             ast::Expr::Check(check) => self.walk_check_expr(check),
             ast::Expr::Lambda(lambda) => self.walk_lambda_expr(lambda),
+            ast::Expr::Match(match_expr) => self.walk_match_expr(match_expr),
             ast::Expr::Subscript(subscript) => self.walk_subscript(subscript),
             ast::Expr::Keyword(keyword) => self.walk_keyword(keyword),
             ast::Expr::Arguments(arguments) => self.walk_arguments(arguments),
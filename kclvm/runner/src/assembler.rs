@@ -301,6 +301,7 @@ impl KclvmAssembler {
                 modules: self.program.modules.clone(),
                 pkgs_not_imported: HashMap::new(),
                 modules_not_imported: HashMap::new(),
+                plugin_decls: HashMap::new(),
             };
             compile_progs.insert(
                 pkgpath,
@@ -0,0 +1,622 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! A debug adapter protocol (DAP) server built on the KCL evaluator, which
+//! supports line and conditional breakpoints, stepping through schema bodies
+//! and lambdas, inspecting the scope variables and the call stack.
+//!
+//! The server reads the requests in a separated thread and the evaluation is
+//! running in the current thread, which is blocked at a stop and answers the
+//! requests that need to inspect the evaluator state.
+
+mod protocol;
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use kclvm_ast::ast;
+use kclvm_evaluator::{DebugHook, Evaluator};
+use kclvm_parser::{parse_expr, ParseSession};
+use kclvm_runtime::ValueRef;
+use serde_json::{json, Value};
+
+use crate::{exec_program_with_debug_hook, ExecProgramArgs};
+use protocol::{command, str_argument, Client};
+pub use protocol::{read_message, write_message};
+
+/// The only thread id because the evaluation is single threaded.
+const THREAD_ID: i64 = 1;
+/// The frame name of the top-level statements.
+const MODULE_FRAME_NAME: &str = "<module>";
+
+/// The breakpoints keyed by the normalized file path.
+type Breakpoints = HashMap<String, Vec<Breakpoint>>;
+
+#[derive(Debug, Clone)]
+struct Breakpoint {
+    line: u64,
+    /// The breakpoint condition expression source, which is parsed into the
+    /// [ExprArena] of the evaluation thread.
+    condition: Option<String>,
+}
+
+/// ExprArena owns the parsed breakpoint conditions and watch expressions of the
+/// debug session. The evaluator borrows the evaluated AST nodes for its lifetime
+/// e.g., in the lambda values, so the arena must outlive the evaluator.
+#[derive(Default)]
+struct ExprArena {
+    /// The expressions keyed by the source, the same source is only parsed once.
+    exprs: RefCell<HashMap<String, Box<ast::Node<ast::Expr>>>>,
+}
+
+impl ExprArena {
+    /// Parse the expression source, or get the expression parsed before.
+    fn parse(&self, src: &str) -> Option<&ast::Node<ast::Expr>> {
+        let mut exprs = self.exprs.borrow_mut();
+        if !exprs.contains_key(src) {
+            exprs.insert(src.to_string(), parse_expr(src)?);
+        }
+        let expr: *const ast::Node<ast::Expr> = &*exprs[src];
+        // SAFETY: The boxed expressions are never removed or replaced until the
+        // arena is dropped, and moving the boxes in the map does not move the
+        // expressions, so they live as long as the arena borrow.
+        Some(unsafe { &*expr })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+enum StepMode {
+    /// Only stop at the breakpoints.
+    #[default]
+    Continue,
+    /// Stop at the next statement.
+    StepIn,
+    /// Stop at the next statement whose call depth is not greater than the depth.
+    StepOver(usize),
+    /// Stop at the next statement whose call depth is less than the depth.
+    StepOut(usize),
+}
+
+#[derive(Debug, Clone)]
+struct StackFrame {
+    name: String,
+    filename: String,
+    line: u64,
+}
+
+#[derive(Default)]
+struct DebugState {
+    step: StepMode,
+    /// The call stack frames, the outermost frame is the first one.
+    frames: Vec<StackFrame>,
+    /// The variable tables referenced by the DAP `variablesReference` i.e., the index plus one,
+    /// which are valid until the evaluation is resumed.
+    variables: Vec<Vec<(String, ValueRef)>>,
+    /// The normalized file path cache.
+    paths: HashMap<String, String>,
+}
+
+/// Start a DAP server using the standard input and output.
+pub fn start_stdio_server() -> Result<()> {
+    serve(BufReader::new(std::io::stdin()), std::io::stdout())
+}
+
+/// Start a DAP server using the reader and writer, returns when the client
+/// disconnects or the input is closed.
+pub fn serve<R, W>(reader: R, writer: W) -> Result<()>
+where
+    R: BufRead + Send + 'static,
+    W: Write + Send + 'static,
+{
+    let client = Client::new(writer);
+    let breakpoints: Arc<Mutex<Breakpoints>> = Default::default();
+    let pause = Arc::new(AtomicBool::new(false));
+    let (sender, requests) = channel();
+    {
+        let client = client.clone();
+        let breakpoints = breakpoints.clone();
+        let pause = pause.clone();
+        std::thread::spawn(move || read_requests(reader, client, breakpoints, pause, sender));
+    }
+    // Wait for the launch arguments and the configuration done request.
+    let mut launch = None;
+    let mut configured = false;
+    while launch.is_none() || !configured {
+        let request = match requests.recv() {
+            Ok(request) => request,
+            Err(_) => return Ok(()),
+        };
+        match command(&request) {
+            "launch" => match launch_args(&request) {
+                Ok(args) => {
+                    launch = Some(args);
+                    client.response(&request, json!({}));
+                }
+                Err(err) => client.error(&request, &err.to_string()),
+            },
+            "configurationDone" => {
+                configured = true;
+                client.response(&request, json!({}));
+            }
+            "disconnect" | "terminate" => return Ok(()),
+            _ => client.error(&request, "the program is not launched"),
+        }
+    }
+    let (args, stop_on_entry) = launch.expect("the launch arguments are set");
+    let debugger = Rc::new(Debugger {
+        client: client.clone(),
+        requests,
+        breakpoints,
+        pause,
+        evaluating: Cell::new(false),
+        exprs: ExprArena::default(),
+        state: RefCell::new(DebugState {
+            step: if stop_on_entry {
+                StepMode::StepIn
+            } else {
+                StepMode::Continue
+            },
+            ..Default::default()
+        }),
+    });
+    let result =
+        exec_program_with_debug_hook(Arc::new(ParseSession::default()), &args, debugger.clone());
+    let exit_code = match result {
+        Ok(result) if result.err_message.is_empty() => {
+            client.event(
+                "output",
+                json!({"category": "stdout", "output": result.yaml_result}),
+            );
+            0
+        }
+        Ok(result) => {
+            client.event(
+                "output",
+                json!({"category": "stderr", "output": result.err_message}),
+            );
+            1
+        }
+        Err(err) => {
+            client.event(
+                "output",
+                json!({"category": "stderr", "output": err.to_string()}),
+            );
+            1
+        }
+    };
+    client.event("exited", json!({ "exitCode": exit_code }));
+    client.event("terminated", json!({}));
+    // Answer the remaining requests until the client disconnects.
+    while let Ok(request) = debugger.requests.recv() {
+        match command(&request) {
+            "disconnect" | "terminate" => break,
+            _ => client.error(&request, "the program is terminated"),
+        }
+    }
+    Ok(())
+}
+
+/// Read the requests and answer the ones which do not need the evaluator state,
+/// others are sent to the evaluation thread.
+fn read_requests<R: BufRead>(
+    mut reader: R,
+    client: Client,
+    breakpoints: Arc<Mutex<Breakpoints>>,
+    pause: Arc<AtomicBool>,
+    sender: Sender<Value>,
+) {
+    while let Ok(Some(request)) = read_message(&mut reader) {
+        if request["type"] != "request" {
+            continue;
+        }
+        match command(&request) {
+            "initialize" => {
+                client.response(
+                    &request,
+                    json!({
+                        "supportsConfigurationDoneRequest": true,
+                        "supportsConditionalBreakpoints": true,
+                        "supportsEvaluateForHovers": true,
+                        "supportsTerminateRequest": true,
+                    }),
+                );
+                client.event("initialized", json!({}));
+            }
+            "setBreakpoints" => match set_breakpoints(&breakpoints, &request) {
+                Ok(body) => client.response(&request, body),
+                Err(err) => client.error(&request, &err.to_string()),
+            },
+            "threads" => client.response(
+                &request,
+                json!({"threads": [{"id": THREAD_ID, "name": "main"}]}),
+            ),
+            "pause" => {
+                pause.store(true, Ordering::SeqCst);
+                client.response(&request, json!({}));
+            }
+            "disconnect" | "terminate" => {
+                client.response(&request, json!({}));
+                let _ = sender.send(request);
+                break;
+            }
+            _ => {
+                if sender.send(request).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Get the exec arguments and whether to stop on entry from the launch request.
+fn launch_args(request: &Value) -> Result<(ExecProgramArgs, bool)> {
+    let arguments = &request["arguments"];
+    let cwd = arguments["cwd"].as_str();
+    let programs = match &arguments["program"] {
+        Value::String(program) => vec![program.to_string()],
+        Value::Array(programs) => programs
+            .iter()
+            .filter_map(|p| p.as_str().map(|p| p.to_string()))
+            .collect(),
+        _ => vec![],
+    };
+    if programs.is_empty() {
+        return Err(anyhow!("missing the argument 'program'"));
+    }
+    let k_filename_list = programs
+        .iter()
+        .map(|program| match cwd {
+            Some(cwd) if Path::new(program).is_relative() => {
+                Path::new(cwd).join(program).display().to_string()
+            }
+            _ => program.to_string(),
+        })
+        .collect();
+    let args = ExecProgramArgs {
+        work_dir: cwd.map(|cwd| cwd.to_string()),
+        k_filename_list,
+        ..Default::default()
+    };
+    Ok((args, arguments["stopOnEntry"].as_bool().unwrap_or_default()))
+}
+
+/// Replace the breakpoints of the source file and return the response body.
+fn set_breakpoints(breakpoints: &Arc<Mutex<Breakpoints>>, request: &Value) -> Result<Value> {
+    let path = request["arguments"]["source"]["path"]
+        .as_str()
+        .ok_or_else(|| anyhow!("missing the argument 'source.path'"))?;
+    let mut source_breakpoints = vec![];
+    let mut results = vec![];
+    if let Some(items) = request["arguments"]["breakpoints"].as_array() {
+        for item in items {
+            let line = item["line"].as_u64().unwrap_or_default();
+            let condition = match item["condition"].as_str().map(str::trim) {
+                Some(condition) if !condition.is_empty() => match parse_expr(condition) {
+                    Some(_) => Some(condition.to_string()),
+                    None => {
+                        results.push(json!({
+                            "verified": false,
+                            "line": line,
+                            "message": format!("invalid breakpoint condition '{}'", condition),
+                        }));
+                        continue;
+                    }
+                },
+                _ => None,
+            };
+            source_breakpoints.push(Breakpoint { line, condition });
+            results.push(json!({"verified": true, "line": line}));
+        }
+    }
+    let mut breakpoints = breakpoints
+        .lock()
+        .map_err(|_| anyhow!("failed to acquire the breakpoints lock"))?;
+    breakpoints.insert(normalize_path(path), source_breakpoints);
+    Ok(json!({ "breakpoints": results }))
+}
+
+/// Get the canonical file path, or the original path when it does not exist.
+fn normalize_path(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+/// Debugger implements the debug hook, which stops the evaluation at the
+/// breakpoints and steps, and answers the requests during the stop.
+struct Debugger {
+    client: Client,
+    requests: Receiver<Value>,
+    breakpoints: Arc<Mutex<Breakpoints>>,
+    pause: Arc<AtomicBool>,
+    /// Whether a breakpoint condition or a watch expression is being evaluated,
+    /// in which the evaluation never stops.
+    evaluating: Cell<bool>,
+    state: RefCell<DebugState>,
+    exprs: ExprArena,
+}
+
+impl DebugHook for Debugger {
+    fn on_stmt<'ctx>(&'ctx self, evaluator: &Evaluator<'ctx>, stmt: &ast::Node<ast::Stmt>) {
+        if self.evaluating.get() {
+            return;
+        }
+        let (filename, depth, step) = {
+            let mut state = self.state.borrow_mut();
+            let filename = state
+                .paths
+                .entry(stmt.filename.clone())
+                .or_insert_with(|| normalize_path(&stmt.filename))
+                .clone();
+            if state.frames.is_empty() {
+                state.frames.push(StackFrame {
+                    name: MODULE_FRAME_NAME.to_string(),
+                    filename: filename.clone(),
+                    line: stmt.line,
+                });
+            }
+            if let Some(frame) = state.frames.last_mut() {
+                frame.filename = filename.clone();
+                frame.line = stmt.line;
+            }
+            (filename, state.frames.len(), state.step)
+        };
+        let reason = if self.pause.swap(false, Ordering::SeqCst) {
+            Some("pause")
+        } else if match step {
+            StepMode::Continue => false,
+            StepMode::StepIn => true,
+            StepMode::StepOver(d) => depth <= d,
+            StepMode::StepOut(d) => depth < d,
+        } {
+            Some("step")
+        } else if self.hit_breakpoint(evaluator, &filename, stmt.line) {
+            Some("breakpoint")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            self.stop(evaluator, reason, depth);
+        }
+    }
+
+    fn on_call_enter(&self, name: &str) {
+        let mut state = self.state.borrow_mut();
+        let (filename, line) = match state.frames.last() {
+            Some(frame) => (frame.filename.clone(), frame.line),
+            None => Default::default(),
+        };
+        state.frames.push(StackFrame {
+            name: name.to_string(),
+            filename,
+            line,
+        });
+    }
+
+    fn on_call_exit(&self) {
+        self.state.borrow_mut().frames.pop();
+    }
+}
+
+impl Debugger {
+    fn hit_breakpoint<'ctx>(
+        &'ctx self,
+        evaluator: &Evaluator<'ctx>,
+        filename: &str,
+        line: u64,
+    ) -> bool {
+        let conditions: Vec<Option<String>> = match self.breakpoints.lock() {
+            Ok(breakpoints) => match breakpoints.get(filename) {
+                Some(breakpoints) => breakpoints
+                    .iter()
+                    .filter(|b| b.line == line)
+                    .map(|b| b.condition.clone())
+                    .collect(),
+                None => return false,
+            },
+            Err(_) => return false,
+        };
+        conditions.iter().any(|condition| match condition {
+            Some(condition) => match self.eval(evaluator, condition) {
+                Ok(value) => value.is_truthy(),
+                Err(err) => {
+                    self.client.event(
+                        "output",
+                        json!({
+                            "category": "stderr",
+                            "output": format!("failed to evaluate the breakpoint condition: {}\n", err),
+                        }),
+                    );
+                    true
+                }
+            },
+            None => true,
+        })
+    }
+
+    /// Stop the evaluation and answer the requests until the evaluation is resumed.
+    fn stop<'ctx>(&'ctx self, evaluator: &Evaluator<'ctx>, reason: &str, depth: usize) {
+        self.client.event(
+            "stopped",
+            json!({"reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true}),
+        );
+        loop {
+            let request = match self.requests.recv() {
+                Ok(request) => request,
+                Err(_) => panic!("the debug session is disconnected"),
+            };
+            let step = match command(&request) {
+                "continue" => Some(StepMode::Continue),
+                "next" => Some(StepMode::StepOver(depth)),
+                "stepIn" => Some(StepMode::StepIn),
+                "stepOut" => Some(StepMode::StepOut(depth)),
+                "disconnect" | "terminate" => panic!("the debug session is terminated"),
+                _ => None,
+            };
+            if let Some(step) = step {
+                {
+                    let mut state = self.state.borrow_mut();
+                    state.step = step;
+                    state.variables.clear();
+                }
+                self.client
+                    .response(&request, json!({"allThreadsContinued": true}));
+                return;
+            }
+            match self.handle_request(evaluator, &request) {
+                Ok(body) => self.client.response(&request, body),
+                Err(err) => self.client.error(&request, &err.to_string()),
+            }
+        }
+    }
+
+    /// Answer the request which inspects the evaluator state during the stop.
+    fn handle_request<'ctx>(
+        &'ctx self,
+        evaluator: &Evaluator<'ctx>,
+        request: &Value,
+    ) -> Result<Value> {
+        match command(request) {
+            "stackTrace" => {
+                let state = self.state.borrow();
+                let frames: Vec<Value> = state
+                    .frames
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .map(|(id, frame)| {
+                        json!({
+                            "id": id,
+                            "name": frame.name,
+                            "source": {
+                                "name": Path::new(&frame.filename)
+                                    .file_name()
+                                    .map(|name| name.to_string_lossy().to_string())
+                                    .unwrap_or_default(),
+                                "path": frame.filename,
+                            },
+                            "line": frame.line,
+                            "column": 1,
+                        })
+                    })
+                    .collect();
+                Ok(json!({"stackFrames": frames, "totalFrames": frames.len()}))
+            }
+            "scopes" => {
+                let frame_id = request["arguments"]["frameId"].as_u64().unwrap_or_default();
+                let innermost = self.state.borrow().frames.len().saturating_sub(1) as u64;
+                let scopes: Vec<Value> = evaluator
+                    .debug_scopes()
+                    .into_iter()
+                    // The local and schema variables are only available in the innermost frame.
+                    .filter(|scope| frame_id == innermost || scope.name == "Globals")
+                    .map(|scope| {
+                        json!({
+                            "name": scope.name,
+                            "variablesReference": self.add_variables(scope.variables),
+                            "expensive": false,
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "scopes": scopes }))
+            }
+            "variables" => {
+                let reference = request["arguments"]["variablesReference"]
+                    .as_u64()
+                    .unwrap_or_default() as usize;
+                let variables = self
+                    .state
+                    .borrow()
+                    .variables
+                    .get(reference.wrapping_sub(1))
+                    .cloned()
+                    .ok_or_else(|| anyhow!("invalid variables reference {}", reference))?;
+                let variables: Vec<Value> = variables
+                    .iter()
+                    .map(|(name, value)| {
+                        json!({
+                            "name": name,
+                            "value": display_value(value),
+                            "type": value.type_str(),
+                            "variablesReference": self.add_value(value),
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "variables": variables }))
+            }
+            "evaluate" => {
+                let expression = str_argument(request, "expression")?;
+                let value = self.eval(evaluator, expression)?;
+                Ok(json!({
+                    "result": display_value(&value),
+                    "type": value.type_str(),
+                    "variablesReference": self.add_value(&value),
+                }))
+            }
+            command => Err(anyhow!("unsupported request '{}' during the stop", command)),
+        }
+    }
+
+    /// Evaluate the breakpoint condition or the watch expression source.
+    fn eval<'ctx>(&'ctx self, evaluator: &Evaluator<'ctx>, src: &str) -> Result<ValueRef> {
+        let expr = self
+            .exprs
+            .parse(src)
+            .ok_or_else(|| anyhow!("invalid expression '{}'", src))?;
+        self.evaluating.set(true);
+        let result = evaluator.eval_debug_expr(expr);
+        self.evaluating.set(false);
+        result
+    }
+
+    /// Add the variable table and return its reference.
+    fn add_variables(&self, variables: Vec<(String, ValueRef)>) -> usize {
+        let mut state = self.state.borrow_mut();
+        state.variables.push(variables);
+        state.variables.len()
+    }
+
+    /// Add the children of the list or config value and return the reference,
+    /// returns zero for other values which have no children.
+    fn add_value(&self, value: &ValueRef) -> usize {
+        if value.is_list() {
+            let variables = value
+                .as_list_ref()
+                .values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i.to_string(), v.clone()))
+                .collect();
+            self.add_variables(variables)
+        } else if value.is_config() {
+            let variables = value
+                .as_dict_ref()
+                .values
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            self.add_variables(variables)
+        } else {
+            0
+        }
+    }
+}
+
+/// Get the display string of the value in the variables view.
+fn display_value(value: &ValueRef) -> String {
+    if value.is_func() {
+        "<function>".to_string()
+    } else if value.is_list() {
+        format!("[...] ({} items)", value.len())
+    } else if value.is_config() {
+        format!("{} {{...}} ({} items)", value.type_str(), value.len())
+    } else {
+        value.to_json_string()
+    }
+}
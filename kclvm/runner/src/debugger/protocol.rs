@@ -0,0 +1,126 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use std::io::{BufRead, Write};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+const CONTENT_LENGTH_HEADER: &str = "Content-Length:";
+
+/// Read a DAP message with the `Content-Length` header, returns `None` when
+/// the input is closed.
+pub fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some(len) = line.strip_prefix(CONTENT_LENGTH_HEADER) {
+            content_length = Some(len.trim().parse::<usize>()?);
+        }
+    }
+    let mut content = vec![0; content_length.unwrap_or_default()];
+    reader.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+/// Write a DAP message with the `Content-Length` header.
+pub fn write_message<W: Write + ?Sized>(writer: &mut W, message: &Value) -> Result<()> {
+    let content = serde_json::to_string(message)?;
+    write!(
+        writer,
+        "{} {}\r\n\r\n{}",
+        CONTENT_LENGTH_HEADER,
+        content.len(),
+        content
+    )?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Client sends the responses and events to the DAP client, which can be shared
+/// between the request reading thread and the evaluation thread.
+#[derive(Clone)]
+pub(crate) struct Client {
+    inner: Arc<Mutex<ClientWriter>>,
+}
+
+struct ClientWriter {
+    writer: Box<dyn Write + Send>,
+    seq: i64,
+}
+
+impl Client {
+    pub(crate) fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ClientWriter {
+                writer: Box::new(writer),
+                seq: 0,
+            })),
+        }
+    }
+
+    /// Send a successful response of the request with the body.
+    pub(crate) fn response(&self, request: &Value, body: Value) {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": true,
+            "body": body,
+        }));
+    }
+
+    /// Send a failed response of the request with the error message.
+    pub(crate) fn error(&self, request: &Value, message: &str) {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": false,
+            "message": message,
+        }));
+    }
+
+    /// Send an event with the body.
+    pub(crate) fn event(&self, event: &str, body: Value) {
+        self.send(json!({
+            "type": "event",
+            "event": event,
+            "body": body,
+        }));
+    }
+
+    fn send(&self, mut message: Value) {
+        let mut inner = match self.inner.lock() {
+            Ok(inner) => inner,
+            Err(err) => err.into_inner(),
+        };
+        inner.seq += 1;
+        message["seq"] = json!(inner.seq);
+        // The write error is ignored because the client may have been disconnected.
+        let _ = write_message(inner.writer.as_mut(), &message);
+    }
+}
+
+/// Get the command name of the request.
+#[inline]
+pub(crate) fn command(request: &Value) -> &str {
+    request["command"].as_str().unwrap_or_default()
+}
+
+/// Get the required string argument of the request.
+pub(crate) fn str_argument<'a>(request: &'a Value, name: &str) -> Result<&'a str> {
+    request["arguments"][name]
+        .as_str()
+        .ok_or_else(|| anyhow!("missing the argument '{}'", name))
+}
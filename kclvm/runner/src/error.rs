@@ -0,0 +1,155 @@
+//! Structured runtime errors of the KCL program execution, which carry the
+//! error kind, code, source ranges and the KCL call stack for the API clients.
+
+use kclvm_error::{ErrorKind, E3M38, ERRORS};
+use kclvm_runtime::{BacktraceFrame, PanicInfo, RuntimeErrorType};
+use serde::{Deserialize, Serialize};
+
+/// ExecError denotes a structured runtime error of the KCL program.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExecError {
+    /// The error kind e.g., `EvaluationError` and `SchemaCheckFailure`.
+    pub kind: String,
+    /// The error code e.g., `E3M38`.
+    pub code: String,
+    /// The error message.
+    pub message: String,
+    /// The primary source range where the error occurs.
+    pub range: Option<SourceRange>,
+    /// The related source ranges with their messages. For the schema check
+    /// failure, it contains the config meta location where the schema is
+    /// instantiated.
+    pub related: Vec<RelatedInfo>,
+    /// The KCL call stack from the innermost frame where the error occurs
+    /// to the outermost frame.
+    pub backtrace: Vec<StackFrame>,
+}
+
+/// SourcePos denotes a source position with the 1-based line and column,
+/// the column is 0 when it is unknown.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourcePos {
+    pub filename: String,
+    pub line: u64,
+    pub column: u64,
+}
+
+/// SourceRange denotes a source range between the start and end positions.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceRange {
+    pub start: SourcePos,
+    pub end: SourcePos,
+}
+
+/// RelatedInfo denotes a related source range of the error with its message.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct RelatedInfo {
+    pub message: String,
+    pub range: SourceRange,
+}
+
+/// StackFrame denotes a frame of the KCL call stack.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct StackFrame {
+    /// The function name of the frame.
+    pub func: String,
+    /// The source position in the frame.
+    pub pos: SourcePos,
+}
+
+impl SourceRange {
+    /// New a source range denoting a single position, the runtime only
+    /// records the start position of the errors.
+    fn at(filename: &str, line: i32, column: i32) -> Option<Self> {
+        if filename.is_empty() {
+            return None;
+        }
+        let pos = SourcePos {
+            filename: filename.to_string(),
+            line: line.max(0) as u64,
+            column: column.max(0) as u64,
+        };
+        Some(Self {
+            start: pos.clone(),
+            end: pos,
+        })
+    }
+}
+
+impl From<&BacktraceFrame> for StackFrame {
+    fn from(frame: &BacktraceFrame) -> Self {
+        Self {
+            func: frame.func.clone(),
+            pos: SourcePos {
+                filename: frame.file.clone(),
+                line: frame.line.max(0) as u64,
+                column: frame.col.max(0) as u64,
+            },
+        }
+    }
+}
+
+impl ExecError {
+    /// New a structured error from the runtime panic information and the KCL
+    /// call stack from the outermost frame to the innermost frame.
+    pub fn from_panic_info(panic_info: &PanicInfo, backtrace: &[BacktraceFrame]) -> Self {
+        let kind = error_kind(panic_info.err_type_code);
+        let code = ERRORS
+            .iter()
+            .find(|(_, error)| error.kind == kind)
+            .map_or(E3M38.code, |(code, _)| *code);
+        let message = if panic_info.kcl_arg_msg.is_empty() {
+            &panic_info.message
+        } else {
+            &panic_info.kcl_arg_msg
+        };
+        let related = SourceRange::at(
+            &panic_info.kcl_config_meta_file,
+            panic_info.kcl_config_meta_line,
+            panic_info.kcl_config_meta_col,
+        )
+        .map(|range| RelatedInfo {
+            message: panic_info.kcl_config_meta_arg_msg.clone(),
+            range,
+        });
+        Self {
+            kind: kind.name(),
+            code: code.to_string(),
+            message: message.to_string(),
+            range: SourceRange::at(
+                &panic_info.kcl_file,
+                panic_info.kcl_line,
+                panic_info.kcl_col,
+            ),
+            related: related.into_iter().collect(),
+            backtrace: backtrace.iter().rev().map(StackFrame::from).collect(),
+        }
+    }
+}
+
+/// Get the error kind of the runtime error type code.
+fn error_kind(err_type_code: i32) -> ErrorKind {
+    const KINDS: &[(RuntimeErrorType, ErrorKind)] = &[
+        (RuntimeErrorType::RecursiveLoad, ErrorKind::RecursiveLoad),
+        (RuntimeErrorType::FloatOverflow, ErrorKind::FloatOverflow),
+        (RuntimeErrorType::FloatUnderflow, ErrorKind::FloatUnderflow),
+        (RuntimeErrorType::IntOverflow, ErrorKind::IntOverflow),
+        (RuntimeErrorType::TypeError, ErrorKind::TypeError),
+        (RuntimeErrorType::AssertionError, ErrorKind::AssertionError),
+        (RuntimeErrorType::Deprecated, ErrorKind::Deprecated),
+        (RuntimeErrorType::DeprecatedWarning, ErrorKind::Deprecated),
+        (
+            RuntimeErrorType::SchemaCheckFailure,
+            ErrorKind::SchemaCheckFailure,
+        ),
+        (RuntimeErrorType::RecursionError, ErrorKind::RecursionError),
+        (
+            RuntimeErrorType::ResourceLimitExceeded,
+            ErrorKind::ResourceLimitError,
+        ),
+    ];
+    KINDS
+        .iter()
+        .find(|(ty, _)| *ty as i32 == err_type_code)
+        .map_or(ErrorKind::EvaluationError, |(_, kind)| kind.clone())
+}
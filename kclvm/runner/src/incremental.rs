@@ -0,0 +1,260 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The `incremental` mode caches the program at three granularities:
+//!
+//! - The parsed AST of each source file, which is keyed by the file content hash
+//!   and reused by the parser for the unchanged files.
+//! - The evaluated values of each imported package, which are keyed by the exec
+//!   arguments and the digests of the source files of the package and its
+//!   transitive dependencies. The schemas, rules and lambdas of a cached package
+//!   are defined again from the AST when the package is installed.
+//! - The result of the whole program, which is keyed by the exec arguments and
+//!   the digests of all the source files.
+//!
+//! Thus changing a source file only evaluates again the package of the file,
+//! the packages importing it transitively and the main package.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use anyhow::Result;
+use kclvm_ast::ast;
+use kclvm_config::cache::{content_hash, load_content_cache, save_content_cache};
+use kclvm_evaluator::{package_import_graph, PackageCache, PackageResult};
+use kclvm_primitives::IndexMap;
+use kclvm_runtime::EvalInputs;
+use serde::{Deserialize, Serialize};
+
+use crate::{ExecProgramArgs, ExecProgramResult};
+
+/// The cache kind of the program results.
+const RESULT_CACHE_KIND: &str = "result";
+/// The cache kind of the package results.
+const PACKAGE_CACHE_KIND: &str = "package";
+/// The file digest of directories.
+const DIR_DIGEST: &str = "dir";
+
+/// ResultCache caches the result of the whole program, which is keyed by the
+/// hash of the exec arguments and all the source files of the program. The
+/// external inputs read by the evaluation e.g., files and environment variables
+/// are recorded in the cache entry and checked when loading it.
+pub(crate) struct ResultCache {
+    root: String,
+    key: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ResultCacheEntry {
+    result: ExecProgramResult,
+    inputs: Vec<InputDigest>,
+}
+
+/// PackageValueCache caches the evaluated values of the imported packages, which
+/// are keyed by the hash of the exec arguments and the source files of the package
+/// and its transitive dependencies. The external inputs read by the package and
+/// its dependencies are recorded in the cache entry and checked when loading it.
+pub(crate) struct PackageValueCache {
+    root: String,
+    keys: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PackageCacheEntry {
+    result: PackageResult,
+    inputs: Vec<InputDigest>,
+}
+
+/// InputDigest denotes the state of an external input when the result is cached.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(tag = "kind")]
+enum InputDigest {
+    File { path: String, digest: String },
+    Glob { pattern: String, paths: Vec<String> },
+    Env { key: String, value: Option<String> },
+}
+
+impl InputDigest {
+    fn file(path: &str) -> Self {
+        Self::File {
+            path: path.to_string(),
+            digest: file_digest(path),
+        }
+    }
+
+    fn glob(pattern: &str) -> Self {
+        let paths = match glob::glob(pattern) {
+            Ok(paths) => paths
+                .flatten()
+                .map(|path| path.display().to_string())
+                .collect(),
+            Err(_) => vec![],
+        };
+        Self::Glob {
+            pattern: pattern.to_string(),
+            paths,
+        }
+    }
+
+    fn env(key: &str) -> Self {
+        Self::Env {
+            key: key.to_string(),
+            value: std::env::var(key).ok(),
+        }
+    }
+
+    /// Whether the input is unchanged since the result is cached.
+    fn is_unchanged(&self) -> bool {
+        let current = match self {
+            InputDigest::File { path, .. } => Self::file(path),
+            InputDigest::Glob { pattern, .. } => Self::glob(pattern),
+            InputDigest::Env { key, .. } => Self::env(key),
+        };
+        self == &current
+    }
+
+    /// Get the current digests of the external inputs.
+    fn from_inputs(inputs: &EvalInputs) -> Vec<Self> {
+        inputs
+            .files
+            .iter()
+            .map(|path| Self::file(path))
+            .chain(inputs.globs.iter().map(|pattern| Self::glob(pattern)))
+            .chain(inputs.envs.iter().map(|key| Self::env(key)))
+            .collect()
+    }
+}
+
+impl ResultCache {
+    /// New a result cache of the program with the exec arguments. The cache
+    /// is stored in the work directory or the `KCL_CACHE_PATH` directory.
+    pub(crate) fn new(program: &ast::Program, args: &ExecProgramArgs) -> Result<Self> {
+        let cwd = std::env::current_dir()?.display().to_string();
+        let pkgpaths: BTreeSet<&String> = program.pkgs.keys().collect();
+        Ok(Self {
+            key: source_key(program, args, &cwd, pkgpaths)?,
+            root: args.work_dir.clone().unwrap_or(cwd),
+        })
+    }
+
+    /// Load the cached result, returns `None` when the result is not cached or
+    /// any of the external inputs read by the evaluation is changed.
+    pub(crate) fn load(&self) -> Option<ExecProgramResult> {
+        let entry: ResultCacheEntry = load_content_cache(&self.root, RESULT_CACHE_KIND, &self.key)?;
+        if entry.inputs.iter().all(InputDigest::is_unchanged) {
+            Some(entry.result)
+        } else {
+            None
+        }
+    }
+
+    /// Save the evaluation result with the external inputs read by the evaluation.
+    /// Error results and results with side effects are not cached.
+    pub(crate) fn save(&self, result: &ExecProgramResult, inputs: &EvalInputs) {
+        if inputs.uncacheable || !result.err_message.is_empty() {
+            return;
+        }
+        let entry = ResultCacheEntry {
+            result: result.clone(),
+            inputs: InputDigest::from_inputs(inputs),
+        };
+        // The cache is an optimization, thus the cache writing error is ignored.
+        let _ = save_content_cache(&self.root, RESULT_CACHE_KIND, &self.key, &entry);
+    }
+}
+
+impl PackageValueCache {
+    /// New a package value cache of the program with the exec arguments. The cache
+    /// is stored in the work directory or the `KCL_CACHE_PATH` directory.
+    pub(crate) fn new(program: &ast::Program, args: &ExecProgramArgs) -> Result<Self> {
+        let cwd = std::env::current_dir()?.display().to_string();
+        let graph = package_import_graph(program);
+        let mut keys = HashMap::new();
+        for pkgpath in graph.keys() {
+            let pkgpaths = transitive_dependencies(&graph, pkgpath);
+            keys.insert(pkgpath.clone(), source_key(program, args, &cwd, pkgpaths)?);
+        }
+        Ok(Self {
+            root: args.work_dir.clone().unwrap_or(cwd),
+            keys,
+        })
+    }
+}
+
+impl PackageCache for PackageValueCache {
+    /// Load the cached package result, returns `None` when the result is not cached
+    /// or any of the external inputs read by the package is changed.
+    fn load(&self, pkgpath: &str) -> Option<PackageResult> {
+        let key = self.keys.get(pkgpath)?;
+        let entry: PackageCacheEntry = load_content_cache(&self.root, PACKAGE_CACHE_KIND, key)?;
+        if entry.inputs.iter().all(InputDigest::is_unchanged) {
+            Some(entry.result)
+        } else {
+            None
+        }
+    }
+
+    /// Save the package result with the external inputs read by the package. The
+    /// results with side effects are not cached.
+    fn save(&self, pkgpath: &str, result: &PackageResult) {
+        let inputs = result.inputs();
+        if inputs.uncacheable {
+            return;
+        }
+        if let Some(key) = self.keys.get(pkgpath) {
+            let entry = PackageCacheEntry {
+                result: result.clone(),
+                inputs: InputDigest::from_inputs(inputs),
+            };
+            // The cache is an optimization, thus the cache writing error is ignored.
+            let _ = save_content_cache(&self.root, PACKAGE_CACHE_KIND, key, &entry);
+        }
+    }
+}
+
+/// Get the cache key of the exec arguments and the source files of the packages.
+fn source_key(
+    program: &ast::Program,
+    args: &ExecProgramArgs,
+    cwd: &str,
+    pkgpaths: BTreeSet<&String>,
+) -> Result<String> {
+    let mut parts = vec![serde_json::to_string(args)?, cwd.to_string()];
+    for pkgpath in pkgpaths {
+        parts.push(pkgpath.clone());
+        for filename in program.pkgs.get(pkgpath).into_iter().flatten() {
+            parts.push(filename.clone());
+            parts.push(file_digest(filename));
+        }
+    }
+    let parts: Vec<&[u8]> = parts.iter().map(|part| part.as_bytes()).collect();
+    Ok(content_hash(&parts))
+}
+
+/// Get the package and its transitive dependencies in the package import graph.
+fn transitive_dependencies<'a>(
+    graph: &'a IndexMap<String, Vec<String>>,
+    pkgpath: &'a String,
+) -> BTreeSet<&'a String> {
+    let mut pkgpaths = BTreeSet::new();
+    let mut stack = vec![pkgpath];
+    while let Some(pkgpath) = stack.pop() {
+        if pkgpaths.insert(pkgpath) {
+            stack.extend(graph.get(pkgpath).into_iter().flatten());
+        }
+    }
+    pkgpaths
+}
+
+/// Get the content digest of the file, which is also changed when the file is
+/// created or deleted.
+fn file_digest(path: &str) -> String {
+    let path = Path::new(path);
+    if path.is_dir() {
+        DIR_DIGEST.to_string()
+    } else {
+        match std::fs::read(path) {
+            Ok(content) => content_hash(&[&content]),
+            Err(_) => String::new(),
+        }
+    }
+}
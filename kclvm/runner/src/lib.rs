@@ -1,24 +1,33 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
     ffi::OsStr,
+    io::Write,
     path::Path,
+    rc::Rc,
     sync::{Arc, RwLock},
 };
 
 use anyhow::{anyhow, bail, Result};
 use assembler::KclvmLibAssembler;
+pub use error::ExecError;
+use incremental::{PackageValueCache, ResultCache};
 use kclvm_ast::{
     ast::{Module, Program},
     MAIN_PKG,
 };
 use kclvm_config::cache::KCL_CACHE_PATH_ENV_VAR;
+use kclvm_config::modfile::get_mod_plugins;
+use kclvm_evaluator::DebugHook;
 use kclvm_parser::{load_program, KCLModuleCache, ParseSessionRef};
 use kclvm_query::apply_overrides;
+use kclvm_runtime::val_plan::PlanFormat;
 use kclvm_sema::resolver::{
     resolve_program, resolve_program_with_opts, scope::ProgramScope, Options,
 };
 use kclvm_utils::fslock::open_lock_file;
 use linker::Command;
+pub use output::OutputFormat;
 pub use runner::{Artifact, ExecProgramArgs, ExecProgramResult, MapErrorResult};
 use runner::{FastRunner, RunnerOptions};
 #[cfg(feature = "llvm")]
@@ -26,7 +35,13 @@ use runner::{LibRunner, ProgramRunner};
 use tempfile::tempdir;
 
 pub mod assembler;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod debugger;
+pub mod error;
+mod incremental;
 pub mod linker;
+pub mod output;
+pub mod plugin;
 pub mod runner;
 
 #[cfg(test)]
@@ -80,6 +95,59 @@ pub const KCL_FAST_EVAL_ENV_VAR: &str = "KCL_FAST_EVAL";
 /// let result = exec_program(sess, &args).unwrap();
 /// ```
 pub fn exec_program(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
+    let program = load_program_with_args(sess.clone(), args)?;
+    execute(sess, program, args)
+}
+
+/// Execute the KCL program with the evaluator and call the debug hook during the
+/// evaluation, which is used by the debug adapter to stop at breakpoints.
+pub fn exec_program_with_debug_hook(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    hook: Rc<dyn DebugHook>,
+) -> Result<ExecProgramResult> {
+    let mut program = load_program_with_args(sess.clone(), args)?;
+    let scope = resolve_program(&mut program);
+    emit_compile_diag_to_string(sess, &scope, false)?;
+    let args = args_with_mod_plugins(&program, args)?;
+    FastRunner::new(Some(RunnerOptions {
+        plugin_agent_ptr: args.plugin_agent,
+    }))
+    .with_debug_hook(hook)
+    .run(&program, &args)
+}
+
+/// Execute the KCL program and write the result in the output format of the exec
+/// arguments into the writer. The evaluator writes the result as it is planned,
+/// which does not hold the whole JSON and YAML results in memory for very large
+/// outputs. The result is still built in memory and then written when the program
+/// is run by the native backend or the cached result is used by the incremental
+/// mode. Only the YAML and NDJSON formats can be streamed, and the JSON and YAML
+/// results of the returned [ExecProgramResult] are empty.
+pub fn exec_program_to_writer(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    writer: &mut dyn Write,
+) -> Result<ExecProgramResult> {
+    let format = plan_format(args.format)?;
+    let program = load_program_with_args(sess.clone(), args)?;
+    execute_with_output(sess, program, args, Some((format, writer)))
+}
+
+/// Get the plan format to stream the output format.
+fn plan_format(format: OutputFormat) -> Result<PlanFormat> {
+    Ok(match format {
+        OutputFormat::Yaml => PlanFormat::Yaml,
+        OutputFormat::Ndjson => PlanFormat::Json,
+        format => bail!(
+            "the {} output format can not be streamed",
+            format.extension()
+        ),
+    })
+}
+
+/// Load the KCL program with the exec arguments and apply the overrides.
+fn load_program_with_args(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<Program> {
     // parse args from json string
     let opts = args.get_load_program_options();
     let kcl_paths_str = args
@@ -101,7 +169,21 @@ pub fn exec_program(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<Exe
         &[],
         args.print_override_ast || args.debug > 0,
     )?;
-    execute(sess, program, args)
+    Ok(program)
+}
+
+/// Get the exec arguments with the WebAssembly plugins in the 'kcl.mod' file of
+/// the program root registered.
+fn args_with_mod_plugins<'a>(
+    program: &Program,
+    args: &'a ExecProgramArgs,
+) -> Result<Cow<'a, ExecProgramArgs>> {
+    if get_mod_plugins(&program.root).is_empty() {
+        return Ok(Cow::Borrowed(args));
+    }
+    let mut args = args.clone();
+    args.plugins.register_mod_plugins(&program.root)?;
+    Ok(Cow::Owned(args))
 }
 
 /// Execute the KCL artifact with args.
@@ -111,6 +193,10 @@ pub fn exec_artifact<P: AsRef<OsStr>>(
 ) -> Result<ExecProgramResult> {
     #[cfg(feature = "llvm")]
     {
+        // The compiled artifact calls the nondeterministic built-in functions natively.
+        if args.hermetic.enabled {
+            bail!("the hermetic mode is not supported by the compiled artifact");
+        }
         Artifact::from_path(path)?.run(args)
     }
     #[cfg(not(feature = "llvm"))]
@@ -172,9 +258,19 @@ pub fn exec_artifact<P: AsRef<OsStr>>(
 /// let result = execute(sess, prog, &args).unwrap();
 /// ```
 pub fn execute(
+    sess: ParseSessionRef,
+    program: Program,
+    args: &ExecProgramArgs,
+) -> Result<ExecProgramResult> {
+    execute_with_output(sess, program, args, None)
+}
+
+/// Execute the program and write the planned result into the output writer if any.
+fn execute_with_output(
     sess: ParseSessionRef,
     mut program: Program,
     args: &ExecProgramArgs,
+    mut output: Option<(PlanFormat, &mut dyn Write)>,
 ) -> Result<ExecProgramResult> {
     // If the user only wants to compile the kcl program, the following code will only resolve ast.
     if args.compile_only {
@@ -189,60 +285,121 @@ pub fn execute(
     let scope = resolve_program(&mut program);
     // Emit parse and resolve errors if exists.
     emit_compile_diag_to_string(sess, &scope, false)?;
-    Ok(
-        // Use the fast evaluator to run the kcl program.
-        if args.fast_eval || std::env::var(KCL_FAST_EVAL_ENV_VAR).is_ok() {
-            FastRunner::new(Some(RunnerOptions {
-                plugin_agent_ptr: args.plugin_agent,
-            }))
-            .run(&program, args)?
-        } else {
-            // Compile the kcl program to native lib and run it.
-            #[cfg(feature = "llvm")]
-            {
-                // Create a temp entry file and the temp dir will be delete automatically
-                let temp_dir = tempdir()?;
-                let temp_dir_path = temp_dir.path().to_str().ok_or(anyhow!(
-                    "Internal error: {}: No such file or directory",
-                    temp_dir.path().display()
-                ))?;
-                let temp_entry_file = temp_file(temp_dir_path)?;
+    // Reuse the cached result of the whole program when the program inputs are unchanged.
+    // The profiling mode always evaluates the program.
+    let result_cache = if args.incremental && args.profile_output.is_none() {
+        Some(ResultCache::new(&program, args)?)
+    } else {
+        None
+    };
+    if let Some(result) = result_cache.as_ref().and_then(ResultCache::load) {
+        return write_result(result, output);
+    }
+    let args = args_with_mod_plugins(&program, args)?;
+    let args = args.as_ref();
+    // Use the fast evaluator to run the kcl program, which is also required
+    // by the profiling, provenance, incremental, parallel and hermetic mode.
+    let result = if args.fast_eval
+        || args.profile_output.is_some()
+        || args.provenance
+        || args.incremental
+        || args.parallel
+        || args.hermetic.enabled
+        || std::env::var(KCL_FAST_EVAL_ENV_VAR).is_ok()
+    {
+        let runner = FastRunner::new(Some(RunnerOptions {
+            plugin_agent_ptr: args.plugin_agent,
+        }));
+        match (result_cache, output.take()) {
+            // The cached result must be built in memory.
+            (Some(result_cache), _) => {
+                let runner = runner.with_result_cache(result_cache);
+                // Reuse the evaluated values of the unchanged packages, except in the
+                // hermetic mode whose random values depend on the evaluation order.
+                let runner = if args.hermetic.enabled {
+                    runner
+                } else {
+                    runner.with_package_cache(PackageValueCache::new(&program, args)?)
+                };
+                runner.run(&program, args)?
+            }
+            (None, Some((format, writer))) => {
+                return runner.run_to_writer(&program, args, format, writer)
+            }
+            (None, None) => runner.run(&program, args)?,
+        }
+    } else {
+        // Compile the kcl program to native lib and run it.
+        #[cfg(feature = "llvm")]
+        {
+            // Create a temp entry file and the temp dir will be delete automatically
+            let temp_dir = tempdir()?;
+            let temp_dir_path = temp_dir.path().to_str().ok_or(anyhow!(
+                "Internal error: {}: No such file or directory",
+                temp_dir.path().display()
+            ))?;
+            let temp_entry_file = temp_file(temp_dir_path)?;
 
-                // Generate libs
-                let lib_paths = assembler::KclvmAssembler::new(
-                    program,
-                    scope,
-                    temp_entry_file.clone(),
-                    KclvmLibAssembler::LLVM,
-                    args.get_package_maps_from_external_pkg(),
-                )
-                .gen_libs(args)?;
+            // Generate libs
+            let lib_paths = assembler::KclvmAssembler::new(
+                program,
+                scope,
+                temp_entry_file.clone(),
+                KclvmLibAssembler::LLVM,
+                args.get_package_maps_from_external_pkg(),
+            )
+            .gen_libs(args)?;
 
-                // Link libs into one library
-                let lib_suffix = Command::get_lib_suffix();
-                let temp_out_lib_file = format!("{}{}", temp_entry_file, lib_suffix);
-                let lib_path = linker::KclvmLinker::link_all_libs(lib_paths, temp_out_lib_file)?;
+            // Link libs into one library
+            let lib_suffix = Command::get_lib_suffix();
+            let temp_out_lib_file = format!("{}{}", temp_entry_file, lib_suffix);
+            let lib_path = linker::KclvmLinker::link_all_libs(lib_paths, temp_out_lib_file)?;
 
-                // Run the library
-                let runner = LibRunner::new(Some(RunnerOptions {
-                    plugin_agent_ptr: args.plugin_agent,
-                }));
-                let result = runner.run(&lib_path, args)?;
+            // Run the library
+            let runner = LibRunner::new(Some(RunnerOptions {
+                plugin_agent_ptr: args.plugin_agent,
+            }));
+            let result = runner.run(&lib_path, args)?;
 
-                remove_file(&lib_path)?;
-                clean_tmp_files(&temp_entry_file, &lib_suffix)?;
-                result
-            }
-            // If we don't enable llvm feature, the default running path is through the evaluator.
-            #[cfg(not(feature = "llvm"))]
-            {
-                FastRunner::new(Some(RunnerOptions {
-                    plugin_agent_ptr: args.plugin_agent,
-                }))
-                .run(&program, args)?
+            remove_file(&lib_path)?;
+            clean_tmp_files(&temp_entry_file, &lib_suffix)?;
+            result
+        }
+        // If we don't enable llvm feature, the default running path is through the evaluator.
+        #[cfg(not(feature = "llvm"))]
+        {
+            let runner = FastRunner::new(Some(RunnerOptions {
+                plugin_agent_ptr: args.plugin_agent,
+            }));
+            match output.take() {
+                Some((format, writer)) => {
+                    return runner.run_to_writer(&program, args, format, writer)
+                }
+                None => runner.run(&program, args)?,
             }
-        },
-    )
+        }
+    };
+    write_result(result, output)
+}
+
+/// Write the result built in memory into the output writer if any, the JSON and
+/// YAML results are cleared as the streamed result.
+fn write_result(
+    mut result: ExecProgramResult,
+    output: Option<(PlanFormat, &mut dyn Write)>,
+) -> Result<ExecProgramResult> {
+    if let Some((format, writer)) = output {
+        if result.err_message.is_empty() {
+            let content = match format {
+                PlanFormat::Yaml => &result.yaml_result,
+                PlanFormat::Json => &result.json_result,
+            };
+            writer.write_all(content.as_bytes())?;
+        }
+        result.json_result.clear();
+        result.yaml_result.clear();
+    }
+    Ok(result)
 }
 
 /// `execute_module` can directly execute the ast `Module`.
@@ -263,6 +420,7 @@ pub fn execute_module(m: Module) -> Result<ExecProgramResult> {
         modules,
         pkgs_not_imported: HashMap::new(),
         modules_not_imported: HashMap::new(),
+        plugin_decls: HashMap::new(),
     };
 
     execute(
@@ -0,0 +1,345 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, Result};
+use kclvm_runtime::{Context, JsonEncodeOptions, Provenance, ValueRef};
+use serde::{Deserialize, Serialize};
+
+use crate::ExecProgramResult;
+
+const JSON_INDENT: i64 = 4;
+const YAML_DOCUMENT_SEPARATOR: &str = "---";
+const YAML_LIST_ITEM_PREFIX: &str = "- ";
+
+/// OutputFormat denotes the output format of the KCL program result.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Yaml,
+    Json,
+    Toml,
+    /// JSON lines, one document per line.
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Get the file extension of the output format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Json => "json",
+            OutputFormat::Toml => "toml",
+            OutputFormat::Ndjson => "ndjson",
+        }
+    }
+
+    /// Format a single document value.
+    fn format_value(&self, value: &ValueRef) -> Result<String> {
+        match self {
+            OutputFormat::Yaml => Ok(value.to_yaml_string()),
+            OutputFormat::Json => Ok(format!(
+                "{}\n",
+                value.to_json_string_with_options(&JsonEncodeOptions {
+                    indent: JSON_INDENT,
+                    ..Default::default()
+                })
+            )),
+            OutputFormat::Toml => value
+                .to_toml_string_with_options(&Default::default())
+                .map_err(|err| anyhow!(err)),
+            OutputFormat::Ndjson => Ok(format!("{}\n", value.to_json_string())),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            "json" => Ok(OutputFormat::Json),
+            "toml" => Ok(OutputFormat::Toml),
+            "ndjson" | "jsonl" => Ok(OutputFormat::Ndjson),
+            _ => bail!(
+                "invalid output format '{}', expected one of yaml, json, toml and ndjson",
+                s
+            ),
+        }
+    }
+}
+
+impl ExecProgramResult {
+    /// Get the result documents. There are multiple documents when the program
+    /// uses `manifests.yaml_stream` or outputs standalone schema instances.
+    pub fn documents(&self, ctx: &mut Context) -> Result<Vec<ValueRef>> {
+        // The JSON result is a JSON stream with one document per line.
+        self.json_result
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| ValueRef::from_json(ctx, line).map_err(|err| anyhow!(err)))
+            .collect()
+    }
+
+    /// Format the result with the output format. Multiple documents are
+    /// written as a JSON list for the JSON format and are not supported by
+    /// the TOML format.
+    pub fn format(&self, format: OutputFormat) -> Result<String> {
+        if format == OutputFormat::Yaml {
+            return Ok(self.yaml_result.clone());
+        }
+        let mut ctx = Context::new();
+        let mut documents = self.documents(&mut ctx)?;
+        let output = match format {
+            OutputFormat::Json if documents.len() > 1 => {
+                let documents: Vec<&ValueRef> = documents.iter().collect();
+                format.format_value(&ValueRef::list(Some(&documents)))?
+            }
+            OutputFormat::Toml if documents.len() > 1 => {
+                bail!("toml output does not support multiple documents, use the split mode instead")
+            }
+            OutputFormat::Ndjson => documents
+                .iter()
+                .map(|doc| format.format_value(doc))
+                .collect::<Result<Vec<String>>>()?
+                .concat(),
+            _ => match documents.pop() {
+                Some(doc) => format.format_value(&doc)?,
+                None => String::new(),
+            },
+        };
+        Ok(output.trim_end_matches('\n').to_string())
+    }
+
+    /// Split the result and write each document into its own file in the
+    /// output directory. When there are multiple documents, the files are
+    /// named by the document index, `kind` and `metadata.name`, otherwise
+    /// each top level key of the result is written into a file named by the key.
+    /// Returns the written file paths, or an error without writing any file when
+    /// a key is empty or two keys are sanitized to the same file name.
+    pub fn split<P: AsRef<Path>>(&self, format: OutputFormat, dir: P) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        let mut ctx = Context::new();
+        let documents = self.documents(&mut ctx)?;
+        // The entries are the keys, the file stems and the values.
+        let entries: Vec<(String, String, ValueRef)> =
+            if documents.len() == 1 && documents[0].is_dict() {
+                documents[0]
+                    .as_dict_ref()
+                    .values
+                    .iter()
+                    .map(|(k, v)| (k.clone(), file_stem(k), v.clone()))
+                    .collect()
+            } else {
+                let width = documents.len().to_string().len();
+                documents
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, doc)| {
+                        let stem = document_file_stem(i, width, &doc);
+                        (stem.clone(), stem, doc)
+                    })
+                    .collect()
+            };
+        let mut keys: HashMap<&str, &str> = HashMap::new();
+        for (key, stem, _) in &entries {
+            if stem.is_empty() {
+                bail!("the empty key can not be used as the output file name");
+            }
+            if let Some(prev_key) = keys.insert(stem, key) {
+                bail!(
+                    "the keys '{}' and '{}' are written into the same output file '{}.{}'",
+                    prev_key,
+                    key,
+                    stem,
+                    format.extension()
+                );
+            }
+        }
+        std::fs::create_dir_all(dir)?;
+        let mut paths = vec![];
+        for (_, stem, value) in entries {
+            let path = dir.join(format!("{}.{}", stem, format.extension()));
+            let content = format
+                .format_value(&value)
+                .map_err(|err| anyhow!("failed to write '{}': {}", path.display(), err))?;
+            std::fs::write(&path, content)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Annotate the YAML result with the source position comments of each field
+    /// e.g., `replicas: 3  # from main.k:4`, which requires the provenance result
+    /// recorded in the provenance mode.
+    pub fn annotate_yaml(&self) -> Result<String> {
+        let provenance_map: HashMap<String, Provenance> = if self.provenance_result.is_empty() {
+            HashMap::new()
+        } else {
+            serde_json::from_str(&self.provenance_result)?
+        };
+        let mut annotator = YamlAnnotator::default();
+        let lines: Vec<String> = self
+            .yaml_result
+            .lines()
+            .map(|line| match annotator.key_path(line) {
+                Some(path) => match provenance_map.get(&path) {
+                    Some(provenance) => format!(
+                        "{}  # from {}:{}",
+                        line, provenance.filename, provenance.line
+                    ),
+                    None => line.to_string(),
+                },
+                None => line.to_string(),
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+}
+
+/// A YAML line frame, which is a mapping key or a list item.
+struct YamlFrame {
+    column: usize,
+    path: String,
+    is_item: bool,
+    items: usize,
+}
+
+/// YamlAnnotator tracks the key path of each line in the YAML output by the
+/// indentation. It only handles the block style YAML output by KCL.
+#[derive(Default)]
+struct YamlAnnotator {
+    frames: Vec<YamlFrame>,
+    /// The column of the last scalar value, the more indented lines are the
+    /// continuation lines of the scalar e.g., the block scalar lines.
+    scalar_column: Option<usize>,
+}
+
+impl YamlAnnotator {
+    /// Get the key path of the line, returns `None` when the line has no key.
+    fn key_path(&mut self, line: &str) -> Option<String> {
+        if line.trim_end() == YAML_DOCUMENT_SEPARATOR {
+            self.frames.clear();
+            self.scalar_column = None;
+            return None;
+        }
+        let content = line.trim_start_matches(' ');
+        let mut column = line.len() - content.len();
+        if content.is_empty() {
+            return None;
+        }
+        if let Some(scalar_column) = self.scalar_column {
+            if column > scalar_column {
+                return None;
+            }
+        }
+        self.scalar_column = None;
+        let mut content = content;
+        while let Some(rest) = content
+            .strip_prefix(YAML_LIST_ITEM_PREFIX)
+            .or_else(|| (content == "-").then_some(""))
+        {
+            self.push_item(column);
+            column += YAML_LIST_ITEM_PREFIX.len();
+            content = rest.trim_start_matches(' ');
+        }
+        match split_yaml_key(content) {
+            Some((key, value)) => {
+                while matches!(self.frames.last(), Some(frame) if frame.column >= column) {
+                    self.frames.pop();
+                }
+                let path = match self.frames.last() {
+                    Some(frame) => format!("{}.{}", frame.path, key),
+                    None => key,
+                };
+                if !value.is_empty() {
+                    self.scalar_column = Some(column);
+                }
+                self.frames.push(YamlFrame {
+                    column,
+                    path: path.clone(),
+                    is_item: false,
+                    items: 0,
+                });
+                Some(path)
+            }
+            None => {
+                self.scalar_column = Some(column.saturating_sub(YAML_LIST_ITEM_PREFIX.len()));
+                None
+            }
+        }
+    }
+
+    /// Push a list item frame at the column.
+    fn push_item(&mut self, column: usize) {
+        while matches!(self.frames.last(), Some(frame) if frame.column > column || (frame.is_item && frame.column == column))
+        {
+            self.frames.pop();
+        }
+        let path = match self.frames.last_mut() {
+            Some(frame) => {
+                frame.items += 1;
+                format!("{}[{}]", frame.path, frame.items - 1)
+            }
+            None => String::new(),
+        };
+        self.frames.push(YamlFrame {
+            column,
+            path,
+            is_item: true,
+            items: 0,
+        });
+    }
+}
+
+/// Split the YAML mapping line into the key and the value.
+fn split_yaml_key(content: &str) -> Option<(String, &str)> {
+    let (key, rest) = match content.chars().next() {
+        Some(quote @ ('\'' | '"')) => {
+            let end = content[1..].find(quote)? + 1;
+            (content[1..end].to_string(), &content[end + 1..])
+        }
+        _ => {
+            let end = content
+                .find(": ")
+                .or_else(|| content.strip_suffix(':').map(|k| k.len()))?;
+            (content[..end].to_string(), &content[end..])
+        }
+    };
+    let value = rest.strip_prefix(':')?;
+    if !value.is_empty() && !value.starts_with(' ') {
+        return None;
+    }
+    Some((key, value.trim()))
+}
+
+/// Get the file stem of a document e.g., `01-deployment-nginx`.
+fn document_file_stem(index: usize, width: usize, doc: &ValueRef) -> String {
+    let mut parts = vec![format!("{:0width$}", index, width = width)];
+    if doc.is_dict() {
+        if let Some(kind) = doc.get_by_key("kind").filter(|v| v.is_str()) {
+            parts.push(kind.as_str().to_lowercase());
+        }
+        if let Some(name) = doc.get_by_path("metadata.name").filter(|v| v.is_str()) {
+            parts.push(name.as_str());
+        }
+    }
+    file_stem(&parts.join("-"))
+}
+
+/// Replace the characters which are not safe in file names.
+fn file_stem(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,281 @@
+//! Native plugin registration API for the applications embedding the KCL runner.
+//!
+//! A native plugin is a named KCL module e.g., `kcl_plugin.hello` whose functions
+//! are Rust closures with declared parameter and return types. The declarations are
+//! type-checked by the resolver, and the functions are invoked in the fast evaluator
+//! through the runtime context or in the compiled artifact through the plugin agent.
+//!
+//! # Examples
+//!
+//! ```
+//! use kclvm_runner::plugin::NativePlugin;
+//! use kclvm_runner::ExecProgramArgs;
+//!
+//! let mut args = ExecProgramArgs::default();
+//! args.plugins.register(NativePlugin::new("hello").function(
+//!     "add",
+//!     &[("x", "int"), ("y", "int")],
+//!     "int",
+//!     |_, args, _| {
+//!         let x = args.arg_i_int(0, None).unwrap_or_default();
+//!         let y = args.arg_i_int(1, None).unwrap_or_default();
+//!         Ok((x + y).into())
+//!     },
+//! ));
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::c_char;
+use std::sync::Arc;
+
+use kclvm_primitives::IndexMap;
+use kclvm_runtime::{Context, PluginFunction, ValueRef};
+use kclvm_sema::plugin::PLUGIN_MODULE_PREFIX;
+
+mod wasm;
+
+/// The plugin agent function type set by `kclvm_plugin_init`.
+pub(crate) type PluginAgent = extern "C-unwind" fn(
+    method: *const c_char,
+    args_json: *const c_char,
+    kwargs_json: *const c_char,
+) -> *const c_char;
+
+/// A native function of the plugin with its declared signature.
+#[derive(Clone)]
+pub struct NativeFunction {
+    /// The parameter names and type annotations e.g., `("x", "int")`.
+    pub params: Vec<(String, String)>,
+    /// The return type annotation e.g., `int`.
+    pub return_ty: String,
+    /// The Rust function called with the runtime context, the positional
+    /// arguments list and the keyword arguments dict.
+    pub func: PluginFunction,
+}
+
+/// A native plugin module which is imported by `import kcl_plugin.<name>`.
+#[derive(Clone, Default)]
+pub struct NativePlugin {
+    /// The plugin module name e.g., `hello`.
+    pub name: String,
+    /// The plugin functions keyed by the function name.
+    pub functions: IndexMap<String, NativeFunction>,
+    /// The KCL declaration code of the plugin, which is used instead of the code
+    /// generated by the function signatures when it is set.
+    pub decl: Option<String>,
+}
+
+impl NativePlugin {
+    /// New a native plugin with the module name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            functions: IndexMap::default(),
+            decl: None,
+        }
+    }
+
+    /// Add a function with the parameter name and type pairs and the return type.
+    pub fn function<F>(
+        mut self,
+        name: &str,
+        params: &[(&str, &str)],
+        return_ty: &str,
+        func: F,
+    ) -> Self
+    where
+        F: Fn(&Context, &ValueRef, &ValueRef) -> anyhow::Result<ValueRef> + Send + Sync + 'static,
+    {
+        self.functions.insert(
+            name.to_string(),
+            NativeFunction {
+                params: params
+                    .iter()
+                    .map(|(name, ty)| (name.to_string(), ty.to_string()))
+                    .collect(),
+                return_ty: return_ty.to_string(),
+                func: Arc::new(func),
+            },
+        );
+        self
+    }
+
+    /// Set the KCL declaration code of the plugin.
+    pub fn decl(mut self, code: &str) -> Self {
+        self.decl = Some(code.to_string());
+        self
+    }
+
+    /// Get the KCL declaration code of the plugin functions e.g.,
+    /// `add = lambda x: int, y: int -> int {}`.
+    pub fn decl_code(&self) -> String {
+        if let Some(decl) = &self.decl {
+            return decl.clone();
+        }
+        self.functions
+            .iter()
+            .map(|(name, function)| {
+                let params = function
+                    .params
+                    .iter()
+                    .map(|(name, ty)| format!("{}: {}", name, ty))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                if params.is_empty() {
+                    format!("{} = lambda -> {} {{}}\n", name, function.return_ty)
+                } else {
+                    format!(
+                        "{} = lambda {} -> {} {{}}\n",
+                        name, params, function.return_ty
+                    )
+                }
+            })
+            .collect()
+    }
+}
+
+/// The registry of the native plugins, which is set in the exec arguments.
+#[derive(Clone, Default)]
+pub struct PluginRegistry {
+    plugins: IndexMap<String, NativePlugin>,
+}
+
+impl fmt::Debug for PluginRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.plugins.keys()).finish()
+    }
+}
+
+impl PluginRegistry {
+    /// Register the native plugin, a plugin with the same name is replaced.
+    pub fn register(&mut self, plugin: NativePlugin) -> &mut Self {
+        self.plugins.insert(plugin.name.clone(), plugin);
+        self
+    }
+
+    /// Whether there are no registered plugins.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Get the plugin functions keyed by `<module_name>.<func_name>` e.g., `hello.add`,
+    /// which is the form of the runtime context plugin functions.
+    pub fn plugin_functions(&self) -> IndexMap<String, PluginFunction> {
+        let mut plugin_functions = IndexMap::default();
+        for (plugin_name, plugin) in &self.plugins {
+            for (func_name, function) in &plugin.functions {
+                plugin_functions.insert(
+                    format!("{}.{}", plugin_name, func_name),
+                    function.func.clone(),
+                );
+            }
+        }
+        plugin_functions
+    }
+
+    /// Get the declaration code of all the plugins keyed by the plugin pkgpath
+    /// e.g., `kcl_plugin.hello`.
+    pub fn decl_codes(&self) -> HashMap<String, String> {
+        self.plugins
+            .iter()
+            .map(|(name, plugin)| {
+                (
+                    format!("{}{}", PLUGIN_MODULE_PREFIX, name),
+                    plugin.decl_code(),
+                )
+            })
+            .collect()
+    }
+
+    /// Invoke the plugin function with the JSON encoded arguments and return the
+    /// JSON encoded result, `None` denotes the method is not registered.
+    fn invoke_json(&self, method: &str, args_json: &str, kwargs_json: &str) -> Option<String> {
+        let method = method.strip_prefix(PLUGIN_MODULE_PREFIX).unwrap_or(method);
+        let (plugin_name, func_name) = method.rsplit_once('.')?;
+        let function = self.plugins.get(plugin_name)?.functions.get(func_name)?;
+        let mut ctx = Context::new();
+        let result = match (
+            ValueRef::from_json(&mut ctx, args_json),
+            ValueRef::from_json(&mut ctx, kwargs_json),
+        ) {
+            (Ok(args), Ok(kwargs)) => (function.func)(&ctx, &args, &kwargs),
+            (Err(err), _) | (_, Err(err)) => Err(err.into()),
+        };
+        Some(match result {
+            Ok(value) => value.to_json_string_with_null(),
+            Err(err) => {
+                let mut panic_info = ValueRef::dict(None);
+                panic_info
+                    .dict_update_key_value("__kcl_PanicInfo__", ValueRef::str(&err.to_string()));
+                panic_info.to_json_string()
+            }
+        })
+    }
+}
+
+thread_local! {
+    /// The native plugins and the fallback plugin agent used by the compiled artifact
+    /// running on the current thread.
+    static NATIVE_PLUGINS: RefCell<(PluginRegistry, Option<PluginAgent>)> =
+        RefCell::new((PluginRegistry::default(), None));
+    /// The result buffer of the last plugin invocation on the current thread held for the caller.
+    static NATIVE_PLUGIN_RESULT: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// The plugin agent which invokes the native plugins of the current thread, and falls
+/// back to the previous plugin agent for the unregistered methods.
+extern "C-unwind" fn native_plugin_agent(
+    method: *const c_char,
+    args_json: *const c_char,
+    kwargs_json: *const c_char,
+) -> *const c_char {
+    let (method_str, args_str, kwargs_str) = unsafe {
+        (
+            CStr::from_ptr(method).to_string_lossy(),
+            CStr::from_ptr(args_json).to_string_lossy(),
+            CStr::from_ptr(kwargs_json).to_string_lossy(),
+        )
+    };
+    let (result, fallback) = NATIVE_PLUGINS.with(|native_plugins| {
+        let native_plugins = native_plugins.borrow();
+        (
+            native_plugins
+                .0
+                .invoke_json(&method_str, &args_str, &kwargs_str),
+            native_plugins.1,
+        )
+    });
+    match (result, fallback) {
+        (Some(result), _) => NATIVE_PLUGIN_RESULT.with(|buffer| {
+            let mut buffer = buffer.borrow_mut();
+            *buffer = CString::new(result).unwrap_or_default();
+            buffer.as_ptr()
+        }),
+        (None, Some(fallback)) => fallback(method, args_json, kwargs_json),
+        (None, None) => panic!("the plugin method '{}' is not found", method_str),
+    }
+}
+
+/// Get the plugin agent address used by the compiled artifact. When there are native
+/// plugins in the registry, the native plugin agent is returned and the plugin agent
+/// address `plugin_agent` is used as the fallback. The plugins are set for the current
+/// thread, thus the artifact must be run on the same thread. The address can be passed
+/// to `kclvm_plugin_init` by the applications running the artifact themselves.
+pub fn plugin_agent_ptr(plugins: &PluginRegistry, plugin_agent: u64) -> u64 {
+    if plugins.is_empty() {
+        return plugin_agent;
+    }
+    let fallback = if plugin_agent > 0 {
+        Some(unsafe { std::mem::transmute::<u64, PluginAgent>(plugin_agent) })
+    } else {
+        None
+    };
+    NATIVE_PLUGINS.with(|native_plugins| {
+        *native_plugins.borrow_mut() = (plugins.clone(), fallback);
+    });
+    native_plugin_agent as u64
+}
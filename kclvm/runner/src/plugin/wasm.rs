@@ -0,0 +1,221 @@
+//! WebAssembly plugin host. The plugin modules are interpreted in a sandbox without
+//! any host imports such as WASI, so a plugin can neither access the file system,
+//! the network and the environment nor depend on the platform it was built on.
+//!
+//! A plugin module exports the linear memory `memory`, the allocator function
+//! `kcl_plugin_alloc(len: i32) -> i32` and the plugin functions with the signature
+//! `(args_ptr: i32, args_len: i32, kwargs_ptr: i32, kwargs_len: i32) -> i64`.
+//! The arguments are JSON encoded in the same way as `kclvm_plugin_invoke_json`,
+//! and the result is the JSON string at the address `result >> 32` with the length
+//! `result & 0xFFFFFFFF`. An error is returned as `{"__kcl_PanicInfo__": "<msg>"}`.
+//!
+//! Every plugin call is metered with the fuel, thus a looping plugin fails with an
+//! error instead of hanging the evaluation.
+
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, bail, Result};
+use kclvm_config::modfile::{get_mod_plugins, KCL_FILE_EXTENSION};
+use kclvm_runtime::{Context, ValueRef};
+use wasmi::core::{TrapCode, ValType};
+use wasmi::{
+    Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+    TypedFunc,
+};
+
+use super::{NativeFunction, NativePlugin, PluginRegistry};
+
+/// The exported linear memory name of the plugin module.
+const MEMORY_EXPORT: &str = "memory";
+/// The exported allocator function name of the plugin module.
+const ALLOC_EXPORT: &str = "kcl_plugin_alloc";
+/// The maximum linear memory size of a plugin instance in bytes.
+const MAX_MEMORY_SIZE: usize = 256 << 20;
+/// The fuel of a plugin call when the evaluation step limit is not set, which is
+/// roughly the number of the executed WebAssembly instructions.
+const DEFAULT_FUEL: u64 = 1 << 30;
+/// The fuel of a plugin call for each evaluation step when the step limit is set.
+const FUEL_PER_STEP: u64 = 1000;
+
+/// A plugin module instance, which is shared by all the plugin functions.
+struct WasmInstance {
+    store: Store<StoreLimits>,
+    instance: Instance,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+}
+
+impl WasmInstance {
+    fn new(engine: &Engine, module: &Module) -> Result<Self> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_SIZE)
+            .build();
+        let mut store = Store::new(engine, limits);
+        store.limiter(|limits| limits);
+        store
+            .set_fuel(DEFAULT_FUEL)
+            .map_err(|err| anyhow!("{}", err))?;
+        // No host functions are defined in the linker, thus the modules importing
+        // anything are rejected.
+        let instance = Linker::new(engine)
+            .instantiate(&mut store, module)
+            .and_then(|instance| instance.start(&mut store))?;
+        let memory = instance
+            .get_memory(&store, MEMORY_EXPORT)
+            .ok_or_else(|| anyhow!("the memory export '{}' is not found", MEMORY_EXPORT))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&store, ALLOC_EXPORT)?;
+        Ok(Self {
+            store,
+            instance,
+            memory,
+            alloc,
+        })
+    }
+
+    /// Copy the data into the plugin memory and return its address and length.
+    fn write(&mut self, data: &[u8]) -> Result<(i32, i32)> {
+        let len = i32::try_from(data.len())?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        self.memory
+            .write(&mut self.store, ptr as u32 as usize, data)
+            .map_err(|err| anyhow!("{}", err))?;
+        Ok((ptr, len))
+    }
+
+    /// Call the plugin function with the JSON encoded arguments and return the
+    /// JSON encoded result, the call fails when all the `fuel` is consumed.
+    fn call(
+        &mut self,
+        func: &str,
+        args_json: &str,
+        kwargs_json: &str,
+        fuel: u64,
+    ) -> Result<String> {
+        let func = self
+            .instance
+            .get_typed_func::<(i32, i32, i32, i32), i64>(&self.store, func)?;
+        self.store
+            .set_fuel(fuel)
+            .map_err(|err| anyhow!("{}", err))?;
+        let result = self
+            .write(args_json.as_bytes())
+            .and_then(|(args_ptr, args_len)| {
+                let (kwargs_ptr, kwargs_len) = self.write(kwargs_json.as_bytes())?;
+                Ok(func.call(
+                    &mut self.store,
+                    (args_ptr, args_len, kwargs_ptr, kwargs_len),
+                )?)
+            })
+            .map_err(|err| match err.downcast_ref::<wasmi::Error>() {
+                Some(err) if err.as_trap_code() == Some(TrapCode::OutOfFuel) => {
+                    anyhow!("the plugin runs out of the fuel {}", fuel)
+                }
+                _ => err,
+            })? as u64;
+        let mut buffer = vec![0; (result & 0xFFFF_FFFF) as usize];
+        self.memory
+            .read(&self.store, (result >> 32) as usize, &mut buffer)
+            .map_err(|err| anyhow!("{}", err))?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+/// Get the fuel of a plugin call from the evaluation step limit.
+fn call_fuel(ctx: &Context) -> u64 {
+    match ctx.cfg.limits.max_steps {
+        0 => DEFAULT_FUEL,
+        max_steps => max_steps.saturating_mul(FUEL_PER_STEP),
+    }
+}
+
+/// Whether the exported function has the plugin function signature.
+fn is_plugin_func(params: &[ValType], results: &[ValType]) -> bool {
+    params == [ValType::I32; 4] && results == [ValType::I64]
+}
+
+impl NativePlugin {
+    /// Load the plugin from the WebAssembly module file, whose exported functions
+    /// are the plugin functions. The declaration file next to the module file with
+    /// the `.k` extension is used as the plugin declaration if it exists.
+    pub fn from_wasm<P: AsRef<Path>>(name: &str, path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let bytes = fs::read(path)
+            .map_err(|err| anyhow!("failed to read the plugin '{}': {}", path.display(), err))?;
+        let module = Module::new(&engine, &bytes)?;
+        let instance = WasmInstance::new(&engine, &module)
+            .map_err(|err| anyhow!("failed to load the plugin '{}': {}", path.display(), err))?;
+        let instance = Arc::new(Mutex::new(instance));
+        let mut plugin = NativePlugin::new(name);
+        for export in module.exports() {
+            let func_ty = export.ty().func();
+            if !func_ty.map_or(false, |ty| is_plugin_func(ty.params(), ty.results())) {
+                continue;
+            }
+            let func_name = export.name().to_string();
+            let method = format!("{}.{}", name, func_name);
+            let instance = instance.clone();
+            plugin.functions.insert(
+                func_name.clone(),
+                NativeFunction {
+                    params: vec![],
+                    return_ty: "any".to_string(),
+                    func: Arc::new(move |ctx: &Context, args: &ValueRef, kwargs: &ValueRef| {
+                        let result = instance
+                            .lock()
+                            .map_err(|err| anyhow!("{}", err))?
+                            .call(
+                                &func_name,
+                                &args.to_json_string_with_null(),
+                                &kwargs.to_json_string_with_null(),
+                                call_fuel(ctx),
+                            )
+                            .map_err(|err| {
+                                anyhow!("failed to call the plugin function '{}': {}", method, err)
+                            })?;
+                        let value = ValueRef::from_json(&mut Context::new(), &result)?;
+                        if let Some(msg) = value.dict_get_value("__kcl_PanicInfo__") {
+                            bail!("{}", msg.as_str());
+                        }
+                        Ok(value)
+                    }),
+                },
+            );
+        }
+        // The functions are untyped without the declaration file.
+        let decl_path = path.with_extension(KCL_FILE_EXTENSION);
+        plugin.decl = Some(if decl_path.is_file() {
+            fs::read_to_string(decl_path)?
+        } else {
+            plugin
+                .functions
+                .keys()
+                .map(|name| format!("{}: any = None\n", name))
+                .collect()
+        });
+        Ok(plugin)
+    }
+}
+
+impl PluginRegistry {
+    /// Register the WebAssembly plugins in the 'kcl.mod' file of the package root.
+    /// The plugins already registered by the application are not replaced, which
+    /// are also used in the type checking before the 'kcl.mod' plugins.
+    pub fn register_mod_plugins(&mut self, root: &str) -> Result<&mut Self> {
+        for (name, source) in get_mod_plugins(root) {
+            if self.plugins.contains_key(&name) {
+                continue;
+            }
+            let mut plugin = NativePlugin::from_wasm(&name, &source.path)?;
+            if let Some(decl_path) = source.get_decl_path() {
+                plugin.decl = Some(fs::read_to_string(decl_path)?);
+            }
+            self.register(plugin);
+        }
+        Ok(self)
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::{anyhow, Result};
-use kclvm_evaluator::Evaluator;
+use kclvm_evaluator::{DebugHook, Evaluator, ProfileFormat};
 use std::collections::HashMap;
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, panic::AssertUnwindSafe, rc::Rc, time::Instant};
 
 use kclvm_ast::ast;
 use kclvm_config::{
@@ -9,17 +9,26 @@ use kclvm_config::{
     settings::{SettingsFile, SettingsPathBuf},
 };
 use kclvm_error::{Diagnostic, Handler};
+use kclvm_primitives::IndexMap;
 #[cfg(not(target_arch = "wasm32"))]
 use kclvm_runtime::kclvm_plugin_init;
 #[cfg(feature = "llvm")]
 use kclvm_runtime::FFIRunOptions;
-use kclvm_runtime::{Context, PanicInfo, RuntimePanicRecord};
+use kclvm_runtime::{
+    val_plan::PlanFormat, BacktraceFrame, CapabilityPolicy, Context, HermeticConfig, PanicInfo,
+    Provenance, ResourceLimits, RuntimePanicRecord, ValueRef,
+};
 #[cfg(target_arch = "wasm32")]
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::ffi::OsStr;
 use std::os::raw::c_char;
 
+use crate::error::ExecError;
+use crate::incremental::{PackageValueCache, ResultCache};
+use crate::output::OutputFormat;
+use crate::plugin::PluginRegistry;
+
 const RESULT_SIZE: usize = 2048 * 2048;
 const KCL_DEBUG_ERROR_ENV_VAR: &str = "KCL_DEBUG_ERROR";
 
@@ -28,6 +37,8 @@ pub type kclvm_char_t = c_char;
 #[allow(non_camel_case_types)]
 pub type kclvm_size_t = i32;
 #[allow(non_camel_case_types)]
+pub type kclvm_bool_t = i8;
+#[allow(non_camel_case_types)]
 pub type kclvm_context_t = std::ffi::c_void;
 #[allow(non_camel_case_types)]
 pub type kclvm_value_ref_t = std::ffi::c_void;
@@ -46,7 +57,11 @@ pub struct ExecProgramArgs {
     pub overrides: Vec<String>,
     /// -S path_selector
     pub path_selector: Vec<String>,
+    /// Whether to skip planning the YAML result.
     pub disable_yaml_result: bool,
+    /// Whether to skip planning the JSON result.
+    #[serde(default)]
+    pub disable_json_result: bool,
     /// Whether to apply overrides on the source code.
     pub print_override_ast: bool,
     /// -r --strict-range-check
@@ -65,15 +80,66 @@ pub struct ExecProgramArgs {
     pub include_schema_type_path: bool,
     /// Whether to compile only.
     pub compile_only: bool,
+    /// --format: the output format of the result, default is YAML.
+    #[serde(default)]
+    pub format: OutputFormat,
+    /// The capability policy to restrict the file system, environment variable
+    /// and plugin side effects of the program, default allows all of them.
+    #[serde(default)]
+    pub capabilities: CapabilityPolicy,
+    /// The resource limits of the evaluation such as the timeout and the
+    /// maximum call depth, zero means unlimited except the call depth limit
+    /// which defaults to 1000.
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub limits: ResourceLimits,
+    /// The profiling output file path, the evaluation is profiled when it is set.
+    /// A `.json` file is written in the Chrome trace format, and other files are
+    /// written in the flamegraph folded stack format.
+    #[serde(default)]
+    pub profile_output: Option<String>,
+    /// Whether to record the source positions where the output fields are set,
+    /// which are returned in the provenance result.
+    #[serde(default)]
+    pub provenance: bool,
+    /// Whether to cache the parsed AST of each source file, the evaluated values of each
+    /// imported package and the result of the whole program in the cache directory. The
+    /// cached package values are reused when the source files of the package and its
+    /// transitive dependencies, the arguments and the external inputs e.g., files and
+    /// environment variables read by the package are unchanged.
+    #[serde(default)]
+    pub incremental: bool,
+    /// Whether to evaluate the independent imported packages in parallel threads,
+    /// the package results are merged in the import order.
+    #[serde(default)]
+    pub parallel: bool,
+    /// The hermetic mode config to make the output reproducible, the nondeterministic
+    /// built-in functions use the injected clock, random seed and environment variable
+    /// snapshot, or fail when they are not injected.
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub hermetic: HermeticConfig,
     /// plugin_agent is the address of plugin.
     #[serde(skip)]
     pub plugin_agent: u64,
+    /// The native plugins registered by the embedding application, which are
+    /// imported by `import kcl_plugin.<name>` and type-checked by their declarations.
+    #[serde(skip)]
+    pub plugins: PluginRegistry,
     /// fast_eval denotes directly executing at the AST level to obtain
     /// the result without any form of compilation.
     #[serde(skip)]
     pub fast_eval: bool,
 }
 
+/// Deserialize the `null` value into the default value, which is used for the
+/// optional message fields of the API arguments.
+fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 impl ExecProgramArgs {
     /// [`get_package_maps_from_external_pkg`] gets the package name to package path mapping.
     pub fn get_package_maps_from_external_pkg(&self) -> HashMap<String, String> {
@@ -103,6 +169,15 @@ pub struct ExecProgramResult {
     pub yaml_result: String,
     pub log_message: String,
     pub err_message: String,
+    /// The provenance map in JSON format keyed by the output field path e.g.,
+    /// `{"app.replicas": {"filename": "main.k", "line": 3, "column": 4, "op": "="}}`,
+    /// which is only set in the provenance mode.
+    #[serde(default)]
+    pub provenance_result: String,
+    /// The structured runtime errors with the source ranges and the KCL call
+    /// stack, the `err_message` is the rendered message of them.
+    #[serde(default)]
+    pub errors: Vec<ExecError>,
 }
 
 pub trait MapErrorResult {
@@ -165,7 +240,15 @@ impl ExecProgramArgs {
             vendor_dirs: vec![get_vendor_home()],
             package_maps: self.get_package_maps_from_external_pkg(),
             k_code_list: self.k_code_list.clone(),
-            load_plugins: self.plugin_agent > 0,
+            load_plugins: self.plugin_agent > 0 || !self.plugins.is_empty(),
+            plugin_decl_codes: self.plugins.decl_codes(),
+            cache_dir: if self.incremental {
+                self.work_dir
+                    .clone()
+                    .or_else(|| Some(std::env::current_dir().ok()?.display().to_string()))
+            } else {
+                None
+            },
             ..Default::default()
         }
     }
@@ -193,6 +276,9 @@ impl TryFrom<SettingsFile> for ExecProgramArgs {
                 args.overrides.push(override_str);
             }
             args.path_selector = cli_configs.path_selector.unwrap_or_default();
+            if let Some(format) = cli_configs.format {
+                args.format = format.parse()?;
+            }
             args.set_external_pkg_from_package_maps(
                 cli_configs.package_maps.unwrap_or(HashMap::default()),
             )
@@ -240,7 +326,10 @@ impl ProgramRunner for Artifact {
     fn run(&self, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
         #[cfg(feature = "llvm")]
         unsafe {
-            LibRunner::lib_kclvm_plugin_init(&self.0, args.plugin_agent)?;
+            LibRunner::lib_kclvm_plugin_init(
+                &self.0,
+                crate::plugin::plugin_agent_ptr(&args.plugins, args.plugin_agent),
+            )?;
             LibRunner::lib_kcl_run(&self.0, args)
         }
         #[cfg(not(feature = "llvm"))]
@@ -298,7 +387,10 @@ impl LibRunner {
     pub fn run(&self, lib_path: &str, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
         unsafe {
             let lib = libloading::Library::new(std::path::PathBuf::from(lib_path).canonicalize()?)?;
-            Self::lib_kclvm_plugin_init(&lib, self.opts.plugin_agent_ptr)?;
+            Self::lib_kclvm_plugin_init(
+                &lib,
+                crate::plugin::plugin_agent_ptr(&args.plugins, self.opts.plugin_agent_ptr),
+            )?;
             Self::lib_kcl_run(&lib, args)
         }
     }
@@ -405,6 +497,16 @@ impl LibRunner {
             .collect();
         p_argv.push(std::ptr::null());
         let path_selector = p_argv.as_ptr();
+        // capability policy of the run
+        if !args.capabilities.is_unrestricted() {
+            let set_capabilities: libloading::Symbol<
+                unsafe extern "C-unwind" fn(policy: *const kclvm_char_t) -> kclvm_bool_t,
+            > = lib.get(b"kclvm_set_capabilities")?;
+            let policy = std::ffi::CString::new(serde_json::to_string(&args.capabilities)?)?;
+            if set_capabilities(policy.as_ptr()) == 0 {
+                return Err(anyhow!("invalid capability policy {:?}", args.capabilities));
+            }
+        }
 
         let opts = FFIRunOptions {
             strict_range_check: args.strict_range_check as i32,
@@ -444,18 +546,12 @@ impl LibRunner {
             json_result: json_buffer.to_string()?,
             log_message: log_buffer.to_string()?,
             err_message: err_buffer.to_string()?,
+            ..Default::default()
         };
-        // Wrap runtime JSON Panic error string into diagnostic style string.
-        if !result.err_message.is_empty() && std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
-            result.err_message = match Handler::default()
-                .add_diagnostic(<PanicInfo as Into<Diagnostic>>::into(PanicInfo::from(
-                    result.err_message.as_str(),
-                )))
-                .emit_to_string()
-            {
-                Ok(msg) => msg,
-                Err(err) => err.to_string(),
-            };
+        if !result.err_message.is_empty() {
+            let panic_info = PanicInfo::from(result.err_message.as_str());
+            let backtrace = panic_info.backtrace.clone();
+            set_runtime_error(&mut result, panic_info, &backtrace);
         }
         Ok(result)
     }
@@ -491,6 +587,9 @@ static ONCE_PANIC_HOOK: Lazy<()> = Lazy::new(|| {
 
 pub struct FastRunner {
     opts: RunnerOptions,
+    debug_hook: Option<Rc<dyn DebugHook>>,
+    result_cache: Option<ResultCache>,
+    package_cache: Option<PackageValueCache>,
 }
 
 impl FastRunner {
@@ -498,13 +597,67 @@ impl FastRunner {
     pub fn new(opts: Option<RunnerOptions>) -> Self {
         Self {
             opts: opts.unwrap_or_default(),
+            debug_hook: None,
+            result_cache: None,
+            package_cache: None,
         }
     }
 
+    /// Set the debug hook called by the evaluator during the run.
+    pub fn with_debug_hook(mut self, hook: Rc<dyn DebugHook>) -> Self {
+        self.debug_hook = Some(hook);
+        self
+    }
+
+    /// Set the evaluation cache to save the run result.
+    pub(crate) fn with_result_cache(mut self, result_cache: ResultCache) -> Self {
+        self.result_cache = Some(result_cache);
+        self
+    }
+
+    /// Set the package cache to reuse the evaluated values of the unchanged packages.
+    pub(crate) fn with_package_cache(mut self, package_cache: PackageValueCache) -> Self {
+        self.package_cache = Some(package_cache);
+        self
+    }
+
     /// Run kcl library with exec arguments.
+    #[inline]
     pub fn run(&self, program: &ast::Program, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
+        self.run_with_output(program, args, None)
+    }
+
+    /// Run kcl library with exec arguments and write the planned result in the format
+    /// into the writer. The JSON and YAML results of the returned [ExecProgramResult]
+    /// are empty because the result is not built in memory.
+    #[inline]
+    pub fn run_to_writer(
+        &self,
+        program: &ast::Program,
+        args: &ExecProgramArgs,
+        format: PlanFormat,
+        writer: &mut dyn std::io::Write,
+    ) -> Result<ExecProgramResult> {
+        self.run_with_output(program, args, Some((format, writer)))
+    }
+
+    fn run_with_output(
+        &self,
+        program: &ast::Program,
+        args: &ExecProgramArgs,
+        output: Option<(PlanFormat, &mut dyn std::io::Write)>,
+    ) -> Result<ExecProgramResult> {
         let ctx = Rc::new(RefCell::new(args_to_ctx(program, args)));
         let evaluator = Evaluator::new_with_runtime_ctx(program, ctx.clone());
+        if args.profile_output.is_some() {
+            evaluator.enable_profiler();
+        }
+        if let Some(hook) = &self.debug_hook {
+            evaluator.set_debug_hook(hook.as_ref());
+        }
+        if let Some(package_cache) = &self.package_cache {
+            evaluator.set_package_cache(package_cache);
+        }
         #[cfg(target_arch = "wasm32")]
         // Ensure the panic hook is set (this will only happen once) for the WASM target,
         // because it is single threaded.
@@ -532,7 +685,10 @@ impl FastRunner {
                 }
             })
         }));
+        let output = AssertUnwindSafe(output);
         let evaluator_result = std::panic::catch_unwind(|| {
+            // Capture the whole unwind safe wrapper rather than its field.
+            let output = output;
             if self.opts.plugin_agent_ptr > 0 {
                 #[cfg(not(target_arch = "wasm32"))]
                 unsafe {
@@ -544,14 +700,39 @@ impl FastRunner {
                     kclvm_plugin_init(plugin_method);
                 }
             }
-            evaluator.run()
+            // Plugin agents are not guaranteed to be thread-safe, the resource limits
+            // are checked by each evaluator and the hermetic random values depend on
+            // the evaluation order, thus they are evaluated serially.
+            if args.parallel
+                && self.opts.plugin_agent_ptr == 0
+                && args.limits == Default::default()
+                && !args.hermetic.enabled
+            {
+                let threads = std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1);
+                evaluator.preload_packages(threads, &|| args_to_ctx(program, args));
+            }
+            match output.0 {
+                Some((format, writer)) => evaluator
+                    .run_to_writer(format, writer)
+                    .map(|_| (String::new(), String::new())),
+                None => evaluator.run(),
+            }
         });
         #[cfg(not(target_arch = "wasm32"))]
         std::panic::set_hook(prev_hook);
+        if evaluator_result.is_err() {
+            evaluator.record_panic_backtrace();
+        }
         KCL_RUNTIME_PANIC_RECORD.with(|record| {
             let record = record.borrow();
             ctx.borrow_mut().set_panic_info(&record);
         });
+        // Write the profiling result of the evaluation.
+        if let (Some(path), Some(profiler)) = (&args.profile_output, evaluator.take_profiler()) {
+            std::fs::write(path, profiler.output(ProfileFormat::from_path(path)))?;
+        }
         let mut result = ExecProgramResult {
             log_message: ctx.borrow().log_message.clone(),
             ..Default::default()
@@ -562,6 +743,9 @@ impl FastRunner {
                 Ok((json, yaml)) => {
                     result.json_result = json;
                     result.yaml_result = yaml;
+                    if args.provenance {
+                        result.provenance_result = provenance_to_json(&evaluator.provenance_map());
+                    }
                 }
                 Err(err) => {
                     result.err_message = err.to_string();
@@ -577,17 +761,18 @@ impl FastRunner {
                 };
             }
         }
-        // Wrap runtime JSON Panic error string into diagnostic style string.
-        if !result.err_message.is_empty() && std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
-            result.err_message = match Handler::default()
-                .add_diagnostic(<PanicInfo as Into<Diagnostic>>::into(PanicInfo::from(
-                    result.err_message.as_str(),
-                )))
-                .emit_to_string()
-            {
-                Ok(msg) => msg,
-                Err(err) => err.to_string(),
+        if !result.err_message.is_empty() {
+            let panic_info = PanicInfo::from(result.err_message.as_str());
+            // The call stack of the runtime panic is always recorded by the evaluator.
+            let backtrace = if is_err {
+                ctx.borrow().panic_backtrace()
+            } else {
+                vec![]
             };
+            set_runtime_error(&mut result, panic_info, &backtrace);
+        }
+        if let Some(result_cache) = &self.result_cache {
+            result_cache.save(&result, &ctx.borrow().inputs);
         }
         // Free all value references at runtime. This is because the runtime context marks
         // all KCL objects and holds their copies, so it is necessary to actively GC them.
@@ -596,15 +781,63 @@ impl FastRunner {
     }
 }
 
+/// Record the structured runtime error and wrap the runtime JSON panic error
+/// string into the diagnostic style string.
+fn set_runtime_error(
+    result: &mut ExecProgramResult,
+    panic_info: PanicInfo,
+    backtrace: &[BacktraceFrame],
+) {
+    result
+        .errors
+        .push(ExecError::from_panic_info(&panic_info, backtrace));
+    if std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
+        result.err_message = match Handler::default()
+            .add_diagnostic(<PanicInfo as Into<Diagnostic>>::into(panic_info))
+            .emit_to_string()
+        {
+            Ok(msg) => msg,
+            Err(err) => err.to_string(),
+        };
+    }
+}
+
+/// Encode the provenance map into a JSON string.
+fn provenance_to_json(provenance_map: &IndexMap<String, Provenance>) -> String {
+    let mut dict = ValueRef::dict(None);
+    for (path, provenance) in provenance_map {
+        dict.dict_update_key_value(
+            path,
+            ValueRef::dict(Some(&[
+                ("filename", &ValueRef::str(&provenance.filename)),
+                ("line", &ValueRef::int(provenance.line as i64)),
+                ("column", &ValueRef::int(provenance.column as i64)),
+                ("op", &ValueRef::str(&provenance.op)),
+            ])),
+        );
+    }
+    dict.to_json_string()
+}
+
 pub(crate) fn args_to_ctx(program: &ast::Program, args: &ExecProgramArgs) -> Context {
     let mut ctx = Context::new();
     ctx.cfg.strict_range_check = args.strict_range_check;
     ctx.cfg.debug_mode = args.debug != 0;
+    ctx.cfg.capabilities = args.capabilities.clone();
+    ctx.cfg.limits = args.limits.clone();
+    ctx.cfg.provenance = args.provenance;
+    ctx.cfg.hermetic = args.hermetic.clone();
+    if args.limits.timeout_ms > 0 {
+        ctx.resource_usage.start = Some(Instant::now());
+    }
     ctx.plan_opts.disable_none = args.disable_none;
     ctx.plan_opts.show_hidden = args.show_hidden;
     ctx.plan_opts.sort_keys = args.sort_keys;
     ctx.plan_opts.include_schema_type_path = args.include_schema_type_path;
     ctx.plan_opts.query_paths = args.path_selector.clone();
+    ctx.plan_opts.disable_json = args.disable_json_result;
+    ctx.plan_opts.disable_yaml = args.disable_yaml_result;
+    ctx.plugin_functions = args.plugins.plugin_functions();
     for arg in &args.args {
         ctx.builtin_option_init(&arg.name, &arg.value);
     }
@@ -0,0 +1,7 @@
+[package]
+name = "wasm_plugin"
+edition = "0.0.1"
+version = "0.0.1"
+
+[plugins]
+hello = { path = "plugins/hello.wasm" }
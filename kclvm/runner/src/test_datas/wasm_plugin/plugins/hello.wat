@@ -0,0 +1,32 @@
+;; The source of hello.wasm, which is compiled by `wat2wasm hello.wat`.
+(module
+  (memory (export "memory") 1)
+  (global $heap (mut i32) (i32.const 1024))
+  (data (i32.const 0) "\"1.0.0\"")
+  (data (i32.const 16) "{\"__kcl_PanicInfo__\":\"invalid argument\"}")
+
+  ;; A bump allocator for the JSON encoded arguments.
+  (func (export "kcl_plugin_alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+    (local.get $ptr))
+
+  ;; version() -> "1.0.0"
+  (func (export "version") (param i32 i32 i32 i32) (result i64)
+    (i64.const 7))
+
+  ;; echo(*args) -> args
+  (func (export "echo") (param $args_ptr i32) (param $args_len i32) (param i32 i32) (result i64)
+    (i64.or
+      (i64.shl (i64.extend_i32_u (local.get $args_ptr)) (i64.const 32))
+      (i64.extend_i32_u (local.get $args_len))))
+
+  ;; fail() -> panic "invalid argument"
+  (func (export "fail") (param i32 i32 i32 i32) (result i64)
+    (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 40)))
+
+  ;; spin() -> never returns
+  (func (export "spin") (param i32 i32 i32 i32) (result i64)
+    (loop $continue (br $continue))
+    (i64.const 0)))
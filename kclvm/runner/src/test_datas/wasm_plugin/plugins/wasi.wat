@@ -0,0 +1,9 @@
+;; The source of wasi.wasm, which is compiled by `wat2wasm wasi.wat`.
+(module
+  (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+  (memory (export "memory") 1)
+  (func (export "kcl_plugin_alloc") (param i32) (result i32)
+    (i32.const 0))
+  (func (export "exit") (param i32 i32 i32 i32) (result i64)
+    (call $proc_exit (i32.const 1))
+    (i64.const 0)))
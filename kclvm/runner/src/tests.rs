@@ -7,9 +7,12 @@ use crate::assembler::KclvmLibAssembler;
 #[cfg(feature = "llvm")]
 use crate::assembler::LibAssembler;
 use crate::exec_program;
+use crate::exec_program_to_writer;
+use crate::plugin::{NativePlugin, PluginRegistry};
 #[cfg(feature = "llvm")]
 use crate::temp_file;
 use crate::{execute, runner::ExecProgramArgs};
+use crate::{ExecProgramResult, OutputFormat};
 #[cfg(feature = "llvm")]
 use anyhow::Context;
 use anyhow::Result;
@@ -17,8 +20,10 @@ use kclvm_ast::ast::{Module, Program};
 #[cfg(feature = "llvm")]
 use kclvm_compiler::codegen::OBJECT_FILE_SUFFIX;
 use kclvm_config::settings::load_file;
+use kclvm_evaluator::PackageCache;
 use kclvm_parser::load_program;
 use kclvm_parser::ParseSession;
+use kclvm_runtime::{CapabilityPolicy, HermeticConfig, ResourceLimits};
 #[cfg(feature = "llvm")]
 use kclvm_sema::resolver::resolve_program;
 use kclvm_utils::path::PathPrefix;
@@ -32,7 +37,6 @@ use std::{
     collections::HashMap,
     fs::{self, File},
 };
-#[cfg(feature = "llvm")]
 use tempfile::tempdir;
 use uuid::Uuid;
 use walkdir::WalkDir;
@@ -190,6 +194,7 @@ fn construct_program(module: Module) -> Program {
         modules,
         pkgs_not_imported: HashMap::new(),
         modules_not_imported: HashMap::new(),
+        plugin_decls: HashMap::new(),
     }
 }
 
@@ -735,3 +740,722 @@ fn test_kcl_issue_1799() {
         )
     );
 }
+
+#[test]
+fn test_exec_program_result_format() {
+    let result = ExecProgramResult {
+        json_result: "{\"kind\": \"Service\", \"metadata\": {\"name\": \"web\"}}\n{\"kind\": \"Deployment\", \"metadata\": {\"name\": \"web\"}}".to_string(),
+        yaml_result: "kind: Service\nmetadata:\n  name: web\n---\nkind: Deployment\nmetadata:\n  name: web".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        result.format(OutputFormat::Yaml).unwrap(),
+        result.yaml_result
+    );
+    assert_eq!(
+        result.format(OutputFormat::Ndjson).unwrap(),
+        result.json_result
+    );
+    let json: Value = serde_json::from_str(&result.format(OutputFormat::Json).unwrap()).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 2);
+    assert!(result.format(OutputFormat::Toml).is_err());
+    assert_eq!(
+        "ndjson".parse::<OutputFormat>().unwrap(),
+        OutputFormat::Ndjson
+    );
+    assert!("xml".parse::<OutputFormat>().is_err());
+}
+
+#[test]
+fn test_exec_program_result_split() {
+    let dir = tempdir().unwrap();
+    let result = ExecProgramResult {
+        json_result: "{\"kind\": \"Service\", \"metadata\": {\"name\": \"web\"}}\n{\"kind\": \"Deployment\", \"metadata\": {\"name\": \"web\"}}".to_string(),
+        ..Default::default()
+    };
+    let paths = result.split(OutputFormat::Yaml, dir.path()).unwrap();
+    assert_eq!(
+        paths,
+        vec![
+            dir.path().join("0-service-web.yaml"),
+            dir.path().join("1-deployment-web.yaml")
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(&paths[0]).unwrap(),
+        "kind: Service\nmetadata:\n  name: web\n"
+    );
+
+    let result = ExecProgramResult {
+        json_result: "{\"app\": {\"name\": \"web\", \"port\": 80}, \"db\": {\"name\": \"db\"}}"
+            .to_string(),
+        ..Default::default()
+    };
+    let paths = result.split(OutputFormat::Toml, dir.path()).unwrap();
+    assert_eq!(
+        paths,
+        vec![dir.path().join("app.toml"), dir.path().join("db.toml")]
+    );
+    assert_eq!(
+        fs::read_to_string(&paths[0]).unwrap(),
+        "name = \"web\"\nport = 80\n"
+    );
+
+    // The keys sanitized to the same file name and the empty keys are errors.
+    let dir = tempdir().unwrap();
+    let result = ExecProgramResult {
+        json_result: "{\"a/b\": 1, \"a_b\": 2}".to_string(),
+        ..Default::default()
+    };
+    let err = result.split(OutputFormat::Yaml, dir.path()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the keys 'a/b' and 'a_b' are written into the same output file 'a_b.yaml'"
+    );
+    let result = ExecProgramResult {
+        json_result: "{\"\": 1, \"app\": 2}".to_string(),
+        ..Default::default()
+    };
+    let err = result.split(OutputFormat::Yaml, dir.path()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the empty key can not be used as the output file name"
+    );
+    // No file is written on the errors.
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_exec_program_with_capabilities() {
+    let dir = tempdir().unwrap();
+    let main_file = dir.path().join("main.k");
+    let output_file = dir.path().join("output.txt");
+    fs::write(
+        &main_file,
+        format!(
+            "import file\n\nfile.write(r\"{}\", \"hello\")\n",
+            output_file.display()
+        ),
+    )
+    .unwrap();
+    for fast_eval in [true, false] {
+        let mut args = ExecProgramArgs {
+            k_filename_list: vec![main_file.display().to_string()],
+            fast_eval,
+            capabilities: CapabilityPolicy::sandbox(&[dir.path().display().to_string()]),
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args);
+        let err = match result {
+            Ok(result) => result.err_message,
+            Err(err) => err.to_string(),
+        };
+        assert!(
+            err.contains("is not allowed in the read-only mode"),
+            "{}",
+            err
+        );
+        assert!(!output_file.exists());
+        // Allow writing files in the allowed directories.
+        args.capabilities.read_only = false;
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert!(result.err_message.is_empty(), "{}", result.err_message);
+        assert_eq!(fs::read_to_string(&output_file).unwrap(), "hello");
+        fs::remove_file(&output_file).unwrap();
+    }
+}
+
+#[test]
+fn test_exec_program_with_native_plugins() {
+    let mut plugins = PluginRegistry::default();
+    plugins.register(NativePlugin::new("hello").function(
+        "add",
+        &[("x", "int"), ("y", "int")],
+        "int",
+        |_, args, _| {
+            let x = args.arg_i_int(0, None).unwrap_or_default();
+            let y = args.arg_i_int(1, None).unwrap_or_default();
+            Ok((x + y).into())
+        },
+    ));
+    for fast_eval in [true, false] {
+        let mut args = ExecProgramArgs {
+            k_filename_list: vec!["main.k".to_string()],
+            k_code_list: vec!["import kcl_plugin.hello\n\nsum = hello.add(1, 2)\n".to_string()],
+            fast_eval,
+            plugins: plugins.clone(),
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert!(result.err_message.is_empty(), "{}", result.err_message);
+        assert_eq!(result.yaml_result, "sum: 3");
+        // The plugin calls are type-checked by the declared function signatures.
+        args.k_code_list =
+            vec!["import kcl_plugin.hello\n\nsum = hello.add(1, \"2\")\n".to_string()];
+        let err = exec_program(Arc::new(ParseSession::default()), &args)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expected int, got str(2)"), "{}", err);
+    }
+}
+
+#[test]
+fn test_native_plugin_agent_per_thread() {
+    // The native plugins are set per thread, thus the concurrent runs only call their own plugins.
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            std::thread::spawn(move || {
+                let mut plugins = PluginRegistry::default();
+                plugins.register(NativePlugin::new("hello").function(
+                    "id",
+                    &[],
+                    "int",
+                    move |_, _, _| Ok((i as i64).into()),
+                ));
+                let agent: crate::plugin::PluginAgent =
+                    unsafe { std::mem::transmute(crate::plugin::plugin_agent_ptr(&plugins, 0)) };
+                let method = std::ffi::CString::new("kcl_plugin.hello.id").unwrap();
+                let args = std::ffi::CString::new("[]").unwrap();
+                let kwargs = std::ffi::CString::new("{}").unwrap();
+                for _ in 0..100 {
+                    let result = agent(method.as_ptr(), args.as_ptr(), kwargs.as_ptr());
+                    let result = unsafe { std::ffi::CStr::from_ptr(result) };
+                    assert_eq!(result.to_str().unwrap(), i.to_string());
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_exec_program_with_wasm_plugins() {
+    let root = Path::new("src").join("test_datas").join("wasm_plugin");
+    for fast_eval in [true, false] {
+        let mut args = ExecProgramArgs {
+            k_filename_list: vec![root.join("main.k").display().to_string()],
+            fast_eval,
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert!(result.err_message.is_empty(), "{}", result.err_message);
+        assert_eq!(result.yaml_result, "version: 1.0.0\necho:\n- 1\n- a");
+        // The plugin calls are type-checked by the declaration file next to the module.
+        args.k_code_list =
+            vec!["import kcl_plugin.hello\n\necho = hello.echo(\"1\", \"a\")\n".to_string()];
+        let err = exec_program(Arc::new(ParseSession::default()), &args)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expected int, got str(1)"), "{}", err);
+        args.k_code_list = vec!["import kcl_plugin.hello\n\nfail = hello.fail()\n".to_string()];
+        let err = match exec_program(Arc::new(ParseSession::default()), &args) {
+            Ok(result) => result.err_message,
+            Err(err) => err.to_string(),
+        };
+        assert!(err.contains("invalid argument"), "{}", err);
+        // The looping plugin calls fail when the fuel set from the step limit is consumed.
+        if fast_eval {
+            args.k_code_list = vec!["import kcl_plugin.hello\n\nspin = hello.spin()\n".to_string()];
+            args.limits.max_steps = 100;
+            let err = match exec_program(Arc::new(ParseSession::default()), &args) {
+                Ok(result) => result.err_message,
+                Err(err) => err.to_string(),
+            };
+            assert!(err.contains("the plugin runs out of the fuel"), "{}", err);
+            args.limits = Default::default();
+        }
+    }
+    // The plugin modules can not import any host functions e.g., WASI.
+    let err = NativePlugin::from_wasm("wasi", root.join("plugins").join("wasi.wasm"))
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(err.contains("wasi_snapshot_preview1::proc_exit"), "{}", err);
+}
+
+#[test]
+fn test_exec_program_with_limits() {
+    let cases = [
+        (
+            "f = lambda n: int -> int {\n    f(n + 1)\n}\na = f(1)\n",
+            ResourceLimits {
+                max_call_depth: 50,
+                ..Default::default()
+            },
+            "RecursionError",
+        ),
+        (
+            "a = [i for i in range(1000000000)]\n",
+            ResourceLimits {
+                max_values: 1000,
+                ..Default::default()
+            },
+            "ResourceLimitError",
+        ),
+        (
+            "a = [i for i in range(1000)]\n",
+            ResourceLimits {
+                max_steps: 100,
+                ..Default::default()
+            },
+            "ResourceLimitError",
+        ),
+    ];
+    for (code, limits, err_kind) in cases {
+        let args = ExecProgramArgs {
+            k_filename_list: vec!["main.k".to_string()],
+            k_code_list: vec![code.to_string()],
+            fast_eval: true,
+            limits,
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args);
+        let err = match result {
+            Ok(result) => result.err_message,
+            Err(err) => err.to_string(),
+        };
+        assert!(err.contains(err_kind), "{}", err);
+    }
+}
+
+#[test]
+fn test_exec_program_with_profile_output() {
+    let code = "schema Person:\n    name: str\n\nf = lambda x {\n    x + 1\n}\np = Person {name = \"Alice\"}\na = f(1)\n";
+    let temp_dir = tempdir().unwrap();
+    for (file, expected) in [
+        ("profile.folded", "main.k:7;__main__.Person "),
+        ("profile.json", "\"ph\": \"X\""),
+    ] {
+        let profile_output = temp_dir.path().join(file).display().to_string();
+        let args = ExecProgramArgs {
+            k_filename_list: vec!["main.k".to_string()],
+            k_code_list: vec![code.to_string()],
+            profile_output: Some(profile_output.clone()),
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert!(result.err_message.is_empty(), "{}", result.err_message);
+        let profile = std::fs::read_to_string(&profile_output).unwrap();
+        assert!(profile.contains(expected), "{}", profile);
+        assert!(profile.contains("lambda@main.k:5"), "{}", profile);
+    }
+}
+
+#[test]
+fn test_exec_program_with_provenance() {
+    let code = "schema App:\n    name: str\n    replicas: int = 1\n\napp: App {\n    name = \"web\"\n}\napp: App {\n    replicas = 3\n}\nlabels = {env = \"prod\"}\n";
+    let args = ExecProgramArgs {
+        k_filename_list: vec!["main.k".to_string()],
+        k_code_list: vec![code.to_string()],
+        provenance: true,
+        ..Default::default()
+    };
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result.err_message.is_empty(), "{}", result.err_message);
+    let provenance: Value = serde_json::from_str(&result.provenance_result).unwrap();
+    assert_eq!(provenance["app.name"]["line"], 6);
+    assert_eq!(provenance["app.name"]["op"], "=");
+    assert_eq!(provenance["app.replicas"]["line"], 9);
+    assert_eq!(provenance["labels"]["line"], 11);
+    assert_eq!(provenance["labels.env"]["filename"], "main.k");
+    let yaml = result.annotate_yaml().unwrap();
+    assert!(yaml.contains("  replicas: 3  # from main.k:9"), "{}", yaml);
+    assert!(yaml.contains("  env: prod  # from main.k:11"), "{}", yaml);
+    // The provenance result is empty when the provenance mode is disabled.
+    let args = ExecProgramArgs {
+        provenance: false,
+        ..args
+    };
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result.provenance_result.is_empty());
+}
+
+#[test]
+fn test_exec_program_incremental() {
+    let temp_dir = tempdir().unwrap();
+    let work_dir = temp_dir.path().display().to_string();
+    let data_file = temp_dir.path().join("data.txt");
+    let main_file = temp_dir.path().join("main.k");
+    std::fs::write(&data_file, "a").unwrap();
+    std::fs::write(
+        &main_file,
+        format!(
+            "import file\n\ndata = file.read(\"{}\")\n",
+            data_file.display().to_string().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    let args = ExecProgramArgs {
+        work_dir: Some(work_dir.clone()),
+        k_filename_list: vec![main_file.display().to_string()],
+        incremental: true,
+        ..Default::default()
+    };
+    let run = || exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    let is_cached = || {
+        let program =
+            crate::load_program_with_args(Arc::new(ParseSession::default()), &args).unwrap();
+        crate::incremental::ResultCache::new(&program, &args)
+            .unwrap()
+            .load()
+            .is_some()
+    };
+    assert_eq!(run().yaml_result, "data: a");
+    assert!(is_cached());
+    // Changed external inputs invalidate the cached result.
+    std::fs::write(&data_file, "b").unwrap();
+    assert!(!is_cached());
+    assert_eq!(run().yaml_result, "data: b");
+    assert!(is_cached());
+    // Changed source files invalidate the cached result.
+    std::fs::write(&main_file, "data = \"c\"\n").unwrap();
+    assert!(!is_cached());
+    assert_eq!(run().yaml_result, "data: c");
+    // The parsed ASTs are cached in the work directory.
+    let cache_dir = Path::new(&work_dir).join(".kclvm").join("cache");
+    assert!(WalkDir::new(cache_dir)
+        .into_iter()
+        .flatten()
+        .any(|entry| entry.file_name() == "ast" && entry.path().read_dir().unwrap().count() > 0));
+}
+
+#[test]
+fn test_exec_program_incremental_packages() {
+    let temp_dir = tempdir().unwrap();
+    let files = [
+        ("kcl.mod", "[package]\nname = \"app\"\n"),
+        ("c/c.k", "base = {replicas = 1}\n"),
+        (
+            "a/a.k",
+            "import c\n\nschema App:\n    name: str\n    replicas: int\n\nprint(\"a\")\napp = App {name = \"a\"} | c.base\n",
+        ),
+        ("b/b.k", "name = \"b\"\n"),
+        (
+            "main.k",
+            "import a\nimport b\n\napp = a.App {name = b.name, replicas = a.app.replicas}\n",
+        ),
+    ];
+    for (file, content) in files {
+        let path = temp_dir.path().join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    let args = ExecProgramArgs {
+        work_dir: Some(temp_dir.path().display().to_string()),
+        k_filename_list: vec![temp_dir.path().join("main.k").display().to_string()],
+        incremental: true,
+        ..Default::default()
+    };
+    let run = || exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    let is_cached = |pkgpath: &str| {
+        let program =
+            crate::load_program_with_args(Arc::new(ParseSession::default()), &args).unwrap();
+        crate::incremental::PackageValueCache::new(&program, &args)
+            .unwrap()
+            .load(pkgpath)
+            .is_some()
+    };
+    let result = run();
+    assert_eq!(result.yaml_result, "app:\n  name: b\n  replicas: 1");
+    assert!(["a", "b", "c"].iter().all(|pkgpath| is_cached(pkgpath)));
+    // Changed source files only invalidate the cached values of the packages
+    // depending on them.
+    std::fs::write(temp_dir.path().join("b/b.k"), "name = \"d\"\n").unwrap();
+    assert!(is_cached("a") && is_cached("c"));
+    assert!(!is_cached("b"));
+    let result = run();
+    assert_eq!(result.yaml_result, "app:\n  name: d\n  replicas: 1");
+    // The print messages of the cached packages are still output.
+    assert_eq!(result.log_message, "a\n");
+    std::fs::write(temp_dir.path().join("c/c.k"), "base = {replicas = 2}\n").unwrap();
+    assert!(!is_cached("a") && !is_cached("c"));
+    assert!(is_cached("b"));
+    let result = run();
+    assert_eq!(result.yaml_result, "app:\n  name: d\n  replicas: 2");
+}
+
+#[test]
+fn test_exec_program_parallel() {
+    let temp_dir = tempdir().unwrap();
+    let files = [
+        ("kcl.mod", "[package]\nname = \"app\"\n"),
+        ("c/c.k", "print(\"c\")\nbase = {replicas = 1}\n"),
+        (
+            "a/a.k",
+            "import c\n\nprint(\"a\")\nconfig = c.base | {name = \"a\"}\n",
+        ),
+        (
+            "b/b.k",
+            "schema App:\n    name: str\n    replicas: int = 1\n\nprint(\"b\")\napp = App {name = \"b\"}\n",
+        ),
+        // The lambda globals are defined again when the package is installed.
+        ("d/d.k", "f = lambda x {\n    x + 1\n}\nvalue = f(1)\n"),
+        (
+            "main.k",
+            "import a\nimport b\nimport c\nimport d\n\nconfig = a.config\napp = b.app\nbase = c.base\nvalue = d.value\n",
+        ),
+    ];
+    for (path, content) in files {
+        let path = temp_dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    let mut args = ExecProgramArgs {
+        work_dir: Some(temp_dir.path().display().to_string()),
+        k_filename_list: vec![temp_dir.path().join("main.k").display().to_string()],
+        fast_eval: true,
+        ..Default::default()
+    };
+    let serial = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    args.parallel = true;
+    let parallel = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(serial.err_message, "");
+    assert_eq!(parallel.err_message, "");
+    assert_eq!(parallel.yaml_result, serial.yaml_result);
+    assert_eq!(parallel.json_result, serial.json_result);
+    // The print messages are merged in the import order.
+    assert_eq!(parallel.log_message, "c\na\nb\n");
+    assert_eq!(parallel.log_message, serial.log_message);
+}
+
+#[test]
+fn test_exec_program_parallel_side_effects() {
+    let temp_dir = tempdir().unwrap();
+    let output = temp_dir.path().join("output.txt");
+    let output_path = output.display().to_string().replace('\\', "/");
+    let files = [
+        ("kcl.mod", "[package]\nname = \"app\"\n".to_string()),
+        (
+            "a/a.k",
+            format!("import file\n\nresult = file.append(\"{output_path}\", \"a\\n\")\n"),
+        ),
+        // The function in the data global makes the package fall back to the serial
+        // evaluation after the side effect.
+        (
+            "b/b.k",
+            format!(
+                "import file\n\nresult = file.append(\"{output_path}\", \"b\\n\")\nfs = [lambda x {{\n    x\n}}]\n"
+            ),
+        ),
+        ("main.k", "import a\nimport b\n\nvalue = 1\n".to_string()),
+    ];
+    for (path, content) in files {
+        let path = temp_dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    let args = ExecProgramArgs {
+        work_dir: Some(temp_dir.path().display().to_string()),
+        k_filename_list: vec![temp_dir.path().join("main.k").display().to_string()],
+        parallel: true,
+        ..Default::default()
+    };
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(result.err_message, "");
+    assert_eq!(result.yaml_result, "value: 1");
+    // The side effects are not repeated by the worker evaluators.
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), "a\nb\n");
+}
+
+#[test]
+fn test_exec_program_to_writer() {
+    let temp_dir = tempdir().unwrap();
+    let main_file = temp_dir.path().join("main.k");
+    std::fs::write(
+        &main_file,
+        "schema Person:\n    name: str\n    age?: int\n\nalice = Person {name = \"alice\", age = 18}\nbob = {name = \"bob\", tags = [1, 2]}\n_hidden = 1\nnothing = None\n",
+    )
+    .unwrap();
+    let mut args = ExecProgramArgs {
+        k_filename_list: vec![main_file.display().to_string()],
+        ..Default::default()
+    };
+    let expected = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    for (format, result) in [
+        (OutputFormat::Yaml, &expected.yaml_result),
+        (OutputFormat::Ndjson, &expected.json_result),
+    ] {
+        args.format = format;
+        let mut output = vec![];
+        let result_with_writer =
+            exec_program_to_writer(Arc::new(ParseSession::default()), &args, &mut output).unwrap();
+        assert_eq!(result_with_writer.err_message, "");
+        assert_eq!(String::from_utf8(output).unwrap(), *result);
+    }
+    // The cached result of the incremental mode is also written into the writer.
+    args.format = OutputFormat::Yaml;
+    args.work_dir = Some(temp_dir.path().display().to_string());
+    args.incremental = true;
+    for _ in 0..2 {
+        let mut output = vec![];
+        exec_program_to_writer(Arc::new(ParseSession::default()), &args, &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), expected.yaml_result);
+    }
+    args.format = OutputFormat::Toml;
+    assert!(exec_program_to_writer(Arc::new(ParseSession::default()), &args, &mut vec![]).is_err());
+}
+
+#[test]
+fn test_exec_program_structured_errors() {
+    let temp_dir = tempdir().unwrap();
+    let main_file = temp_dir.path().join("main.k").display().to_string();
+    std::fs::write(
+        &main_file,
+        "schema Person:\n    age: int\n\n    check:\n        age > 0, \"age must be positive\"\n\nf = lambda x {\n    Person {age = x}\n}\np = f(-1)\n",
+    )
+    .unwrap();
+    let args = ExecProgramArgs {
+        k_filename_list: vec![main_file.clone()],
+        ..Default::default()
+    };
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(!result.err_message.is_empty());
+    assert_eq!(result.errors.len(), 1);
+    let error = &result.errors[0];
+    assert_eq!(error.kind, "SchemaCheckFailure");
+    assert_eq!(error.code, "E3M38");
+    assert_eq!(
+        error.message,
+        "Check failed on the condition: age must be positive"
+    );
+    let range = error.range.as_ref().unwrap();
+    assert_eq!(
+        (range.start.filename.as_str(), range.start.line),
+        (main_file.as_str(), 5)
+    );
+    // The config meta location where the schema is instantiated.
+    assert_eq!(error.related.len(), 1);
+    assert_eq!(error.related[0].message, "Instance check failed");
+    assert_eq!(error.related[0].range.start.line, 8);
+    // The call stack is recorded without the debug mode.
+    assert!(error.backtrace.len() > 1);
+    assert_eq!(error.backtrace[0].pos.line, 5);
+    assert_eq!(error.backtrace.last().unwrap().pos.line, 10);
+    // The calls of the caught runtime panics are not left in the call stack.
+    std::fs::write(
+        &main_file,
+        "import runtime\n\nmsg = runtime.catch(lambda {\n    assert False\n})\ng = lambda x {\n    assert x > 0, \"x must be positive\"\n}\nv = g(-1)\n",
+    )
+    .unwrap();
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(result.errors.len(), 1);
+    let backtrace = &result.errors[0].backtrace;
+    assert_eq!(backtrace.len(), 2);
+    assert_eq!(
+        (backtrace[0].func.as_str(), backtrace[0].pos.line),
+        ("lambda", 7)
+    );
+    assert_eq!(backtrace[1].pos.line, 9);
+}
+
+#[test]
+fn test_exec_program_hermetic() {
+    let temp_dir = tempdir().unwrap();
+    let main_file = temp_dir.path().join("main.k");
+    std::fs::write(
+        &main_file,
+        "import datetime\nimport crypto\nimport file\n\ndate = datetime.date()\nids = [crypto.uuid() for _ in range(2)]\nenv = file.read_env(\"KCL_HERMETIC_KEY\")\n",
+    )
+    .unwrap();
+    let mut args = ExecProgramArgs {
+        k_filename_list: vec![main_file.display().to_string()],
+        hermetic: HermeticConfig {
+            enabled: true,
+            clock: Some(0),
+            random_seed: Some(42),
+            env: [("KCL_HERMETIC_KEY".to_string(), "value".to_string())].into(),
+        },
+        ..Default::default()
+    };
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(result.err_message, "");
+    assert!(result
+        .json_result
+        .contains("\"date\": \"1970-01-01 00:00:00\""));
+    assert!(result.json_result.contains("\"env\": \"value\""));
+    // The regenerated result is byte-identical.
+    let regenerated = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(regenerated.yaml_result, result.yaml_result);
+    // The nondeterministic built-in functions fail without the injected values.
+    args.hermetic.clock = None;
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result
+        .err_message
+        .contains("reading the current time is not allowed in the hermetic mode"));
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_debugger_breakpoint() {
+    let temp_dir = tempdir().unwrap();
+    let main_file = temp_dir.path().join("main.k").display().to_string();
+    std::fs::write(
+        &main_file,
+        "schema Person:\n    name: str\n\nf = lambda x: int {\n    y = x + 1\n    y\n}\na = f(1)\nb = f(2)\np = Person {name = \"Alice\"}\n",
+    )
+    .unwrap();
+    let requests = [
+        serde_json::json!({"command": "initialize", "arguments": {}}),
+        serde_json::json!({"command": "launch", "arguments": {"program": main_file}}),
+        serde_json::json!({"command": "setBreakpoints", "arguments": {
+            "source": {"path": main_file},
+            "breakpoints": [{"line": 5, "condition": "x == 2"}],
+        }}),
+        serde_json::json!({"command": "configurationDone"}),
+        serde_json::json!({"command": "stackTrace", "arguments": {"threadId": 1}}),
+        serde_json::json!({"command": "scopes", "arguments": {"frameId": 1}}),
+        serde_json::json!({"command": "variables", "arguments": {"variablesReference": 1}}),
+        serde_json::json!({"command": "evaluate", "arguments": {"expression": "x + 10"}}),
+        serde_json::json!({"command": "continue", "arguments": {"threadId": 1}}),
+    ];
+    let mut input = vec![];
+    for (seq, mut request) in requests.into_iter().enumerate() {
+        request["seq"] = serde_json::json!(seq + 1);
+        request["type"] = serde_json::json!("request");
+        crate::debugger::write_message(&mut input, &request).unwrap();
+    }
+    let output = SharedBuffer::default();
+    crate::debugger::serve(std::io::Cursor::new(input), output.clone()).unwrap();
+    let output = output.0.lock().unwrap().clone();
+    let mut reader = std::io::Cursor::new(output);
+    let mut messages = vec![];
+    while let Some(message) = crate::debugger::read_message(&mut reader).unwrap() {
+        messages.push(message);
+    }
+    let find = |kind: &str, name: &str| {
+        messages
+            .iter()
+            .find(|m| m["type"] == kind && (m["command"] == name || m["event"] == name))
+            .unwrap_or_else(|| panic!("{} {} is not found in {:?}", kind, name, messages))
+    };
+    assert_eq!(find("event", "stopped")["body"]["reason"], "breakpoint");
+    let frames = &find("response", "stackTrace")["body"]["stackFrames"];
+    assert!(frames[0]["name"].as_str().unwrap().starts_with("lambda@"));
+    assert_eq!(frames[0]["line"], 5);
+    assert_eq!(frames[1]["name"], "<module>");
+    assert_eq!(frames[1]["line"], 9);
+    let variables = &find("response", "variables")["body"]["variables"];
+    assert!(variables
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|v| v["name"] == "x" && v["value"] == "2"));
+    assert_eq!(find("response", "evaluate")["body"]["result"], "12");
+    let output = find("event", "output")["body"]["output"].as_str().unwrap();
+    assert!(output.contains("a: 2\nb: 3"), "{}", output);
+    assert_eq!(find("event", "exited")["body"]["exitCode"], 0);
+}
@@ -63,7 +63,27 @@ pub struct FFIRunOptions {
 }
 
 thread_local! {
-    static KCL_RUNTIME_PANIC_RECORD: std::cell::RefCell<RuntimePanicRecord>  = std::cell::RefCell::new(RuntimePanicRecord::default())
+    static KCL_RUNTIME_PANIC_RECORD: std::cell::RefCell<RuntimePanicRecord>  = std::cell::RefCell::new(RuntimePanicRecord::default());
+    /// The capability policy of the next `_kcl_run` call on the current thread.
+    static KCL_RUNTIME_CAPABILITIES: std::cell::RefCell<CapabilityPolicy> = std::cell::RefCell::new(CapabilityPolicy::default());
+}
+
+/// Set the capability policy of the next `_kcl_run` call on the current thread
+/// with the JSON string of [CapabilityPolicy]. The policy is set apart from
+/// [FFIRunOptions] to keep its C layout unchanged. Returns 0 when the policy
+/// is invalid.
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_set_capabilities(
+    policy: *const kclvm_char_t,
+) -> kclvm_bool_t {
+    match serde_json::from_str::<CapabilityPolicy>(c2str(policy)) {
+        Ok(policy) => {
+            KCL_RUNTIME_CAPABILITIES.with(|capabilities| *capabilities.borrow_mut() = policy);
+            1
+        }
+        Err(_) => 0,
+    }
 }
 
 fn new_ctx_with_opts(opts: FFIRunOptions, path_selector: &[String]) -> Context {
@@ -72,6 +92,8 @@ fn new_ctx_with_opts(opts: FFIRunOptions, path_selector: &[String]) -> Context {
     ctx.cfg.strict_range_check = opts.strict_range_check != 0;
     ctx.cfg.disable_schema_check = opts.disable_schema_check != 0;
     ctx.cfg.debug_mode = opts.debug_mode != 0;
+    // Capability policy, which is only applied to one run.
+    ctx.cfg.capabilities = KCL_RUNTIME_CAPABILITIES.with(|capabilities| capabilities.take());
     // Plan options
     ctx.plan_opts.disable_none = opts.disable_none != 0;
     ctx.plan_opts.show_hidden = opts.show_hidden != 0;
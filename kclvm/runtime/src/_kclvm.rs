@@ -37,6 +37,7 @@ pub enum ApiFunc {
     kclvm_builtin_bin,
     kclvm_builtin_bool,
     kclvm_builtin_dict,
+    kclvm_builtin_filter,
     kclvm_builtin_float,
     kclvm_builtin_hex,
     kclvm_builtin_int,
@@ -44,6 +45,7 @@ pub enum ApiFunc {
     kclvm_builtin_isunique,
     kclvm_builtin_len,
     kclvm_builtin_list,
+    kclvm_builtin_map,
     kclvm_builtin_max,
     kclvm_builtin_min,
     kclvm_builtin_multiplyof,
@@ -55,7 +57,9 @@ pub enum ApiFunc {
     kclvm_builtin_pow,
     kclvm_builtin_print,
     kclvm_builtin_range,
+    kclvm_builtin_reduce,
     kclvm_builtin_round,
+    kclvm_builtin_sort_by,
     kclvm_builtin_sorted,
     kclvm_builtin_str,
     kclvm_builtin_str_capitalize,
@@ -119,6 +123,8 @@ pub enum ApiFunc {
     kclvm_crypto_sha384,
     kclvm_crypto_sha512,
     kclvm_crypto_uuid,
+    kclvm_csv_decode,
+    kclvm_csv_encode,
     kclvm_datetime_date,
     kclvm_datetime_now,
     kclvm_datetime_ticks,
@@ -160,6 +166,9 @@ pub enum ApiFunc {
     kclvm_file_size,
     kclvm_file_workdir,
     kclvm_file_write,
+    kclvm_hcl_encode,
+    kclvm_ini_decode,
+    kclvm_ini_encode,
     kclvm_iterator_cur_key,
     kclvm_iterator_cur_value,
     kclvm_iterator_delete,
@@ -229,6 +238,8 @@ pub enum ApiFunc {
     kclvm_plugin_init,
     kclvm_plugin_invoke,
     kclvm_plugin_invoke_json,
+    kclvm_properties_decode,
+    kclvm_properties_encode,
     kclvm_regex_compile,
     kclvm_regex_findall,
     kclvm_regex_match,
@@ -240,6 +251,7 @@ pub enum ApiFunc {
     kclvm_schema_backtrack_cache,
     kclvm_schema_default_settings,
     kclvm_schema_do_check_with_index_sign_attr,
+    kclvm_schema_final_check,
     kclvm_schema_get_value,
     kclvm_schema_instances,
     kclvm_schema_optional_check,
@@ -250,6 +262,7 @@ pub enum ApiFunc {
     kclvm_scope_get,
     kclvm_scope_new,
     kclvm_scope_set,
+    kclvm_set_capabilities,
     kclvm_template_execute,
     kclvm_template_html_escape,
     kclvm_units_to_G,
@@ -306,6 +319,10 @@ pub enum ApiFunc {
     kclvm_value_load_attr_option,
     kclvm_value_logic_and,
     kclvm_value_logic_or,
+    kclvm_value_match_failed,
+    kclvm_value_match_mapping,
+    kclvm_value_match_rest_mapping,
+    kclvm_value_match_sequence,
     kclvm_value_not_in,
     kclvm_value_op_add,
     kclvm_value_op_aug_add,
@@ -351,6 +368,8 @@ pub enum ApiFunc {
     kclvm_value_unary_plus,
     kclvm_value_union,
     kclvm_value_union_all,
+    kclvm_xml_decode,
+    kclvm_xml_encode,
     kclvm_yaml_decode,
     kclvm_yaml_decode_all,
     kclvm_yaml_dump_all_to_file,
@@ -16,6 +16,7 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_builtin_bin" => crate::kclvm_builtin_bin as *const () as u64,
         "kclvm_builtin_bool" => crate::kclvm_builtin_bool as *const () as u64,
         "kclvm_builtin_dict" => crate::kclvm_builtin_dict as *const () as u64,
+        "kclvm_builtin_filter" => crate::kclvm_builtin_filter as *const () as u64,
         "kclvm_builtin_float" => crate::kclvm_builtin_float as *const () as u64,
         "kclvm_builtin_hex" => crate::kclvm_builtin_hex as *const () as u64,
         "kclvm_builtin_int" => crate::kclvm_builtin_int as *const () as u64,
@@ -23,6 +24,7 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_builtin_isunique" => crate::kclvm_builtin_isunique as *const () as u64,
         "kclvm_builtin_len" => crate::kclvm_builtin_len as *const () as u64,
         "kclvm_builtin_list" => crate::kclvm_builtin_list as *const () as u64,
+        "kclvm_builtin_map" => crate::kclvm_builtin_map as *const () as u64,
         "kclvm_builtin_max" => crate::kclvm_builtin_max as *const () as u64,
         "kclvm_builtin_min" => crate::kclvm_builtin_min as *const () as u64,
         "kclvm_builtin_multiplyof" => crate::kclvm_builtin_multiplyof as *const () as u64,
@@ -34,7 +36,9 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_builtin_pow" => crate::kclvm_builtin_pow as *const () as u64,
         "kclvm_builtin_print" => crate::kclvm_builtin_print as *const () as u64,
         "kclvm_builtin_range" => crate::kclvm_builtin_range as *const () as u64,
+        "kclvm_builtin_reduce" => crate::kclvm_builtin_reduce as *const () as u64,
         "kclvm_builtin_round" => crate::kclvm_builtin_round as *const () as u64,
+        "kclvm_builtin_sort_by" => crate::kclvm_builtin_sort_by as *const () as u64,
         "kclvm_builtin_sorted" => crate::kclvm_builtin_sorted as *const () as u64,
         "kclvm_builtin_str" => crate::kclvm_builtin_str as *const () as u64,
         "kclvm_builtin_str_capitalize" => crate::kclvm_builtin_str_capitalize as *const () as u64,
@@ -120,6 +124,8 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_crypto_sha384" => crate::kclvm_crypto_sha384 as *const () as u64,
         "kclvm_crypto_sha512" => crate::kclvm_crypto_sha512 as *const () as u64,
         "kclvm_crypto_uuid" => crate::kclvm_crypto_uuid as *const () as u64,
+        "kclvm_csv_decode" => crate::kclvm_csv_decode as *const () as u64,
+        "kclvm_csv_encode" => crate::kclvm_csv_encode as *const () as u64,
         "kclvm_datetime_date" => crate::kclvm_datetime_date as *const () as u64,
         "kclvm_datetime_now" => crate::kclvm_datetime_now as *const () as u64,
         "kclvm_datetime_ticks" => crate::kclvm_datetime_ticks as *const () as u64,
@@ -165,6 +171,9 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_file_size" => crate::kclvm_file_size as *const () as u64,
         "kclvm_file_workdir" => crate::kclvm_file_workdir as *const () as u64,
         "kclvm_file_write" => crate::kclvm_file_write as *const () as u64,
+        "kclvm_hcl_encode" => crate::kclvm_hcl_encode as *const () as u64,
+        "kclvm_ini_decode" => crate::kclvm_ini_decode as *const () as u64,
+        "kclvm_ini_encode" => crate::kclvm_ini_encode as *const () as u64,
         "kclvm_iterator_cur_key" => crate::kclvm_iterator_cur_key as *const () as u64,
         "kclvm_iterator_cur_value" => crate::kclvm_iterator_cur_value as *const () as u64,
         "kclvm_iterator_delete" => crate::kclvm_iterator_delete as *const () as u64,
@@ -242,6 +251,8 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_plugin_init" => crate::kclvm_plugin_init as *const () as u64,
         "kclvm_plugin_invoke" => crate::kclvm_plugin_invoke as *const () as u64,
         "kclvm_plugin_invoke_json" => crate::kclvm_plugin_invoke_json as *const () as u64,
+        "kclvm_properties_decode" => crate::kclvm_properties_decode as *const () as u64,
+        "kclvm_properties_encode" => crate::kclvm_properties_encode as *const () as u64,
         "kclvm_regex_compile" => crate::kclvm_regex_compile as *const () as u64,
         "kclvm_regex_findall" => crate::kclvm_regex_findall as *const () as u64,
         "kclvm_regex_match" => crate::kclvm_regex_match as *const () as u64,
@@ -255,6 +266,7 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_schema_do_check_with_index_sign_attr" => {
             crate::kclvm_schema_do_check_with_index_sign_attr as *const () as u64
         }
+        "kclvm_schema_final_check" => crate::kclvm_schema_final_check as *const () as u64,
         "kclvm_schema_get_value" => crate::kclvm_schema_get_value as *const () as u64,
         "kclvm_schema_instances" => crate::kclvm_schema_instances as *const () as u64,
         "kclvm_schema_optional_check" => crate::kclvm_schema_optional_check as *const () as u64,
@@ -265,6 +277,7 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_scope_get" => crate::kclvm_scope_get as *const () as u64,
         "kclvm_scope_new" => crate::kclvm_scope_new as *const () as u64,
         "kclvm_scope_set" => crate::kclvm_scope_set as *const () as u64,
+        "kclvm_set_capabilities" => crate::kclvm_set_capabilities as *const () as u64,
         "kclvm_template_execute" => crate::kclvm_template_execute as *const () as u64,
         "kclvm_template_html_escape" => crate::kclvm_template_html_escape as *const () as u64,
         "kclvm_units_to_G" => crate::kclvm_units_to_G as *const () as u64,
@@ -329,6 +342,12 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_value_load_attr_option" => crate::kclvm_value_load_attr_option as *const () as u64,
         "kclvm_value_logic_and" => crate::kclvm_value_logic_and as *const () as u64,
         "kclvm_value_logic_or" => crate::kclvm_value_logic_or as *const () as u64,
+        "kclvm_value_match_failed" => crate::kclvm_value_match_failed as *const () as u64,
+        "kclvm_value_match_mapping" => crate::kclvm_value_match_mapping as *const () as u64,
+        "kclvm_value_match_rest_mapping" => {
+            crate::kclvm_value_match_rest_mapping as *const () as u64
+        }
+        "kclvm_value_match_sequence" => crate::kclvm_value_match_sequence as *const () as u64,
         "kclvm_value_not_in" => crate::kclvm_value_not_in as *const () as u64,
         "kclvm_value_op_add" => crate::kclvm_value_op_add as *const () as u64,
         "kclvm_value_op_aug_add" => crate::kclvm_value_op_aug_add as *const () as u64,
@@ -378,6 +397,8 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_value_unary_plus" => crate::kclvm_value_unary_plus as *const () as u64,
         "kclvm_value_union" => crate::kclvm_value_union as *const () as u64,
         "kclvm_value_union_all" => crate::kclvm_value_union_all as *const () as u64,
+        "kclvm_xml_decode" => crate::kclvm_xml_decode as *const () as u64,
+        "kclvm_xml_encode" => crate::kclvm_xml_encode as *const () as u64,
         "kclvm_yaml_decode" => crate::kclvm_yaml_decode as *const () as u64,
         "kclvm_yaml_decode_all" => crate::kclvm_yaml_decode_all as *const () as u64,
         "kclvm_yaml_dump_all_to_file" => crate::kclvm_yaml_dump_all_to_file as *const () as u64,
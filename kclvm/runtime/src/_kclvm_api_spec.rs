@@ -2,6 +2,10 @@
 
 // Auto generated by <make gen-api-spec> command, DONOT EDIT!!!
 
+// api-spec:       kclvm_set_capabilities
+// api-spec(c):    kclvm_bool_t kclvm_set_capabilities(kclvm_char_t* policy);
+// api-spec(llvm): declare %kclvm_bool_t @kclvm_set_capabilities(%kclvm_char_t* %policy);
+
 // api-spec:       kclvm_context_new
 // api-spec(c):    kclvm_context_t* kclvm_context_new();
 // api-spec(llvm): declare %kclvm_context_t* @kclvm_context_new();
@@ -151,8 +155,8 @@
 // api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_value_Function_using_ptr(%kclvm_context_t* %ctx, i64* %fn_ptr, %kclvm_char_t* %name);
 
 // api-spec:       kclvm_value_schema_function
-// api-spec(c):    kclvm_value_ref_t* kclvm_value_schema_function(kclvm_context_t* ctx, uint64_t* fn_ptr, uint64_t* check_fn_ptr, kclvm_value_ref_t* attr_map, kclvm_char_t* tpe);
-// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_value_schema_function(%kclvm_context_t* %ctx, i64* %fn_ptr, i64* %check_fn_ptr, %kclvm_value_ref_t* %attr_map, %kclvm_char_t* %tpe);
+// api-spec(c):    kclvm_value_ref_t* kclvm_value_schema_function(kclvm_context_t* ctx, uint64_t* fn_ptr, uint64_t* check_fn_ptr, kclvm_value_ref_t* attr_map, kclvm_value_ref_t* bases, kclvm_char_t* tpe);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_value_schema_function(%kclvm_context_t* %ctx, i64* %fn_ptr, i64* %check_fn_ptr, %kclvm_value_ref_t* %attr_map, %kclvm_value_ref_t* %bases, %kclvm_char_t* %tpe);
 
 // api-spec:       kclvm_value_from_json
 // api-spec(c):    kclvm_value_ref_t* kclvm_value_from_json(kclvm_context_t* ctx, kclvm_char_t* s);
@@ -394,6 +398,22 @@
 // api-spec(c):    kclvm_size_t kclvm_value_len(kclvm_value_ref_t* p);
 // api-spec(llvm): declare %kclvm_size_t @kclvm_value_len(%kclvm_value_ref_t* %p);
 
+// api-spec:       kclvm_value_match_sequence
+// api-spec(c):    kclvm_bool_t kclvm_value_match_sequence(kclvm_value_ref_t* p, kclvm_size_t len, kclvm_bool_t has_rest);
+// api-spec(llvm): declare %kclvm_bool_t @kclvm_value_match_sequence(%kclvm_value_ref_t* %p, %kclvm_size_t %len, %kclvm_bool_t %has_rest);
+
+// api-spec:       kclvm_value_match_mapping
+// api-spec(c):    kclvm_bool_t kclvm_value_match_mapping(kclvm_value_ref_t* p, kclvm_value_ref_t* keys);
+// api-spec(llvm): declare %kclvm_bool_t @kclvm_value_match_mapping(%kclvm_value_ref_t* %p, %kclvm_value_ref_t* %keys);
+
+// api-spec:       kclvm_value_match_rest_mapping
+// api-spec(c):    kclvm_value_ref_t* kclvm_value_match_rest_mapping(kclvm_context_t* ctx, kclvm_value_ref_t* p, kclvm_value_ref_t* keys);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_value_match_rest_mapping(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %p, %kclvm_value_ref_t* %keys);
+
+// api-spec:       kclvm_value_match_failed
+// api-spec(c):    void kclvm_value_match_failed(kclvm_context_t* _ctx, kclvm_value_ref_t* p);
+// api-spec(llvm): declare void @kclvm_value_match_failed(%kclvm_context_t* %_ctx, %kclvm_value_ref_t* %p);
+
 // api-spec:       kclvm_value_cmp_equal_to
 // api-spec(c):    kclvm_value_ref_t* kclvm_value_cmp_equal_to(kclvm_context_t* ctx, kclvm_value_ref_t* a, kclvm_value_ref_t* b);
 // api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_value_cmp_equal_to(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %a, %kclvm_value_ref_t* %b);
@@ -614,6 +634,10 @@
 // api-spec(c):    void kclvm_schema_optional_check(kclvm_context_t* ctx, kclvm_value_ref_t* p);
 // api-spec(llvm): declare void @kclvm_schema_optional_check(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %p);
 
+// api-spec:       kclvm_schema_final_check
+// api-spec(c):    void kclvm_schema_final_check(kclvm_context_t* ctx, kclvm_value_ref_t* config, kclvm_value_ref_t* config_meta, kclvm_value_ref_t* final_attrs, kclvm_char_t* schema_name);
+// api-spec(llvm): declare void @kclvm_schema_final_check(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %config, %kclvm_value_ref_t* %config_meta, %kclvm_value_ref_t* %final_attrs, %kclvm_char_t* %schema_name);
+
 // api-spec:       kclvm_schema_default_settings
 // api-spec(c):    void kclvm_schema_default_settings(kclvm_value_ref_t* schema_value, kclvm_value_ref_t* _config_value, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs, kclvm_char_t* runtime_type);
 // api-spec(llvm): declare void @kclvm_schema_default_settings(%kclvm_value_ref_t* %schema_value, %kclvm_value_ref_t* %_config_value, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs, %kclvm_char_t* %runtime_type);
@@ -1078,6 +1102,22 @@
 // api-spec(c):    kclvm_value_ref_t* kclvm_builtin_sorted(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
 // api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_builtin_sorted(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
 
+// api-spec:       kclvm_builtin_map
+// api-spec(c):    kclvm_value_ref_t* kclvm_builtin_map(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_builtin_map(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_builtin_filter
+// api-spec(c):    kclvm_value_ref_t* kclvm_builtin_filter(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_builtin_filter(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_builtin_reduce
+// api-spec(c):    kclvm_value_ref_t* kclvm_builtin_reduce(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_builtin_reduce(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_builtin_sort_by
+// api-spec(c):    kclvm_value_ref_t* kclvm_builtin_sort_by(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_builtin_sort_by(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
 // api-spec:       kclvm_builtin_int
 // api-spec(c):    kclvm_value_ref_t* kclvm_builtin_int(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
 // api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_builtin_int(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
@@ -1326,7 +1366,42 @@
 // api-spec(c):    kclvm_value_ref_t* kclvm_template_html_escape(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
 // api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_template_html_escape(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
 
+// api-spec:       kclvm_csv_encode
+// api-spec(c):    kclvm_value_ref_t* kclvm_csv_encode(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_csv_encode(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_csv_decode
+// api-spec(c):    kclvm_value_ref_t* kclvm_csv_decode(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_csv_decode(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_ini_encode
+// api-spec(c):    kclvm_value_ref_t* kclvm_ini_encode(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_ini_encode(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_ini_decode
+// api-spec(c):    kclvm_value_ref_t* kclvm_ini_decode(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_ini_decode(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_properties_encode
+// api-spec(c):    kclvm_value_ref_t* kclvm_properties_encode(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_properties_encode(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_properties_decode
+// api-spec(c):    kclvm_value_ref_t* kclvm_properties_decode(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_properties_decode(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_xml_encode
+// api-spec(c):    kclvm_value_ref_t* kclvm_xml_encode(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_xml_encode(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_xml_decode
+// api-spec(c):    kclvm_value_ref_t* kclvm_xml_decode(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_xml_decode(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
+// api-spec:       kclvm_hcl_encode
+// api-spec(c):    kclvm_value_ref_t* kclvm_hcl_encode(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
+// api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_hcl_encode(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
+
 // api-spec:       kclvm_runtime_catch
 // api-spec(c):    kclvm_value_ref_t* kclvm_runtime_catch(kclvm_context_t* ctx, kclvm_value_ref_t* args, kclvm_value_ref_t* kwargs);
 // api-spec(llvm): declare %kclvm_value_ref_t* @kclvm_runtime_catch(%kclvm_context_t* %ctx, %kclvm_value_ref_t* %args, %kclvm_value_ref_t* %kwargs);
-
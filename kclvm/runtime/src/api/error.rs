@@ -13,4 +13,6 @@ pub enum RuntimeErrorType {
     Deprecated = 8,
     DeprecatedWarning = 9,
     SchemaCheckFailure = 10,
+    RecursionError = 11,
+    ResourceLimitExceeded = 12,
 }
@@ -1,6 +1,8 @@
 //! Copyright The KCL Authors. All rights reserved.
 
-use crate::{new_mut_ptr, val_plan::PlanOptions};
+use crate::{
+    new_mut_ptr, val_plan::PlanOptions, CapabilityPolicy, EvalInputs, ResourceLimits, ResourceUsage,
+};
 use generational_arena::Index;
 use kclvm_primitives::{IndexMap, IndexSet};
 use serde::{Deserialize, Serialize};
@@ -225,7 +227,7 @@ pub struct ListValue {
     pub values: Vec<ValueRef>,
 }
 
-#[derive(PartialEq, Eq, Clone, Default, Debug)]
+#[derive(Eq, Clone, Default, Debug)]
 pub struct DictValue {
     pub values: IndexMap<String, ValueRef>,
     pub ops: IndexMap<String, ConfigEntryOperationKind>,
@@ -234,6 +236,30 @@ pub struct DictValue {
     pub attr_map: IndexMap<String, String>,
     /// The runtime dict to schema reflect type string.
     pub potential_schema: Option<String>,
+    /// The source positions where the keys are set, which are only recorded
+    /// when the provenance mode is enabled.
+    pub provenance: IndexMap<String, Provenance>,
+}
+
+/// The provenance is not a part of the dict value, thus it is ignored in the comparison.
+impl PartialEq for DictValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+            && self.ops == other.ops
+            && self.insert_indexs == other.insert_indexs
+            && self.attr_map == other.attr_map
+            && self.potential_schema == other.potential_schema
+    }
+}
+
+/// Provenance denotes the source position and the operator where a config key is set.
+#[derive(PartialEq, Eq, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct Provenance {
+    pub filename: String,
+    pub line: u64,
+    pub column: u64,
+    /// The operator symbol e.g., `:`, `=` and `+=`.
+    pub op: String,
 }
 
 #[derive(PartialEq, Clone, Default, Debug)]
@@ -312,6 +338,14 @@ pub struct ContextConfig {
     pub debug_mode: bool,
     pub strict_range_check: bool,
     pub disable_schema_check: bool,
+    /// The capability policy to restrict the program side effects.
+    pub capabilities: CapabilityPolicy,
+    /// The resource limits of the evaluation.
+    pub limits: ResourceLimits,
+    /// Whether to record the source positions where the config keys are set.
+    pub provenance: bool,
+    /// The hermetic mode config to make the output reproducible.
+    pub hermetic: HermeticConfig,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -353,6 +387,9 @@ pub struct Context {
     pub instances: IndexMap<String, IndexMap<String, Vec<ValueRef>>>,
     /// All schema types
     pub all_schemas: HashMap<String, SchemaType>,
+    /// The parent schema and mixin runtime types of all schema types, the key
+    /// of the map is the schema runtime type.
+    pub schema_bases: HashMap<String, Vec<String>>,
     /// Import graph
     pub import_names: IndexMap<String, IndexMap<String, String>>,
     /// A buffer to store plugin or hooks function calling results.
@@ -371,6 +408,13 @@ pub struct Context {
     pub plan_opts: PlanOptions,
     /// Builtin plugin functions, the key of the map is the form <module_name>.<module_func> e.g., `hello.say_hello`
     pub plugin_functions: IndexMap<String, PluginFunction>,
+    /// Resource usage checked with the resource limits in the config.
+    pub resource_usage: ResourceUsage,
+    /// External inputs read by the evaluation to validate the cached result.
+    pub inputs: EvalInputs,
+    /// The random generator state of the hermetic mode, which is initialized
+    /// with the random seed.
+    pub random_state: Option<u64>,
 }
 
 impl UnwindSafe for Context {}
@@ -442,7 +486,7 @@ pub enum Kind {
     Func = 18,
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Hash, Default)]
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Default, Serialize, Deserialize)]
 pub enum ConfigEntryOperationKind {
     #[default]
     Union = 0,
@@ -461,4 +505,13 @@ impl ConfigEntryOperationKind {
             _ => panic!("Invalid AttrOpKind integer {v}, expected 0, 1 or 2"),
         }
     }
+
+    /// Get the operator symbol e.g., `:` for the union operation.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ConfigEntryOperationKind::Union => ":",
+            ConfigEntryOperationKind::Override => "=",
+            ConfigEntryOperationKind::Insert => "+=",
+        }
+    }
 }
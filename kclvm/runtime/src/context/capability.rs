@@ -0,0 +1,184 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// CapabilityPolicy restricts the side effects of the KCL program such as file
+/// system access, environment variable reading and plugin calling. The default
+/// policy allows all the operations.
+#[derive(PartialEq, Eq, Clone, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CapabilityPolicy {
+    /// Deny all the file system write operations e.g., `file.write`, `file.delete`
+    /// and `yaml.dump_to_file`.
+    pub read_only: bool,
+    /// The directories in which the file system operations are allowed. All the
+    /// directories are allowed when it is empty.
+    pub allowed_dirs: Vec<String>,
+    /// Deny reading the environment variables e.g., `file.read_env`.
+    pub deny_env: bool,
+    /// Deny calling the plugin functions.
+    pub deny_plugins: bool,
+}
+
+impl CapabilityPolicy {
+    /// A sandbox policy which is read-only, denies environment variables and plugins,
+    /// and only allows the file system access in `allowed_dirs`.
+    pub fn sandbox(allowed_dirs: &[String]) -> Self {
+        Self {
+            read_only: true,
+            allowed_dirs: allowed_dirs.to_vec(),
+            deny_env: true,
+            deny_plugins: true,
+        }
+    }
+
+    /// Whether the policy allows all the operations.
+    #[inline]
+    pub fn is_unrestricted(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn is_allowed_path(&self, path: &str) -> bool {
+        if self.allowed_dirs.is_empty() {
+            return true;
+        }
+        let path = normalize_path(Path::new(path));
+        self.allowed_dirs
+            .iter()
+            .any(|dir| path.starts_with(normalize_path(Path::new(dir))))
+    }
+}
+
+impl crate::Context {
+    /// Check whether the capability policy allows reading the file path,
+    /// raise a runtime error if not.
+    pub fn check_file_read(&self, path: &str) {
+        if !self.cfg.capabilities.is_allowed_path(path) {
+            panic!(
+                "permission denied: reading '{}' is not allowed, the path is outside the allowed directories",
+                path
+            );
+        }
+    }
+
+    /// Check whether the capability policy allows writing the file path,
+    /// raise a runtime error if not.
+    pub fn check_file_write(&self, path: &str) {
+        if self.cfg.capabilities.read_only {
+            panic!(
+                "permission denied: writing '{}' is not allowed in the read-only mode",
+                path
+            );
+        }
+        if !self.cfg.capabilities.is_allowed_path(path) {
+            panic!(
+                "permission denied: writing '{}' is not allowed, the path is outside the allowed directories",
+                path
+            );
+        }
+    }
+
+    /// Check whether the capability policy allows reading the environment
+    /// variable, raise a runtime error if not.
+    pub fn check_env_read(&self, key: &str) {
+        if self.cfg.capabilities.deny_env {
+            panic!(
+                "permission denied: reading the environment variable '{}' is not allowed",
+                key
+            );
+        }
+    }
+
+    /// Check whether the capability policy allows calling the plugin function,
+    /// raise a runtime error if not.
+    pub fn check_plugin_call(&self, method: &str) {
+        if self.cfg.capabilities.deny_plugins {
+            panic!(
+                "permission denied: calling the plugin function '{}' is not allowed",
+                method
+            );
+        }
+    }
+}
+
+/// Get the absolute path without `.` and `..` components, and resolve the symbolic
+/// links of the longest existing ancestor, so that a path can not escape the allowed
+/// directories through `..` or symbolic links even if it does not exist yet.
+fn normalize_path(path: &Path) -> PathBuf {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    let mut existing = normalized.as_path();
+    let mut rest = vec![];
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return rest
+                .iter()
+                .rev()
+                .fold(canonical, |path: PathBuf, name| path.join(name));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return normalized,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_capability {
+    use super::*;
+    use crate::{assert_panic, Context};
+
+    #[test]
+    fn test_capability_policy() {
+        let dir = std::env::temp_dir().join("kcl_capability_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = dir.display().to_string();
+        let mut ctx = Context::new();
+        ctx.check_file_write("/any/path");
+        ctx.check_env_read("HOME");
+        ctx.cfg.capabilities = CapabilityPolicy::sandbox(&[dir.clone()]);
+        ctx.check_file_read(&format!("{dir}/a.k"));
+        ctx.check_file_read(&format!("{dir}/sub/../a.k"));
+
+        let prev_hook = std::panic::take_hook();
+        // Disable print panic info in stderr.
+        std::panic::set_hook(Box::new(|_| {}));
+        let outside = format!("{dir}/../a.k");
+        assert_panic(
+            &format!("permission denied: reading '{outside}' is not allowed, the path is outside the allowed directories"),
+            || ctx.check_file_read(&outside),
+        );
+        let inside = format!("{dir}/a.k");
+        assert_panic(
+            &format!("permission denied: writing '{inside}' is not allowed in the read-only mode"),
+            || ctx.check_file_write(&inside),
+        );
+        assert_panic(
+            "permission denied: reading the environment variable 'HOME' is not allowed",
+            || ctx.check_env_read("HOME"),
+        );
+        assert_panic(
+            "permission denied: calling the plugin function 'hello.say_hello' is not allowed",
+            || ctx.check_plugin_call("hello.say_hello"),
+        );
+        std::panic::set_hook(prev_hook);
+    }
+}
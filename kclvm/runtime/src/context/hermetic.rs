@@ -0,0 +1,144 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset, Local};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// HermeticConfig makes the output of the KCL program reproducible. In the
+/// hermetic mode, the nondeterministic built-in functions use the injected
+/// fixed values or raise a runtime error when the values are not injected,
+/// the `file.glob` results are sorted and the plugin calls are denied.
+#[derive(PartialEq, Eq, Clone, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HermeticConfig {
+    /// Enable the hermetic mode.
+    pub enabled: bool,
+    /// The fixed clock in seconds since the Unix epoch used by the `datetime`
+    /// functions, the time is in the UTC timezone.
+    pub clock: Option<i64>,
+    /// The random seed used by `crypto.uuid`.
+    pub random_seed: Option<u64>,
+    /// The environment variable snapshot used by `file.read_env`.
+    pub env: BTreeMap<String, String>,
+}
+
+impl crate::Context {
+    /// Get the current time, which is the fixed clock in the hermetic mode.
+    pub fn now(&self) -> DateTime<FixedOffset> {
+        let hermetic = &self.cfg.hermetic;
+        if !hermetic.enabled {
+            return Local::now().fixed_offset();
+        }
+        match hermetic.clock {
+            Some(clock) => DateTime::from_timestamp(clock, 0)
+                .unwrap_or_else(|| panic!("invalid hermetic clock {}", clock))
+                .fixed_offset(),
+            None => panic!(
+                "reading the current time is not allowed in the hermetic mode without a fixed clock"
+            ),
+        }
+    }
+
+    /// Generate a random UUID, which is generated from the random seed in the
+    /// hermetic mode.
+    pub fn new_uuid(&mut self) -> Uuid {
+        let hermetic = &self.cfg.hermetic;
+        if !hermetic.enabled {
+            return Uuid::new_v4();
+        }
+        let seed = hermetic.random_seed.unwrap_or_else(|| {
+            panic!("generating a random uuid is not allowed in the hermetic mode without a random seed")
+        });
+        let state = self.random_state.get_or_insert(seed);
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&splitmix64(state).to_le_bytes());
+        bytes[8..].copy_from_slice(&splitmix64(state).to_le_bytes());
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+
+    /// Read the environment variable, which is read from the environment
+    /// variable snapshot in the hermetic mode.
+    pub fn read_env(&self, key: &str) -> Option<String> {
+        let hermetic = &self.cfg.hermetic;
+        if hermetic.enabled {
+            hermetic.env.get(key).cloned()
+        } else {
+            std::env::var(key).ok()
+        }
+    }
+
+    /// Check whether the hermetic mode allows calling the plugin function,
+    /// raise a runtime error if not.
+    pub fn check_hermetic_plugin_call(&self, method: &str) {
+        if self.cfg.hermetic.enabled {
+            panic!(
+                "calling the plugin function '{}' is not allowed in the hermetic mode",
+                method
+            );
+        }
+    }
+}
+
+/// The SplitMix64 generator, which is enough for the reproducible random
+/// values and has no external dependency.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod test_hermetic {
+    use super::*;
+    use crate::{assert_panic, Context};
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn test_hermetic_config() {
+        let mut ctx = Context::new();
+        ctx.cfg.hermetic = HermeticConfig {
+            enabled: true,
+            clock: Some(0),
+            random_seed: Some(1),
+            env: BTreeMap::from([("KEY".to_string(), "value".to_string())]),
+        };
+        assert_eq!(ctx.now().timestamp(), 0);
+        assert_eq!(ctx.now().offset().local_minus_utc(), 0);
+        let uuid = ctx.new_uuid();
+        assert_ne!(ctx.new_uuid(), uuid);
+        // The same seed generates the same uuid sequence.
+        ctx.random_state = None;
+        assert_eq!(ctx.new_uuid(), uuid);
+        assert_eq!(ctx.read_env("KEY"), Some("value".to_string()));
+        assert_eq!(ctx.read_env("PATH"), None);
+
+        let prev_hook = std::panic::take_hook();
+        // Disable print panic info in stderr.
+        std::panic::set_hook(Box::new(|_| {}));
+        assert_panic(
+            "calling the plugin function 'hello.say_hello' is not allowed in the hermetic mode",
+            || ctx.check_hermetic_plugin_call("hello.say_hello"),
+        );
+        ctx.cfg.hermetic = HermeticConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert_panic(
+            "reading the current time is not allowed in the hermetic mode without a fixed clock",
+            || {
+                ctx.now();
+            },
+        );
+        assert_panic(
+            "generating a random uuid is not allowed in the hermetic mode without a random seed",
+            AssertUnwindSafe(|| {
+                ctx.new_uuid();
+            }),
+        );
+        std::panic::set_hook(prev_hook);
+    }
+}
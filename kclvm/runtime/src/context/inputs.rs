@@ -0,0 +1,46 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use kclvm_primitives::IndexSet;
+use serde::{Deserialize, Serialize};
+
+/// EvalInputs records the external inputs read by the evaluation besides the
+/// program source files and arguments, which are used to check whether a cached
+/// evaluation result is still valid.
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct EvalInputs {
+    /// The file paths read or checked by the program e.g., `file.read` and `file.exists`.
+    pub files: IndexSet<String>,
+    /// The glob patterns matched by the program e.g., `file.glob`.
+    pub globs: IndexSet<String>,
+    /// The environment variables read by the program e.g., `file.read_env`.
+    pub envs: IndexSet<String>,
+    /// Whether the evaluation has side effects or non-deterministic results e.g.,
+    /// file writes, plugin calls and the current time, whose result can not be cached.
+    pub uncacheable: bool,
+}
+
+impl crate::Context {
+    /// Record the file path read by the program.
+    #[inline]
+    pub fn record_file_input(&mut self, path: &str) {
+        self.inputs.files.insert(path.to_string());
+    }
+
+    /// Record the glob pattern matched by the program.
+    #[inline]
+    pub fn record_glob_input(&mut self, pattern: &str) {
+        self.inputs.globs.insert(pattern.to_string());
+    }
+
+    /// Record the environment variable read by the program.
+    #[inline]
+    pub fn record_env_input(&mut self, key: &str) {
+        self.inputs.envs.insert(key.to_string());
+    }
+
+    /// Mark the evaluation result as uncacheable.
+    #[inline]
+    pub fn mark_uncacheable(&mut self) {
+        self.inputs.uncacheable = true;
+    }
+}
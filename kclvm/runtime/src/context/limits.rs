@@ -0,0 +1,166 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::RuntimeErrorType;
+
+/// The call depth limit used when [ResourceLimits::max_call_depth] is zero, which
+/// raises a recursion error instead of overflowing the native stack.
+pub const DEFAULT_MAX_CALL_DEPTH: u64 = 1000;
+
+/// ResourceLimits denotes the resource limits of the KCL program evaluation.
+/// A limit is disabled when it is zero, except the call depth limit which uses
+/// [DEFAULT_MAX_CALL_DEPTH].
+#[derive(PartialEq, Eq, Clone, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceLimits {
+    /// The maximum wall time of the evaluation in milliseconds.
+    pub timeout_ms: u64,
+    /// The maximum evaluation steps, a step is a function call or a loop iteration.
+    pub max_steps: u64,
+    /// The maximum call depth of lambdas, schemas and rules, default is
+    /// [DEFAULT_MAX_CALL_DEPTH].
+    pub max_call_depth: u64,
+    /// The maximum number of values allocated by comprehensions and `range()`.
+    pub max_values: u64,
+}
+
+/// ResourceUsage records the resource usage of the evaluation which is checked
+/// with the [ResourceLimits] in the context config.
+#[derive(Clone, Default, Debug)]
+pub struct ResourceUsage {
+    /// The start time of the evaluation, set at the first evaluation step.
+    pub start: Option<Instant>,
+    pub steps: u64,
+    pub call_depth: u64,
+    pub values: u64,
+}
+
+impl crate::Context {
+    /// Record an evaluation step and check the step and time limits.
+    pub fn check_step(&mut self) {
+        self.resource_usage.steps += 1;
+        let max_steps = self.cfg.limits.max_steps;
+        if max_steps > 0 && self.resource_usage.steps > max_steps {
+            self.set_err_type(&RuntimeErrorType::ResourceLimitExceeded);
+            panic!("maximum evaluation steps exceeded, the step limit is {max_steps}");
+        }
+        let timeout_ms = self.cfg.limits.timeout_ms;
+        if timeout_ms > 0 {
+            let start = *self.resource_usage.start.get_or_insert_with(Instant::now);
+            if start.elapsed().as_millis() > timeout_ms as u128 {
+                self.set_err_type(&RuntimeErrorType::ResourceLimitExceeded);
+                panic!("evaluation timeout, the time limit is {timeout_ms}ms");
+            }
+        }
+    }
+
+    /// Enter a function call and check the call depth limit. The call depth is
+    /// not increased when the limit is exceeded.
+    pub fn enter_call(&mut self) {
+        self.check_step();
+        let call_depth = self.resource_usage.call_depth + 1;
+        let max_call_depth = match self.cfg.limits.max_call_depth {
+            0 => DEFAULT_MAX_CALL_DEPTH,
+            max_call_depth => max_call_depth,
+        };
+        if call_depth > max_call_depth {
+            self.set_err_type(&RuntimeErrorType::RecursionError);
+            panic!("maximum recursion depth exceeded, the call depth limit is {max_call_depth}");
+        }
+        self.resource_usage.call_depth = call_depth;
+    }
+
+    /// Leave a function call.
+    #[inline]
+    pub fn exit_call(&mut self) {
+        self.resource_usage.call_depth = self.resource_usage.call_depth.saturating_sub(1);
+    }
+
+    /// Get the current call depth, which is restored with [Context::unwind_calls]
+    /// after catching a runtime panic.
+    #[inline]
+    pub fn call_depth(&self) -> u64 {
+        self.resource_usage.call_depth
+    }
+
+    /// Restore the call depth left by the calls unwound by a caught runtime panic.
+    #[inline]
+    pub fn unwind_calls(&mut self, depth: u64) {
+        self.resource_usage.call_depth = depth;
+    }
+
+    /// Record `n` allocated values and check the value limit.
+    pub fn alloc_values(&mut self, n: u64) {
+        self.resource_usage.values = self.resource_usage.values.saturating_add(n);
+        let max_values = self.cfg.limits.max_values;
+        if max_values > 0 && self.resource_usage.values > max_values {
+            self.set_err_type(&RuntimeErrorType::ResourceLimitExceeded);
+            panic!("maximum allocated values exceeded, the value limit is {max_values}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_limits {
+    use super::*;
+    use crate::{assert_panic, Context};
+
+    #[test]
+    fn test_resource_limits() {
+        let prev_hook = std::panic::take_hook();
+        // Disable print panic info in stderr.
+        std::panic::set_hook(Box::new(|_| {}));
+        let mut ctx = Context::new();
+        ctx.cfg.limits = ResourceLimits {
+            max_steps: 10,
+            max_call_depth: 2,
+            max_values: 10,
+            ..Default::default()
+        };
+        ctx.enter_call();
+        ctx.exit_call();
+        ctx.enter_call();
+        ctx.alloc_values(10);
+        assert_panic("the call depth limit is 2", move || {
+            let mut ctx = ctx;
+            ctx.enter_call();
+            ctx.enter_call();
+        });
+        let mut ctx = Context::new();
+        for _ in 0..DEFAULT_MAX_CALL_DEPTH {
+            ctx.enter_call();
+        }
+        assert_panic("the call depth limit is 1000", move || {
+            let mut ctx = ctx;
+            ctx.enter_call();
+        });
+        let mut ctx = Context::new();
+        let depth = ctx.call_depth();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            ctx.enter_call();
+            ctx.enter_call();
+            panic!("runtime error");
+        }));
+        assert!(result.is_err());
+        assert_eq!(ctx.call_depth(), 2);
+        ctx.unwind_calls(depth);
+        assert_eq!(ctx.call_depth(), 0);
+        let mut ctx = Context::new();
+        ctx.cfg.limits.max_steps = 1;
+        ctx.check_step();
+        assert_panic("the step limit is 1", move || {
+            let mut ctx = ctx;
+            ctx.check_step();
+        });
+        let mut ctx = Context::new();
+        ctx.cfg.limits.max_values = 1;
+        assert_panic("the value limit is 1", move || {
+            let mut ctx = ctx;
+            ctx.alloc_values(2);
+        });
+        std::panic::set_hook(prev_hook);
+    }
+}
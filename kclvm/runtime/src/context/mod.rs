@@ -2,6 +2,14 @@
 
 pub mod api;
 pub use api::*;
+pub mod capability;
+pub use capability::*;
+pub mod hermetic;
+pub use hermetic::*;
+pub mod inputs;
+pub use inputs::*;
+pub mod limits;
+pub use limits::*;
 use std::fmt;
 
 use crate::{kclvm_value_delete, kclvm_value_ref_t, BacktraceFrame, PanicInfo, RuntimePanicRecord};
@@ -166,13 +174,7 @@ impl crate::Context {
 
         self.panic_info.message = record.message.clone();
         if self.cfg.debug_mode {
-            self.panic_info.backtrace = self.backtrace.clone();
-            self.panic_info.backtrace.push(BacktraceFrame {
-                file: self.panic_info.kcl_file.clone(),
-                func: self.panic_info.kcl_func.clone(),
-                col: self.panic_info.kcl_col,
-                line: self.panic_info.kcl_line,
-            });
+            self.panic_info.backtrace = self.panic_backtrace();
         }
 
         self.panic_info.rust_file = record.rust_file.clone();
@@ -180,6 +182,14 @@ impl crate::Context {
         self.panic_info.rust_col = record.rust_col;
     }
 
+    /// Get the KCL call stack of the panic from the outermost frame to the
+    /// innermost frame where the panic occurs.
+    pub fn panic_backtrace(&self) -> Vec<BacktraceFrame> {
+        let mut backtrace = self.backtrace.clone();
+        backtrace.push(BacktraceFrame::from_panic_info(&self.panic_info));
+        backtrace
+    }
+
     pub fn gc(&self) {
         unsafe {
             for o in &self.objects {
@@ -12,7 +12,6 @@ use crate::encoding::encode_text;
 use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
 
 use crate::*;
-use uuid::Uuid;
 
 #[allow(non_camel_case_types)]
 type kclvm_value_ref_t = ValueRef;
@@ -227,7 +226,9 @@ pub extern "C-unwind" fn kclvm_crypto_uuid(
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
     let ctx = mut_ptr_as_ref(ctx);
-    return ValueRef::str(Uuid::new_v4().to_string().as_ref()).into_raw(ctx);
+    ctx.mark_uncacheable();
+    let uuid = ctx.new_uuid();
+    return ValueRef::str(uuid.to_string().as_ref()).into_raw(ctx);
 }
 
 #[no_mangle]
@@ -242,6 +243,8 @@ pub extern "C-unwind" fn kclvm_crypto_filesha256(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_file_read(&filepath);
+        ctx.record_file_input(&filepath);
         // Open the file
         let mut file = File::open(&filepath)
             .unwrap_or_else(|e| panic!("failed to access file '{}': {}", filepath, e));
@@ -282,6 +285,8 @@ pub extern "C-unwind" fn kclvm_crypto_filesha512(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_file_read(&filepath);
+        ctx.record_file_input(&filepath);
         let mut file = File::open(&filepath)
             .unwrap_or_else(|e| panic!("failed to access file '{}': {}", filepath, e));
 
@@ -320,6 +325,8 @@ pub extern "C-unwind" fn kclvm_crypto_fileblake3(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_file_read(&filepath);
+        ctx.record_file_input(&filepath);
         let mut file = File::open(&filepath)
             .unwrap_or_else(|e| panic!("failed to access file '{}': {}", filepath, e));
 
@@ -0,0 +1,377 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use kclvm_primitives::{DefaultHashBuilder, IndexMap};
+
+use crate::*;
+
+// data, header=None, delimiter=","
+
+/// Serialize a list of dicts or a list of lists into a CSV formatted string.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_csv_encode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(data) = get_call_arg(args, kwargs, 0, Some("data")) {
+        let header = get_call_arg(args, kwargs, 1, Some("header"))
+            .filter(|header| !header.is_none_or_undefined())
+            .map(|header| {
+                header
+                    .as_list_ref()
+                    .values
+                    .iter()
+                    .map(|v| v.as_str())
+                    .collect::<Vec<String>>()
+            });
+        let delimiter = args_to_delimiter(args, kwargs, 2);
+        let json = data.build_json(&Default::default());
+        return match encode_csv(&json, header, delimiter) {
+            Ok(s) => ValueRef::str(&s).into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        };
+    }
+    panic!("encode() missing 1 required positional argument: 'data'")
+}
+
+// value, header=True, delimiter=","
+
+/// Deserialize a CSV formatted string. When `header` is true, the first record
+/// is used as the keys and each following record is returned as a dict,
+/// otherwise each record is returned as a list of strings.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_csv_decode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(value) = get_call_arg_str(args, kwargs, 0, Some("value")) {
+        let header = get_call_arg_bool(args, kwargs, 1, Some("header")).unwrap_or(true);
+        let delimiter = args_to_delimiter(args, kwargs, 2);
+        return match decode_csv(&value, header, delimiter) {
+            Ok(json) => ValueRef::parse_json(ctx, &json).into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        };
+    }
+    panic!("decode() missing 1 required positional argument: 'value'")
+}
+
+fn args_to_delimiter(args: &ValueRef, kwargs: &ValueRef, index: usize) -> char {
+    match get_call_arg_str(args, kwargs, index, Some("delimiter")) {
+        Some(delimiter) => {
+            let mut chars = delimiter.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c != '"' && c != '\r' && c != '\n' => c,
+                _ => panic!("csv delimiter must be a single character, got '{delimiter}'"),
+            }
+        }
+        None => ',',
+    }
+}
+
+fn encode_csv(
+    data: &JsonValue,
+    header: Option<Vec<String>>,
+    delimiter: char,
+) -> Result<String, String> {
+    let rows = match data {
+        JsonValue::Array(rows) => rows,
+        _ => return Err("csv data must be a list of dicts or a list of lists".to_string()),
+    };
+    let is_dict_rows = rows.iter().any(|row| matches!(row, JsonValue::Object(_)));
+    // Collect the keys of all dict rows by their first appearance order.
+    let header = match header {
+        Some(header) => Some(header),
+        None if is_dict_rows => {
+            let mut keys: Vec<String> = vec![];
+            for row in rows {
+                if let JsonValue::Object(row) = row {
+                    for key in row.keys() {
+                        if !keys.contains(key) {
+                            keys.push(key.clone());
+                        }
+                    }
+                }
+            }
+            Some(keys)
+        }
+        None => None,
+    };
+    let mut result = String::new();
+    if let Some(header) = &header {
+        let fields: Vec<String> = header.iter().map(|k| csv_field(k, delimiter)).collect();
+        write_record(&mut result, &fields, delimiter);
+    }
+    for (i, row) in rows.iter().enumerate() {
+        let fields: Vec<String> = match row {
+            JsonValue::Object(row) => match &header {
+                Some(header) => header
+                    .iter()
+                    .map(|key| match row.get(key) {
+                        Some(value) => csv_value(value, delimiter),
+                        None => String::new(),
+                    })
+                    .collect(),
+                None => unreachable!(),
+            },
+            JsonValue::Array(row) => row.iter().map(|v| csv_value(v, delimiter)).collect(),
+            _ => {
+                return Err(format!(
+                    "csv row {} must be a dict or a list, got '{}'",
+                    i,
+                    json_type_str(row)
+                ))
+            }
+        };
+        write_record(&mut result, &fields, delimiter);
+    }
+    Ok(result)
+}
+
+fn write_record(result: &mut String, fields: &[String], delimiter: char) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            result.push(delimiter);
+        }
+        result.push_str(field);
+    }
+    result.push('\n');
+}
+
+fn csv_value(value: &JsonValue, delimiter: char) -> String {
+    match value {
+        JsonValue::Null => String::new(),
+        JsonValue::Bool(v) => v.to_string(),
+        JsonValue::Number(v) => v.to_string(),
+        JsonValue::String(v) => csv_field(v, delimiter),
+        // Nested values are kept as JSON strings.
+        JsonValue::Array(_) | JsonValue::Object(_) => {
+            csv_field(&serde_json::to_string(value).unwrap(), delimiter)
+        }
+    }
+}
+
+/// Quote the field when it contains the delimiter, quotes or line breaks.
+fn csv_field(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains(['"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn decode_csv(value: &str, header: bool, delimiter: char) -> Result<JsonValue, String> {
+    let records = parse_records(value, delimiter)?;
+    if !header {
+        return Ok(JsonValue::Array(
+            records
+                .into_iter()
+                .map(|record| JsonValue::Array(record.into_iter().map(JsonValue::String).collect()))
+                .collect(),
+        ));
+    }
+    let mut records = records.into_iter();
+    let keys = match records.next() {
+        Some(keys) => keys,
+        None => return Ok(JsonValue::Array(vec![])),
+    };
+    let mut rows = vec![];
+    for (i, record) in records.enumerate() {
+        if record.len() > keys.len() {
+            return Err(format!(
+                "csv record {} has {} fields, but the header has {} fields",
+                i + 1,
+                record.len(),
+                keys.len()
+            ));
+        }
+        let mut row = IndexMap::with_hasher(DefaultHashBuilder::default());
+        let mut fields = record.into_iter();
+        for key in &keys {
+            let value = match fields.next() {
+                Some(field) => JsonValue::String(field),
+                None => JsonValue::Null,
+            };
+            row.insert(key.clone(), value);
+        }
+        rows.push(JsonValue::Object(row));
+    }
+    Ok(JsonValue::Array(rows))
+}
+
+/// Parse CSV records following RFC 4180. Blank lines are skipped.
+fn parse_records(value: &str, delimiter: char) -> Result<Vec<Vec<String>>, String> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == delimiter {
+            record.push(std::mem::take(&mut field));
+            quoted = false;
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            if !record.is_empty() || !field.is_empty() || quoted {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            quoted = false;
+        } else if c == '"' && field.is_empty() && !quoted {
+            in_quotes = true;
+            quoted = true;
+        } else if quoted {
+            return Err(format!(
+                "csv value has an unexpected character '{c}' after the quoted field \"{field}\""
+            ));
+        } else {
+            field.push(c);
+        }
+    }
+    if in_quotes {
+        return Err("csv value has an unterminated quoted field".to_string());
+    }
+    if !record.is_empty() || !field.is_empty() || quoted {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn json_type_str(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "None",
+        JsonValue::Bool(_) => "bool",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "str",
+        JsonValue::Array(_) => "list",
+        JsonValue::Object(_) => "dict",
+    }
+}
+
+#[cfg(test)]
+mod test_csv {
+    use super::*;
+
+    #[test]
+    fn test_parse_records() {
+        let records = parse_records("a,b\n\"x,1\",\"y \"\"z\"\"\"\r\n\n3,\n", ',').unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["a".to_string(), "b".to_string()],
+                vec!["x,1".to_string(), "y \"z\"".to_string()],
+                vec!["3".to_string(), "".to_string()],
+            ]
+        );
+        assert!(parse_records("\"a", ',').is_err());
+    }
+
+    #[test]
+    fn test_parse_records_edge_cases() {
+        let records = |value: &str, delimiter: char| parse_records(value, delimiter).unwrap();
+        // Quotes inside an unquoted field are kept.
+        assert_eq!(records("a\"b,c", ','), vec![vec!["a\"b", "c"]]);
+        // Empty fields and quoted empty fields.
+        assert_eq!(records(",\n\"\"\n", ','), vec![vec!["", ""], vec![""]]);
+        // Line breaks and delimiters inside the quoted field.
+        assert_eq!(
+            records("\"a\r\nb\",\"c,d\"", ','),
+            vec![vec!["a\r\nb", "c,d"]]
+        );
+        // Unicode fields and delimiters.
+        assert_eq!(
+            records("名前；ポート\n\"値；1\"；🚀\n", '；'),
+            vec![vec!["名前", "ポート"], vec!["値；1", "🚀"]]
+        );
+        assert_eq!(
+            parse_records("\"a\"b,c", ',').unwrap_err(),
+            "csv value has an unexpected character 'b' after the quoted field \"a\""
+        );
+        assert!(parse_records("a,\"b\n", ',').is_err());
+    }
+
+    #[test]
+    fn test_encode_csv_edge_cases() {
+        let encode = |json: &str, header: Option<Vec<String>>| {
+            let data = ValueRef::from_json(&mut Context::new(), json).unwrap();
+            encode_csv(&data.build_json(&Default::default()), header, ',')
+        };
+        assert_eq!(
+            encode(
+                r#"[["a\"b", " c ", "d\ne", "名前"], [null, true, 1.5, [1, 2]], [{"k": "v"}]]"#,
+                None
+            )
+            .unwrap(),
+            "\"a\"\"b\", c ,\"d\ne\",名前\n,true,1.5,\"[1,2]\"\n\"{\"\"k\"\":\"\"v\"\"}\"\n"
+        );
+        // The keys of all dict rows are the header and the missing values are empty.
+        assert_eq!(
+            encode(r#"[{"a": 1}, {"b": "x,y"}]"#, None).unwrap(),
+            "a,b\n1,\n,\"x,y\"\n"
+        );
+        assert_eq!(
+            encode(r#"[{"a": 1, "b": 2}]"#, Some(vec!["b".to_string()])).unwrap(),
+            "b\n2\n"
+        );
+        assert_eq!(encode("[]", None).unwrap(), "");
+        assert!(encode(r#"{"a": 1}"#, None).is_err());
+        assert_eq!(
+            encode("[1]", None).unwrap_err(),
+            "csv row 0 must be a dict or a list, got 'number'"
+        );
+    }
+
+    #[test]
+    fn test_decode_csv_edge_cases() {
+        let decode = |value: &str| {
+            let json = decode_csv(value, true, ',')?;
+            Ok::<String, String>(ValueRef::parse_json(&mut Context::new(), &json).to_json_string())
+        };
+        assert_eq!(decode("").unwrap(), "[]");
+        assert_eq!(decode("a,b\n").unwrap(), "[]");
+        // The missing fields are None.
+        assert_eq!(decode("a,b\n1\n").unwrap(), r#"[{"a": "1", "b": null}]"#);
+        assert_eq!(
+            decode("a,b\n1,2,3\n").unwrap_err(),
+            "csv record 1 has 3 fields, but the header has 2 fields"
+        );
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let mut ctx = Context::new();
+        let value = "name;port\nweb;80\n\"a;b\";\n";
+        let json = decode_csv(value, true, ';').unwrap();
+        let data = ValueRef::parse_json(&mut ctx, &json);
+        assert_eq!(
+            data.to_json_string(),
+            r#"[{"name": "web", "port": "80"}, {"name": "a;b", "port": ""}]"#
+        );
+        assert_eq!(encode_csv(&json, None, ';').unwrap(), value);
+    }
+}
@@ -2,7 +2,7 @@
 
 extern crate chrono;
 
-use chrono::{prelude::Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
 use crate::*;
 
@@ -15,8 +15,9 @@ pub extern "C-unwind" fn kclvm_datetime_today(
     _args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let s = Local::now();
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.mark_uncacheable();
+    let s = ctx.now();
     ValueRef::str(&(s.format("%Y-%m-%d %H:%M:%S").to_string() + "." + &s.timestamp().to_string()))
         .into_raw(ctx)
 }
@@ -31,8 +32,9 @@ pub extern "C-unwind" fn kclvm_datetime_now(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let s = Local::now();
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.mark_uncacheable();
+    let s = ctx.now();
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let format = get_call_arg_str(args, kwargs, 0, Some("format"))
@@ -50,7 +52,8 @@ pub extern "C-unwind" fn kclvm_datetime_ticks(
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
     let ctx = mut_ptr_as_ref(ctx);
-    let x = Local::now().timestamp();
+    ctx.mark_uncacheable();
+    let x = ctx.now().timestamp();
     ValueRef::float(x as f64).into_raw(ctx)
 }
 
@@ -63,8 +66,9 @@ pub extern "C-unwind" fn kclvm_datetime_date(
     _args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let s = Local::now();
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.mark_uncacheable();
+    let s = ctx.now();
     ValueRef::str(&s.format("%Y-%m-%d %H:%M:%S").to_string()).into_raw(ctx)
 }
 
@@ -19,6 +19,8 @@ pub extern "C-unwind" fn kclvm_file_read(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(x) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_file_read(&x);
+        ctx.record_file_input(&x);
         let contents = fs::read_to_string(&x)
             .unwrap_or_else(|e| panic!("failed to access the file '{}': {}", x, e));
 
@@ -42,15 +44,25 @@ pub extern "C-unwind" fn kclvm_file_glob(
 
     let pattern = get_call_arg_str(args, kwargs, 0, Some("pattern"))
         .expect("glob() takes exactly one argument (0 given)");
+    ctx.record_glob_input(&pattern);
 
     let mut matched_paths = vec![];
     for entry in glob(&pattern).unwrap_or_else(|e| panic!("Failed to read glob pattern: {}", e)) {
         match entry {
-            Ok(path) => matched_paths.push(path.display().to_string()),
+            Ok(path) => {
+                let path = path.display().to_string();
+                ctx.check_file_read(&path);
+                ctx.record_file_input(&path);
+                matched_paths.push(path);
+            }
             Err(e) => panic!("failed to access the file matching '{}': {}", pattern, e),
         }
     }
 
+    // Do not depend on the file system iteration order in the hermetic mode.
+    if ctx.cfg.hermetic.enabled {
+        matched_paths.sort();
+    }
     ValueRef::list_str(matched_paths.as_slice()).into_raw(ctx)
 }
 
@@ -106,6 +118,8 @@ pub extern "C-unwind" fn kclvm_file_exists(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_file_read(&path);
+        ctx.record_file_input(&path);
         let exist = Path::new(&path).exists();
         return ValueRef::bool(exist).into_raw(ctx);
     }
@@ -127,6 +141,8 @@ pub extern "C-unwind" fn kclvm_file_abs(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_file_read(&path);
+        ctx.record_file_input(&path);
         if let Ok(abs_path) = Path::new(&path).canonicalize() {
             return ValueRef::str(abs_path.to_str().unwrap()).into_raw(ctx);
         } else {
@@ -149,6 +165,8 @@ pub extern "C-unwind" fn kclvm_file_mkdir(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("directory")) {
+        ctx.check_file_write(&path);
+        ctx.mark_uncacheable();
         let exists = get_call_arg_bool(args, kwargs, 1, Some("exists")).unwrap_or_default();
         if let Err(e) = fs::create_dir_all(&path) {
             // Ignore the file exists error.
@@ -175,6 +193,8 @@ pub extern "C-unwind" fn kclvm_file_delete(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_file_write(&path);
+        ctx.mark_uncacheable();
         if let Err(e) = fs::remove_file(&path) {
             match e.kind() {
                 std::io::ErrorKind::NotFound => {
@@ -207,6 +227,10 @@ pub extern "C-unwind" fn kclvm_file_cp(
 
     if let Some(src_path) = get_call_arg_str(args, kwargs, 0, Some("src")) {
         if let Some(dest_path) = get_call_arg_str(args, kwargs, 1, Some("dest")) {
+            ctx.check_file_read(&src_path);
+            ctx.record_file_input(&src_path);
+            ctx.check_file_write(&dest_path);
+            ctx.mark_uncacheable();
             let src_path = Path::new(&src_path);
             let dest_path = Path::new(&dest_path);
             let result = if src_path.is_dir() {
@@ -244,6 +268,9 @@ pub extern "C-unwind" fn kclvm_file_mv(
 
     if let Some(src_path) = get_call_arg_str(args, kwargs, 0, Some("src")) {
         if let Some(dest_path) = get_call_arg_str(args, kwargs, 1, Some("dest")) {
+            ctx.check_file_write(&src_path);
+            ctx.check_file_write(&dest_path);
+            ctx.mark_uncacheable();
             if let Err(e) = fs::rename(&src_path, &dest_path) {
                 panic!("Failed to move '{}' to '{}': {}", src_path, dest_path, e);
             }
@@ -268,6 +295,8 @@ pub extern "C-unwind" fn kclvm_file_size(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_file_read(&path);
+        ctx.record_file_input(&path);
         let metadata = fs::metadata(&path);
         match metadata {
             Ok(metadata) => {
@@ -297,6 +326,8 @@ pub extern "C-unwind" fn kclvm_file_write(
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         if let Some(content) = get_call_arg_str(args, kwargs, 1, Some("content")) {
+            ctx.check_file_write(&path);
+            ctx.mark_uncacheable();
             match fs::File::create(&path) {
                 Ok(mut file) => {
                     if let Err(e) = file.write_all(content.as_bytes()) {
@@ -327,6 +358,8 @@ pub extern "C-unwind" fn kclvm_file_append(
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         if let Some(content) = get_call_arg_str(args, kwargs, 1, Some("content")) {
+            ctx.check_file_write(&path);
+            ctx.mark_uncacheable();
             // Open the file in append mode, creating it if it doesn't exist
             match fs::OpenOptions::new().append(true).create(true).open(&path) {
                 Ok(mut file) => {
@@ -359,9 +392,11 @@ pub extern "C-unwind" fn kclvm_file_read_env(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(key) = get_call_arg_str(args, kwargs, 0, Some("key")) {
-        match std::env::var(key) {
-            Ok(v) => ValueRef::str(&v).into_raw(ctx),
-            Err(_) => ValueRef::undefined().into_raw(ctx),
+        ctx.check_env_read(&key);
+        ctx.record_env_input(&key);
+        match ctx.read_env(&key) {
+            Some(v) => ValueRef::str(&v).into_raw(ctx),
+            None => ValueRef::undefined().into_raw(ctx),
         }
     } else {
         panic!("read_env() requires 'key' argument");
@@ -0,0 +1,253 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use crate::*;
+
+const INDENT: &str = "  ";
+
+// data, sort_keys=False, ignore_private=False, ignore_none=False
+
+/// Serialize a dict into HCL attributes e.g., a Terraform variable definitions
+/// (`.tfvars`) file.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_hcl_encode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(data) = get_call_arg(args, kwargs, 0, Some("data")) {
+        let json = data.build_json(&args_to_opts(args, kwargs, 1));
+        return match encode_hcl(&json) {
+            Ok(s) => ValueRef::str(&s).into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        };
+    }
+    panic!("encode() missing 1 required positional argument: 'data'")
+}
+
+fn args_to_opts(args: &ValueRef, kwargs: &ValueRef, index: usize) -> JsonEncodeOptions {
+    let mut opts = JsonEncodeOptions::default();
+    if let Some(sort_keys) = get_call_arg_bool(args, kwargs, index, Some("sort_keys")) {
+        opts.sort_keys = sort_keys;
+    }
+    if let Some(ignore_private) = get_call_arg_bool(args, kwargs, index + 1, Some("ignore_private"))
+    {
+        opts.ignore_private = ignore_private;
+    }
+    if let Some(ignore_none) = get_call_arg_bool(args, kwargs, index + 2, Some("ignore_none")) {
+        opts.ignore_none = ignore_none;
+    }
+    opts
+}
+
+fn encode_hcl(data: &JsonValue) -> Result<String, String> {
+    match data {
+        JsonValue::Object(values) => {
+            let mut result = String::new();
+            for (key, value) in values {
+                // Attribute names in the body can't be quoted like the object keys.
+                if !is_attribute_name(key) {
+                    return Err(format!("invalid HCL attribute name '{key}'"));
+                }
+                write_key(&mut result, key);
+                result.push_str(" = ");
+                write_value(&mut result, value, 0);
+                result.push('\n');
+            }
+            Ok(result)
+        }
+        _ => Err("hcl data must be a dict".to_string()),
+    }
+}
+
+fn write_value(result: &mut String, value: &JsonValue, level: usize) {
+    match value {
+        JsonValue::Null => result.push_str("null"),
+        JsonValue::Bool(v) => result.push_str(&v.to_string()),
+        JsonValue::Number(v) => result.push_str(&v.to_string()),
+        JsonValue::String(v) => result.push_str(&quote(v)),
+        JsonValue::Array(values) => {
+            if values.is_empty() {
+                result.push_str("[]");
+            } else if values
+                .iter()
+                .all(|v| !matches!(v, JsonValue::Array(_) | JsonValue::Object(_)))
+            {
+                // Keep a list of scalars on one line.
+                result.push('[');
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str(", ");
+                    }
+                    write_value(result, v, level);
+                }
+                result.push(']');
+            } else {
+                result.push_str("[\n");
+                for v in values {
+                    result.push_str(&INDENT.repeat(level + 1));
+                    write_value(result, v, level + 1);
+                    result.push_str(",\n");
+                }
+                result.push_str(&INDENT.repeat(level));
+                result.push(']');
+            }
+        }
+        JsonValue::Object(values) => {
+            if values.is_empty() {
+                result.push_str("{}");
+                return;
+            }
+            result.push_str("{\n");
+            for (key, v) in values {
+                result.push_str(&INDENT.repeat(level + 1));
+                write_key(result, key);
+                result.push_str(" = ");
+                write_value(result, v, level + 1);
+                result.push('\n');
+            }
+            result.push_str(&INDENT.repeat(level));
+            result.push('}');
+        }
+    }
+}
+
+/// Write the attribute name or the object key. A key is written in the same way
+/// at all levels: attribute names are bare and the other keys are quoted.
+fn write_key(result: &mut String, key: &str) {
+    if is_attribute_name(key) {
+        result.push_str(key);
+    } else {
+        result.push_str(&quote(key));
+    }
+}
+
+/// Whether the key is a HCL identifier which is not read as a literal value or
+/// the start of a `for` expression in an object constructor.
+fn is_attribute_name(s: &str) -> bool {
+    is_identifier(s) && !matches!(s, "true" | "false" | "null" | "for")
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        }
+        _ => false,
+    }
+}
+
+/// Quote the string as a HCL string literal. Template sequences are escaped,
+/// so the string is not interpolated.
+fn quote(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '$' | '%' if chars.peek() == Some(&'{') => {
+                result.push(c);
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod test_hcl {
+    use super::*;
+
+    #[test]
+    fn test_encode_hcl() {
+        let mut ctx = Context::new();
+        let data = ValueRef::from_json(
+            &mut ctx,
+            r#"{"region": "us-east-1", "count": 2, "zones": ["a", "b"], "tags": {"Name": "web ${x}", "cost-center": "1"}, "rules": [{"port": 80}], "extra": null}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            encode_hcl(&data.build_json(&Default::default())).unwrap(),
+            r#"region = "us-east-1"
+count = 2
+zones = ["a", "b"]
+tags = {
+  Name = "web $${x}"
+  cost-center = "1"
+}
+rules = [
+  {
+    port = 80
+  },
+]
+extra = null
+"#
+        );
+        assert!(encode_hcl(&ValueRef::list(None).build_json(&Default::default())).is_err());
+    }
+
+    #[test]
+    fn test_encode_hcl_keys() {
+        let encode = |json: &str| {
+            let data = ValueRef::from_json(&mut Context::new(), json).unwrap();
+            encode_hcl(&data.build_json(&Default::default()))
+        };
+        assert_eq!(
+            encode(r#"{"cost-center": "1", "tags": {"cost-center": "1", "_id": 2, "名前": "値"}}"#)
+                .unwrap(),
+            r#"cost-center = "1"
+tags = {
+  cost-center = "1"
+  _id = 2
+  名前 = "値"
+}
+"#
+        );
+        assert_eq!(
+            encode(r#"{"tags": {"cost center": 1, "1st": 2, "for": 3, "null": 4, "a\"b": 5}}"#)
+                .unwrap(),
+            r#"tags = {
+  "cost center" = 1
+  "1st" = 2
+  "for" = 3
+  "null" = 4
+  "a\"b" = 5
+}
+"#
+        );
+        for (json, key) in [
+            (r#"{"cost center": 1}"#, "cost center"),
+            (r#"{"1st": 1}"#, "1st"),
+            (r#"{"for": 1}"#, "for"),
+            (r#"{"null": 1}"#, "null"),
+            (r#"{"-a": 1}"#, "-a"),
+            (r#"{"": 1}"#, ""),
+        ] {
+            assert_eq!(
+                encode(json).unwrap_err(),
+                format!("invalid HCL attribute name '{key}'")
+            );
+        }
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote(""), r#""""#);
+        assert_eq!(quote("a\"b\\c"), r#""a\"b\\c""#);
+        assert_eq!(quote("a\r\n\tb"), r#""a\r\n\tb""#);
+        assert_eq!(quote("${x} %{if} $x %x"), r#""$${x} %%{if} $x %x""#);
+        assert_eq!(quote("値 🚀"), r#""値 🚀""#);
+    }
+}
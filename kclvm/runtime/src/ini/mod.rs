@@ -0,0 +1,334 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use kclvm_primitives::{DefaultHashBuilder, IndexMap};
+
+use crate::*;
+
+// data, sort_keys=False, ignore_private=False, ignore_none=False
+
+/// Serialize a dict into an INI formatted string. Top level dict values are
+/// written as sections and nested dict values as dotted sub-sections.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_ini_encode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(data) = get_call_arg(args, kwargs, 0, Some("data")) {
+        let json = data.build_json(&args_to_opts(args, kwargs, 1));
+        return match encode_ini(&json) {
+            Ok(s) => ValueRef::str(&s).into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        };
+    }
+    panic!("encode() missing 1 required positional argument: 'data'")
+}
+
+/// Deserialize an INI formatted string into a dict. Keys before the first
+/// section are placed at the top level and all values are strings.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_ini_decode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(value) = get_call_arg_str(args, kwargs, 0, Some("value")) {
+        return match decode_ini(&value) {
+            Ok(json) => ValueRef::parse_json(ctx, &json).into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        };
+    }
+    panic!("decode() missing 1 required positional argument: 'value'")
+}
+
+fn args_to_opts(args: &ValueRef, kwargs: &ValueRef, index: usize) -> JsonEncodeOptions {
+    let mut opts = JsonEncodeOptions::default();
+    if let Some(sort_keys) = get_call_arg_bool(args, kwargs, index, Some("sort_keys")) {
+        opts.sort_keys = sort_keys;
+    }
+    if let Some(ignore_private) = get_call_arg_bool(args, kwargs, index + 1, Some("ignore_private"))
+    {
+        opts.ignore_private = ignore_private;
+    }
+    if let Some(ignore_none) = get_call_arg_bool(args, kwargs, index + 2, Some("ignore_none")) {
+        opts.ignore_none = ignore_none;
+    }
+    opts
+}
+
+fn encode_ini(data: &JsonValue) -> Result<String, String> {
+    match data {
+        JsonValue::Object(values) => {
+            let mut result = String::new();
+            write_section(&mut result, "", values)?;
+            Ok(result)
+        }
+        _ => Err("ini data must be a dict".to_string()),
+    }
+}
+
+fn write_section(
+    result: &mut String,
+    name: &str,
+    values: &IndexMap<String, JsonValue>,
+) -> Result<(), String> {
+    if !name.is_empty() {
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        result.push_str(&format!("[{name}]\n"));
+    }
+    // Write the key-value pairs before sub-sections, otherwise they will be
+    // read as members of the last sub-section.
+    for (key, value) in values {
+        let value = match value {
+            JsonValue::Object(_) => {
+                if !is_valid_section_name(key) {
+                    return Err(format!("invalid ini section name '{key}'"));
+                }
+                continue;
+            }
+            JsonValue::Array(_) => {
+                return Err(format!("ini value of key '{key}' can't be a list"));
+            }
+            JsonValue::Null => String::new(),
+            JsonValue::Bool(v) => v.to_string(),
+            JsonValue::Number(v) => v.to_string(),
+            JsonValue::String(v) => ini_value(v),
+        };
+        if !is_valid_key(key) {
+            return Err(format!("invalid ini key '{key}'"));
+        }
+        result.push_str(&format!("{key} = {value}\n"));
+    }
+    for (key, value) in values {
+        if let JsonValue::Object(values) = value {
+            let name = if name.is_empty() {
+                key.to_string()
+            } else {
+                format!("{name}.{key}")
+            };
+            write_section(result, &name, values)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether the key is read back as the same key instead of a comment, a section
+/// or a key with a value.
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.trim() == key
+        && !key.starts_with([';', '#', '['])
+        && !key.contains(['=', ':', '\n', '\r'])
+}
+
+/// Whether the section name is read back as the same section. Dots are used
+/// to separate the sub-section names.
+fn is_valid_section_name(name: &str) -> bool {
+    !name.is_empty() && name.trim() == name && !name.contains(['.', '[', ']', '\n', '\r'])
+}
+
+/// Quote the value when it would be changed or cut by a reader.
+fn ini_value(value: &str) -> String {
+    if value.trim() != value
+        || value.starts_with(['"', '\''])
+        || value.contains([';', '#', '\n', '\r'])
+    {
+        format!(
+            "\"{}\"",
+            value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        )
+    } else {
+        value.to_string()
+    }
+}
+
+fn decode_ini(value: &str) -> Result<JsonValue, String> {
+    let mut result = IndexMap::with_hasher(DefaultHashBuilder::default());
+    let mut section: Vec<String> = vec![];
+    for (i, line) in value.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with([';', '#']) {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[') {
+            match name.strip_suffix(']') {
+                Some(name) => {
+                    section = name.split('.').map(|s| s.trim().to_string()).collect();
+                    get_section(&mut result, &section, i)?;
+                }
+                None => return Err(format!("invalid ini section on line {}: '{line}'", i + 1)),
+            }
+            continue;
+        }
+        let (key, value) = match line.find(['=', ':']) {
+            Some(pos) => (line[..pos].trim(), line[pos + 1..].trim()),
+            None => return Err(format!("invalid ini key-value on line {}: '{line}'", i + 1)),
+        };
+        let values = get_section(&mut result, &section, i)?;
+        values.insert(key.to_string(), JsonValue::String(unquote(value)));
+    }
+    Ok(JsonValue::Object(result))
+}
+
+fn get_section<'a>(
+    values: &'a mut IndexMap<String, JsonValue>,
+    section: &[String],
+    line: usize,
+) -> Result<&'a mut IndexMap<String, JsonValue>, String> {
+    match section.split_first() {
+        Some((name, rest)) => {
+            let value = values.entry(name.to_string()).or_insert_with(|| {
+                JsonValue::Object(IndexMap::with_hasher(DefaultHashBuilder::default()))
+            });
+            match value {
+                JsonValue::Object(values) => get_section(values, rest, line),
+                _ => Err(format!(
+                    "ini section '{name}' on line {} conflicts with a key",
+                    line + 1
+                )),
+            }
+        }
+        None => Ok(values),
+    }
+}
+
+fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        let mut result = String::new();
+        let mut chars = value[1..value.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                match chars.next() {
+                    Some('n') => result.push('\n'),
+                    Some('r') => result.push('\r'),
+                    Some(c) => result.push(c),
+                    None => result.push('\\'),
+                }
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    } else if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        value[1..value.len() - 1].to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test_ini {
+    use super::*;
+
+    #[test]
+    fn test_ini_round_trip() {
+        let mut ctx = Context::new();
+        let value = r#"name = app
+
+[server]
+host = 0.0.0.0
+note = " a;b"
+
+[server.tls]
+enabled = true
+"#;
+        let json = decode_ini(value).unwrap();
+        let data = ValueRef::parse_json(&mut ctx, &json);
+        assert_eq!(
+            data.to_json_string(),
+            r#"{"name": "app", "server": {"host": "0.0.0.0", "note": " a;b", "tls": {"enabled": "true"}}}"#
+        );
+        assert_eq!(encode_ini(&json).unwrap(), value);
+        assert!(decode_ini("[server").is_err());
+        assert!(decode_ini("key").is_err());
+    }
+
+    #[test]
+    fn test_ini_quoting() {
+        let mut ctx = Context::new();
+        let data = ValueRef::from_json(
+            &mut ctx,
+            r#"{"a": " x ", "b": "'q'", "c": "x;y\\z", "d": "l1\nl2", "e": "k=v", "名": "値"}"#,
+        )
+        .unwrap();
+        let json = data.build_json(&Default::default());
+        let value = encode_ini(&json).unwrap();
+        assert_eq!(
+            value,
+            r#"a = " x "
+b = "'q'"
+c = "x;y\\z"
+d = "l1\nl2"
+e = k=v
+名 = 値
+"#
+        );
+        let json = decode_ini(&value).unwrap();
+        assert_eq!(
+            ValueRef::parse_json(&mut ctx, &json).to_json_string(),
+            data.to_json_string()
+        );
+    }
+
+    #[test]
+    fn test_encode_ini_invalid_names() {
+        let encode = |json: &str| {
+            let data = ValueRef::from_json(&mut Context::new(), json).unwrap();
+            encode_ini(&data.build_json(&Default::default()))
+        };
+        for json in [
+            r#"{"": 1}"#,
+            r#"{" a": 1}"#,
+            r#"{"a=b": 1}"#,
+            r#"{"a:b": 1}"#,
+            r##"{"#a": 1}"##,
+            r#"{"[a": 1}"#,
+            r#"{"a\nb": 1}"#,
+        ] {
+            assert!(encode(json).is_err(), "{json}");
+        }
+        assert_eq!(
+            encode(r#"{"s": {"a=b": 1}}"#).unwrap_err(),
+            "invalid ini key 'a=b'"
+        );
+        assert_eq!(
+            encode(r#"{"a.b": {"c": 1}}"#).unwrap_err(),
+            "invalid ini section name 'a.b'"
+        );
+        assert!(encode(r#"{"a]": {"c": 1}}"#).is_err());
+        assert!(encode(r#"{"a": [1]}"#).is_err());
+    }
+
+    #[test]
+    fn test_decode_ini_edge_cases() {
+        let decode = |value: &str| {
+            let json = decode_ini(value)?;
+            Ok::<String, String>(ValueRef::parse_json(&mut Context::new(), &json).to_json_string())
+        };
+        assert_eq!(
+            decode("a: 1\r\n; c\r\n# d\r\n[s]\r\nb=2=3\r\nc = 'x;y'\r\n").unwrap(),
+            r#"{"a": "1", "s": {"b": "2=3", "c": "x;y"}}"#
+        );
+        assert_eq!(decode("").unwrap(), "{}");
+        assert_eq!(
+            decode("a = 1\n[a]\n").unwrap_err(),
+            "ini section 'a' on line 2 conflicts with a key"
+        );
+    }
+}
@@ -79,6 +79,8 @@ pub extern "C-unwind" fn kclvm_json_dump_to_file(
     match (data, filename) {
         (Some(data), Some(filename)) => {
             let filename = filename.as_str();
+            mut_ptr_as_ref(ctx).check_file_write(&filename);
+            mut_ptr_as_ref(ctx).mark_uncacheable();
             let json = data.to_json_string_with_options(&args_to_opts(args, kwargs, 2));
             std::fs::write(&filename, json)
                 .unwrap_or_else(|e| panic!("Unable to write file '{}': {}", filename, e));
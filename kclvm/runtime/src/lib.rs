@@ -121,6 +121,21 @@ pub use self::file::*;
 pub mod template;
 pub use self::template::*;
 
+pub mod csv;
+pub use self::csv::*;
+
+pub mod ini;
+pub use self::ini::*;
+
+pub mod properties;
+pub use self::properties::*;
+
+pub mod xml;
+pub use self::xml::*;
+
+pub mod hcl;
+pub use self::hcl::*;
+
 pub mod panic;
 pub use self::panic::*;
 
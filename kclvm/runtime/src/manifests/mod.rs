@@ -7,6 +7,7 @@ use crate::*;
 #[cfg(test)]
 mod tests;
 mod yaml;
+use yaml::{encode_yaml_stream_to_manifests, process_manifests, ManifestsOptions};
 
 #[allow(non_camel_case_types)]
 type kclvm_value_ref_t = ValueRef;
@@ -19,7 +20,13 @@ type kclvm_value_ref_t = ValueRef;
 /// - ignore_private: Whether to ignore the attribute whose name starts with
 ///     a character `_` (defaults to false).
 /// - ignore_none: Whether to ignore the attribute whose value is `None` (defaults to false).
-/// - sep: Which separator to use between YAML documents (defaults to "---").
+/// - sep: Which separator to use between YAML documents (defaults to "---"),
+///     `separator` is an alias of `sep`.
+/// - sort_resources: Sort the resources in the Kubernetes apply order e.g., Namespaces,
+///     CRDs and RBAC resources before workloads (defaults to false).
+/// - ignore_empty: Whether to ignore the empty list and dict attributes (defaults to false).
+/// - header: The comment emitted at the beginning of the YAML output (defaults to None).
+/// - as_list: Whether to wrap all the values into a Kubernetes `List` object (defaults to false).
 /// More information: https://github.com/kcl-lang/kcl/issues/94
 ///
 /// - Function signature.
@@ -29,7 +36,11 @@ type kclvm_value_ref_t = ValueRef;
 ///     sort_keys: bool = False
 ///     ignore_private: bool = True
 ///     ignore_none: bool = False
-///     separator: str = "---\n"
+///     sep: str = "---"
+///     sort_resources: bool = False
+///     ignore_empty: bool = False
+///     header?: str
+///     as_list: bool = False
 ///
 /// manifests.yaml_stream(values: [any], * , opts: ManifestsYamlStreamOptions = ManifestsYamlStreamOptions {})
 /// ```
@@ -47,8 +58,11 @@ type kclvm_value_ref_t = ValueRef;
 ///     sort_keys = True
 ///     ignore_none = True
 /// })
+/// manifests.yaml_stream([config1, config2], opts = {
+///     sort_resources = True
+///     header = "Code generated by KCL. DO NOT EDIT."
+/// })
 /// ```
-/// TODO: more options on the function `yaml_stream`.
 #[no_mangle]
 #[runtime_fn]
 pub extern "C-unwind" fn kclvm_manifests_yaml_stream(
@@ -61,9 +75,25 @@ pub extern "C-unwind" fn kclvm_manifests_yaml_stream(
     let ctx = mut_ptr_as_ref(ctx);
 
     // Get the YAML encode options from the second keyword argument `opts`.
+    let mut manifests_opts = ManifestsOptions::default();
+    let mut header = None;
     let opts = match kwargs.kwarg("opts").or_else(|| args.arg_i(1)) {
         Some(opts) => {
             if opts.is_config() {
+                let get_bool = |key: &str| {
+                    opts.get_by_key(key)
+                        .map(|v| v.is_truthy())
+                        .unwrap_or_default()
+                };
+                manifests_opts = ManifestsOptions {
+                    sort_resources: get_bool("sort_resources"),
+                    ignore_empty: get_bool("ignore_empty"),
+                    as_list: get_bool("as_list"),
+                };
+                header = opts
+                    .get_by_key("header")
+                    .filter(|v| v.is_str())
+                    .map(|v| v.as_str());
                 // Get options or default.
                 YamlEncodeOptions {
                     sort_keys: opts
@@ -80,6 +110,7 @@ pub extern "C-unwind" fn kclvm_manifests_yaml_stream(
                         .as_bool(),
                     sep: opts
                         .get_by_key("sep")
+                        .or_else(|| opts.get_by_key("separator"))
                         .unwrap_or_else(|| ValueRef::str("---"))
                         .as_str(),
                 }
@@ -94,7 +125,9 @@ pub extern "C-unwind" fn kclvm_manifests_yaml_stream(
     };
 
     if let Some(value) = get_call_arg(args, kwargs, 0, Some("values")) {
-        self::yaml::encode_yaml_stream_to_manifests(ctx, &value, opts);
+        let value = process_manifests(&value, &manifests_opts);
+        encode_yaml_stream_to_manifests(ctx, &value, opts);
+        ctx.plan_opts.header = header;
     } else {
         panic!("yaml_stream() missing 1 required positional argument: 'values'");
     }
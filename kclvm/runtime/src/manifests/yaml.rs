@@ -1,5 +1,128 @@
 use crate::{Context, ValueRef, YamlEncodeOptions};
 
+/// Kubernetes resource kinds in the apply order. Namespaces, policies, CRDs and
+/// RBAC resources are applied before the workloads which depend on them, and
+/// unknown kinds are applied last.
+const KUBERNETES_APPLY_ORDER: &[&str] = &[
+    "Namespace",
+    "NetworkPolicy",
+    "ResourceQuota",
+    "LimitRange",
+    "PodSecurityPolicy",
+    "PodDisruptionBudget",
+    "ServiceAccount",
+    "Secret",
+    "SecretList",
+    "ConfigMap",
+    "StorageClass",
+    "PersistentVolume",
+    "PersistentVolumeClaim",
+    "CustomResourceDefinition",
+    "ClusterRole",
+    "ClusterRoleList",
+    "ClusterRoleBinding",
+    "ClusterRoleBindingList",
+    "Role",
+    "RoleList",
+    "RoleBinding",
+    "RoleBindingList",
+    "Service",
+    "DaemonSet",
+    "Pod",
+    "ReplicationController",
+    "ReplicaSet",
+    "Deployment",
+    "HorizontalPodAutoscaler",
+    "StatefulSet",
+    "Job",
+    "CronJob",
+    "IngressClass",
+    "Ingress",
+    "APIService",
+    "MutatingWebhookConfiguration",
+    "ValidatingWebhookConfiguration",
+];
+
+/// Manifests options applied on the values before the YAML encoding.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ManifestsOptions {
+    /// Sort the resources in the Kubernetes apply order.
+    pub sort_resources: bool,
+    /// Drop the empty list and dict attributes.
+    pub ignore_empty: bool,
+    /// Wrap all resources into a Kubernetes `List` object.
+    pub as_list: bool,
+}
+
+/// Process the manifest values with the manifests options and returns a new list value.
+pub(crate) fn process_manifests(values: &ValueRef, opts: &ManifestsOptions) -> ValueRef {
+    let mut values: Vec<ValueRef> = values.as_list_ref().values.clone();
+    if opts.ignore_empty {
+        values = values.iter().map(remove_empty_values).collect();
+    }
+    if opts.sort_resources {
+        // Stable sort to keep the user defined order of the same kind.
+        values.sort_by_key(kubernetes_apply_order);
+    }
+    if opts.as_list {
+        let items: Vec<&ValueRef> = values.iter().collect();
+        let list = ValueRef::dict(Some(&[
+            ("apiVersion", &ValueRef::str("v1")),
+            ("kind", &ValueRef::str("List")),
+            ("items", &ValueRef::list(Some(&items))),
+        ]));
+        return ValueRef::list(Some(&[&list]));
+    }
+    let values: Vec<&ValueRef> = values.iter().collect();
+    ValueRef::list(Some(&values))
+}
+
+/// Get the apply order index of the resource by its `kind` attribute.
+fn kubernetes_apply_order(value: &ValueRef) -> usize {
+    let kind = if value.is_config() {
+        value.get_by_key("kind").filter(|kind| kind.is_str())
+    } else {
+        None
+    };
+    match kind {
+        Some(kind) => {
+            let kind = kind.as_str();
+            KUBERNETES_APPLY_ORDER
+                .iter()
+                .position(|k| *k == kind)
+                .unwrap_or(KUBERNETES_APPLY_ORDER.len())
+        }
+        None => KUBERNETES_APPLY_ORDER.len() + 1,
+    }
+}
+
+/// Recursively remove the empty list and dict values in the list and dict value.
+fn remove_empty_values(value: &ValueRef) -> ValueRef {
+    let is_empty = |v: &ValueRef| v.is_list_or_config() && v.is_empty();
+    if value.is_list() {
+        let values: Vec<ValueRef> = value
+            .as_list_ref()
+            .values
+            .iter()
+            .map(remove_empty_values)
+            .filter(|v| !is_empty(v))
+            .collect();
+        let values: Vec<&ValueRef> = values.iter().collect();
+        ValueRef::list(Some(&values))
+    } else if value.is_config() {
+        let mut result = ValueRef::dict(None);
+        for (key, value) in &value.as_dict_ref().values {
+            let value = remove_empty_values(value);
+            if !is_empty(&value) {
+                result.dict_update_key_value(key, value);
+            }
+        }
+        result
+    } else {
+        value.clone()
+    }
+}
+
 /// Encode the list value to the yaml stream format.
 #[inline]
 pub(crate) fn encode_yaml_stream_to_manifests(
@@ -26,7 +149,7 @@ pub(crate) fn encode_yaml_stream_to_manifests(
 
 #[cfg(test)]
 mod test_manifests_yaml {
-    use crate::{manifests::yaml::encode_yaml_stream_to_manifests, *};
+    use crate::{manifests::yaml::*, *};
 
     #[test]
     fn test_encode_yaml_stream_to_manifests() {
@@ -96,6 +219,52 @@ mod test_manifests_yaml {
         }
     }
 
+    #[test]
+    fn test_process_manifests() {
+        let resource = |kind: &str| ValueRef::dict(Some(&[("kind", &ValueRef::str(kind))]));
+        let values = ValueRef::list(Some(&[
+            &resource("Deployment"),
+            &resource("Foo"),
+            &resource("Service"),
+            &resource("Namespace"),
+            &resource("ClusterRole"),
+        ]));
+        let mut ctx = Context::default();
+        let opts = ManifestsOptions {
+            sort_resources: true,
+            ..Default::default()
+        };
+        encode_yaml_stream_to_manifests(
+            &mut ctx,
+            &process_manifests(&values, &opts),
+            YamlEncodeOptions::default(),
+        );
+        assert_eq!(
+            ctx.buffer.custom_manifests_output,
+            Some("kind: Namespace\n\n---\nkind: ClusterRole\n\n---\nkind: Service\n\n---\nkind: Deployment\n\n---\nkind: Foo\n".to_string())
+        );
+
+        let values = ValueRef::list(Some(&[&ValueRef::dict(Some(&[
+            ("a", &ValueRef::int(1)),
+            ("b", &ValueRef::list(None)),
+            ("c", &ValueRef::dict(Some(&[("d", &ValueRef::dict(None))]))),
+        ]))]));
+        let opts = ManifestsOptions {
+            ignore_empty: true,
+            as_list: true,
+            ..Default::default()
+        };
+        encode_yaml_stream_to_manifests(
+            &mut ctx,
+            &process_manifests(&values, &opts),
+            YamlEncodeOptions::default(),
+        );
+        assert_eq!(
+            ctx.buffer.custom_manifests_output,
+            Some("apiVersion: v1\nkind: List\nitems:\n- a: 1\n".to_string())
+        );
+    }
+
     #[test]
     fn test_encode_yaml_stream_to_manifests_failure() {
         let prev_hook = std::panic::take_hook();
@@ -30,6 +30,7 @@ pub extern "C-unwind" fn kclvm_runtime_catch(
             ctx.panic_info.kcl_func = func.name.clone();
         }
         let now_meta_info = ctx.panic_info.clone();
+        let call_depth = ctx.call_depth();
         let fn_ptr = func.fn_ptr;
         let wrapper = UnsafeWrapper::new(|| {
             let args = ValueRef::list(None).into_raw(ctx);
@@ -52,6 +53,7 @@ pub extern "C-unwind" fn kclvm_runtime_catch(
             ctx.backtrace.pop();
         }
         ctx.panic_info = now_meta_info;
+        ctx.unwind_calls(call_depth);
         return match result {
             Ok(_) => ValueRef::undefined(),
             Err(err) => ValueRef::str(&err_to_str(err)),
@@ -0,0 +1,313 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use kclvm_primitives::{DefaultHashBuilder, IndexMap};
+
+use crate::*;
+
+// data, sort_keys=False, ignore_private=False, ignore_none=False
+
+/// Serialize a dict into a Java properties formatted string. Nested dict keys
+/// are joined with `.` and list items are written as `key[index]`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_properties_encode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(data) = get_call_arg(args, kwargs, 0, Some("data")) {
+        let json = data.build_json(&args_to_opts(args, kwargs, 1));
+        return match encode_properties(&json) {
+            Ok(s) => ValueRef::str(&s).into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        };
+    }
+    panic!("encode() missing 1 required positional argument: 'data'")
+}
+
+/// Deserialize a Java properties formatted string into a flat dict of strings.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_properties_decode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(value) = get_call_arg_str(args, kwargs, 0, Some("value")) {
+        return match decode_properties(&value) {
+            Ok(json) => ValueRef::parse_json(ctx, &json).into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        };
+    }
+    panic!("decode() missing 1 required positional argument: 'value'")
+}
+
+fn args_to_opts(args: &ValueRef, kwargs: &ValueRef, index: usize) -> JsonEncodeOptions {
+    let mut opts = JsonEncodeOptions::default();
+    if let Some(sort_keys) = get_call_arg_bool(args, kwargs, index, Some("sort_keys")) {
+        opts.sort_keys = sort_keys;
+    }
+    if let Some(ignore_private) = get_call_arg_bool(args, kwargs, index + 1, Some("ignore_private"))
+    {
+        opts.ignore_private = ignore_private;
+    }
+    if let Some(ignore_none) = get_call_arg_bool(args, kwargs, index + 2, Some("ignore_none")) {
+        opts.ignore_none = ignore_none;
+    }
+    opts
+}
+
+fn encode_properties(data: &JsonValue) -> Result<String, String> {
+    match data {
+        JsonValue::Object(_) => {
+            let mut result = String::new();
+            write_entries(&mut result, "", data);
+            Ok(result)
+        }
+        _ => Err("properties data must be a dict".to_string()),
+    }
+}
+
+fn write_entries(result: &mut String, key: &str, value: &JsonValue) {
+    match value {
+        JsonValue::Object(values) => {
+            for (k, v) in values {
+                let key = if key.is_empty() {
+                    k.to_string()
+                } else {
+                    format!("{key}.{k}")
+                };
+                write_entries(result, &key, v);
+            }
+        }
+        JsonValue::Array(values) => {
+            for (i, v) in values.iter().enumerate() {
+                write_entries(result, &format!("{key}[{i}]"), v);
+            }
+        }
+        JsonValue::Null => write_entry(result, key, ""),
+        JsonValue::Bool(v) => write_entry(result, key, &v.to_string()),
+        JsonValue::Number(v) => write_entry(result, key, &v.to_string()),
+        JsonValue::String(v) => write_entry(result, key, v),
+    }
+}
+
+fn write_entry(result: &mut String, key: &str, value: &str) {
+    result.push_str(&escape(key, true));
+    result.push('=');
+    result.push_str(&escape(value, false));
+    result.push('\n');
+}
+
+fn escape(s: &str, is_key: bool) -> String {
+    let mut result = String::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\t' => result.push_str("\\t"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\x0c' => result.push_str("\\f"),
+            '=' | ':' | '#' | '!' if is_key => {
+                result.push('\\');
+                result.push(c);
+            }
+            // Leading spaces of values and all spaces of keys are significant.
+            ' ' if is_key || i == 0 => result.push_str("\\ "),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+fn decode_properties(value: &str) -> Result<JsonValue, String> {
+    let mut result = IndexMap::with_hasher(DefaultHashBuilder::default());
+    let mut lines = value.lines().enumerate();
+    while let Some((i, line)) = lines.next() {
+        let mut line = line.trim_start().to_string();
+        if line.is_empty() || line.starts_with(['#', '!']) {
+            continue;
+        }
+        // Join the continuation lines ending with an odd number of backslashes.
+        while ends_with_continuation(&line) {
+            line.pop();
+            match lines.next() {
+                Some((_, next)) => line.push_str(next.trim_start()),
+                None => break,
+            }
+        }
+        let (key, value) = split_entry(&line);
+        let key = unescape(key).map_err(|e| format!("{e} on line {}", i + 1))?;
+        let value = unescape(value).map_err(|e| format!("{e} on line {}", i + 1))?;
+        result.insert(key, JsonValue::String(value));
+    }
+    Ok(JsonValue::Object(result))
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+/// Split the logical line at the first unescaped `=`, `:` or whitespace.
+fn split_entry(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '=' || c == ':' || c.is_whitespace() {
+            let key = &line[..i];
+            let mut rest = line[i..].trim_start();
+            if c.is_whitespace() {
+                if let Some(r) = rest.strip_prefix(['=', ':']) {
+                    rest = r;
+                }
+            } else {
+                rest = &rest[1..];
+            }
+            return (key, rest.trim_start());
+        }
+    }
+    (line, "")
+}
+
+fn unescape(s: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('f') => result.push('\x0c'),
+            Some('u') => {
+                let code = unescape_code_unit(&mut chars)?;
+                // Characters outside the BMP are written as UTF-16 surrogate pairs.
+                let c = if (0xD800..0xDC00).contains(&code) {
+                    let low = match (chars.next(), chars.next()) {
+                        (Some('\\'), Some('u')) => unescape_code_unit(&mut chars)?,
+                        _ => return Err(format!("unpaired surrogate '\\u{code:04X}'")),
+                    };
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(format!("unpaired surrogate '\\u{code:04X}'"));
+                    }
+                    char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00))
+                } else {
+                    char::from_u32(code)
+                };
+                match c {
+                    Some(c) => result.push(c),
+                    None => return Err(format!("unpaired surrogate '\\u{code:04X}'")),
+                }
+            }
+            Some(c) => result.push(c),
+            None => {}
+        }
+    }
+    Ok(result)
+}
+
+/// Read the 4 hex digits of an UTF-16 code unit after `\u`.
+fn unescape_code_unit(chars: &mut std::str::Chars) -> Result<u32, String> {
+    let code: String = chars.by_ref().take(4).collect();
+    if code.len() != 4 || !code.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("invalid unicode escape '\\u{code}'"));
+    }
+    Ok(u32::from_str_radix(&code, 16).unwrap())
+}
+
+#[cfg(test)]
+mod test_properties {
+    use super::*;
+
+    #[test]
+    fn test_decode_properties() {
+        let mut ctx = Context::new();
+        let value = "# comment\n! comment\nserver.port = 8080\nname:app\npath  \\\n    /usr/bin\nkey\\ with\\=sep = \\u0041\\tB\nempty\n";
+        let json = decode_properties(value).unwrap();
+        let data = ValueRef::parse_json(&mut ctx, &json);
+        assert_eq!(
+            data.to_json_string(),
+            r#"{"server.port": "8080", "name": "app", "path": "/usr/bin", "key with=sep": "A\tB", "empty": ""}"#
+        );
+    }
+
+    #[test]
+    fn test_encode_properties() {
+        let mut ctx = Context::new();
+        let data = ValueRef::from_json(
+            &mut ctx,
+            r#"{"server": {"port": 8080, "hosts": ["a", "b"]}, "key with=sep": " x\ny"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            encode_properties(&data.build_json(&Default::default())).unwrap(),
+            "server.port=8080\nserver.hosts[0]=a\nserver.hosts[1]=b\nkey\\ with\\=sep=\\ x\\ny\n"
+        );
+        let json = decode_properties("key\\ with\\=sep=\\ x\\ny\n").unwrap();
+        assert_eq!(
+            ValueRef::parse_json(&mut ctx, &json).to_json_string(),
+            r#"{"key with=sep": " x\ny"}"#
+        );
+    }
+
+    #[test]
+    fn test_properties_escaping() {
+        let mut ctx = Context::new();
+        let data = ValueRef::from_json(
+            &mut ctx,
+            r##"{"#k": "#x", "!k": "a:b", "a b": "x ", "名": "é🚀", "t": "\t\\"}"##,
+        )
+        .unwrap();
+        let json = data.build_json(&Default::default());
+        let value = encode_properties(&json).unwrap();
+        assert_eq!(value, "\\#k=#x\n\\!k=a:b\na\\ b=x \n名=é🚀\nt=\\t\\\\\n");
+        let json = decode_properties(&value).unwrap();
+        assert_eq!(
+            ValueRef::parse_json(&mut ctx, &json).to_json_string(),
+            data.to_json_string()
+        );
+    }
+
+    #[test]
+    fn test_decode_properties_edge_cases() {
+        let decode = |value: &str| {
+            let json = decode_properties(value)?;
+            Ok::<String, String>(ValueRef::parse_json(&mut Context::new(), &json).to_json_string())
+        };
+        assert_eq!(
+            decode("a=\\uD83D\\uDE80 \\u00e9\nb : c # d\nend=e\\").unwrap(),
+            r#"{"a": "🚀 é", "b": "c # d", "end": "e"}"#
+        );
+        assert_eq!(decode("").unwrap(), "{}");
+        assert_eq!(
+            decode("a=\\u+041").unwrap_err(),
+            "invalid unicode escape '\\u+041' on line 1"
+        );
+        assert_eq!(
+            decode("a=b\nc=\\u12").unwrap_err(),
+            "invalid unicode escape '\\u12' on line 2"
+        );
+        assert_eq!(
+            decode("a=\\uD83Dx").unwrap_err(),
+            "unpaired surrogate '\\uD83D' on line 1"
+        );
+        assert_eq!(
+            decode("a=\\uDE80").unwrap_err(),
+            "unpaired surrogate '\\uDE80' on line 1"
+        );
+    }
+}
@@ -442,6 +442,83 @@ pub fn list(iterable: Option<&ValueRef>) -> ValueRef {
     }
 }
 
+/// Invoke a KCL function value with positional arguments. The caller provides
+/// it because the native runtime and the evaluator call KCL functions differently.
+pub type FuncInvoker<'a> = dyn FnMut(&ValueRef, &[ValueRef]) -> ValueRef + 'a;
+
+/// Return a list of the results of calling `func` on each item of the iterable.
+/// Dict and schema values are iterated by their keys, the same as comprehensions.
+pub fn map(func: &ValueRef, iterable: &ValueRef, invoke: &mut FuncInvoker) -> ValueRef {
+    let items = list(Some(iterable));
+    let mut result = ValueRef::list(None);
+    for item in items.as_list_ref().values.iter() {
+        result.list_append(&invoke(func, &[item.clone()]));
+    }
+    result
+}
+
+/// Return a list of the items of the iterable for which `func` returns a truthy value.
+pub fn filter(func: &ValueRef, iterable: &ValueRef, invoke: &mut FuncInvoker) -> ValueRef {
+    let items = list(Some(iterable));
+    let mut result = ValueRef::list(None);
+    for item in items.as_list_ref().values.iter() {
+        if invoke(func, &[item.clone()]).is_truthy() {
+            result.list_append(item);
+        }
+    }
+    result
+}
+
+/// Apply `func` of two arguments cumulatively to the items of the iterable from
+/// left to right, so as to reduce the iterable to a single value.
+pub fn reduce(
+    func: &ValueRef,
+    iterable: &ValueRef,
+    initial: Option<&ValueRef>,
+    invoke: &mut FuncInvoker,
+) -> ValueRef {
+    let items = list(Some(iterable));
+    let items = items.as_list_ref();
+    let (mut result, rest) = match initial {
+        Some(initial) => (initial.clone(), &items.values[..]),
+        None => match items.values.split_first() {
+            Some((first, rest)) => (first.clone(), rest),
+            None => panic!("reduce() of empty iterable with no initial value"),
+        },
+    };
+    for item in rest {
+        result = invoke(func, &[result, item.clone()]);
+    }
+    result
+}
+
+/// Return a new list containing all items from the iterable sorted by the value
+/// returned by calling `key` on each item. The sort is stable.
+pub fn sort_by(
+    iterable: &ValueRef,
+    key: &ValueRef,
+    reverse: bool,
+    invoke: &mut FuncInvoker,
+) -> ValueRef {
+    let items = list(Some(iterable));
+    let mut keyed: Vec<(ValueRef, ValueRef)> = items
+        .as_list_ref()
+        .values
+        .iter()
+        .map(|item| (invoke(key, &[item.clone()]), item.clone()))
+        .collect();
+    if reverse {
+        keyed.sort_by(|(a, _), (b, _)| b.cmp(a));
+    } else {
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+    let mut result = ValueRef::list(None);
+    for (_, item) in keyed.iter() {
+        result.list_append(item);
+    }
+    result
+}
+
 pub fn dict(ctx: &mut Context, iterable: Option<&ValueRef>) -> ValueRef {
     match iterable {
         Some(val) => {
@@ -495,6 +572,22 @@ pub fn range(start: &ValueRef, stop: &ValueRef, step: &ValueRef) -> ValueRef {
     }
 }
 
+/// Get the item count of the `range(start, stop, step)` list without building it.
+pub fn range_len(start: &ValueRef, stop: &ValueRef, step: &ValueRef) -> u64 {
+    match (&*start.rc.borrow(), &*stop.rc.borrow(), &*step.rc.borrow()) {
+        (Value::int_value(start), Value::int_value(stop), Value::int_value(step)) if *step != 0 => {
+            let (start, stop, step) = (*start as i128, *stop as i128, *step as i128);
+            let distance = if step > 0 { stop - start } else { start - stop };
+            if distance <= 0 {
+                0
+            } else {
+                ((distance + step.abs() - 1) / step.abs()) as u64
+            }
+        }
+        _ => 0,
+    }
+}
+
 /// Check if the modular result of a and b is 0
 pub fn multiplyof(a: &ValueRef, b: &ValueRef) -> ValueRef {
     match (&*a.rc.borrow(), &*b.rc.borrow()) {
@@ -640,6 +733,44 @@ mod test_builtin {
         });
     }
 
+    #[test]
+    fn test_higher_order_builtins() {
+        // Use the int value as the "function" and apply it as a multiplier.
+        let mut invoke = |func: &ValueRef, args: &[ValueRef]| match args {
+            [x] => ValueRef::int(func.as_int() * x.as_int()),
+            [acc, x] => ValueRef::int(acc.as_int() + func.as_int() * x.as_int()),
+            _ => unreachable!(),
+        };
+        let func = ValueRef::int(2);
+        let values = ValueRef::list_int(&[3, 1, 2]);
+        assert_eq!(
+            builtin::map(&func, &values, &mut invoke),
+            ValueRef::list_int(&[6, 2, 4])
+        );
+        assert_eq!(
+            builtin::sort_by(&values, &func, true, &mut invoke),
+            ValueRef::list_int(&[3, 2, 1])
+        );
+        assert_eq!(
+            builtin::reduce(&func, &values, None, &mut invoke).as_int(),
+            9
+        );
+        assert_eq!(
+            builtin::reduce(&func, &values, Some(&ValueRef::int(1)), &mut invoke).as_int(),
+            13
+        );
+        let mut is_odd =
+            |_: &ValueRef, args: &[ValueRef]| ValueRef::bool(args[0].as_int() % 2 == 1);
+        assert_eq!(
+            builtin::filter(&func, &values, &mut is_odd),
+            ValueRef::list_int(&[3, 1])
+        );
+        assert_panic("reduce() of empty iterable with no initial value", || {
+            let mut invoke = |_: &ValueRef, _: &[ValueRef]| ValueRef::none();
+            let _ = builtin::reduce(&ValueRef::none(), &ValueRef::list(None), None, &mut invoke);
+        });
+    }
+
     #[test]
     fn test_pow() {
         assert_eq!(
@@ -283,6 +283,152 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_sorted(
     panic!("sorted() takes exactly one argument (0 given)");
 }
 
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_builtin_map(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *mut kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let value = builtin_map(args, kwargs, &mut |func, args| invoke_func(ctx, func, args));
+    value.into_raw(mut_ptr_as_ref(ctx))
+}
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_builtin_filter(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *mut kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let value = builtin_filter(args, kwargs, &mut |func, args| invoke_func(ctx, func, args));
+    value.into_raw(mut_ptr_as_ref(ctx))
+}
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_builtin_reduce(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *mut kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let value = builtin_reduce(args, kwargs, &mut |func, args| invoke_func(ctx, func, args));
+    value.into_raw(mut_ptr_as_ref(ctx))
+}
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_builtin_sort_by(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *mut kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let value = builtin_sort_by(args, kwargs, &mut |func, args| invoke_func(ctx, func, args));
+    value.into_raw(mut_ptr_as_ref(ctx))
+}
+
+/// The builtin function implementation which receives KCL function values as
+/// arguments and calls them through the invoker.
+pub type HigherOrderBuiltinFunc = fn(&ValueRef, &ValueRef, &mut FuncInvoker) -> ValueRef;
+
+/// Get the higher order builtin function implementation by the builtin function
+/// pointer, e.g., `map`, `filter`, `reduce`, `sort_by` and `template.execute`. Backends that can't
+/// call KCL functions through the function pointer e.g., the evaluator use it to
+/// provide their own function invoker.
+pub fn get_higher_order_builtin(ptr: u64) -> Option<HigherOrderBuiltinFunc> {
+    if ptr == kclvm_builtin_map as *const () as u64 {
+        Some(builtin_map)
+    } else if ptr == kclvm_builtin_filter as *const () as u64 {
+        Some(builtin_filter)
+    } else if ptr == kclvm_builtin_reduce as *const () as u64 {
+        Some(builtin_reduce)
+    } else if ptr == kclvm_builtin_sort_by as *const () as u64 {
+        Some(builtin_sort_by)
+    } else if ptr == kclvm_template_execute as *const () as u64 {
+        Some(template_execute)
+    } else {
+        None
+    }
+}
+
+fn builtin_map(args: &ValueRef, kwargs: &ValueRef, invoke: &mut FuncInvoker) -> ValueRef {
+    let func = get_callable_arg(args, kwargs, 0, "func", "map");
+    if let Some(iterable) = get_call_arg(args, kwargs, 1, Some("iterable")) {
+        return builtin::map(&func, &iterable, invoke);
+    }
+    panic!("map() missing 1 required positional argument: 'iterable'");
+}
+
+fn builtin_filter(args: &ValueRef, kwargs: &ValueRef, invoke: &mut FuncInvoker) -> ValueRef {
+    let func = get_callable_arg(args, kwargs, 0, "func", "filter");
+    if let Some(iterable) = get_call_arg(args, kwargs, 1, Some("iterable")) {
+        return builtin::filter(&func, &iterable, invoke);
+    }
+    panic!("filter() missing 1 required positional argument: 'iterable'");
+}
+
+fn builtin_reduce(args: &ValueRef, kwargs: &ValueRef, invoke: &mut FuncInvoker) -> ValueRef {
+    let func = get_callable_arg(args, kwargs, 0, "func", "reduce");
+    if let Some(iterable) = get_call_arg(args, kwargs, 1, Some("iterable")) {
+        let initial = get_call_arg(args, kwargs, 2, Some("initial"));
+        return builtin::reduce(&func, &iterable, initial.as_ref(), invoke);
+    }
+    panic!("reduce() missing 1 required positional argument: 'iterable'");
+}
+
+fn builtin_sort_by(args: &ValueRef, kwargs: &ValueRef, invoke: &mut FuncInvoker) -> ValueRef {
+    if let Some(iterable) = get_call_arg(args, kwargs, 0, Some("inval")) {
+        let key = get_callable_arg(args, kwargs, 1, "key", "sort_by");
+        let reverse = get_call_arg_bool(args, kwargs, 2, Some("reverse")).unwrap_or_default();
+        return builtin::sort_by(&iterable, &key, reverse, invoke);
+    }
+    panic!("sort_by() takes exactly one positional argument (0 given)");
+}
+
+fn get_callable_arg(
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    index: usize,
+    key: &str,
+    func_name: &str,
+) -> ValueRef {
+    match get_call_arg(args, kwargs, index, Some(key)) {
+        Some(func) if func.is_func() => func,
+        Some(func) => panic!("'{}' object is not callable", func.type_str()),
+        None => panic!("{func_name}() missing 1 required positional argument: '{key}'"),
+    }
+}
+
+/// Call the KCL function value with positional arguments in the native runtime.
+pub(crate) fn invoke_func(
+    ctx: *mut kclvm_context_t,
+    func: &ValueRef,
+    args: &[ValueRef],
+) -> ValueRef {
+    let mut args = ValueRef::list(Some(&args.iter().collect::<Vec<&ValueRef>>()));
+    let kwargs = ValueRef::dict(None);
+    let is_in_schema = ValueRef::bool(false);
+    let value = unsafe {
+        kclvm_value_function_invoke(
+            func,
+            ctx,
+            &mut args,
+            &kwargs,
+            "\0".as_ptr() as *const c_char,
+            &is_in_schema,
+        )
+    };
+    ptr_as_ref(value).clone()
+}
+
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C-unwind" fn kclvm_builtin_int(
@@ -660,16 +806,19 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_range(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
 
-    match get_call_arg(args, kwargs, 0, Some("start")) {
+    let (start, stop, step) = match get_call_arg(args, kwargs, 0, Some("start")) {
         Some(arg0) => match get_call_arg(args, kwargs, 1, Some("stop")) {
             Some(arg1) => match get_call_arg(args, kwargs, 2, Some("step")) {
-                Some(arg2) => builtin::range(&arg0, &arg1, &arg2).into_raw(ctx_ref),
-                _ => builtin::range(&arg0, &arg1, &ValueRef::int(1)).into_raw(ctx_ref),
+                Some(arg2) => (arg0, arg1, arg2),
+                _ => (arg0, arg1, ValueRef::int(1)),
             },
-            _ => builtin::range(&ValueRef::int(0), &arg0, &ValueRef::int(1)).into_raw(ctx_ref),
+            _ => (ValueRef::int(0), arg0, ValueRef::int(1)),
         },
-        _ => kclvm_value_Undefined(ctx),
-    }
+        _ => return kclvm_value_Undefined(ctx),
+    };
+    // Check the value limit before building the list.
+    ctx_ref.alloc_values(builtin::range_len(&start, &stop, &step));
+    builtin::range(&start, &stop, &step).into_raw(ctx_ref)
 }
 
 /// Return `True` if the input value is `None` or `Undefined`, and `False` otherwise.
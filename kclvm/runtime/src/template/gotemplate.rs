@@ -0,0 +1,1547 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! A template engine compatible with the Go `text/template` package and the
+//! commonly used Helm/Sprig functions. It supports text and actions with
+//! `{{-`/`-}}` trim markers, comments, pipelines, variables, `if`, `with`,
+//! `range`, `define`, `template`, `block`, `break` and `continue`.
+
+use std::collections::HashMap;
+
+use kclvm_primitives::{DefaultHashBuilder, IndexMap};
+
+use crate::*;
+
+/// The max nested template call depth to avoid infinite recursion.
+const MAX_TEMPLATE_DEPTH: usize = 100;
+const ROOT_VAR: &str = "$";
+
+/// Render the Go template with the data, named partial templates and KCL helper functions.
+pub(crate) fn render(
+    template: &str,
+    data: &JsonValue,
+    partials: &IndexMap<String, String>,
+    helpers: &IndexMap<String, ValueRef>,
+    strict: bool,
+    invoke: &mut FuncInvoker,
+) -> Result<String, String> {
+    let mut templates = HashMap::new();
+    for (name, partial) in partials {
+        let nodes = Parser::new(partial, &mut templates)?.parse()?;
+        templates.insert(name.to_string(), nodes);
+    }
+    let nodes = Parser::new(template, &mut templates)?.parse()?;
+    let mut exec = Exec {
+        templates: &templates,
+        helpers,
+        invoke,
+        strict,
+        vars: vec![(ROOT_VAR.to_string(), data.clone())],
+        ctx: Context::new(),
+        depth: 0,
+    };
+    let mut out = String::new();
+    match exec.exec_list(&nodes, data, &mut out)? {
+        Flow::Normal => Ok(out),
+        _ => Err("break or continue outside of a range".to_string()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// `.a.b`, the empty path denotes the dot `.`.
+    Field(Vec<String>),
+    /// `$x.a.b`
+    Var(String, Vec<String>),
+    Ident(String),
+    Str(String),
+    Number(serde_json::Number),
+    Bool(bool),
+    Nil,
+    Pipe,
+    LParen,
+    RParen,
+    Comma,
+    Declare,
+    Assign,
+}
+
+#[derive(Clone)]
+enum Arg {
+    Field(Vec<String>),
+    Var(String, Vec<String>),
+    /// A function name.
+    Ident(String),
+    Value(JsonValue),
+    Pipeline(Pipeline),
+}
+
+#[derive(Clone, Default)]
+struct Pipeline {
+    decl: Vec<String>,
+    is_assign: bool,
+    cmds: Vec<Vec<Arg>>,
+}
+
+#[derive(Clone)]
+enum Node {
+    Text(String),
+    Action(Pipeline),
+    If(Pipeline, Vec<Node>, Vec<Node>),
+    With(Pipeline, Vec<Node>, Vec<Node>),
+    Range(Pipeline, Vec<Node>, Vec<Node>),
+    Template(String, Option<Pipeline>),
+    Break,
+    Continue,
+}
+
+enum Item {
+    Text(String),
+    Action(String),
+}
+
+enum Stop {
+    End,
+    Else(Vec<Token>),
+}
+
+#[derive(PartialEq)]
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+}
+
+struct Parser<'a> {
+    items: Vec<Item>,
+    pos: usize,
+    templates: &'a mut HashMap<String, Vec<Node>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &str, templates: &'a mut HashMap<String, Vec<Node>>) -> Result<Self, String> {
+        Ok(Self {
+            items: split_items(src)?,
+            pos: 0,
+            templates,
+        })
+    }
+
+    fn parse(&mut self) -> Result<Vec<Node>, String> {
+        let (nodes, stop) = self.parse_list()?;
+        match stop {
+            None => Ok(nodes),
+            Some(Stop::End) => Err("unexpected {{end}}".to_string()),
+            Some(Stop::Else(_)) => Err("unexpected {{else}}".to_string()),
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<(Vec<Node>, Option<Stop>), String> {
+        let mut nodes = vec![];
+        while self.pos < self.items.len() {
+            let item = &self.items[self.pos];
+            self.pos += 1;
+            let action = match item {
+                Item::Text(text) => {
+                    if !text.is_empty() {
+                        nodes.push(Node::Text(text.clone()));
+                    }
+                    continue;
+                }
+                Item::Action(action) => action.clone(),
+            };
+            let tokens = lex(&action)?;
+            let keyword = match tokens.first() {
+                Some(Token::Ident(ident)) => ident.as_str(),
+                Some(_) => "",
+                None => return Err("missing value for command".to_string()),
+            };
+            match keyword {
+                "end" => return Ok((nodes, Some(Stop::End))),
+                "else" => return Ok((nodes, Some(Stop::Else(tokens[1..].to_vec())))),
+                "if" | "with" => nodes.push(self.parse_branch(keyword, &tokens[1..])?),
+                "range" => {
+                    let pipeline = parse_pipeline(&tokens[1..], true)?;
+                    let (body, stop) = self.parse_list()?;
+                    let else_body = match stop {
+                        Some(Stop::End) => vec![],
+                        Some(Stop::Else(tokens)) if tokens.is_empty() => self.parse_end("range")?,
+                        _ => return Err("unexpected EOF in range".to_string()),
+                    };
+                    nodes.push(Node::Range(pipeline, body, else_body));
+                }
+                "define" => {
+                    let name = template_name(&tokens[1..], "define")?;
+                    let body = self.parse_end("define")?;
+                    self.templates.insert(name, body);
+                }
+                "block" => {
+                    let name = template_name(&tokens[1..], "block")?;
+                    let pipeline = parse_pipeline(&tokens[2..], false)?;
+                    let body = self.parse_end("block")?;
+                    self.templates.insert(name.clone(), body);
+                    nodes.push(Node::Template(name, Some(pipeline)));
+                }
+                "template" => {
+                    let name = template_name(&tokens[1..], "template")?;
+                    let pipeline = if tokens.len() > 2 {
+                        Some(parse_pipeline(&tokens[2..], false)?)
+                    } else {
+                        None
+                    };
+                    nodes.push(Node::Template(name, pipeline));
+                }
+                "break" => nodes.push(Node::Break),
+                "continue" => nodes.push(Node::Continue),
+                _ => nodes.push(Node::Action(parse_pipeline(&tokens, true)?)),
+            }
+        }
+        Ok((nodes, None))
+    }
+
+    /// Parse the `if` and `with` actions, `{{else if}}` and `{{else with}}`
+    /// are the sugar of the nested `if` and `with` actions in the else branch.
+    fn parse_branch(&mut self, keyword: &str, tokens: &[Token]) -> Result<Node, String> {
+        let pipeline = parse_pipeline(tokens, true)?;
+        let (body, stop) = self.parse_list()?;
+        let else_body = match stop {
+            Some(Stop::End) => vec![],
+            Some(Stop::Else(tokens)) => match tokens.first() {
+                None => self.parse_end(keyword)?,
+                Some(Token::Ident(ident)) if ident == keyword => {
+                    vec![self.parse_branch(keyword, &tokens[1..])?]
+                }
+                _ => return Err(format!("unexpected tokens after else in {keyword}")),
+            },
+            None => return Err(format!("unexpected EOF in {keyword}")),
+        };
+        Ok(match keyword {
+            "if" => Node::If(pipeline, body, else_body),
+            _ => Node::With(pipeline, body, else_body),
+        })
+    }
+
+    fn parse_end(&mut self, keyword: &str) -> Result<Vec<Node>, String> {
+        match self.parse_list()? {
+            (nodes, Some(Stop::End)) => Ok(nodes),
+            (_, Some(Stop::Else(_))) => Err(format!("unexpected {{{{else}}}} in {keyword}")),
+            (_, None) => Err(format!("unexpected EOF in {keyword}")),
+        }
+    }
+}
+
+fn template_name(tokens: &[Token], keyword: &str) -> Result<String, String> {
+    match tokens.first() {
+        Some(Token::Str(name)) => Ok(name.clone()),
+        _ => Err(format!("missing template name in {keyword}")),
+    }
+}
+
+/// Split the source into texts and actions, and apply the trim markers.
+fn split_items(src: &str) -> Result<Vec<Item>, String> {
+    let mut items = vec![];
+    let mut rest = src;
+    let mut trim_next = false;
+    while let Some(start) = rest.find("{{") {
+        let mut text = &rest[..start];
+        if trim_next {
+            text = text.trim_start();
+        }
+        let mut inner = &rest[start + 2..];
+        if inner.starts_with('-') && inner[1..].starts_with(char::is_whitespace) {
+            text = text.trim_end();
+            inner = &inner[1..];
+        }
+        items.push(Item::Text(text.to_string()));
+        let end = find_action_end(inner).ok_or("unclosed action")?;
+        let mut action = &inner[..end];
+        rest = &inner[end + 2..];
+        trim_next = false;
+        if action.ends_with('-') && action[..action.len() - 1].ends_with(char::is_whitespace) {
+            action = &action[..action.len() - 1];
+            trim_next = true;
+        }
+        let action = action.trim();
+        if action.starts_with("/*") {
+            if !action.ends_with("*/") {
+                return Err("unclosed comment".to_string());
+            }
+        } else {
+            items.push(Item::Action(action.to_string()));
+        }
+    }
+    items.push(Item::Text(if trim_next {
+        rest.trim_start().to_string()
+    } else {
+        rest.to_string()
+    }));
+    Ok(items)
+}
+
+/// Find the `}}` of the action outside of the string literals and comments.
+fn find_action_end(s: &str) -> Option<usize> {
+    if s.trim_start_matches(|c: char| c == '-' || c.is_whitespace())
+        .starts_with("/*")
+    {
+        let end = s.find("*/")?;
+        return s[end..].find("}}").map(|i| end + i);
+    }
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) => {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' && q == '"' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c == '"' || c == '`' || c == '\'' => quote = Some(c),
+            None if s[i..].starts_with("}}") => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+fn lex(s: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        match c {
+            '|' => tokens.push(Token::Pipe),
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            ',' => tokens.push(Token::Comma),
+            '=' => tokens.push(Token::Assign),
+            ':' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Declare);
+                i += 1;
+            }
+            '"' => {
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        Some('"') => break,
+                        Some('\\') => {
+                            i += 1;
+                            match chars.get(i) {
+                                Some('n') => value.push('\n'),
+                                Some('t') => value.push('\t'),
+                                Some('r') => value.push('\r'),
+                                Some(c) => value.push(*c),
+                                None => return Err("unterminated quoted string".to_string()),
+                            }
+                        }
+                        Some(c) => value.push(*c),
+                        None => return Err("unterminated quoted string".to_string()),
+                    }
+                    i += 1;
+                }
+                tokens.push(Token::Str(value));
+            }
+            '`' => {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && chars[i] != '`' {
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err("unterminated raw quoted string".to_string());
+                }
+                tokens.push(Token::Str(chars[start..i].iter().collect()));
+            }
+            '.' | '$' => {
+                let start = i;
+                i += 1;
+                let mut name = String::new();
+                if c == '$' {
+                    while i < chars.len() && is_ident_char(chars[i]) {
+                        name.push(chars[i]);
+                        i += 1;
+                    }
+                } else {
+                    i -= 1;
+                }
+                let mut path = vec![];
+                while i < chars.len() && chars[i] == '.' {
+                    let field_start = i + 1;
+                    i = field_start;
+                    while i < chars.len() && is_ident_char(chars[i]) {
+                        i += 1;
+                    }
+                    if i == field_start {
+                        // The single dot `.`.
+                        if c == '.' && i == start + 1 && path.is_empty() {
+                            break;
+                        }
+                        return Err(format!("bad character in field: {s}"));
+                    }
+                    path.push(chars[field_start..i].iter().collect());
+                }
+                tokens.push(if c == '$' {
+                    Token::Var(format!("${name}"), path)
+                } else {
+                    Token::Field(path)
+                });
+                continue;
+            }
+            c if c.is_ascii_digit()
+                || ((c == '-' || c == '+')
+                    && chars.get(i + 1).map_or(false, |c| c.is_ascii_digit())) =>
+            {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                    i += 1;
+                }
+                let literal: String = chars[start..i].iter().collect();
+                let int = match literal.strip_prefix("0x").or(literal.strip_prefix("0X")) {
+                    Some(hex) => i64::from_str_radix(hex, 16),
+                    None => literal.parse::<i64>(),
+                };
+                let number = match int {
+                    Ok(v) => serde_json::Number::from(v),
+                    Err(_) => literal
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .ok_or(format!("bad number syntax: {literal}"))?,
+                };
+                tokens.push(Token::Number(number));
+                continue;
+            }
+            c if is_ident_char(c) => {
+                let start = i;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+                tokens.push(match ident.as_str() {
+                    "true" => Token::Bool(true),
+                    "false" => Token::Bool(false),
+                    "nil" => Token::Nil,
+                    _ => Token::Ident(ident),
+                });
+                continue;
+            }
+            _ => return Err(format!("unexpected character '{c}' in action: {s}")),
+        }
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+/// Parse the pipeline tokens. `allow_decl` denotes whether the pipeline can
+/// declare or assign variables e.g., `$x := .a` and `$i, $v := .list`.
+fn parse_pipeline(tokens: &[Token], allow_decl: bool) -> Result<Pipeline, String> {
+    let mut pipeline = Pipeline::default();
+    let mut tokens = tokens;
+    if allow_decl {
+        let decl_end = tokens
+            .iter()
+            .position(|t| matches!(t, Token::Declare | Token::Assign));
+        if let Some(end) = decl_end {
+            let decl: Option<Vec<String>> = tokens[..end]
+                .split(|t| *t == Token::Comma)
+                .map(|t| match t {
+                    [Token::Var(name, path)] if path.is_empty() => Some(name.clone()),
+                    _ => None,
+                })
+                .collect();
+            if let Some(decl) = decl {
+                if decl.len() > 2 {
+                    return Err("too many declarations in command".to_string());
+                }
+                pipeline.is_assign = tokens[end] == Token::Assign;
+                pipeline.decl = decl;
+                tokens = &tokens[end + 1..];
+            }
+        }
+    }
+    let mut cmd = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Pipe => {
+                if cmd.is_empty() {
+                    return Err("missing command in pipeline".to_string());
+                }
+                pipeline.cmds.push(std::mem::take(&mut cmd));
+            }
+            Token::LParen => {
+                let mut depth = 0;
+                let start = i + 1;
+                loop {
+                    match tokens.get(i) {
+                        Some(Token::LParen) => depth += 1,
+                        Some(Token::RParen) => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        Some(_) => {}
+                        None => return Err("unclosed left paren".to_string()),
+                    }
+                    i += 1;
+                }
+                cmd.push(Arg::Pipeline(parse_pipeline(&tokens[start..i], false)?));
+            }
+            Token::RParen => return Err("unexpected right paren".to_string()),
+            Token::Field(path) => cmd.push(Arg::Field(path.clone())),
+            Token::Var(name, path) => cmd.push(Arg::Var(name.clone(), path.clone())),
+            Token::Ident(name) => cmd.push(Arg::Ident(name.clone())),
+            Token::Str(v) => cmd.push(Arg::Value(JsonValue::String(v.clone()))),
+            Token::Number(v) => cmd.push(Arg::Value(JsonValue::Number(v.clone()))),
+            Token::Bool(v) => cmd.push(Arg::Value(JsonValue::Bool(*v))),
+            Token::Nil => cmd.push(Arg::Value(JsonValue::Null)),
+            Token::Comma | Token::Declare | Token::Assign => {
+                return Err("unexpected declaration in pipeline".to_string())
+            }
+        }
+        i += 1;
+    }
+    if cmd.is_empty() {
+        return Err("missing value for command".to_string());
+    }
+    pipeline.cmds.push(cmd);
+    Ok(pipeline)
+}
+
+struct Exec<'a, 'b> {
+    templates: &'a HashMap<String, Vec<Node>>,
+    helpers: &'a IndexMap<String, ValueRef>,
+    invoke: &'a mut FuncInvoker<'b>,
+    strict: bool,
+    vars: Vec<(String, JsonValue)>,
+    ctx: Context,
+    depth: usize,
+}
+
+impl<'a, 'b> Exec<'a, 'b> {
+    fn exec_list(
+        &mut self,
+        nodes: &[Node],
+        dot: &JsonValue,
+        out: &mut String,
+    ) -> Result<Flow, String> {
+        for node in nodes {
+            let flow = self.exec_node(node, dot, out)?;
+            if flow != Flow::Normal {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn exec_node(
+        &mut self,
+        node: &Node,
+        dot: &JsonValue,
+        out: &mut String,
+    ) -> Result<Flow, String> {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Action(pipeline) => {
+                let value = self.eval_pipeline(pipeline, dot)?;
+                if pipeline.decl.is_empty() {
+                    out.push_str(&print_value(&value));
+                }
+            }
+            Node::If(pipeline, body, else_body) | Node::With(pipeline, body, else_body) => {
+                let vars_len = self.vars.len();
+                let value = self.eval_pipeline(pipeline, dot)?;
+                let flow = if is_true(&value) {
+                    match node {
+                        Node::With(..) => self.exec_list(body, &value, out)?,
+                        _ => self.exec_list(body, dot, out)?,
+                    }
+                } else {
+                    self.exec_list(else_body, dot, out)?
+                };
+                self.vars.truncate(vars_len);
+                return Ok(flow);
+            }
+            Node::Range(pipeline, body, else_body) => {
+                let vars_len = self.vars.len();
+                let mut range_pipeline = pipeline.clone();
+                let decl = std::mem::take(&mut range_pipeline.decl);
+                let value = self.eval_pipeline(&range_pipeline, dot)?;
+                let items: Vec<(JsonValue, JsonValue)> = match &value {
+                    JsonValue::Array(values) => values
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| (JsonValue::Number(i.into()), v.clone()))
+                        .collect(),
+                    JsonValue::Object(values) => {
+                        // Go iterates the map in the sorted key order.
+                        let mut keys: Vec<&String> = values.keys().collect();
+                        keys.sort();
+                        keys.into_iter()
+                            .map(|k| (JsonValue::String(k.clone()), values[k].clone()))
+                            .collect()
+                    }
+                    JsonValue::Number(n) if n.is_i64() => (0..n.as_i64().unwrap())
+                        .map(|i| (JsonValue::Number(i.into()), JsonValue::Number(i.into())))
+                        .collect(),
+                    JsonValue::Null => vec![],
+                    _ => return Err(format!("range can't iterate over {}", print_value(&value))),
+                };
+                if items.is_empty() {
+                    let flow = self.exec_list(else_body, dot, out)?;
+                    self.vars.truncate(vars_len);
+                    return Ok(flow);
+                }
+                for (key, value) in items {
+                    match decl.as_slice() {
+                        [v] => self.vars.push((v.clone(), value.clone())),
+                        [k, v] => {
+                            self.vars.push((k.clone(), key));
+                            self.vars.push((v.clone(), value.clone()));
+                        }
+                        _ => {}
+                    }
+                    let flow = self.exec_list(body, &value, out)?;
+                    self.vars.truncate(vars_len);
+                    if flow == Flow::Break {
+                        break;
+                    }
+                }
+            }
+            Node::Template(name, pipeline) => {
+                let value = match pipeline {
+                    Some(pipeline) => self.eval_pipeline(pipeline, dot)?,
+                    None => JsonValue::Null,
+                };
+                out.push_str(&self.exec_template(name, &value)?);
+            }
+            Node::Break => return Ok(Flow::Break),
+            Node::Continue => return Ok(Flow::Continue),
+        }
+        Ok(Flow::Normal)
+    }
+
+    /// Execute the named template with a new variable scope.
+    fn exec_template(&mut self, name: &str, dot: &JsonValue) -> Result<String, String> {
+        let templates = self.templates;
+        let nodes = templates
+            .get(name)
+            .ok_or_else(|| format!("no such template \"{name}\""))?;
+        if self.depth >= MAX_TEMPLATE_DEPTH {
+            return Err(format!(
+                "exceeded max template depth ({MAX_TEMPLATE_DEPTH}) in \"{name}\""
+            ));
+        }
+        let vars = std::mem::replace(&mut self.vars, vec![(ROOT_VAR.to_string(), dot.clone())]);
+        self.depth += 1;
+        let mut out = String::new();
+        let result = self.exec_list(nodes, dot, &mut out);
+        self.depth -= 1;
+        self.vars = vars;
+        result?;
+        Ok(out)
+    }
+
+    fn eval_pipeline(&mut self, pipeline: &Pipeline, dot: &JsonValue) -> Result<JsonValue, String> {
+        let mut value = None;
+        for cmd in &pipeline.cmds {
+            value = Some(self.eval_command(cmd, dot, value)?);
+        }
+        let value = value.unwrap_or(JsonValue::Null);
+        for name in &pipeline.decl {
+            if pipeline.is_assign {
+                match self.vars.iter_mut().rev().find(|(n, _)| n == name) {
+                    Some(var) => var.1 = value.clone(),
+                    None => return Err(format!("undefined variable: {name}")),
+                }
+            } else {
+                self.vars.push((name.clone(), value.clone()));
+            }
+        }
+        Ok(value)
+    }
+
+    fn eval_command(
+        &mut self,
+        cmd: &[Arg],
+        dot: &JsonValue,
+        last: Option<JsonValue>,
+    ) -> Result<JsonValue, String> {
+        match &cmd[0] {
+            Arg::Ident(name) => {
+                let mut args = cmd[1..]
+                    .iter()
+                    .map(|arg| self.eval_arg(arg, dot))
+                    .collect::<Result<Vec<JsonValue>, String>>()?;
+                if let Some(last) = last {
+                    args.push(last);
+                }
+                self.call_func(name, args)
+            }
+            arg => {
+                if cmd.len() > 1 || last.is_some() {
+                    return Err("can't give argument to non-function".to_string());
+                }
+                self.eval_arg(arg, dot)
+            }
+        }
+    }
+
+    fn eval_arg(&mut self, arg: &Arg, dot: &JsonValue) -> Result<JsonValue, String> {
+        match arg {
+            Arg::Field(path) => self.field(dot, path),
+            Arg::Var(name, path) => {
+                let value = match self.vars.iter().rev().find(|(n, _)| n == name) {
+                    Some((_, value)) => value.clone(),
+                    None => return Err(format!("undefined variable: {name}")),
+                };
+                self.field(&value, path)
+            }
+            Arg::Ident(name) => self.call_func(name, vec![]),
+            Arg::Value(value) => Ok(value.clone()),
+            Arg::Pipeline(pipeline) => self.eval_pipeline(pipeline, dot),
+        }
+    }
+
+    fn field(&self, value: &JsonValue, path: &[String]) -> Result<JsonValue, String> {
+        let mut value = value.clone();
+        for key in path {
+            value = match value {
+                JsonValue::Object(values) => match values.get(key) {
+                    Some(value) => value.clone(),
+                    None if self.strict => {
+                        return Err(format!("map has no entry for key \"{key}\""))
+                    }
+                    None => JsonValue::Null,
+                },
+                JsonValue::Null if self.strict => {
+                    return Err(format!("nil pointer evaluating .{key}"))
+                }
+                JsonValue::Null => JsonValue::Null,
+                _ => {
+                    return Err(format!(
+                        "can't evaluate field {key} in type {}",
+                        type_name(&value)
+                    ))
+                }
+            };
+        }
+        Ok(value)
+    }
+
+    fn call_func(&mut self, name: &str, args: Vec<JsonValue>) -> Result<JsonValue, String> {
+        let helpers = self.helpers;
+        if let Some(func) = helpers.get(name) {
+            let args: Vec<ValueRef> = args
+                .iter()
+                .map(|arg| ValueRef::parse_json(&mut self.ctx, arg))
+                .collect();
+            let value = (self.invoke)(func, &args);
+            return Ok(value.build_json(&Default::default()));
+        }
+        let argc = args.len();
+        let arity = |n: usize| {
+            if argc == n {
+                Ok(())
+            } else {
+                Err(format!(
+                    "wrong number of args for {name}: want {n} got {argc}"
+                ))
+            }
+        };
+        let mut args = args.into_iter();
+        let mut next = || args.next().unwrap_or(JsonValue::Null);
+        Ok(match name {
+            "and" | "or" => {
+                if argc == 0 {
+                    return Err(format!(
+                        "wrong number of args for {name}: want at least 1 got 0"
+                    ));
+                }
+                let mut result = JsonValue::Null;
+                for _ in 0..argc {
+                    result = next();
+                    if is_true(&result) == (name == "or") {
+                        break;
+                    }
+                }
+                result
+            }
+            "not" => {
+                arity(1)?;
+                JsonValue::Bool(!is_true(&next()))
+            }
+            "eq" => {
+                if argc < 2 {
+                    return Err(format!(
+                        "wrong number of args for eq: want at least 2 got {argc}"
+                    ));
+                }
+                let first = next();
+                let rest: Vec<JsonValue> = (1..argc).map(|_| next()).collect();
+                JsonValue::Bool(rest.iter().any(|v| json_eq(&first, v)))
+            }
+            "ne" => {
+                arity(2)?;
+                JsonValue::Bool(!json_eq(&next(), &next()))
+            }
+            "lt" | "le" | "gt" | "ge" => {
+                arity(2)?;
+                let ordering = json_cmp(&next(), &next())?;
+                JsonValue::Bool(match name {
+                    "lt" => ordering.is_lt(),
+                    "le" => ordering.is_le(),
+                    "gt" => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                })
+            }
+            "len" => {
+                arity(1)?;
+                let value = next();
+                JsonValue::Number(
+                    match &value {
+                        JsonValue::String(s) => s.len(),
+                        JsonValue::Array(v) => v.len(),
+                        JsonValue::Object(v) => v.len(),
+                        JsonValue::Null => 0,
+                        _ => return Err(format!("len of type {}", type_name(&value))),
+                    }
+                    .into(),
+                )
+            }
+            "index" => {
+                let mut value = next();
+                for _ in 1..argc {
+                    let key = next();
+                    value = match (&value, &key) {
+                        (JsonValue::Array(values), JsonValue::Number(i)) => {
+                            match i.as_u64().and_then(|i| values.get(i as usize)) {
+                                Some(v) => v.clone(),
+                                None => return Err(format!("index out of range: {i}")),
+                            }
+                        }
+                        (JsonValue::Object(values), JsonValue::String(k)) => {
+                            values.get(k).cloned().unwrap_or(JsonValue::Null)
+                        }
+                        (JsonValue::Null, _) => JsonValue::Null,
+                        _ => return Err(format!("can't index item of type {}", type_name(&value))),
+                    }
+                }
+                value
+            }
+            "print" | "println" => {
+                let values: Vec<JsonValue> = (0..argc).map(|_| next()).collect();
+                let mut result = String::new();
+                for (i, value) in values.iter().enumerate() {
+                    // `print` adds spaces between operands when neither is a string.
+                    if i > 0
+                        && (name == "println"
+                            || !matches!(value, JsonValue::String(_))
+                                && !matches!(values[i - 1], JsonValue::String(_)))
+                    {
+                        result.push(' ');
+                    }
+                    result.push_str(&print_value(value));
+                }
+                if name == "println" {
+                    result.push('\n');
+                }
+                JsonValue::String(result)
+            }
+            "printf" => {
+                let format = as_string(&next());
+                let values: Vec<JsonValue> = (1..argc).map(|_| next()).collect();
+                JsonValue::String(sprintf(&format, &values))
+            }
+            "html" => {
+                let s: Vec<String> = (0..argc).map(|_| print_value(&next())).collect();
+                JsonValue::String(handlebars::html_escape(&s.concat()))
+            }
+            "default" => {
+                let default = next();
+                let given = next();
+                if is_true(&given) {
+                    given
+                } else {
+                    default
+                }
+            }
+            "empty" => {
+                arity(1)?;
+                JsonValue::Bool(!is_true(&next()))
+            }
+            "coalesce" => (0..argc)
+                .map(|_| next())
+                .find(is_true)
+                .unwrap_or(JsonValue::Null),
+            "ternary" => {
+                arity(3)?;
+                let (a, b) = (next(), next());
+                if is_true(&next()) {
+                    a
+                } else {
+                    b
+                }
+            }
+            "required" => {
+                arity(2)?;
+                let message = as_string(&next());
+                let value = next();
+                if value == JsonValue::Null || value == JsonValue::String(String::new()) {
+                    return Err(message);
+                }
+                value
+            }
+            "fail" => {
+                arity(1)?;
+                return Err(as_string(&next()));
+            }
+            "quote" | "squote" => {
+                let values: Vec<String> = (0..argc)
+                    .map(|_| next())
+                    .filter(|v| *v != JsonValue::Null)
+                    .map(|v| {
+                        let s = print_value(&v);
+                        if name == "quote" {
+                            serde_json::to_string(&s).unwrap()
+                        } else {
+                            format!("'{s}'")
+                        }
+                    })
+                    .collect();
+                JsonValue::String(values.join(" "))
+            }
+            "upper" | "lower" | "title" | "trim" | "toString" | "b64enc" | "b64dec" => {
+                arity(1)?;
+                let s = print_value(&next());
+                JsonValue::String(match name {
+                    "upper" => s.to_uppercase(),
+                    "lower" => s.to_lowercase(),
+                    "title" => title(&s),
+                    "trim" => s.trim().to_string(),
+                    "b64enc" => ::base64::encode(s),
+                    "b64dec" => match ::base64::decode(&s) {
+                        Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+                        Err(err) => return Err(err.to_string()),
+                    },
+                    _ => s,
+                })
+            }
+            "trimPrefix" | "trimSuffix" | "contains" | "hasPrefix" | "hasSuffix" => {
+                arity(2)?;
+                let (arg, s) = (as_string(&next()), as_string(&next()));
+                match name {
+                    "trimPrefix" => JsonValue::String(s.strip_prefix(&arg).unwrap_or(&s).into()),
+                    "trimSuffix" => JsonValue::String(s.strip_suffix(&arg).unwrap_or(&s).into()),
+                    "contains" => JsonValue::Bool(s.contains(&arg)),
+                    "hasPrefix" => JsonValue::Bool(s.starts_with(&arg)),
+                    _ => JsonValue::Bool(s.ends_with(&arg)),
+                }
+            }
+            "replace" => {
+                arity(3)?;
+                let (old, new, s) = (as_string(&next()), as_string(&next()), as_string(&next()));
+                JsonValue::String(s.replace(&old, &new))
+            }
+            "repeat" | "indent" | "nindent" => {
+                arity(2)?;
+                let n = match next() {
+                    JsonValue::Number(n) => n.as_u64().unwrap_or_default() as usize,
+                    v => return Err(format!("{name} expects an int, got {}", type_name(&v))),
+                };
+                let s = as_string(&next());
+                JsonValue::String(match name {
+                    "repeat" => s.repeat(n),
+                    _ => {
+                        let pad = " ".repeat(n);
+                        let indented = format!("{pad}{}", s.replace('\n', &format!("\n{pad}")));
+                        if name == "nindent" {
+                            format!("\n{indented}")
+                        } else {
+                            indented
+                        }
+                    }
+                })
+            }
+            "join" => {
+                arity(2)?;
+                let sep = as_string(&next());
+                match next() {
+                    JsonValue::Array(values) => JsonValue::String(
+                        values
+                            .iter()
+                            .map(print_value)
+                            .collect::<Vec<String>>()
+                            .join(&sep),
+                    ),
+                    v => JsonValue::String(print_value(&v)),
+                }
+            }
+            "splitList" => {
+                arity(2)?;
+                let sep = as_string(&next());
+                let s = as_string(&next());
+                JsonValue::Array(
+                    s.split(sep.as_str())
+                        .map(|s| JsonValue::String(s.to_string()))
+                        .collect(),
+                )
+            }
+            "list" => JsonValue::Array((0..argc).map(|_| next()).collect()),
+            "dict" => {
+                let mut values = IndexMap::with_hasher(DefaultHashBuilder::default());
+                for _ in 0..(argc + 1) / 2 {
+                    values.insert(as_string(&next()), next());
+                }
+                JsonValue::Object(values)
+            }
+            "toYaml" => {
+                arity(1)?;
+                let s = serde_yaml::to_string(&next()).map_err(|err| err.to_string())?;
+                let s = s.strip_prefix("---\n").unwrap_or(&s);
+                JsonValue::String(s.trim_end_matches('\n').to_string())
+            }
+            "toJson" | "toPrettyJson" => {
+                arity(1)?;
+                let value = next();
+                let result = if name == "toJson" {
+                    serde_json::to_string(&value)
+                } else {
+                    serde_json::to_string_pretty(&value)
+                };
+                JsonValue::String(result.map_err(|err| err.to_string())?)
+            }
+            "include" => {
+                arity(2)?;
+                let name = as_string(&next());
+                JsonValue::String(self.exec_template(&name, &next())?)
+            }
+            _ => return Err(format!("function \"{name}\" not defined")),
+        })
+    }
+}
+
+/// The truth of the value is false for false, 0, None and any empty string, list or dict.
+fn is_true(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Null => false,
+        JsonValue::Bool(v) => *v,
+        JsonValue::Number(v) => v.as_f64().map_or(false, |v| v != 0.0),
+        JsonValue::String(v) => !v.is_empty(),
+        JsonValue::Array(v) => !v.is_empty(),
+        JsonValue::Object(v) => !v.is_empty(),
+    }
+}
+
+fn json_eq(a: &JsonValue, b: &JsonValue) -> bool {
+    match (a, b) {
+        (JsonValue::Number(a), JsonValue::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => a == b,
+    }
+}
+
+fn json_cmp(a: &JsonValue, b: &JsonValue) -> Result<std::cmp::Ordering, String> {
+    match (a, b) {
+        (JsonValue::Number(x), JsonValue::Number(y)) => x
+            .as_f64()
+            .partial_cmp(&y.as_f64())
+            .ok_or_else(|| "invalid number comparison".to_string()),
+        (JsonValue::String(x), JsonValue::String(y)) => Ok(x.cmp(y)),
+        _ => Err(format!(
+            "incompatible types for comparison: {} and {}",
+            type_name(a),
+            type_name(b)
+        )),
+    }
+}
+
+fn type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "nil",
+        JsonValue::Bool(_) => "bool",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "list",
+        JsonValue::Object(_) => "map",
+    }
+}
+
+fn as_string(value: &JsonValue) -> String {
+    print_value(value)
+}
+
+/// Print the value in the Go `fmt.Sprint` style, and None is printed as an empty string.
+fn print_value(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Null => String::new(),
+        JsonValue::Bool(v) => v.to_string(),
+        JsonValue::Number(v) => v.to_string(),
+        JsonValue::String(v) => v.clone(),
+        JsonValue::Array(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(print_value)
+                .collect::<Vec<String>>()
+                .join(" ")
+        ),
+        JsonValue::Object(values) => {
+            let mut keys: Vec<&String> = values.keys().collect();
+            keys.sort();
+            format!(
+                "map[{}]",
+                keys.iter()
+                    .map(|k| format!("{}:{}", k, print_value(&values[*k])))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            )
+        }
+    }
+}
+
+fn title(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut is_word_start = true;
+    for c in s.chars() {
+        if is_word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.push(c);
+        }
+        is_word_start = c.is_whitespace();
+    }
+    result
+}
+
+/// A subset of the Go `fmt.Sprintf` verbs: `%v`, `%s`, `%d`, `%f`, `%q`, `%t` and `%%`
+/// with the `-` and `0` flags, the width and the precision.
+fn sprintf(format: &str, values: &[JsonValue]) -> String {
+    let mut result = String::new();
+    let mut values = values.iter();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        let mut left_align = false;
+        let mut zero_pad = false;
+        while let Some(c) = chars.peek().filter(|c| matches!(c, '-' | '0')) {
+            if *c == '-' {
+                left_align = true;
+            } else {
+                zero_pad = true;
+            }
+            chars.next();
+        }
+        let mut width = String::new();
+        while let Some(c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+            width.push(*c);
+            chars.next();
+        }
+        // Precision for the `%f` verb e.g., `%.2f`.
+        let mut precision = String::new();
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            while let Some(c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                precision.push(*c);
+                chars.next();
+            }
+        }
+        match chars.next() {
+            Some('%') => result.push('%'),
+            Some(verb) => match values.next() {
+                Some(value) => {
+                    let s = match verb {
+                        'q' => serde_json::to_string(&print_value(value)).unwrap(),
+                        'f' => {
+                            let v = match value {
+                                JsonValue::Number(n) => n.as_f64().unwrap_or_default(),
+                                _ => 0.0,
+                            };
+                            let precision = precision.parse::<usize>().unwrap_or(6);
+                            format!("{v:.precision$}")
+                        }
+                        _ => print_value(value),
+                    };
+                    let padding = width
+                        .parse::<usize>()
+                        .unwrap_or_default()
+                        .saturating_sub(s.chars().count());
+                    if left_align {
+                        result.push_str(&s);
+                        result.push_str(&" ".repeat(padding));
+                    } else if zero_pad && matches!(verb, 'd' | 'f') {
+                        // The zeros are padded after the sign.
+                        let (sign, digits) = match s.strip_prefix('-') {
+                            Some(digits) => ("-", digits),
+                            None => ("", s.as_str()),
+                        };
+                        result.push_str(sign);
+                        result.push_str(&"0".repeat(padding));
+                        result.push_str(digits);
+                    } else {
+                        result.push_str(&" ".repeat(padding));
+                        result.push_str(&s);
+                    }
+                }
+                None => result.push_str(&format!("%!{verb}(MISSING)")),
+            },
+            None => result.push_str("%!(NOVERB)"),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test_gotemplate {
+    use super::*;
+
+    fn render_str(template: &str, data: &str) -> Result<String, String> {
+        render_with(template, data, false)
+    }
+
+    fn render_with(template: &str, data: &str, strict: bool) -> Result<String, String> {
+        let data: JsonValue = serde_json::from_str(data).unwrap();
+        let mut partials = IndexMap::with_hasher(DefaultHashBuilder::default());
+        partials.insert("greeting".to_string(), "hello {{ . }}".to_string());
+        render(
+            template,
+            &data,
+            &partials,
+            &IndexMap::with_hasher(DefaultHashBuilder::default()),
+            strict,
+            &mut |_, _| ValueRef::none(),
+        )
+    }
+
+    #[test]
+    fn test_render() {
+        let data = r#"{"name": "app", "replicas": 2, "labels": {"b": "2", "a": "1"}, "ports": [80, 443], "empty": ""}"#;
+        let cases = [
+            ("name: {{ .name }}", "name: app"),
+            ("{{ .name | upper | quote }}", "\"APP\""),
+            (
+                "{{ if gt .replicas 1 -}} ha {{- else -}} single {{- end }}",
+                "ha",
+            ),
+            ("{{ if .missing }}a{{ else if .name }}b{{ end }}", "b"),
+            ("{{ with .labels }}{{ .a }}{{ end }}", "1"),
+            (
+                "{{ range $k, $v := .labels }}{{ $k }}={{ $v }};{{ end }}",
+                "a=1;b=2;",
+            ),
+            (
+                "{{ range .ports }}{{ if eq . 443 }}{{ break }}{{ end }}{{ . }}{{ end }}",
+                "80",
+            ),
+            ("{{ range .missing }}x{{ else }}none{{ end }}", "none"),
+            ("{{ $x := .name }}{{ $x = \"web\" }}{{ $x }}", "web"),
+            ("{{ .empty | default \"dft\" }}", "dft"),
+            (
+                "{{ printf \"%s-%d-%.1f\" .name .replicas 1.25 }}",
+                "app-2-1.2",
+            ),
+            ("{{ template \"greeting\" .name }}", "hello app"),
+            (
+                "{{ define \"t\" }}[{{ . }}]{{ end }}{{ include \"t\" .name | upper }}",
+                "[APP]",
+            ),
+            (
+                "{{/* comment */}}{{ len .ports }} {{ index .ports 1 }}",
+                "2 443",
+            ),
+            (
+                "ports:{{ toYaml .ports | nindent 2 }}",
+                "ports:\n  - 80\n  - 443",
+            ),
+            ("{{ .labels }} {{ .ports }}", "map[a:1 b:2] [80 443]"),
+            (
+                "{{ and .name .empty | not }} {{ or .empty .name }}",
+                "true app",
+            ),
+        ];
+        for (template, expected) in cases {
+            assert_eq!(render_str(template, data).unwrap(), expected, "{template}");
+        }
+    }
+
+    #[test]
+    fn test_render_error() {
+        let data = r#"{"name": "app"}"#;
+        assert_eq!(render_str("{{ .missing }}", data).unwrap(), "");
+        assert!(render_with("{{ .missing }}", data, true).is_err());
+        assert!(render_str("{{ if .name }}", data).is_err());
+        assert!(render_str("{{ end }}", data).is_err());
+        assert!(render_str("{{ unknown .name }}", data).is_err());
+        assert!(render_str("{{ required \"name is required\" .missing }}", data).is_err());
+        assert!(render_str("{{ template \"undefined\" }}", data).is_err());
+    }
+
+    #[test]
+    fn test_render_functions() {
+        let data = r#"{"name": "app", "replicas": 2, "labels": {"b": "2", "a": "1"}, "ports": [80, 443], "empty": "", "text": " a\nb ", "名前": "値"}"#;
+        let cases = [
+            (
+                "{{ eq .replicas 1 2 }} {{ eq 2 2.0 }} {{ ne .name \"x\" }}",
+                "true true true",
+            ),
+            (
+                "{{ lt \"a\" \"b\" }} {{ le 2 2 }} {{ gt 1.5 2 }} {{ ge .replicas 3 }}",
+                "true true false false",
+            ),
+            ("{{ and 1 0 2 }} {{ or 0 \"\" \"x\" }} {{ not .empty }}", "0 x true"),
+            (
+                "{{ len .name }} {{ len .labels }} {{ len .missing }} {{ len \"値\" }}",
+                "3 2 0 3",
+            ),
+            (
+                "{{ index .labels \"a\" }} {{ index .missing \"a\" }}|{{ index (list (list 1 2)) 0 1 }}",
+                "1 |2",
+            ),
+            ("{{ print 1 2 \"a\" \"b\" 3 }}", "1 2ab3"),
+            ("{{ println 1 \"a\" }}", "1 a\n"),
+            ("{{ print (len .ports) (upper (lower .name)) }}", "2APP"),
+            (
+                "{{ printf \"%v|%q|%t|%%|%d\" .ports .name true }}",
+                "[80 443]|\"app\"|true|%|%!d(MISSING)",
+            ),
+            (
+                "{{ printf \"[%5d][%-4s][%05.1f][%03d][%\" 42 \"ab\" 3.14159 -7 }}",
+                "[   42][ab  ][003.1][-07][%!(NOVERB)",
+            ),
+            ("{{ html \"<b>&\" }}", "&lt;b&gt;&amp;"),
+            (
+                "{{ default \"d\" .missing }} {{ default \"d\" 0 }} {{ .name | default \"d\" }}",
+                "d d app",
+            ),
+            ("{{ empty .empty }} {{ empty .ports }}", "true false"),
+            ("{{ coalesce .missing .empty .name }}", "app"),
+            (
+                "{{ ternary \"y\" \"n\" true }}{{ ternary \"y\" \"n\" .empty }}",
+                "yn",
+            ),
+            (
+                "{{ quote .name 1 nil }} {{ squote .name }}",
+                "\"app\" \"1\" 'app'",
+            ),
+            (
+                "{{ upper \"é\" }} {{ lower \"ABC\" }} {{ title \"hello  world\" }} [{{ trim .text }}]",
+                "É abc Hello  World [a\nb]",
+            ),
+            (
+                "{{ toString 1 }} {{ b64enc .name }} {{ b64dec \"YXBw\" }} {{ b64enc \"値\" }}",
+                "1 YXBw app 5YCk",
+            ),
+            (
+                "{{ trimPrefix \"a\" .name }} {{ trimSuffix \"p\" .name }} {{ contains \"pp\" .name }} {{ hasPrefix \"ap\" .name }} {{ hasSuffix \"x\" .name }}",
+                "pp ap true true false",
+            ),
+            (
+                "{{ replace \"p\" \"P\" .name }} {{ repeat 3 \"ab\" }}",
+                "aPP ababab",
+            ),
+            ("{{ \"a\\nb\" | indent 2 }}", "  a\n  b"),
+            (
+                "{{ join \",\" .ports }} {{ join \",\" .name }} {{ splitList \",\" \"a,b,,c\" | len }}",
+                "80,443 app 4",
+            ),
+            (
+                "{{ index (list 1 \"a\" nil) 1 }} {{ index (dict \"a\" 1 \"b\" 2) \"b\" }} {{ dict \"k\" }}",
+                "a 2 map[k:]",
+            ),
+            (
+                "{{ toJson .labels }} {{ toJson (list 1 \"a\" nil true) }}",
+                "{\"b\":\"2\",\"a\":\"1\"} [1,\"a\",null,true]",
+            ),
+            ("{{ toPrettyJson (list 1) }}", "[\n  1\n]"),
+            ("{{ .名前 }} {{ \"日本\" | upper | len }}", "値 6"),
+        ];
+        for (template, expected) in cases {
+            assert_eq!(render_str(template, data).unwrap(), expected, "{template}");
+        }
+    }
+
+    #[test]
+    fn test_render_actions() {
+        let data =
+            r#"{"name": "app", "replicas": 2, "labels": {"b": "2", "a": "1"}, "ports": [80, 443]}"#;
+        let cases = [
+            ("{{ range 3 }}{{ . }}{{ end }}", "012"),
+            (
+                "{{ range $i, $p := .ports }}{{ $i }}:{{ $p }} {{ end }}",
+                "0:80 1:443 ",
+            ),
+            ("{{ range $p := .ports }}{{ $p }}{{ end }}", "80443"),
+            (
+                "{{ range .ports }}{{ if eq . 80 }}{{ continue }}{{ end }}{{ . }}{{ end }}",
+                "443",
+            ),
+            ("{{ range .labels }}{{ . }}{{ end }}", "12"),
+            ("{{ range 0 }}x{{ else }}none{{ end }}", "none"),
+            (
+                "{{ with .missing }}x{{ else with .name }}{{ . }}{{ end }}",
+                "app",
+            ),
+            ("{{ with .labels }}{{ $.name }}-{{ .b }}{{ end }}", "app-2"),
+            ("{{ if $x := .name }}{{ $x }}{{ end }}", "app"),
+            (
+                "{{ $x := 1 }}{{ range .ports }}{{ $x = . }}{{ end }}{{ $x }}",
+                "443",
+            ),
+            ("a  {{- .name -}}  b {{-3}}", "aappb -3"),
+            ("{{- /* comment */ -}}\n  x", "x"),
+            (
+                "{{ \"}}\" }} {{ `a\\n` }} {{ 0x1F }} {{ -1.5 }}",
+                "}} a\\n 31 -1.5",
+            ),
+            ("{{ block \"b\" .name }}[{{ . }}]{{ end }}", "[app]"),
+            (
+                "{{ define \"t\" }}{{ $ }}{{ end }}{{ $x := 1 }}{{ template \"t\" .replicas }}{{ $x }}",
+                "21",
+            ),
+            ("{{ template \"greeting\" }}", "hello "),
+        ];
+        for (template, expected) in cases {
+            assert_eq!(render_str(template, data).unwrap(), expected, "{template}");
+        }
+    }
+
+    #[test]
+    fn test_render_error_message() {
+        let data = r#"{"name": "app", "labels": {"a": "1"}, "ports": [80, 443], "n": null}"#;
+        assert_eq!(render_str("{{ .missing.a }}", data).unwrap(), "");
+        for (template, expected) in [
+            ("{{ .missing }}", "map has no entry for key \"missing\""),
+            ("{{ .missing.a }}", "map has no entry for key \"missing\""),
+            ("{{ .n.a }}", "nil pointer evaluating .a"),
+        ] {
+            assert_eq!(
+                render_with(template, data, true).unwrap_err(),
+                expected,
+                "{template}"
+            );
+        }
+        let cases = [
+            ("{{ .labels.a.b }}", "can't evaluate field b in type string"),
+            ("{{ $x }}", "undefined variable: $x"),
+            ("{{ $x = 1 }}", "undefined variable: $x"),
+            (
+                "{{ if true }}{{ $y := 1 }}{{ end }}{{ $y }}",
+                "undefined variable: $y",
+            ),
+            (
+                "{{ $x := 1 }}{{ define \"t\" }}{{ $x }}{{ end }}{{ template \"t\" }}",
+                "undefined variable: $x",
+            ),
+            ("{{ unknown }}", "function \"unknown\" not defined"),
+            ("{{ not }}", "wrong number of args for not: want 1 got 0"),
+            (
+                "{{ toJson }}",
+                "wrong number of args for toJson: want 1 got 0",
+            ),
+            (
+                "{{ eq 1 }}",
+                "wrong number of args for eq: want at least 2 got 1",
+            ),
+            (
+                "{{ lt 1 \"a\" }}",
+                "incompatible types for comparison: number and string",
+            ),
+            ("{{ index .ports 2 }}", "index out of range: 2"),
+            ("{{ index .name 0 }}", "can't index item of type string"),
+            ("{{ len 1 }}", "len of type number"),
+            ("{{ range .name }}{{ end }}", "range can't iterate over app"),
+            (
+                "{{ repeat \"a\" \"b\" }}",
+                "repeat expects an int, got string",
+            ),
+            ("{{ fail \"boom\" }}", "boom"),
+            (
+                "{{ required \"name is required\" .missing }}",
+                "name is required",
+            ),
+            ("{{ break }}", "break or continue outside of a range"),
+            (
+                "{{ template \"undefined\" }}",
+                "no such template \"undefined\"",
+            ),
+            (
+                "{{ define \"r\" }}{{ template \"r\" }}{{ end }}{{ template \"r\" }}",
+                "exceeded max template depth (100) in \"r\"",
+            ),
+            ("{{ .name 1 }}", "can't give argument to non-function"),
+            ("{{ }}", "missing value for command"),
+            ("{{ | upper }}", "missing command in pipeline"),
+            ("{{ (upper .name }}", "unclosed left paren"),
+            ("{{ .name) }}", "unexpected right paren"),
+            (
+                "{{ $a, $b, $c := .ports }}",
+                "too many declarations in command",
+            ),
+            ("{{ .name", "unclosed action"),
+            ("{{ \"abc }}", "unclosed action"),
+            ("{{/* comment }}", "unclosed action"),
+            ("{{ 1x }}", "bad number syntax: 1x"),
+            ("{{ .a. }}", "bad character in field: .a."),
+            ("{{ # }}", "unexpected character '#' in action: #"),
+            ("{{ end }}", "unexpected {{end}}"),
+            ("{{ else }}", "unexpected {{else}}"),
+            ("{{ if .name }}", "unexpected EOF in if"),
+            ("{{ range .ports }}", "unexpected EOF in range"),
+            (
+                "{{ if .a }}{{ else }}{{ else }}{{ end }}",
+                "unexpected {{else}} in if",
+            ),
+            (
+                "{{ if .a }}{{ else range .b }}{{ end }}",
+                "unexpected tokens after else in if",
+            ),
+            ("{{ define }}{{ end }}", "missing template name in define"),
+        ];
+        for (template, expected) in cases {
+            assert_eq!(
+                render_str(template, data).unwrap_err(),
+                expected,
+                "{template}"
+            );
+        }
+        assert!(render_str("{{ b64dec \"!\" }}", data).is_err());
+    }
+
+    #[test]
+    fn test_render_helpers() {
+        let data: JsonValue = serde_json::from_str(r#"{"replicas": 2, "name": "app"}"#).unwrap();
+        let mut helpers = IndexMap::with_hasher(DefaultHashBuilder::default());
+        // The helpers take precedence over the builtin functions.
+        for name in ["double", "upper"] {
+            helpers.insert(
+                name.to_string(),
+                ValueRef::func(0, 0, ValueRef::undefined(), name, "", false),
+            );
+        }
+        let result = render(
+            "{{ double .replicas }} {{ .replicas | double | double }} {{ upper .name 1 }}",
+            &data,
+            &IndexMap::with_hasher(DefaultHashBuilder::default()),
+            &helpers,
+            false,
+            &mut |func, args| match func.as_function().name.as_str() {
+                "double" => ValueRef::int(args[0].as_int() * 2),
+                _ => ValueRef::str(&format!("{}:{}", args.len(), args[0].as_str())),
+            },
+        );
+        assert_eq!(result.unwrap(), "4 8 2:app");
+    }
+}
@@ -1,7 +1,21 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{mpsc, Mutex},
+};
 
 use crate::*;
-use handlebars::{html_escape, Handlebars};
+use handlebars::{
+    html_escape, Handlebars, Helper, HelperDef, RenderContext, RenderError, RenderErrorReason,
+    ScopedJson,
+};
+use kclvm_primitives::{DefaultHashBuilder, IndexMap};
+
+mod gotemplate;
+
+const HANDLEBARS_ENGINE: &str = "handlebars";
+const GO_ENGINE: &str = "go";
+
+// template, data={}, partials={}, helpers={}, strict=False, engine="handlebars"
 
 /// Applies a parsed template to the specified data object and
 /// returns the string output.
@@ -14,28 +28,185 @@ pub extern "C-unwind" fn kclvm_template_execute(
 ) -> *const kclvm_value_ref_t {
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
-    let ctx = mut_ptr_as_ref(ctx);
+    let value = template_execute(args, kwargs, &mut |func, args| invoke_func(ctx, func, args));
+    value.into_raw(mut_ptr_as_ref(ctx))
+}
 
+/// Render the template with the `handlebars` or the Go template engine. KCL
+/// functions in `helpers` are called through `invoke` while rendering.
+pub(crate) fn template_execute(
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    invoke: &mut FuncInvoker,
+) -> ValueRef {
     if let Some(template) = get_call_arg_str(args, kwargs, 0, Some("template")) {
-        let mut handlebars = Handlebars::new();
-        handlebars
-            .register_template_string("template", template)
-            .expect("register template failed");
         let data = get_call_arg(args, kwargs, 1, Some("data")).unwrap_or(ValueRef::dict(None));
-        let data: HashMap<String, JsonValue> = HashMap::from_iter(
-            data.as_dict_ref()
-                .values
-                .iter()
-                .map(|(k, v)| (k.to_string(), v.build_json(&Default::default()))),
-        );
-        let result = handlebars
-            .render("template", &data)
-            .expect("render template failed");
-        return ValueRef::str(&result).into_raw(ctx);
+        let mut partials = IndexMap::with_hasher(DefaultHashBuilder::default());
+        if let Some(values) = get_call_arg(args, kwargs, 2, Some("partials")) {
+            if !values.is_none_or_undefined() {
+                for (name, partial) in &values.as_dict_ref().values {
+                    partials.insert(name.to_string(), partial.as_str());
+                }
+            }
+        }
+        let mut helpers = IndexMap::with_hasher(DefaultHashBuilder::default());
+        if let Some(values) = get_call_arg(args, kwargs, 3, Some("helpers")) {
+            if !values.is_none_or_undefined() {
+                for (name, helper) in &values.as_dict_ref().values {
+                    if !helper.is_func() {
+                        panic!("template helper '{}' must be a function", name);
+                    }
+                    helpers.insert(name.to_string(), helper.clone());
+                }
+            }
+        }
+        let strict = get_call_arg_bool(args, kwargs, 4, Some("strict")).unwrap_or_default();
+        let engine = get_call_arg_str(args, kwargs, 5, Some("engine"))
+            .unwrap_or_else(|| HANDLEBARS_ENGINE.to_string());
+        let result = match engine.as_str() {
+            HANDLEBARS_ENGINE => {
+                render_handlebars(&template, &data, &partials, &helpers, strict, invoke)
+            }
+            GO_ENGINE => gotemplate::render(
+                &template,
+                &data.build_json(&Default::default()),
+                &partials,
+                &helpers,
+                strict,
+                invoke,
+            ),
+            _ => panic!(
+                "invalid template engine '{}', expected '{}' or '{}'",
+                engine, HANDLEBARS_ENGINE, GO_ENGINE
+            ),
+        };
+        return match result {
+            Ok(result) => ValueRef::str(&result),
+            Err(err) => panic!("{}", err),
+        };
     }
     panic!("execute() takes exactly one argument (0 given)");
 }
 
+fn render_handlebars(
+    template: &str,
+    data: &ValueRef,
+    partials: &IndexMap<String, String>,
+    helpers: &IndexMap<String, ValueRef>,
+    strict: bool,
+    invoke: &mut FuncInvoker,
+) -> Result<String, String> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(strict);
+    handlebars
+        .register_template_string("template", template)
+        .map_err(|err| format!("register template failed: {err}"))?;
+    for (name, partial) in partials {
+        handlebars
+            .register_partial(name, partial)
+            .map_err(|err| format!("register partial '{name}' failed: {err}"))?;
+    }
+    let data: HashMap<String, JsonValue> = HashMap::from_iter(
+        data.as_dict_ref()
+            .values
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.build_json(&Default::default()))),
+    );
+    if helpers.is_empty() {
+        return handlebars
+            .render("template", &data)
+            .map_err(|err| format!("render template failed: {err}"));
+    }
+    // The handlebars registry requires `Send` and `Sync` helpers, but KCL values
+    // and functions are bound to the current thread. Render the template on a
+    // scoped thread and serve the helper calls on the current thread.
+    let (call_tx, call_rx) = mpsc::channel::<HelperCall>();
+    for name in helpers.keys() {
+        handlebars.register_helper(
+            name,
+            Box::new(KclHelper {
+                name: name.to_string(),
+                calls: Mutex::new(call_tx.clone()),
+            }),
+        );
+    }
+    drop(call_tx);
+    std::thread::scope(|scope| {
+        // The helpers drop their senders with the registry when the rendering
+        // is finished, which ends the loop below.
+        let render = scope.spawn(move || {
+            handlebars
+                .render("template", &data)
+                .map_err(|err| format!("render template failed: {err}"))
+        });
+        for call in call_rx {
+            let _ = call
+                .result
+                .send(call_helper(&helpers[&call.name], &call.args, invoke));
+        }
+        render
+            .join()
+            .unwrap_or_else(|err| std::panic::resume_unwind(err))
+    })
+}
+
+fn call_helper(
+    func: &ValueRef,
+    args: &[serde_json::Value],
+    invoke: &mut FuncInvoker,
+) -> Result<serde_json::Value, String> {
+    let mut ctx = Context::new();
+    let args = args
+        .iter()
+        .map(|arg| ValueRef::from_json(&mut ctx, &arg.to_string()).map_err(|err| err.to_string()))
+        .collect::<Result<Vec<ValueRef>, String>>()?;
+    let value = invoke(func, &args);
+    serde_json::from_str(&value.to_json_string()).map_err(|err| err.to_string())
+}
+
+/// A helper call sent from the rendering thread to the thread owning the KCL
+/// functions.
+struct HelperCall {
+    name: String,
+    args: Vec<serde_json::Value>,
+    result: mpsc::Sender<Result<serde_json::Value, String>>,
+}
+
+/// A handlebars helper which calls the KCL function with the helper parameters.
+struct KclHelper {
+    name: String,
+    calls: Mutex<mpsc::Sender<HelperCall>>,
+}
+
+impl HelperDef for KclHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc handlebars::Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        let (result_tx, result_rx) = mpsc::channel();
+        let call = HelperCall {
+            name: self.name.clone(),
+            args: h.params().iter().map(|p| p.value().clone()).collect(),
+            result: result_tx,
+        };
+        self.calls
+            .lock()
+            .map_err(|err| RenderErrorReason::Other(err.to_string()))?
+            .send(call)
+            .map_err(|err| RenderErrorReason::Other(err.to_string()))?;
+        // The result sender is dropped without a result when the KCL function
+        // panics, and the panic is resumed on the calling thread.
+        let value = result_rx
+            .recv()
+            .map_err(|err| RenderErrorReason::Other(err.to_string()))?
+            .map_err(RenderErrorReason::Other)?;
+        Ok(ScopedJson::Derived(value))
+    }
+}
+
 /// Replaces the characters `&"<>` with the equivalent html / xml entities.
 #[no_mangle]
 #[runtime_fn]
@@ -53,3 +224,85 @@ pub extern "C-unwind" fn kclvm_template_html_escape(
     }
     panic!("html_escape() takes exactly one argument (0 given)");
 }
+
+#[cfg(test)]
+mod test_template {
+    use super::*;
+
+    fn execute(kwargs: &[(&str, &ValueRef)]) -> String {
+        let args = ValueRef::list(None);
+        let kwargs = ValueRef::dict(Some(kwargs));
+        template_execute(&args, &kwargs, &mut |_, args| {
+            ValueRef::str(&args[0].as_str().to_uppercase())
+        })
+        .as_str()
+    }
+
+    #[test]
+    fn test_template_execute() {
+        let data = ValueRef::dict(Some(&[("name", &ValueRef::str("app"))]));
+        let partials = ValueRef::dict(Some(&[("greeting", &ValueRef::str("hello {{name}}"))]));
+        assert_eq!(
+            execute(&[
+                ("template", &ValueRef::str("{{> greeting}}!")),
+                ("data", &data),
+                ("partials", &partials),
+            ]),
+            "hello app!"
+        );
+        assert_eq!(
+            execute(&[
+                ("template", &ValueRef::str("{{ .name }}")),
+                ("data", &data),
+                ("engine", &ValueRef::str(GO_ENGINE)),
+            ]),
+            "app"
+        );
+    }
+    #[test]
+    fn test_template_helpers() {
+        let helper = ValueRef::func(0, 0, ValueRef::undefined(), "upper", "", false);
+        let helpers = ValueRef::dict(Some(&[("upper", &helper)]));
+        let data = ValueRef::dict(Some(&[("name", &ValueRef::str("app"))]));
+        for (engine, template) in [
+            (HANDLEBARS_ENGINE, "{{upper name}}-{{upper \"名前\"}}"),
+            (GO_ENGINE, "{{ upper .name }}-{{ \"名前\" | upper }}"),
+        ] {
+            assert_eq!(
+                execute(&[
+                    ("template", &ValueRef::str(template)),
+                    ("data", &data),
+                    ("helpers", &helpers),
+                    ("engine", &ValueRef::str(engine)),
+                ]),
+                "APP-名前"
+            );
+        }
+        // Multiple parameters and the JSON values returned by the helper.
+        let args = ValueRef::list(None);
+        let kwargs = ValueRef::dict(Some(&[
+            (
+                "template",
+                &ValueRef::str("{{#each (pair name 1)}}[{{this}}]{{/each}}"),
+            ),
+            ("data", &data),
+            ("helpers", &ValueRef::dict(Some(&[("pair", &helper)]))),
+        ]));
+        let value = template_execute(&args, &kwargs, &mut |_, args| {
+            ValueRef::list(Some(&[&args[0], &args[1]]))
+        });
+        assert_eq!(value.as_str(), "[app][1]");
+    }
+
+    #[test]
+    #[should_panic(expected = "helper failed")]
+    fn test_template_helper_panic() {
+        let helper = ValueRef::func(0, 0, ValueRef::undefined(), "fail", "", false);
+        let args = ValueRef::list(None);
+        let kwargs = ValueRef::dict(Some(&[
+            ("template", &ValueRef::str("{{fail 1}}")),
+            ("helpers", &ValueRef::dict(Some(&[("fail", &helper)]))),
+        ]));
+        template_execute(&args, &kwargs, &mut |_, _| panic!("helper failed"));
+    }
+}
@@ -340,6 +340,7 @@ pub unsafe extern "C-unwind" fn kclvm_value_schema_function(
     fn_ptr: *const u64,
     check_fn_ptr: *const u64,
     attr_map: *const kclvm_value_ref_t,
+    bases: *const kclvm_value_ref_t,
     tpe: *const kclvm_char_t,
 ) -> *mut kclvm_value_ref_t {
     // Schema function closures
@@ -391,6 +392,17 @@ pub unsafe extern "C-unwind" fn kclvm_value_schema_function(
         func: schema_func.clone(),
     };
     ctx.all_schemas.insert(runtime_type.to_string(), schema_ty);
+    // The parent schema and mixin functions.
+    let bases = ptr_as_ref(bases);
+    ctx.schema_bases.insert(
+        runtime_type.to_string(),
+        bases
+            .as_list_ref()
+            .values
+            .iter()
+            .filter_map(|base| base.try_get_runtime_type())
+            .collect(),
+    );
     new_mut_ptr(ctx, schema_func)
 }
 
@@ -1348,6 +1360,64 @@ pub unsafe extern "C-unwind" fn kclvm_value_len(p: *const kclvm_value_ref_t) ->
     p.len() as kclvm_size_t
 }
 
+// match
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_value_match_sequence(
+    p: *const kclvm_value_ref_t,
+    len: kclvm_size_t,
+    has_rest: kclvm_bool_t,
+) -> kclvm_bool_t {
+    let p = ptr_as_ref(p);
+    p.match_sequence(len as usize, has_rest != 0) as kclvm_bool_t
+}
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_value_match_mapping(
+    p: *const kclvm_value_ref_t,
+    keys: *const kclvm_value_ref_t,
+) -> kclvm_bool_t {
+    let p = ptr_as_ref(p);
+    let keys = ptr_as_ref(keys);
+    let keys: Vec<String> = keys
+        .as_list_ref()
+        .values
+        .iter()
+        .map(|k| k.as_str())
+        .collect();
+    p.match_mapping(&keys) as kclvm_bool_t
+}
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_value_match_rest_mapping(
+    ctx: *mut kclvm_context_t,
+    p: *const kclvm_value_ref_t,
+    keys: *const kclvm_value_ref_t,
+) -> *mut kclvm_value_ref_t {
+    let p = ptr_as_ref(p);
+    let keys = ptr_as_ref(keys);
+    let keys: Vec<String> = keys
+        .as_list_ref()
+        .values
+        .iter()
+        .map(|k| k.as_str())
+        .collect();
+    p.match_rest_mapping(&keys).into_raw(mut_ptr_as_ref(ctx))
+}
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_value_match_failed(
+    _ctx: *mut kclvm_context_t,
+    p: *const kclvm_value_ref_t,
+) {
+    let p = ptr_as_ref(p);
+    panic!("no match case found for the value {}", p.to_json_string());
+}
+
 // compare
 
 #[no_mangle]
@@ -1445,7 +1515,7 @@ pub unsafe extern "C-unwind" fn kclvm_value_is(
 ) -> *mut kclvm_value_ref_t {
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    if a == b {
+    if a.is(mut_ptr_as_ref(ctx), b) {
         return kclvm_value_Bool(ctx, 1);
     }
     kclvm_value_Bool(ctx, 0)
@@ -1460,7 +1530,7 @@ pub unsafe extern "C-unwind" fn kclvm_value_is_not(
 ) -> *mut kclvm_value_ref_t {
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    if a == b {
+    if a.is(mut_ptr_as_ref(ctx), b) {
         return kclvm_value_Bool(ctx, 0);
     }
     kclvm_value_Bool(ctx, 1)
@@ -2266,6 +2336,30 @@ pub unsafe extern "C-unwind" fn kclvm_schema_optional_check(
     }
 }
 
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_schema_final_check(
+    ctx: *mut kclvm_context_t,
+    config: *const kclvm_value_ref_t,
+    config_meta: *const kclvm_value_ref_t,
+    final_attrs: *const kclvm_value_ref_t,
+    schema_name: *const kclvm_char_t,
+) {
+    let config = ptr_as_ref(config);
+    let config_meta = ptr_as_ref(config_meta);
+    let final_attrs: Vec<String> = ptr_as_ref(final_attrs)
+        .as_list_ref()
+        .values
+        .iter()
+        .map(|v| v.as_str())
+        .collect();
+    let schema_name = c2str(schema_name);
+    let ctx = mut_ptr_as_ref(ctx);
+    if !ctx.cfg.disable_schema_check {
+        config.schema_check_attr_final(ctx, config_meta, &final_attrs, schema_name);
+    }
+}
+
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C-unwind" fn kclvm_schema_default_settings(
@@ -35,6 +35,8 @@ pub mod val_is_in;
 
 pub mod val_list;
 
+pub mod val_match;
+
 pub mod val_dict;
 
 pub mod val_fmt;
@@ -72,4 +74,6 @@ pub use val_union::*;
 pub mod val_yaml;
 pub use val_yaml::*;
 
+pub mod val_toml;
+
 pub mod walker;
@@ -60,6 +60,7 @@ impl ValueRef {
                     dict.dict_update_entry(key.as_str(), &val.deep_copy(), &op.clone(), index);
                 }
                 dict.set_potential_schema_type(&v.potential_schema.clone().unwrap_or_default());
+                dict.as_dict_mut_ref().provenance = v.provenance.clone();
                 dict
             }
             Value::schema_value(ref v) => {
@@ -79,6 +80,7 @@ impl ValueRef {
                         dict.update_attr_map(key, type_str);
                     }
                 }
+                dict.as_dict_mut_ref().provenance = v.config.provenance.clone();
                 return ValueRef {
                     rc: Rc::new(RefCell::new(Value::schema_value(Box::new(SchemaValue {
                         name: v.name.clone(),
@@ -149,6 +149,11 @@ impl ValueRef {
                     let index = dict.insert_indexs.get(key);
                     d.dict_update_entry(key, value, op, index);
                     d.set_potential_schema_type(&dict.potential_schema.clone().unwrap_or_default());
+                    if let Some(provenance) = dict.provenance.get(key) {
+                        d.as_dict_mut_ref()
+                            .provenance
+                            .insert(key.to_string(), provenance.clone());
+                    }
                     Some(d)
                 } else {
                     None
@@ -168,6 +173,11 @@ impl ValueRef {
                     d.set_potential_schema_type(
                         &schema.config.potential_schema.clone().unwrap_or_default(),
                     );
+                    if let Some(provenance) = schema.config.provenance.get(key) {
+                        d.as_dict_mut_ref()
+                            .provenance
+                            .insert(key.to_string(), provenance.clone());
+                    }
                     Some(d)
                 } else {
                     None
@@ -10,4 +10,14 @@ impl ValueRef {
             _ => None,
         }
     }
+
+    /// Try get the runtime type of the schema or rule function
+    pub fn try_get_runtime_type(&self) -> Option<String> {
+        match &*self.rc.borrow() {
+            crate::Value::func_value(func) if !func.runtime_type.is_empty() => {
+                Some(func.runtime_type.clone())
+            }
+            _ => None,
+        }
+    }
 }
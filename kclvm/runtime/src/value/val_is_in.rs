@@ -1,5 +1,7 @@
 //! Copyright The KCL Authors. All rights reserved.
 
+use std::collections::HashSet;
+
 use crate::*;
 
 // is
@@ -114,6 +116,47 @@ impl ValueRef {
     }
 }
 
+// is
+
+impl ValueRef {
+    /// Whether the value is identical to `x`, or the value is an instance of
+    /// the schema type `x` or its sub schema types e.g., `person is Person`.
+    pub fn is(&self, ctx: &Context, x: &Self) -> bool {
+        if let Value::func_value(ref func) = &*x.rc.borrow() {
+            if !func.runtime_type.is_empty() {
+                if let Value::schema_value(ref schema) = &*self.rc.borrow() {
+                    let runtime_type = schema_runtime_type(&schema.name, &schema.pkgpath);
+                    return is_schema_subtype(ctx, &runtime_type, &func.runtime_type);
+                }
+            }
+        }
+        self == x
+    }
+
+    #[inline]
+    pub fn is_not(&self, ctx: &Context, x: &Self) -> bool {
+        !self.is(ctx, x)
+    }
+}
+
+/// Whether the schema type is the base schema type or inherits it through
+/// the parent schemas and mixins.
+fn is_schema_subtype(ctx: &Context, runtime_type: &str, base: &str) -> bool {
+    let mut types = vec![runtime_type];
+    let mut visited = HashSet::new();
+    while let Some(ty) = types.pop() {
+        if ty == base {
+            return true;
+        }
+        if visited.insert(ty) {
+            if let Some(bases) = ctx.schema_bases.get(ty) {
+                types.extend(bases.iter().map(|b| b.as_str()));
+            }
+        }
+    }
+    false
+}
+
 // in
 
 impl ValueRef {
@@ -201,4 +244,41 @@ mod test_value_in {
             ("key2", "value1"),
         ])));
     }
+
+    #[test]
+    fn test_is() {
+        let mut ctx = Context::new();
+        assert!(ValueRef::none().is(&ctx, &ValueRef::none()));
+        assert!(ValueRef::int(1).is_not(&ctx, &ValueRef::none()));
+
+        let student = ValueRef::dict(None).dict_to_schema(
+            "Student",
+            MAIN_PKG_PATH,
+            &[],
+            &ValueRef::dict(None),
+            &ValueRef::dict(None),
+            None,
+            None,
+        );
+        let student_type =
+            ValueRef::func(0, 0, ValueRef::undefined(), "", "__main__.Student", false);
+        let person_type = ValueRef::func(0, 0, ValueRef::undefined(), "", "__main__.Person", false);
+        let named_type =
+            ValueRef::func(0, 0, ValueRef::undefined(), "", "__main__.NameMixin", false);
+        let pet_type = ValueRef::func(0, 0, ValueRef::undefined(), "", "__main__.Pet", false);
+        assert!(student.is(&ctx, &student_type));
+        assert!(student.is_not(&ctx, &person_type));
+        ctx.schema_bases.insert(
+            "__main__.Student".to_string(),
+            vec!["__main__.Person".to_string()],
+        );
+        ctx.schema_bases.insert(
+            "__main__.Person".to_string(),
+            vec!["__main__.NameMixin".to_string()],
+        );
+        assert!(student.is(&ctx, &person_type));
+        assert!(student.is(&ctx, &named_type));
+        assert!(student.is_not(&ctx, &pet_type));
+        assert!(ValueRef::dict(None).is_not(&ctx, &person_type));
+    }
 }
@@ -210,6 +210,96 @@ impl Serialize for JsonValue {
     }
 }
 
+/// JsonStream serializes the value without building the intermediate [JsonValue]
+/// of the list and config values, which is used to write large values.
+struct JsonStream<'a> {
+    value: &'a ValueRef,
+    opts: &'a JsonEncodeOptions,
+}
+
+impl Serialize for JsonStream<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        use serde::ser::{SerializeMap, SerializeSeq};
+        match &*self.value.rc.borrow() {
+            crate::Value::list_value(v) => {
+                let values: Vec<&ValueRef> = v
+                    .values
+                    .iter()
+                    .filter(|x| is_json_emitted(x, self.opts))
+                    .collect();
+                let mut seq = tri!(serializer.serialize_seq(Some(values.len())));
+                for x in values {
+                    tri!(seq.serialize_element(&JsonStream {
+                        value: x,
+                        opts: self.opts
+                    }));
+                }
+                seq.end()
+            }
+            crate::Value::dict_value(v) => {
+                let entries = json_entries(&v.values, self.opts);
+                let mut map = tri!(serializer.serialize_map(Some(entries.len())));
+                for (k, v) in entries {
+                    tri!(map.serialize_entry(
+                        k,
+                        &JsonStream {
+                            value: v,
+                            opts: self.opts
+                        }
+                    ));
+                }
+                map.end()
+            }
+            crate::Value::schema_value(v) => {
+                let entries = json_entries(&v.config.values, self.opts);
+                let mut map = tri!(serializer.serialize_map(Some(entries.len())));
+                for (k, v) in entries {
+                    tri!(map.serialize_entry(
+                        k,
+                        &JsonStream {
+                            value: v,
+                            opts: self.opts
+                        }
+                    ));
+                }
+                map.end()
+            }
+            _ => self.value.build_json(self.opts).serialize(serializer),
+        }
+    }
+}
+
+/// Whether the list item or the config value is emitted in the JSON result.
+#[inline]
+fn is_json_emitted(value: &ValueRef, opts: &JsonEncodeOptions) -> bool {
+    match *value.rc.borrow() {
+        crate::Value::undefined | crate::Value::func_value(_) => false,
+        crate::Value::none => !opts.ignore_none,
+        _ => true,
+    }
+}
+
+/// Get the config entries emitted in the JSON result with the encode options.
+pub(crate) fn json_entries<'a>(
+    values: &'a IndexMap<String, ValueRef>,
+    opts: &JsonEncodeOptions,
+) -> Vec<(&'a String, &'a ValueRef)> {
+    let mut entries: Vec<(&String, &ValueRef)> = values
+        .iter()
+        .filter(|(k, v)| {
+            !(opts.ignore_private && k.starts_with(KCL_PRIVATE_VAR_PREFIX))
+                && is_json_emitted(v, opts)
+        })
+        .collect();
+    if opts.sort_keys {
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+    }
+    entries
+}
+
 impl JsonFormatter {
     /// Construct a pretty printer formatter that defaults to using two spaces for indentation.
     pub fn new() -> Self {
@@ -434,6 +524,20 @@ impl ValueRef {
         writer.to_str().unwrap().to_string()
     }
 
+    /// Write the JSON string of the value into the writer without building the
+    /// whole JSON string in memory.
+    pub fn write_json_with_options(
+        &self,
+        writer: &mut dyn std::io::Write,
+        opts: &JsonEncodeOptions,
+    ) -> std::io::Result<()> {
+        let formatter = JsonFormatter::with_indent(opts.indent);
+        let mut serializer = serde_json::Serializer::with_formatter(writer, formatter);
+        JsonStream { value: self, opts }
+            .serialize(&mut serializer)
+            .map_err(std::io::Error::from)
+    }
+
     pub fn to_json_string_with_null(&self) -> String {
         let json = self.build_json(&Default::default());
         let formatter = JsonFormatter::new();
@@ -621,4 +725,35 @@ mod test_value_json {
             assert_eq!(result, expected);
         }
     }
+
+    #[test]
+    fn test_value_write_json() {
+        let value = ValueRef::dict(Some(&[
+            (
+                "b",
+                &ValueRef::list(Some(&[&ValueRef::none(), &ValueRef::int(1)])),
+            ),
+            ("a", &ValueRef::dict(Some(&[("_c", &ValueRef::str("s"))]))),
+            ("d", &ValueRef::undefined()),
+            ("e", &ValueRef::none()),
+            ("f", &ValueRef::list(None)),
+        ]));
+        let cases = [
+            JsonEncodeOptions::default(),
+            JsonEncodeOptions {
+                sort_keys: true,
+                indent: 4,
+                ignore_private: true,
+                ignore_none: true,
+            },
+        ];
+        for opts in cases {
+            let mut writer = vec![];
+            value.write_json_with_options(&mut writer, &opts).unwrap();
+            assert_eq!(
+                String::from_utf8(writer).unwrap(),
+                value.to_json_string_with_options(&opts)
+            );
+        }
+    }
 }
@@ -0,0 +1,72 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use crate::*;
+
+// match patterns
+
+impl ValueRef {
+    /// Whether the value is a list matched by the list pattern with `len` items,
+    /// `has_rest` denotes whether the pattern has the rest items e.g., `[x, *rest]`.
+    pub fn match_sequence(&self, len: usize, has_rest: bool) -> bool {
+        match &*self.rc.borrow() {
+            Value::list_value(ref list) => {
+                if has_rest {
+                    list.values.len() >= len
+                } else {
+                    list.values.len() == len
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the value is a dict or a schema which contains all the pattern keys.
+    pub fn match_mapping(&self, keys: &[String]) -> bool {
+        match &*self.rc.borrow() {
+            Value::dict_value(ref dict) => keys.iter().all(|key| dict.values.contains_key(key)),
+            Value::schema_value(ref schema) => keys
+                .iter()
+                .all(|key| schema.config.values.contains_key(key)),
+            _ => false,
+        }
+    }
+
+    /// Get the entries of the dict or schema value which are not in the pattern keys
+    /// e.g., `rest` in the dict pattern `{"name": name, **rest}`.
+    pub fn match_rest_mapping(&self, keys: &[String]) -> ValueRef {
+        let mut rest = ValueRef::dict(None);
+        let dict = self.as_dict_ref();
+        for (key, value) in dict.values.iter() {
+            if !keys.contains(key) {
+                rest.dict_update_key_value(key, value.clone());
+            }
+        }
+        rest
+    }
+}
+
+#[cfg(test)]
+mod test_value_match {
+    use crate::*;
+
+    #[test]
+    fn test_match_sequence() {
+        let list = ValueRef::list_int(&[1, 2, 3]);
+        assert!(list.match_sequence(3, false));
+        assert!(!list.match_sequence(2, false));
+        assert!(list.match_sequence(2, true));
+        assert!(!list.match_sequence(4, true));
+        assert!(!ValueRef::str("abc").match_sequence(3, false));
+    }
+
+    #[test]
+    fn test_match_mapping() {
+        let dict = ValueRef::dict_str(&[("key1", "value1"), ("key2", "value2")]);
+        assert!(dict.match_mapping(&["key1".to_string()]));
+        assert!(!dict.match_mapping(&["key1".to_string(), "key3".to_string()]));
+        assert!(!ValueRef::list_int(&[1]).match_mapping(&[]));
+        let rest = dict.match_rest_mapping(&["key1".to_string()]);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest.dict_get_value("key2"), Some(ValueRef::str("value2")));
+    }
+}
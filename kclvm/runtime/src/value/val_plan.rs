@@ -24,6 +24,79 @@ pub struct PlanOptions {
     pub query_paths: Vec<String>,
     /// YAML plan separator string, default is `---`.
     pub sep: Option<String>,
+    /// YAML plan header comment, each line is prefixed with `# `.
+    pub header: Option<String>,
+    /// Whether to skip the JSON result in the plan process.
+    pub disable_json: bool,
+    /// Whether to skip the YAML result in the plan process.
+    pub disable_yaml: bool,
+}
+
+/// PlanFormat denotes the format of the planned result written by [ValueRef::plan_to_writer].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PlanFormat {
+    /// The JSON stream with one document per line.
+    Json,
+    /// The YAML stream separated by the plan separator.
+    Yaml,
+}
+
+/// Get the YAML header comment in the context plan options.
+fn header_comment(ctx: &Context) -> Option<String> {
+    ctx.plan_opts.header.as_ref().map(|header| {
+        header
+            .lines()
+            .map(|line| {
+                if line.starts_with('#') {
+                    line.to_string()
+                } else if line.is_empty() {
+                    "#".to_string()
+                } else {
+                    format!("# {line}")
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    })
+}
+
+/// Prepend the header comment in the context plan options to the YAML result.
+fn with_header(ctx: &Context, yaml_result: String) -> String {
+    match header_comment(ctx) {
+        Some(header) => format!("{header}\n{yaml_result}"),
+        None => yaml_result,
+    }
+}
+
+/// A writer which holds back the last trailing newline, because the newline at
+/// the end of each YAML document is stripped in the plan result.
+struct TrimNewlineWriter<'a> {
+    writer: &'a mut dyn std::io::Write,
+    pending_newline: bool,
+}
+
+impl std::io::Write for TrimNewlineWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.pending_newline {
+            self.writer.write_all(b"\n")?;
+            self.pending_newline = false;
+        }
+        match buf.strip_suffix(b"\n") {
+            Some(content) => {
+                self.writer.write_all(content)?;
+                self.pending_newline = true;
+            }
+            None => self.writer.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
 }
 
 /// Filter list or config results with context options.
@@ -201,58 +274,144 @@ pub fn type_of(v: &ValueRef, full_name: bool) -> String {
     builtin::type_of(v, &ValueRef::bool(full_name)).as_str()
 }
 
-impl ValueRef {
-    /// Plan the value to JSON and YAML strings.
-    pub fn plan(&self, ctx: &Context) -> (String, String) {
-        // Encoding options
-        let json_opts = JsonEncodeOptions {
+/// Get the JSON and YAML encoding options of the plan options.
+fn plan_encode_options(ctx: &Context) -> (JsonEncodeOptions, YamlEncodeOptions) {
+    (
+        JsonEncodeOptions {
             sort_keys: ctx.plan_opts.sort_keys,
             ..Default::default()
-        };
-        let yaml_opts = YamlEncodeOptions {
+        },
+        YamlEncodeOptions {
             sort_keys: ctx.plan_opts.sort_keys,
             ..Default::default()
-        };
-        // Filter values with query paths
-        let value = if ctx.plan_opts.query_paths.is_empty() {
-            self.clone()
-        } else {
-            self.filter_by_path(&ctx.plan_opts.query_paths)
-                .unwrap_or_else(|e| panic!("{e}"))
-        };
+        },
+    )
+}
+
+/// Get the YAML document separator of the plan options.
+#[inline]
+fn plan_sep(ctx: &Context) -> String {
+    ctx.plan_opts
+        .sep
+        .clone()
+        .unwrap_or_else(|| "---".to_string())
+}
+
+impl ValueRef {
+    /// Plan the value to JSON and YAML strings, the disabled result in the plan
+    /// options is an empty string.
+    pub fn plan(&self, ctx: &Context) -> (String, String) {
+        let (json_opts, yaml_opts) = plan_encode_options(ctx);
+        let value = self.plan_query(ctx);
         if value.is_list_or_config() {
             let results = filter_results(ctx, &value);
-            let sep = ctx
-                .plan_opts
-                .sep
-                .clone()
-                .unwrap_or_else(|| "---".to_string());
             // Plan YAML result
-            let yaml_result = results
-                .iter()
-                .map(|r| {
-                    r.to_yaml_string_with_options(&yaml_opts)
-                        .strip_suffix('\n')
-                        .unwrap()
-                        .to_string()
-                })
-                .collect::<Vec<String>>()
-                .join(&format!("\n{}\n", sep));
+            let yaml_result = if ctx.plan_opts.disable_yaml {
+                String::new()
+            } else {
+                let yaml_result = results
+                    .iter()
+                    .map(|r| {
+                        r.to_yaml_string_with_options(&yaml_opts)
+                            .strip_suffix('\n')
+                            .unwrap()
+                            .to_string()
+                    })
+                    .collect::<Vec<String>>()
+                    .join(&format!("\n{}\n", plan_sep(ctx)));
+                with_header(ctx, yaml_result)
+            };
             // Plan JSON result
-            let json_result = results
-                .iter()
-                .map(|r| r.to_json_string_with_options(&json_opts))
-                .collect::<Vec<String>>()
-                .join(JSON_STREAM_SEP);
+            let json_result = if ctx.plan_opts.disable_json {
+                String::new()
+            } else {
+                results
+                    .iter()
+                    .map(|r| r.to_json_string_with_options(&json_opts))
+                    .collect::<Vec<String>>()
+                    .join(JSON_STREAM_SEP)
+            };
             (json_result, yaml_result)
         } else {
             (
-                value.to_json_string_with_options(&json_opts),
-                value.to_yaml_string_with_options(&yaml_opts),
+                if ctx.plan_opts.disable_json {
+                    String::new()
+                } else {
+                    value.to_json_string_with_options(&json_opts)
+                },
+                if ctx.plan_opts.disable_yaml {
+                    String::new()
+                } else {
+                    with_header(ctx, value.to_yaml_string_with_options(&yaml_opts))
+                },
             )
         }
     }
 
+    /// Plan the value and write the result in the format into the writer document by
+    /// document. The written result is the same as the result string of [ValueRef::plan],
+    /// but the whole result string is not built in memory.
+    pub fn plan_to_writer(
+        &self,
+        ctx: &Context,
+        format: PlanFormat,
+        writer: &mut dyn std::io::Write,
+    ) -> std::io::Result<()> {
+        let (json_opts, yaml_opts) = plan_encode_options(ctx);
+        let value = self.plan_query(ctx);
+        let results = if value.is_list_or_config() {
+            filter_results(ctx, &value)
+        } else {
+            vec![]
+        };
+        match format {
+            PlanFormat::Json => {
+                if !value.is_list_or_config() {
+                    return value.write_json_with_options(writer, &json_opts);
+                }
+                for (i, result) in results.iter().enumerate() {
+                    if i > 0 {
+                        writer.write_all(JSON_STREAM_SEP.as_bytes())?;
+                    }
+                    result.write_json_with_options(writer, &json_opts)?;
+                }
+            }
+            PlanFormat::Yaml => {
+                if let Some(header) = header_comment(ctx) {
+                    writer.write_all(header.as_bytes())?;
+                    writer.write_all(b"\n")?;
+                }
+                if !value.is_list_or_config() {
+                    return value.write_yaml_with_options(writer, &yaml_opts);
+                }
+                let sep = plan_sep(ctx);
+                for (i, result) in results.iter().enumerate() {
+                    if i > 0 {
+                        writer.write_all(format!("\n{}\n", sep).as_bytes())?;
+                    }
+                    result.write_yaml_with_options(
+                        &mut TrimNewlineWriter {
+                            writer: &mut *writer,
+                            pending_newline: false,
+                        },
+                        &yaml_opts,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Filter values with the query paths in the plan options.
+    fn plan_query(&self, ctx: &Context) -> ValueRef {
+        if ctx.plan_opts.query_paths.is_empty() {
+            self.clone()
+        } else {
+            self.filter_by_path(&ctx.plan_opts.query_paths)
+                .unwrap_or_else(|e| panic!("{e}"))
+        }
+    }
+
     /// Filter values using path selectors.
     pub fn filter_by_path(&self, path_selector: &[String]) -> Result<ValueRef, String> {
         if self.is_config() && !path_selector.is_empty() {
@@ -287,7 +446,11 @@ impl ValueRef {
 
 #[cfg(test)]
 mod test_value_plan {
-    use crate::{schema_runtime_type, val_plan::PlanOptions, Context, ValueRef, MAIN_PKG_PATH};
+    use crate::{
+        schema_runtime_type,
+        val_plan::{PlanFormat, PlanOptions},
+        Context, ValueRef, MAIN_PKG_PATH,
+    };
 
     use super::filter_results;
 
@@ -443,4 +606,35 @@ mod test_value_plan {
         assert_eq!(json_string, "{}");
         assert_eq!(yaml_string, "{}");
     }
+
+    #[test]
+    fn test_value_plan_to_writer() {
+        let mut ctx = Context::new();
+        let value = ValueRef::list(Some(&[
+            &ValueRef::dict(Some(&[
+                ("b", &ValueRef::list_int(&[1, 2])),
+                ("a", &ValueRef::dict_int(&[("c", 1)])),
+            ])),
+            &ValueRef::dict(None),
+            &ValueRef::dict_int(&[("d", 2)]),
+        ]));
+        let plan_to_string = |ctx: &Context, value: &ValueRef, format: PlanFormat| {
+            let mut writer = vec![];
+            value.plan_to_writer(ctx, format, &mut writer).unwrap();
+            String::from_utf8(writer).unwrap()
+        };
+        for (sort_keys, header) in [(false, None), (true, Some("generated".to_string()))] {
+            ctx.plan_opts.sort_keys = sort_keys;
+            ctx.plan_opts.header = header;
+            for value in [&value, &ValueRef::int(1)] {
+                let (json_string, yaml_string) = value.plan(&ctx);
+                assert_eq!(plan_to_string(&ctx, value, PlanFormat::Json), json_string);
+                assert_eq!(plan_to_string(&ctx, value, PlanFormat::Yaml), yaml_string);
+            }
+        }
+        ctx.plan_opts.disable_json = true;
+        let (json_string, yaml_string) = value.plan(&ctx);
+        assert_eq!(json_string, "");
+        assert_eq!(yaml_string, plan_to_string(&ctx, &value, PlanFormat::Yaml));
+    }
 }
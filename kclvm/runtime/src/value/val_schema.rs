@@ -209,6 +209,32 @@ impl ValueRef {
         }
     }
 
+    /// Check that the final attributes of the schema are not overridden by the config.
+    pub fn schema_check_attr_final(
+        &self,
+        ctx: &mut Context,
+        config_meta: &ValueRef,
+        final_attrs: &[String],
+        schema_name: &str,
+    ) {
+        for attr in final_attrs {
+            if self.dict_get_value(attr).is_some() {
+                if let Some(item_meta) = config_meta.get_by_key(attr) {
+                    if let Some(filename) = item_meta.get_by_key(CONFIG_ITEM_META_FILENAME) {
+                        ctx.set_kcl_filename(&filename.as_str());
+                    }
+                    if let Some(line) = item_meta.get_by_key(CONFIG_ITEM_META_LINE) {
+                        ctx.panic_info.kcl_line = line.as_int() as i32;
+                    }
+                }
+                panic!(
+                    "can't override the final attribute '{}' of the schema '{}'",
+                    attr, schema_name
+                );
+            }
+        }
+    }
+
     /// Set the schema instance value with arguments and keyword arguments.
     pub fn set_schema_args(&mut self, args: &ValueRef, kwargs: &ValueRef) {
         if let Value::schema_value(ref mut schema) = &mut *self.rc.borrow_mut() {
@@ -289,6 +315,9 @@ impl ValueRef {
             // Reserve config keys for the schema update process. Issue: #785
             schema.config_keys = value.config_keys.clone();
             schema.config.potential_schema = value.config.potential_schema.clone();
+            for (k, v) in &value.config.provenance {
+                schema.config.provenance.insert(k.clone(), v.clone());
+            }
             for (k, v) in &value.config.values {
                 let op = value
                     .config
@@ -0,0 +1,190 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use kclvm_primitives::IndexMap;
+
+use crate::*;
+
+impl ValueRef {
+    /// Encode the dict value to a TOML document string. TOML has no null value,
+    /// so None dict values are omitted.
+    pub fn to_toml_string_with_options(&self, opts: &JsonEncodeOptions) -> Result<String, String> {
+        match self.build_json(opts) {
+            JsonValue::Object(values) => {
+                let mut result = String::new();
+                write_toml_table(&mut result, &[], &values)?;
+                Ok(result)
+            }
+            _ => Err(format!(
+                "toml document must be a dict, got '{}'",
+                self.type_str()
+            )),
+        }
+    }
+}
+
+fn write_toml_table(
+    result: &mut String,
+    path: &[String],
+    values: &IndexMap<String, JsonValue>,
+) -> Result<(), String> {
+    // Write the key-value pairs before sub-tables, otherwise they will be
+    // read as members of the last sub-table.
+    for (key, value) in values {
+        if matches!(value, JsonValue::Null | JsonValue::Object(_)) || is_toml_table_array(value) {
+            continue;
+        }
+        result.push_str(&toml_key(key));
+        result.push_str(" = ");
+        write_toml_value(result, value)?;
+        result.push('\n');
+    }
+    for (key, value) in values {
+        let mut path = path.to_vec();
+        path.push(toml_key(key));
+        match value {
+            JsonValue::Object(values) => {
+                write_toml_header(result, &format!("[{}]", path.join(".")));
+                write_toml_table(result, &path, values)?;
+            }
+            JsonValue::Array(items) if is_toml_table_array(value) => {
+                for item in items {
+                    if let JsonValue::Object(values) = item {
+                        write_toml_header(result, &format!("[[{}]]", path.join(".")));
+                        write_toml_table(result, &path, values)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn write_toml_header(result: &mut String, header: &str) {
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result.push_str(header);
+    result.push('\n');
+}
+
+fn write_toml_value(result: &mut String, value: &JsonValue) -> Result<(), String> {
+    match value {
+        JsonValue::Null => return Err("toml does not support None values in lists".to_string()),
+        JsonValue::Bool(v) => result.push_str(&v.to_string()),
+        JsonValue::Number(v) => result.push_str(&v.to_string()),
+        JsonValue::String(v) => result.push_str(&toml_str(v)),
+        JsonValue::Array(items) => {
+            result.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    result.push_str(", ");
+                }
+                write_toml_value(result, item)?;
+            }
+            result.push(']');
+        }
+        JsonValue::Object(values) => {
+            let values: Vec<(&String, &JsonValue)> = values
+                .iter()
+                .filter(|(_, v)| !matches!(v, JsonValue::Null))
+                .collect();
+            if values.is_empty() {
+                result.push_str("{}");
+                return Ok(());
+            }
+            result.push_str("{ ");
+            for (i, (key, value)) in values.into_iter().enumerate() {
+                if i > 0 {
+                    result.push_str(", ");
+                }
+                result.push_str(&toml_key(key));
+                result.push_str(" = ");
+                write_toml_value(result, value)?;
+            }
+            result.push_str(" }");
+        }
+    }
+    Ok(())
+}
+
+/// A non-empty list that only contains dicts is written as an array of tables.
+fn is_toml_table_array(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Array(items) => {
+            !items.is_empty() && items.iter().all(|v| matches!(v, JsonValue::Object(_)))
+        }
+        _ => false,
+    }
+}
+
+fn toml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        key.to_string()
+    } else {
+        toml_str(key)
+    }
+}
+
+fn toml_str(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04X}", c as u32)),
+            _ => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod test_value_toml {
+    use crate::*;
+
+    #[test]
+    fn test_value_to_toml_string() {
+        let mut ctx = Context::new();
+        let value = ValueRef::from_json(
+            &mut ctx,
+            r#"{"name": "app", "none": null, "ports": [80, 443], "server": {"host": "a.b", "tls": {"enabled": true}}, "rules": [{"port": 80}, {"port": 443}], "labels": {"app.kubernetes.io/name": "app"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value
+                .to_toml_string_with_options(&Default::default())
+                .unwrap(),
+            r#"name = "app"
+ports = [80, 443]
+
+[server]
+host = "a.b"
+
+[server.tls]
+enabled = true
+
+[[rules]]
+port = 80
+
+[[rules]]
+port = 443
+
+[labels]
+"app.kubernetes.io/name" = "app"
+"#
+        );
+        assert!(ValueRef::list_int(&[1])
+            .to_toml_string_with_options(&Default::default())
+            .is_err());
+    }
+}
@@ -397,6 +397,9 @@ pub fn check_type(value: &ValueRef, pkgpath: &str, tpe: &str, strict: bool) -> b
     if is_type_union(tpe) {
         return check_type_union(value, pkgpath, tpe);
     }
+    if is_refined_type(tpe) {
+        return check_type_refined(value, tpe);
+    }
 
     if check_type_literal(value, tpe) {
         return true;
@@ -487,6 +490,41 @@ pub fn check_type_literal(value: &ValueRef, tpe: &str) -> bool {
     false
 }
 
+/// check_type_refined returns the value wether match the given the refined type string
+/// e.g., `int(1..65535)` and `str("^[a-z0-9-]+$")`.
+pub fn check_type_refined(value: &ValueRef, tpe: &str) -> bool {
+    let (base_type, constraint) = match split_refined_type(tpe) {
+        Some(refined_type) => refined_type,
+        None => return false,
+    };
+    if !match_builtin_type(value, base_type) {
+        return false;
+    }
+    if base_type == BUILTIN_TYPE_STR {
+        match parse_pattern_constraint(constraint) {
+            Some(pattern) => match fancy_regex::Regex::new(&pattern) {
+                Ok(re) => re.is_match(&value.as_str()).unwrap_or(false),
+                Err(err) => panic!("invalid regular expression pattern '{pattern}': {err}"),
+            },
+            None => false,
+        }
+    } else {
+        match split_range_constraint(constraint) {
+            Some((min, max)) => {
+                let value = if value.is_int() {
+                    value.as_int() as f64
+                } else {
+                    value.as_float()
+                };
+                let in_min = min.is_empty() || min.parse::<f64>().map_or(false, |min| value >= min);
+                let in_max = max.is_empty() || max.parse::<f64>().map_or(false, |max| value <= max);
+                in_min && in_max
+            }
+            None => false,
+        }
+    }
+}
+
 /// check_number_multiplier_type returns the value wether match the given the type string
 pub fn check_number_multiplier_type(value: &ValueRef, tpe: &str) -> bool {
     if value.is_unit() {
@@ -575,6 +613,48 @@ pub fn is_literal_type(tpe: &str) -> bool {
     false
 }
 
+/// is_refined_type returns the type string whether is a refined type
+/// e.g., `int(1..65535)`, `float(0.0..)` and `str("^[a-z0-9-]+$")`.
+#[inline]
+pub fn is_refined_type(tpe: &str) -> bool {
+    split_refined_type(tpe).is_some()
+}
+
+/// split_refined_type splits the refined type string into the basic type string
+/// and the constraint string e.g., "int(1..65535)" -> ("int", "1..65535").
+pub fn split_refined_type(tpe: &str) -> Option<(&str, &str)> {
+    let (base_type, constraint) = tpe.split_once('(')?;
+    let constraint = constraint.strip_suffix(')')?;
+    if [BUILTIN_TYPE_INT, BUILTIN_TYPE_FLOAT, BUILTIN_TYPE_STR].contains(&base_type) {
+        Some((base_type, constraint))
+    } else {
+        None
+    }
+}
+
+/// split_range_constraint splits the range constraint string into the minimum and
+/// maximum strings which may be empty e.g., "1..65535" -> ("1", "65535"), "1.." -> ("1", "").
+#[inline]
+pub fn split_range_constraint(constraint: &str) -> Option<(&str, &str)> {
+    constraint.split_once("..")
+}
+
+/// parse_pattern_constraint returns the regular expression pattern in the quoted
+/// pattern constraint string, where the backslashes and quotes are escaped.
+pub fn parse_pattern_constraint(constraint: &str) -> Option<String> {
+    let quoted = constraint.strip_prefix('"')?.strip_suffix('"')?;
+    let mut pattern = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            pattern.push(chars.next()?);
+        } else {
+            pattern.push(c);
+        }
+    }
+    Some(pattern)
+}
+
 /// is_dict_type returns the type string whether is a dict type
 #[inline]
 pub fn is_dict_type(tpe: &str) -> bool {
@@ -607,6 +687,7 @@ pub fn is_schema_type(expected_type: &str) -> bool {
         && !is_dict_type(expected_type)
         && !is_builtin_type(expected_type)
         && !is_literal_type(expected_type)
+        && !is_refined_type(expected_type)
 }
 
 /// is union type
@@ -803,6 +884,35 @@ mod test_value_type {
         }
     }
 
+    #[test]
+    fn test_check_type_refined() {
+        let cases = [
+            // true cases
+            (ValueRef::int(80), "int(1..65535)", true),
+            (ValueRef::int(1), "int(1..)", true),
+            (ValueRef::int(-1), "int(..0)", true),
+            (ValueRef::int(1), "float(0.5..1.5)", true),
+            (ValueRef::float(0.5), "float(0.5..1.5)", true),
+            (ValueRef::str("kcl-lang"), r#"str("^[a-z0-9-]+$")"#, true),
+            (ValueRef::str("a\"b"), r#"str("^a\"b$")"#, true),
+            (ValueRef::str("123"), r#"str("^\\d+$")"#, true),
+            (ValueRef::list_int(&[1, 2, 3]), "[int(1..3)]", true),
+            (ValueRef::int(80), "int(1..65535) | str", true),
+            // false cases
+            (ValueRef::int(0), "int(1..65535)", false),
+            (ValueRef::int(65536), "int(1..65535)", false),
+            (ValueRef::float(1.6), "float(0.5..1.5)", false),
+            (ValueRef::float(1.0), "int(0..2)", false),
+            (ValueRef::str("KCL"), r#"str("^[a-z0-9-]+$")"#, false),
+            (ValueRef::str("abc"), r#"str("^\\d+$")"#, false),
+            (ValueRef::int(1), r#"str("^\\d+$")"#, false),
+            (ValueRef::list_int(&[1, 2, 3]), "[int(1..2)]", false),
+        ];
+        for (value, tpe, expected) in cases {
+            assert_eq!(check_type(&value, "", tpe, false), expected, "{tpe}");
+        }
+    }
+
     #[test]
     fn test_check_number_multiplier_type() {
         let cases = [
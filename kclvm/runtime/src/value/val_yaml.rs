@@ -122,6 +122,41 @@ impl ValueRef {
             Err(err) => panic!("{}", err),
         }
     }
+
+    /// Write the YAML string of the value into the writer. Each top level entry
+    /// of the config value is encoded and written separately, thus the whole YAML
+    /// string is not built in memory.
+    pub fn write_yaml_with_options(
+        &self,
+        writer: &mut dyn std::io::Write,
+        opts: &YamlEncodeOptions,
+    ) -> std::io::Result<()> {
+        let json_opts = JsonEncodeOptions {
+            sort_keys: opts.sort_keys,
+            indent: 0,
+            ignore_private: opts.ignore_private,
+            ignore_none: opts.ignore_none,
+        };
+        let entries: Vec<ValueRef> = match &*self.rc.borrow() {
+            Value::dict_value(v) => json_entries(&v.values, &json_opts)
+                .into_iter()
+                .map(|(k, v)| ValueRef::dict(Some(&[(k.as_str(), v)])))
+                .collect(),
+            Value::schema_value(v) => json_entries(&v.config.values, &json_opts)
+                .into_iter()
+                .map(|(k, v)| ValueRef::dict(Some(&[(k.as_str(), v)])))
+                .collect(),
+            _ => vec![],
+        };
+        // The empty config and other values are written as a whole.
+        if entries.is_empty() {
+            return writer.write_all(self.to_yaml_string_with_options(opts).as_bytes());
+        }
+        for entry in entries {
+            writer.write_all(entry.to_yaml_string_with_options(opts).as_bytes())?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -305,6 +340,9 @@ mod test_value_yaml {
         for (value, expected, opts) in cases {
             let result = ValueRef::to_yaml_string_with_options(&value, &opts);
             assert_eq!(result, expected);
+            let mut writer = vec![];
+            value.write_yaml_with_options(&mut writer, &opts).unwrap();
+            assert_eq!(String::from_utf8(writer).unwrap(), expected);
         }
     }
 }
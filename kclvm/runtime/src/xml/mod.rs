@@ -0,0 +1,531 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! XML elements are mapped to KCL values in the following way:
+//!
+//! + An element with only text content is a string, and an empty element is `None`.
+//! + Attributes are dict keys prefixed with `@`, and the text content of an
+//!   element which also has attributes or children is the `#text` key.
+//! + Repeated child elements with the same name are a list.
+
+use kclvm_primitives::{DefaultHashBuilder, IndexMap};
+
+use crate::*;
+
+const ATTRIBUTE_PREFIX: &str = "@";
+const TEXT_KEY: &str = "#text";
+
+// data, indent=0, sort_keys=False, ignore_private=False, ignore_none=False
+
+/// Serialize a dict with exactly one root key into a XML formatted string.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_xml_encode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(data) = get_call_arg(args, kwargs, 0, Some("data")) {
+        let indent = get_call_arg_int(args, kwargs, 1, Some("indent")).unwrap_or_default();
+        let json = data.build_json(&args_to_opts(args, kwargs, 2));
+        return match encode_xml(&json, indent.max(0) as usize) {
+            Ok(s) => ValueRef::str(&s).into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        };
+    }
+    panic!("encode() missing 1 required positional argument: 'data'")
+}
+
+/// Deserialize a XML formatted string into a dict with the root element name as the key.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_xml_decode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(value) = get_call_arg_str(args, kwargs, 0, Some("value")) {
+        return match XmlParser::new(&value).parse() {
+            Ok(json) => ValueRef::parse_json(ctx, &json).into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        };
+    }
+    panic!("decode() missing 1 required positional argument: 'value'")
+}
+
+fn args_to_opts(args: &ValueRef, kwargs: &ValueRef, index: usize) -> JsonEncodeOptions {
+    let mut opts = JsonEncodeOptions::default();
+    if let Some(sort_keys) = get_call_arg_bool(args, kwargs, index, Some("sort_keys")) {
+        opts.sort_keys = sort_keys;
+    }
+    if let Some(ignore_private) = get_call_arg_bool(args, kwargs, index + 1, Some("ignore_private"))
+    {
+        opts.ignore_private = ignore_private;
+    }
+    if let Some(ignore_none) = get_call_arg_bool(args, kwargs, index + 2, Some("ignore_none")) {
+        opts.ignore_none = ignore_none;
+    }
+    opts
+}
+
+fn encode_xml(data: &JsonValue, indent: usize) -> Result<String, String> {
+    match data {
+        JsonValue::Object(values) if values.len() == 1 => {
+            let (name, value) = values.first().unwrap();
+            if let JsonValue::Array(_) = value {
+                return Err("xml root element can't be a list".to_string());
+            }
+            let mut result = String::new();
+            write_element(&mut result, name, value, indent, 0)?;
+            Ok(result)
+        }
+        _ => Err("xml data must be a dict with exactly one root key".to_string()),
+    }
+}
+
+fn write_element(
+    result: &mut String,
+    name: &str,
+    value: &JsonValue,
+    indent: usize,
+    level: usize,
+) -> Result<(), String> {
+    if !is_valid_name(name) {
+        return Err(format!("invalid xml element name '{name}'"));
+    }
+    if let JsonValue::Array(values) = value {
+        for value in values {
+            write_element(result, name, value, indent, level)?;
+        }
+        return Ok(());
+    }
+    let newline = if indent > 0 { "\n" } else { "" };
+    result.push_str(&" ".repeat(indent * level));
+    result.push('<');
+    result.push_str(name);
+    match value {
+        JsonValue::Object(values) => {
+            let mut text = None;
+            let mut children = vec![];
+            for (key, value) in values {
+                if let Some(attr) = key.strip_prefix(ATTRIBUTE_PREFIX) {
+                    if !is_valid_name(attr) {
+                        return Err(format!("invalid xml attribute name '{attr}'"));
+                    }
+                    let value = escape_attr(&scalar_text(value)?)?;
+                    result.push_str(&format!(" {attr}=\"{value}\""));
+                } else if key == TEXT_KEY {
+                    text = Some(scalar_text(value)?);
+                } else {
+                    children.push((key, value));
+                }
+            }
+            if text.is_none() && children.is_empty() {
+                result.push_str("/>");
+            } else {
+                result.push('>');
+                if let Some(text) = text {
+                    result.push_str(&escape(&text)?);
+                }
+                if !children.is_empty() {
+                    result.push_str(newline);
+                    for (key, value) in children {
+                        write_element(result, key, value, indent, level + 1)?;
+                    }
+                    result.push_str(&" ".repeat(indent * level));
+                }
+                result.push_str(&format!("</{name}>"));
+            }
+        }
+        JsonValue::Null => result.push_str("/>"),
+        _ => result.push_str(&format!(">{}</{name}>", escape(&scalar_text(value)?)?)),
+    }
+    result.push_str(newline);
+    Ok(())
+}
+
+fn scalar_text(value: &JsonValue) -> Result<String, String> {
+    match value {
+        JsonValue::Null => Ok(String::new()),
+        JsonValue::Bool(v) => Ok(v.to_string()),
+        JsonValue::Number(v) => Ok(v.to_string()),
+        JsonValue::String(v) => Ok(v.clone()),
+        _ => Err("xml attribute and text values must be scalars".to_string()),
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' || c == ':' => {
+            chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '-' | '.'))
+        }
+        _ => false,
+    }
+}
+
+fn escape(s: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            // These characters can't appear in a XML 1.0 document even as character references.
+            '\u{0}'..='\u{8}'
+            | '\u{b}'
+            | '\u{c}'
+            | '\u{e}'..='\u{1f}'
+            | '\u{fffe}'
+            | '\u{ffff}' => {
+                return Err(format!(
+                    "xml text can't contain the character '\\u{{{:04x}}}'",
+                    c as u32
+                ))
+            }
+            _ => result.push(c),
+        }
+    }
+    Ok(result)
+}
+
+/// Escape the attribute value. Whitespace characters are written as character
+/// references, otherwise they are normalized to spaces by the readers.
+fn escape_attr(s: &str) -> Result<String, String> {
+    Ok(escape(s)?
+        .replace('\t', "&#9;")
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;"))
+}
+
+/// A minimal non-validating XML parser. Declarations, processing instructions,
+/// comments and DTDs are skipped.
+struct XmlParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> XmlParser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn parse(&mut self) -> Result<JsonValue, String> {
+        self.skip_misc()?;
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected a root element"));
+        }
+        let (name, value) = self.parse_element()?;
+        self.skip_misc()?;
+        if self.pos < self.src.len() {
+            return Err(self.error("unexpected content after the root element"));
+        }
+        let mut result = IndexMap::with_hasher(DefaultHashBuilder::default());
+        result.insert(name, value);
+        Ok(JsonValue::Object(result))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn error(&self, msg: &str) -> String {
+        let line = self.src[..self.pos].matches('\n').count() + 1;
+        format!("invalid xml on line {line}: {msg}")
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip the content until the end delimiter (inclusive) and return the skipped content.
+    fn skip_until(&mut self, end: &str) -> Result<&'a str, String> {
+        match self.rest().find(end) {
+            Some(i) => {
+                let content = &self.rest()[..i];
+                self.pos += i + end.len();
+                Ok(content)
+            }
+            None => Err(self.error(&format!("missing '{end}'"))),
+        }
+    }
+
+    /// Skip whitespace, comments, declarations and processing instructions.
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if rest.starts_with("<!DOCTYPE") {
+                // Internal DTD subsets are not supported.
+                self.skip_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, String> {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(rest.len());
+        let name = &rest[..end];
+        if !is_valid_name(name) {
+            return Err(self.error(&format!("invalid name '{name}'")));
+        }
+        self.pos += end;
+        Ok(name.to_string())
+    }
+
+    fn parse_element(&mut self) -> Result<(String, JsonValue), String> {
+        // Skip '<'
+        self.pos += 1;
+        let name = self.parse_name()?;
+        let mut values = IndexMap::with_hasher(DefaultHashBuilder::default());
+        // Attributes
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok((name, into_element_value(values, String::new())));
+            } else if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let attr = self.parse_name()?;
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(self.error(&format!("missing value of attribute '{attr}'")));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(c @ ('"' | '\'')) => c,
+                _ => return Err(self.error(&format!("unquoted value of attribute '{attr}'"))),
+            };
+            self.pos += 1;
+            let value = self.skip_until(&quote.to_string())?;
+            let value = self.unescape(value)?;
+            let key = format!("{ATTRIBUTE_PREFIX}{attr}");
+            if values.contains_key(&key) {
+                return Err(self.error(&format!("duplicate attribute '{attr}'")));
+            }
+            values.insert(key, JsonValue::String(value));
+        }
+        // Content
+        let mut text = String::new();
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(self.error(&format!("missing end tag of element '{name}'")));
+            } else if rest.starts_with("</") {
+                self.pos += 2;
+                let end_name = self.parse_name()?;
+                if end_name != name {
+                    return Err(self.error(&format!(
+                        "mismatched end tag, expected '</{name}>', got '</{end_name}>'"
+                    )));
+                }
+                self.skip_whitespace();
+                if !self.rest().starts_with('>') {
+                    return Err(self.error(&format!("unclosed end tag of element '{name}'")));
+                }
+                self.pos += 1;
+                return Ok((name, into_element_value(values, text)));
+            } else if rest.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                text.push_str(self.skip_until("]]>")?);
+            } else if rest.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if rest.starts_with('<') {
+                let (child_name, child) = self.parse_element()?;
+                match values.get_mut(&child_name) {
+                    Some(JsonValue::Array(items)) => items.push(child),
+                    Some(item) => {
+                        let first = std::mem::replace(item, JsonValue::Null);
+                        *item = JsonValue::Array(vec![first, child]);
+                    }
+                    None => {
+                        values.insert(child_name, child);
+                    }
+                }
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let content = &rest[..end];
+                self.pos += end;
+                text.push_str(&self.unescape(content)?);
+            }
+        }
+    }
+
+    fn unescape(&self, s: &str) -> Result<String, String> {
+        let mut result = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(i) = rest.find('&') {
+            result.push_str(&rest[..i]);
+            rest = &rest[i + 1..];
+            let end = match rest.find(';') {
+                Some(end) => end,
+                None => return Err(self.error("unterminated entity reference")),
+            };
+            let entity = &rest[..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => {
+                    if let Some(hex) = entity.strip_prefix("#x") {
+                        hex.chars()
+                            .all(|c| c.is_ascii_hexdigit())
+                            .then(|| u32::from_str_radix(hex, 16).ok())
+                            .flatten()
+                            .and_then(char::from_u32)
+                    } else if let Some(dec) = entity.strip_prefix('#') {
+                        dec.chars()
+                            .all(|c| c.is_ascii_digit())
+                            .then(|| dec.parse::<u32>().ok())
+                            .flatten()
+                            .and_then(char::from_u32)
+                    } else {
+                        None
+                    }
+                }
+            };
+            match c {
+                Some(c) => result.push(c),
+                None => return Err(self.error(&format!("unknown entity '&{entity};'"))),
+            }
+            rest = &rest[end + 1..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+}
+
+fn into_element_value(mut values: IndexMap<String, JsonValue>, text: String) -> JsonValue {
+    let text = text.trim();
+    if values.is_empty() {
+        if text.is_empty() {
+            JsonValue::Null
+        } else {
+            JsonValue::String(text.to_string())
+        }
+    } else {
+        if !text.is_empty() {
+            values.insert(TEXT_KEY.to_string(), JsonValue::String(text.to_string()));
+        }
+        JsonValue::Object(values)
+    }
+}
+
+#[cfg(test)]
+mod test_xml {
+    use super::*;
+
+    #[test]
+    fn test_decode_xml() {
+        let mut ctx = Context::new();
+        let value = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- servers -->
+<config version="1">
+    <server name="a">web &amp; api</server>
+    <server name="b"><![CDATA[<raw>]]></server>
+    <port>80</port>
+    <empty/>
+</config>
+"#;
+        let json = XmlParser::new(value).parse().unwrap();
+        let data = ValueRef::parse_json(&mut ctx, &json);
+        assert_eq!(
+            data.to_json_string(),
+            r##"{"config": {"@version": "1", "server": [{"@name": "a", "#text": "web & api"}, {"@name": "b", "#text": "<raw>"}], "port": "80", "empty": null}}"##
+        );
+        assert_eq!(
+            encode_xml(&json, 0).unwrap(),
+            r#"<config version="1"><server name="a">web &amp; api</server><server name="b">&lt;raw&gt;</server><port>80</port><empty/></config>"#
+        );
+    }
+
+    #[test]
+    fn test_encode_xml_indent() {
+        let mut ctx = Context::new();
+        let data =
+            ValueRef::from_json(&mut ctx, r#"{"a": {"b": [1, 2], "c": {"d": true}}}"#).unwrap();
+        assert_eq!(
+            encode_xml(&data.build_json(&Default::default()), 2).unwrap(),
+            "<a>\n  <b>1</b>\n  <b>2</b>\n  <c>\n    <d>true</d>\n  </c>\n</a>\n"
+        );
+    }
+
+    #[test]
+    fn test_decode_xml_error() {
+        assert!(XmlParser::new("<a><b></a>").parse().is_err());
+        assert!(XmlParser::new("<a>&unknown;</a>").parse().is_err());
+        assert!(XmlParser::new("<a></a><b/>").parse().is_err());
+        assert!(XmlParser::new("").parse().is_err());
+        assert!(XmlParser::new("<a>").parse().is_err());
+        assert!(XmlParser::new("<a b=c/>").parse().is_err());
+        assert!(XmlParser::new("<1a/>").parse().is_err());
+        assert_eq!(
+            XmlParser::new("<a b=\"1\"\nb='2'/>").parse().unwrap_err(),
+            "invalid xml on line 2: duplicate attribute 'b'"
+        );
+        for entity in ["&#x+41;", "&#+65;", "&#x;", "&#xD800;", "&amp"] {
+            assert!(
+                XmlParser::new(&format!("<a>{entity}</a>")).parse().is_err(),
+                "{entity}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_xml_escaping() {
+        let mut ctx = Context::new();
+        let value = "<名前 属性='&quot;値&quot;' b=\"a&#10;b\">&#x1F680;&#233; &lt;&gt;</名前>";
+        let json = XmlParser::new(value).parse().unwrap();
+        assert_eq!(
+            ValueRef::parse_json(&mut ctx, &json).to_json_string(),
+            r##"{"名前": {"@属性": "\"値\"", "@b": "a\nb", "#text": "🚀é <>"}}"##
+        );
+    }
+
+    #[test]
+    fn test_encode_xml_escaping() {
+        let encode = |json: &str| {
+            let data = ValueRef::from_json(&mut Context::new(), json).unwrap();
+            encode_xml(&data.build_json(&Default::default()), 0)
+        };
+        assert_eq!(
+            encode(r##"{"a": {"@b": "x\"y'\n\t", "#text": "<&>🚀"}}"##).unwrap(),
+            r#"<a b="x&quot;y&apos;&#10;&#9;">&lt;&amp;&gt;🚀</a>"#
+        );
+        assert_eq!(
+            encode(r#"{"a": "x\u0001"}"#).unwrap_err(),
+            "xml text can't contain the character '\\u{0001}'"
+        );
+        assert!(encode(r#"{"a": {"@b": [1]}}"#).is_err());
+        assert!(encode(r#"{"a b": 1}"#).is_err());
+        assert!(encode(r#"{"a": {"@1": 1}}"#).is_err());
+        assert!(encode(r#"{"a": [1]}"#).is_err());
+        assert!(encode(r#"{"a": 1, "b": 2}"#).is_err());
+    }
+}
@@ -107,6 +107,8 @@ pub extern "C-unwind" fn kclvm_yaml_dump_to_file(
     match (data, filename) {
         (Some(data), Some(filename)) => {
             let filename = filename.as_str();
+            mut_ptr_as_ref(ctx).check_file_write(&filename);
+            mut_ptr_as_ref(ctx).mark_uncacheable();
 
             let yaml = data.to_yaml_string_with_options(&args_to_opts(args, kwargs, 2));
             std::fs::write(&filename, yaml)
@@ -135,6 +137,8 @@ pub extern "C-unwind" fn kclvm_yaml_dump_all_to_file(
     match (data, filename) {
         (Some(data), Some(filename)) => {
             let filename = filename.as_str();
+            mut_ptr_as_ref(ctx).check_file_write(&filename);
+            mut_ptr_as_ref(ctx).mark_uncacheable();
             let opts = args_to_opts(args, kwargs, 2);
             let results = data
                 .as_list_ref()
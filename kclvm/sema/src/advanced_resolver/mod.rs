@@ -120,11 +120,23 @@ impl<'ctx> AdvancedResolver<'ctx> {
             },
         };
         // Scan all scehma symbol
-        for (name, modules) in advanced_resolver.ctx.program.pkgs.iter() {
+        for (name, modules) in advanced_resolver
+            .ctx
+            .program
+            .pkgs
+            .iter()
+            .chain(advanced_resolver.ctx.program.plugin_decls.iter())
+        {
             advanced_resolver.scan_schemas(name, modules)?;
         }
 
-        for (name, modules) in advanced_resolver.ctx.program.pkgs.iter() {
+        for (name, modules) in advanced_resolver
+            .ctx
+            .program
+            .pkgs
+            .iter()
+            .chain(advanced_resolver.ctx.program.plugin_decls.iter())
+        {
             advanced_resolver.walk_pkg(name, modules)?;
         }
 
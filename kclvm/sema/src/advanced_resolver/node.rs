@@ -13,7 +13,7 @@ use crate::{
         scope::{ConfigScopeContext, LocalSymbolScopeKind},
         symbol::{
             CommentOrDocSymbol, DecoratorSymbol, ExpressionSymbol, SymbolHint, SymbolHintKind,
-            SymbolRef, SymbolSemanticInfo, UnresolvedSymbol, ValueSymbol,
+            SymbolKind, SymbolRef, SymbolSemanticInfo, UnresolvedSymbol, ValueSymbol,
         },
     },
     ty::{Parameter, Type, TypeKind, ANY_TYPE_STR, SCHEMA_MEMBER_FUNCTIONS},
@@ -225,6 +225,10 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for AdvancedResolver<'_> {
         Ok(Some(unresolved_ref))
     }
 
+    fn walk_export_stmt(&mut self, _export_stmt: &'ctx ast::ExportStmt) -> Self::Result {
+        Ok(None)
+    }
+
     fn walk_schema_stmt(&mut self, schema_stmt: &'ctx ast::SchemaStmt) -> Self::Result {
         let (start, end) = (self.ctx.start_pos.clone(), self.ctx.end_pos.clone());
         let schema_ty = self
@@ -426,6 +430,64 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for AdvancedResolver<'_> {
         Ok(Some(schema_symbol))
     }
 
+    fn walk_enum_stmt(&mut self, enum_stmt: &'ctx ast::EnumStmt) -> Self::Result {
+        let enum_ty = self
+            .ctx
+            .node_ty_map
+            .borrow()
+            .get(&self.ctx.get_node_key(&enum_stmt.name.id))
+            .ok_or(anyhow!("enum_ty not found"))?
+            .clone();
+        let enum_symbol = self
+            .gs
+            .get_symbols()
+            .get_type_symbol(&enum_ty, self.get_current_module_info())
+            .ok_or(anyhow!("enum_symbol not found"))?;
+        let (doc, members) = match &enum_ty.kind {
+            TypeKind::Enum(ty) => (ty.doc.clone(), ty.members.clone()),
+            _ => return Ok(None),
+        };
+        if let Some(symbol) = self
+            .gs
+            .get_symbols_mut()
+            .enums
+            .get_mut(enum_symbol.get_id())
+        {
+            symbol.sema_info = SymbolSemanticInfo {
+                ty: Some(enum_ty.clone()),
+                doc: Some(doc),
+            };
+        }
+        for member in &enum_stmt.members {
+            let member_symbol = match self
+                .gs
+                .get_symbols()
+                .symbols_info
+                .node_symbol_map
+                .get(&self.ctx.get_node_key(&member.node.name.id))
+            {
+                Some(member_symbol) => *member_symbol,
+                None => continue,
+            };
+            if let Some(symbol) = self
+                .gs
+                .get_symbols_mut()
+                .attributes
+                .get_mut(member_symbol.get_id())
+            {
+                let member_ty = members.get(&member.node.name.node);
+                symbol.sema_info = SymbolSemanticInfo {
+                    ty: member_ty.map(|member| member.ty.clone()),
+                    doc: member_ty.and_then(|member| member.doc.clone()),
+                };
+            }
+            if let Some(value) = &member.node.value {
+                self.expr(value)?;
+            }
+        }
+        Ok(Some(enum_symbol))
+    }
+
     fn walk_rule_stmt(&mut self, rule_stmt: &'ctx ast::RuleStmt) -> Self::Result {
         let rule_ty = self
             .ctx
@@ -932,6 +994,26 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for AdvancedResolver<'_> {
         Ok(None)
     }
 
+    fn walk_match_expr(&mut self, match_expr: &'ctx ast::MatchExpr) -> Self::Result {
+        self.expr(&match_expr.subject)?;
+        for case in match_expr.cases.iter() {
+            let (start, end) = case.get_span_pos();
+            self.enter_local_scope(
+                &self.ctx.current_filename.clone().unwrap(),
+                start,
+                end,
+                LocalSymbolScopeKind::MatchCase,
+            );
+            self.walk_pattern(&case.node.pattern)?;
+            if let Some(guard) = &case.node.guard {
+                self.expr(guard)?;
+            }
+            self.expr(&case.node.body)?;
+            self.leave_scope();
+        }
+        Ok(None)
+    }
+
     fn walk_keyword(&mut self, keyword: &'ctx ast::Keyword) -> Self::Result {
         self.ctx.maybe_def = true;
         self.walk_identifier_expr(&keyword.arg)?;
@@ -1184,6 +1266,22 @@ impl<'ctx> AdvancedResolver<'_> {
                 );
                 let name = def_symbol.get_name();
                 first_unresolved.def = Some(symbol_ref);
+                // Record the narrowed type of the variable reference e.g., `x` in the body
+                // of `if x != None:`, which is different from its definition type.
+                if !maybe_def && matches!(symbol_ref.get_kind(), SymbolKind::Value) {
+                    if let (Some(ref_ty), Some(def_ty)) = (
+                        self.ctx
+                            .node_ty_map
+                            .borrow()
+                            .get(&self.ctx.get_node_key(&first_name.id)),
+                        &def_symbol.get_sema_info().ty,
+                    ) {
+                        if ref_ty.ty_str() != def_ty.ty_str() {
+                            first_unresolved.sema_info.ty = Some(ref_ty.clone());
+                            first_unresolved.is_narrowed = true;
+                        }
+                    }
+                }
                 let first_unresolved_ref = self.gs.get_symbols_mut().alloc_unresolved_symbol(
                     first_unresolved,
                     self.ctx.get_node_key(&ast_id),
@@ -1762,9 +1860,24 @@ impl<'ctx> AdvancedResolver<'_> {
                     );
                 }
                 ast::Type::Named(identifier) => {
-                    self.walk_identifier(identifier)?;
+                    // The type parameters of the generic schemas and lambdas have no symbols.
+                    let is_type_param = self
+                        .ctx
+                        .node_ty_map
+                        .borrow()
+                        .get(&self.ctx.get_node_key(&ty_node.id))
+                        .map_or(false, |ty| ty.is_type_param());
+                    if !is_type_param {
+                        self.walk_identifier(identifier)?;
+                    }
+                }
+                ast::Type::Generic(generic_type) => {
+                    self.walk_identifier(&generic_type.name)?;
+                    for type_arg in generic_type.type_args.iter() {
+                        self.walk_type_expr(Some(type_arg))?;
+                    }
                 }
-                ast::Type::Basic(_) => {}
+                ast::Type::Basic(_) | ast::Type::Refined(_) => {}
                 ast::Type::List(list_type) => {
                     self.walk_type_expr(list_type.inner_type.as_ref().map(|ty| ty.as_ref()))?;
                 }
@@ -2018,6 +2131,95 @@ impl<'ctx> AdvancedResolver<'_> {
         Ok(())
     }
 
+    pub(crate) fn walk_pattern(
+        &mut self,
+        pattern: &'ctx ast::NodeRef<ast::Pattern>,
+    ) -> anyhow::Result<()> {
+        match &pattern.node {
+            ast::Pattern::Wildcard => {}
+            ast::Pattern::Value(value) => {
+                self.expr(value)?;
+            }
+            ast::Pattern::Capture(name) => self.define_pattern_capture(name),
+            ast::Pattern::Schema(schema_pattern) => {
+                self.walk_identifier_expr(&schema_pattern.name)?;
+                for entry in schema_pattern.entries.iter() {
+                    self.walk_pattern(&entry.node.value)?;
+                }
+            }
+            ast::Pattern::List(list_pattern) => {
+                for elt in list_pattern.elts.iter() {
+                    self.walk_pattern(elt)?;
+                }
+                if let Some(rest) = &list_pattern.rest {
+                    self.define_pattern_capture(rest);
+                }
+            }
+            ast::Pattern::Dict(dict_pattern) => {
+                for entry in dict_pattern.entries.iter() {
+                    self.walk_pattern(&entry.node.value)?;
+                }
+                if let Some(rest) = &dict_pattern.rest {
+                    self.define_pattern_capture(rest);
+                }
+            }
+            ast::Pattern::Or(alternatives) => {
+                for alternative in alternatives.iter() {
+                    self.walk_pattern(alternative)?;
+                }
+            }
+            ast::Pattern::As(as_pattern) => {
+                self.walk_pattern(&as_pattern.pattern)?;
+                self.define_pattern_capture(&as_pattern.name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Define the capture variable of the match case pattern in the current local scope.
+    fn define_pattern_capture(&mut self, name: &'ctx ast::NodeRef<ast::Identifier>) {
+        let var_name = name.node.get_name();
+        if var_name == "_" {
+            return;
+        }
+        let cur_scope = *self.ctx.scopes.last().unwrap();
+        let (start_pos, end_pos): Range = name.get_span_pos();
+        let ast_id = match name.node.names.last() {
+            Some(name) => &name.id,
+            None => &name.id,
+        };
+        let value = self.gs.get_symbols_mut().alloc_value_symbol(
+            ValueSymbol::new(var_name.clone(), start_pos, end_pos.clone(), None, false),
+            self.ctx.get_node_key(ast_id),
+            self.ctx.current_pkgpath.clone().unwrap(),
+        );
+        self.gs
+            .get_scopes_mut()
+            .add_def_to_scope(cur_scope, var_name, value);
+        let ty = self
+            .ctx
+            .node_ty_map
+            .borrow()
+            .get(&self.ctx.get_node_key(ast_id))
+            .cloned();
+        let symbols = self.gs.get_symbols_mut();
+        if let Some(symbol) = symbols.values.get_mut(value.get_id()) {
+            symbol.sema_info = SymbolSemanticInfo {
+                ty: ty.clone(),
+                doc: None,
+            };
+        }
+        if let Some(ty) = ty {
+            symbols.alloc_hint(
+                SymbolHint {
+                    kind: SymbolHintKind::TypeHint(ty.ty_hint()),
+                    pos: end_pos,
+                },
+                self.ctx.current_pkgpath.clone().unwrap(),
+            );
+        }
+    }
+
     pub(crate) fn resolve_decorator(&mut self, decorators: &'ctx [ast::NodeRef<ast::CallExpr>]) {
         for decorator in decorators {
             let func_ident = &decorator.node.func;
@@ -11,7 +11,7 @@ use kclvm_error::diagnostic::dummy_range;
 use kclvm_primitives::IndexMap;
 use once_cell::sync::Lazy;
 
-use crate::ty::{Parameter, Type};
+use crate::ty::{Parameter, Type, TypeRef};
 pub use decorator::BUILTIN_DECORATORS;
 pub use string::STRING_MEMBER_FUNCTIONS;
 pub use system_module::*;
@@ -34,6 +34,51 @@ macro_rules! register_builtin {
     )
 }
 
+/// The function type `(any) -> any` used by higher order builtin functions.
+fn unary_func_ty() -> TypeRef {
+    Arc::new(Type::function(
+        None,
+        Arc::new(Type::ANY),
+        &[Parameter {
+            name: "x".to_string(),
+            ty: Arc::new(Type::ANY),
+            has_default: false,
+            default_value: None,
+            range: dummy_range(),
+        }],
+        "",
+        false,
+        None,
+    ))
+}
+
+/// The function type `(any, any) -> any` used by higher order builtin functions.
+fn binary_func_ty() -> TypeRef {
+    Arc::new(Type::function(
+        None,
+        Arc::new(Type::ANY),
+        &[
+            Parameter {
+                name: "x".to_string(),
+                ty: Arc::new(Type::ANY),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "y".to_string(),
+                ty: Arc::new(Type::ANY),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        "",
+        false,
+        None,
+    ))
+}
+
 register_builtin! {
     option => Type::function(
         None,
@@ -301,6 +346,124 @@ flag can be set to request the result in descending order."#,
         false,
         Some(1),
     )
+    map => Type::function(
+        None,
+        Type::list_ref(Arc::new(Type::ANY)),
+        &[
+            Parameter {
+                name: "func".to_string(),
+                ty: unary_func_ty(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "iterable".to_string(),
+                ty: Type::iterable(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return a list of the results of calling func on each item of the iterable.
+
+Dict values are iterated by their keys."#,
+        false,
+        None,
+    )
+    filter => Type::function(
+        None,
+        Type::list_ref(Arc::new(Type::ANY)),
+        &[
+            Parameter {
+                name: "func".to_string(),
+                ty: unary_func_ty(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "iterable".to_string(),
+                ty: Type::iterable(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return a list of the items of the iterable for which func returns True.
+
+Dict values are iterated by their keys."#,
+        false,
+        None,
+    )
+    reduce => Type::function(
+        None,
+        Arc::new(Type::ANY),
+        &[
+            Parameter {
+                name: "func".to_string(),
+                ty: binary_func_ty(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "iterable".to_string(),
+                ty: Type::iterable(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "initial".to_string(),
+                ty: Arc::new(Type::ANY),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Apply a function of two arguments cumulatively to the items of the iterable,
+from left to right, so as to reduce the iterable to a single value.
+
+If initial is present, it is placed before the items of the iterable in the
+calculation, and serves as a default when the iterable is empty."#,
+        false,
+        None,
+    )
+    sort_by => Type::function(
+        None,
+        Type::list_ref(Arc::new(Type::ANY)),
+        &[
+            Parameter {
+                name: "inval".to_string(),
+                ty: Type::iterable(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "key".to_string(),
+                ty: unary_func_ty(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "reverse".to_string(),
+                ty: Arc::new(Type::BOOL),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return a new list containing all items from the iterable sorted by the
+result of calling the key function on each item.
+
+The sort is stable, and the reverse flag can be set to request the result in
+descending order."#,
+        false,
+        None,
+    )
     range => Type::function(
         None,
         Type::list_ref(Arc::new(Type::INT)),
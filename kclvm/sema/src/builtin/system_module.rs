@@ -565,6 +565,10 @@ opts - The YAML serialization options
  + ignore_private: Whether to ignore the attribute output whose name starts with the character _ (the default value is True).
  + ignore_none: Whether to ignore the attribute with the value of' None '(the default value is False).
  + sep: Set the separator between multiple YAML documents (the default value is "---").
+ + sort_resources: Whether to sort the resources in the Kubernetes apply order e.g., Namespaces, CRDs and RBAC resources before workloads (the default value is False).
+ + ignore_empty: Whether to ignore the empty list and dict attributes (the default value is False).
+ + header: The comment emitted at the beginning of the YAML output (the default value is None).
+ + as_list: Whether to wrap all the values into a Kubernetes List object (the default value is False).
 "#,
         false,
         None,
@@ -2309,8 +2313,36 @@ register_template_member! {
                 default_value: None,
                 range: dummy_range(),
             },
+            Parameter {
+                name: "partials".to_string(),
+                ty: Type::dict_ref(Type::str_ref(), Type::str_ref()),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "helpers".to_string(),
+                ty: Type::dict_ref(Type::str_ref(), Type::any_ref()),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "strict".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "engine".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
         ],
-        r#"Applies a parsed template to the specified data object and returns the string output. See https://handlebarsjs.com/ for more documents and examples."#,
+        r#"Applies a parsed template to the specified data object and returns the string output. `partials` are the named templates which can be included by the template, `helpers` are the KCL functions which can be called by the template, and `strict` raises an error on missing variables. The `engine` is "handlebars" (default, see https://handlebarsjs.com/) or "go" (the Go text/template syntax with the common Helm functions)."#,
         false,
         None,
     )
@@ -2368,9 +2400,372 @@ register_runtime_member! {
     )
 }
 
+// ------------------------------
+// csv system package
+// ------------------------------
+
+pub const CSV: &str = "csv";
+macro_rules! register_csv_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const CSV_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const CSV_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_csv_member! {
+    encode => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "data".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "header".to_string(),
+                ty: Type::list_ref(Type::str_ref()),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "delimiter".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Serialize a list of dicts or a list of lists `data` to a CSV formatted str. The header is the dict keys in their first appearance order when `header` is not set."#,
+        false,
+        Some(1),
+    )
+    decode => Type::function(
+        None,
+        Type::list_ref(Type::any_ref()),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "header".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "delimiter".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Deserialize `value` (a string instance containing a CSV document) to a list. Each record is a dict keyed by the first record when `header` is True, otherwise a list of strings."#,
+        false,
+        Some(1),
+    )
+}
+
+// ------------------------------
+// ini system package
+// ------------------------------
+
+pub const INI: &str = "ini";
+macro_rules! register_ini_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const INI_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const INI_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_ini_member! {
+    encode => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "data".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "sort_keys".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_private".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_none".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Serialize a KCL dict `data` to an INI formatted str. Nested dicts are written as sections."#,
+        false,
+        Some(1),
+    )
+    decode => Type::function(
+        None,
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Deserialize `value` (a string instance containing an INI document) to a KCL dict. All values are strings."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// properties system package
+// ------------------------------
+
+pub const PROPERTIES: &str = "properties";
+macro_rules! register_properties_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const PROPERTIES_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const PROPERTIES_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_properties_member! {
+    encode => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "data".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "sort_keys".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_private".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_none".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Serialize a KCL dict `data` to a Java properties formatted str. Nested keys are joined with `.` and list items are written as `key[index]`."#,
+        false,
+        Some(1),
+    )
+    decode => Type::function(
+        None,
+        Type::dict_ref(Type::str_ref(), Type::str_ref()),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Deserialize `value` (a string instance containing a Java properties document) to a flat KCL dict of strings."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// xml system package
+// ------------------------------
+
+pub const XML: &str = "xml";
+macro_rules! register_xml_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const XML_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const XML_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_xml_member! {
+    encode => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "data".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "indent".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "sort_keys".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_private".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_none".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Serialize a KCL dict `data` with exactly one root key to a XML formatted str. Keys prefixed with `@` are attributes and the `#text` key is the element text."#,
+        false,
+        Some(1),
+    )
+    decode => Type::function(
+        None,
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Deserialize `value` (a string instance containing a XML document) to a KCL dict with the root element name as the key."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// hcl system package
+// ------------------------------
+
+pub const HCL: &str = "hcl";
+macro_rules! register_hcl_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const HCL_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const HCL_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_hcl_member! {
+    encode => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "data".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "sort_keys".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_private".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_none".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Serialize a KCL dict `data` to HCL attributes e.g., a Terraform variable definitions (`.tfvars`) file."#,
+        false,
+        Some(1),
+    )
+}
+
 pub const STANDARD_SYSTEM_MODULES: &[&str] = &[
     COLLECTION, NET, MANIFESTS, MATH, DATETIME, REGEX, YAML, JSON, CRYPTO, BASE64, UNITS, FILE,
-    TEMPLATE, RUNTIME, BASE32,
+    TEMPLATE, RUNTIME, BASE32, CSV, INI, PROPERTIES, XML, HCL,
 ];
 
 pub const STANDARD_SYSTEM_MODULE_NAMES_WITH_AT: &[&str] = &[
@@ -2389,6 +2784,11 @@ pub const STANDARD_SYSTEM_MODULE_NAMES_WITH_AT: &[&str] = &[
     "@template",
     "@runtime",
     "@base32",
+    "@csv",
+    "@ini",
+    "@properties",
+    "@xml",
+    "@hcl",
 ];
 
 /// Get the system module members
@@ -2413,6 +2813,11 @@ pub fn get_system_module_members(name: &str) -> Vec<&str> {
         FILE => FILE_FUNCTION_NAMES.to_vec(),
         TEMPLATE => TEMPLATE_FUNCTION_NAMES.to_vec(),
         RUNTIME => RUNTIME_FUNCTION_NAMES.to_vec(),
+        CSV => CSV_FUNCTION_NAMES.to_vec(),
+        INI => INI_FUNCTION_NAMES.to_vec(),
+        PROPERTIES => PROPERTIES_FUNCTION_NAMES.to_vec(),
+        XML => XML_FUNCTION_NAMES.to_vec(),
+        HCL => HCL_FUNCTION_NAMES.to_vec(),
         _ => bug!("invalid system module name '{}'", name),
     }
 }
@@ -2480,6 +2885,26 @@ pub fn get_system_member_function_ty(name: &str, func: &str) -> TypeRef {
             let types = RUNTIME_FUNCTION_TYPES;
             types.get(func).cloned()
         }
+        CSV => {
+            let types = CSV_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        INI => {
+            let types = INI_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        PROPERTIES => {
+            let types = PROPERTIES_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        XML => {
+            let types = XML_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        HCL => {
+            let types = HCL_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
         _ => None,
     };
     optional_ty
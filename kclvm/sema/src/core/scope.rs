@@ -455,6 +455,7 @@ pub enum LocalSymbolScopeKind {
     Config,
     Check,
     Callable,
+    MatchCase,
 }
 
 impl Scope for LocalSymbolScope {
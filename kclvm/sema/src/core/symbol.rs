@@ -70,6 +70,7 @@ pub struct SymbolData {
     pub(crate) attributes: Arena<AttributeSymbol>,
     pub(crate) schemas: Arena<SchemaSymbol>,
     pub(crate) type_aliases: Arena<TypeAliasSymbol>,
+    pub(crate) enums: Arena<EnumSymbol>,
     pub(crate) unresolved: Arena<UnresolvedSymbol>,
     pub(crate) rules: Arena<RuleSymbol>,
     pub(crate) exprs: Arena<ExpressionSymbol>,
@@ -142,6 +143,14 @@ impl SymbolData {
         }
     }
 
+    pub fn get_enum_symbol(&self, id: SymbolRef) -> Option<&EnumSymbol> {
+        if matches!(id.get_kind(), SymbolKind::Enum) {
+            self.enums.get(id.get_id())
+        } else {
+            None
+        }
+    }
+
     pub fn get_schema_symbol(&self, id: SymbolRef) -> Option<&SchemaSymbol> {
         if matches!(id.get_kind(), SymbolKind::Schema) {
             self.schemas.get(id.get_id())
@@ -204,6 +213,10 @@ impl SymbolData {
                 .type_aliases
                 .get(id.get_id())
                 .map(|symbol| symbol as &KCLSymbol),
+            SymbolKind::Enum => self
+                .enums
+                .get(id.get_id())
+                .map(|symbol| symbol as &KCLSymbol),
             SymbolKind::Unresolved => self
                 .unresolved
                 .get(id.get_id())
@@ -254,6 +267,9 @@ impl SymbolData {
             SymbolKind::TypeAlias => {
                 self.type_aliases.remove(id.get_id());
             }
+            SymbolKind::Enum => {
+                self.enums.remove(id.get_id());
+            }
             SymbolKind::Unresolved => {
                 self.unresolved.remove(id.get_id());
             }
@@ -307,6 +323,12 @@ impl SymbolData {
                     symbol
                 });
             }
+            SymbolKind::Enum => {
+                self.enums.get_mut(id.get_id()).map(|symbol| {
+                    symbol.sema_info.ty = Some(ty);
+                    symbol
+                });
+            }
             SymbolKind::Unresolved => {
                 self.unresolved.get_mut(id.get_id()).map(|symbol| {
                     symbol.sema_info.ty = Some(ty);
@@ -355,6 +377,7 @@ impl SymbolData {
             //TODO: builtin ty symbol,now we just return none
             TypeKind::None => None,
             TypeKind::Any => None,
+            TypeKind::TypeParam(_) => None,
             TypeKind::Void => None,
             TypeKind::Bool => None,
             TypeKind::BoolLit(_) => None,
@@ -383,6 +406,11 @@ impl SymbolData {
 
                 self.get_symbol_by_fully_qualified_name(&fully_qualified_ty_name)
             }
+            TypeKind::Enum(enum_ty) => {
+                let fully_qualified_ty_name = enum_ty.pkgpath.clone() + "." + &enum_ty.name;
+
+                self.get_symbol_by_fully_qualified_name(&fully_qualified_ty_name)
+            }
             TypeKind::Module(module_ty) => {
                 self.get_symbol_by_fully_qualified_name(&module_ty.pkgpath)
             }
@@ -413,6 +441,7 @@ impl SymbolData {
             //TODO: builtin ty symbol,now we just return none
             TypeKind::None => vec![],
             TypeKind::Any => vec![],
+            TypeKind::TypeParam(_) => vec![],
             TypeKind::Void => vec![],
             TypeKind::Bool => vec![],
             TypeKind::BoolLit(_) => vec![],
@@ -449,6 +478,19 @@ impl SymbolData {
                 }
                 result
             }
+            TypeKind::Enum(enum_ty) => {
+                if enum_ty.is_instance {
+                    self.get_type_all_attribute(&enum_ty.member_union_ty(), name, module_info)
+                } else {
+                    let mut result = vec![];
+                    if let Some(symbol_ref) = self.get_type_symbol(ty, module_info) {
+                        if let Some(symbol) = self.get_symbol(symbol_ref) {
+                            result = symbol.get_all_attributes(self, module_info);
+                        }
+                    }
+                    result
+                }
+            }
             TypeKind::Module(_) => {
                 let mut result = vec![];
                 if let Some(symbol_ref) = self.get_type_symbol(ty, module_info) {
@@ -479,6 +521,7 @@ impl SymbolData {
         match &ty.kind {
             TypeKind::None => None,
             TypeKind::Any => None,
+            TypeKind::TypeParam(_) => None,
             TypeKind::Void => None,
             TypeKind::Bool => None,
             TypeKind::BoolLit(_) => None,
@@ -507,6 +550,14 @@ impl SymbolData {
             TypeKind::Schema(_) => self
                 .get_symbol(self.get_type_symbol(ty, module_info)?)?
                 .get_attribute(name, self, module_info),
+            TypeKind::Enum(enum_ty) => {
+                if enum_ty.is_instance {
+                    self.get_type_attribute(&enum_ty.member_union_ty(), name, module_info)
+                } else {
+                    self.get_symbol(self.get_type_symbol(ty, module_info)?)?
+                        .get_attribute(name, self, module_info)
+                }
+            }
             TypeKind::Module(_) => self
                 .get_symbol(self.get_type_symbol(ty, module_info)?)?
                 .get_attribute(name, self, module_info),
@@ -569,6 +620,17 @@ impl SymbolData {
             );
         }
 
+        for (id, _) in self.enums.iter() {
+            let symbol_ref = SymbolRef {
+                id,
+                kind: SymbolKind::Enum,
+            };
+            self.symbols_info.fully_qualified_name_map.insert(
+                self.get_fully_qualified_name(symbol_ref).unwrap(),
+                symbol_ref,
+            );
+        }
+
         for (id, _) in self.attributes.iter() {
             let symbol_ref = SymbolRef {
                 id,
@@ -710,6 +772,29 @@ impl SymbolData {
         symbol_ref
     }
 
+    pub fn alloc_enum_symbol(
+        &mut self,
+        r#enum: EnumSymbol,
+        node_key: NodeKey,
+        pkg_name: String,
+    ) -> SymbolRef {
+        self.symbols_info.symbol_pos_set.insert(r#enum.end.clone());
+        let symbol_id = self.enums.insert(r#enum);
+        let symbol_ref = SymbolRef {
+            id: symbol_id,
+            kind: SymbolKind::Enum,
+        };
+        self.symbols_info
+            .node_symbol_map
+            .insert(node_key.clone(), symbol_ref);
+        self.symbols_info
+            .symbol_node_map
+            .insert(symbol_ref, node_key);
+        self.enums.get_mut(symbol_id).unwrap().id = Some(symbol_ref);
+        self.insert_package_symbol(symbol_ref, pkg_name);
+        symbol_ref
+    }
+
     pub fn alloc_rule_symbol(
         &mut self,
         rule: RuleSymbol,
@@ -989,6 +1074,13 @@ impl SymbolData {
                     .r#ref
                     .insert(r#ref);
             }
+            SymbolKind::Enum => {
+                self.enums
+                    .get_mut(def.get_id())
+                    .unwrap()
+                    .r#ref
+                    .insert(r#ref);
+            }
             SymbolKind::Rule => {
                 self.rules
                     .get_mut(def.get_id())
@@ -1009,6 +1101,7 @@ pub enum SymbolKind {
     Function,
     Package,
     TypeAlias,
+    Enum,
     Unresolved,
     Rule,
     Expression,
@@ -1858,6 +1951,134 @@ impl TypeAliasSymbol {
 }
 #[allow(unused)]
 #[derive(Debug, Clone)]
+pub struct EnumSymbol {
+    pub(crate) id: Option<SymbolRef>,
+    pub(crate) name: String,
+    pub(crate) start: Position,
+    pub(crate) end: Position,
+    pub(crate) owner: SymbolRef,
+    pub(crate) sema_info: SymbolSemanticInfo,
+
+    pub(crate) members: IndexMap<String, SymbolRef>,
+    pub(crate) r#ref: HashSet<SymbolRef>,
+}
+
+impl Symbol for EnumSymbol {
+    type SymbolData = SymbolData;
+    type SemanticInfo = SymbolSemanticInfo;
+    type SymbolHint = SymbolHint;
+
+    fn is_global(&self) -> bool {
+        true
+    }
+    fn get_range(&self) -> Range {
+        (self.start.clone(), self.end.clone())
+    }
+
+    fn get_owner(&self) -> Option<SymbolRef> {
+        Some(self.owner)
+    }
+
+    fn get_definition(&self) -> Option<SymbolRef> {
+        self.id.clone()
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_id(&self) -> Option<SymbolRef> {
+        self.id.clone()
+    }
+
+    fn get_attribute(
+        &self,
+        name: &str,
+        _data: &Self::SymbolData,
+        _module_info: Option<&ModuleInfo>,
+    ) -> Option<SymbolRef> {
+        self.members.get(name).cloned()
+    }
+
+    fn get_all_attributes(
+        &self,
+        _data: &Self::SymbolData,
+        _module_info: Option<&ModuleInfo>,
+    ) -> Vec<SymbolRef> {
+        self.members.values().cloned().collect()
+    }
+
+    fn has_attribute(
+        &self,
+        name: &str,
+        _data: &Self::SymbolData,
+        _module_info: Option<&ModuleInfo>,
+    ) -> bool {
+        self.members.contains_key(name)
+    }
+
+    fn simple_dump(&self) -> String {
+        let mut output = "{\n".to_string();
+        output.push_str("\"kind\": \"EnumSymbol\",\n");
+        output.push_str(&format!("\"name\":\"{}\",\n", self.name));
+        output.push_str(&format!(
+            "\"range\": \"{}:{}",
+            self.start.filename, self.start.line
+        ));
+        if let Some(start_col) = self.start.column {
+            output.push_str(&format!(":{}", start_col));
+        }
+
+        output.push_str(&format!(" to {}", self.end.line));
+        if let Some(end_col) = self.end.column {
+            output.push_str(&format!(":{}", end_col));
+        }
+        output.push_str("\"\n}");
+        output
+    }
+
+    fn full_dump(&self, data: &Self::SymbolData) -> Option<String> {
+        let mut output = format!("{{\n\"simple_info\": {},\n", self.simple_dump());
+        output.push_str("\"additional_info\": {\n");
+        let owner_symbol = data.get_symbol(self.owner)?;
+        output.push_str(&format!("\"owner\": {},\n", owner_symbol.simple_dump()));
+        output.push_str("\"members\": {\n");
+        for (index, (key, member)) in self.members.iter().enumerate() {
+            let member_symbol = data.get_symbol(*member)?;
+            output.push_str(&format!("\"{}\": {}", key, member_symbol.simple_dump()));
+            if index + 1 < self.members.len() {
+                output.push_str(",\n");
+            }
+        }
+        output.push_str("\n}\n}\n}");
+        Some(output)
+    }
+
+    fn get_sema_info(&self) -> &Self::SemanticInfo {
+        &self.sema_info
+    }
+
+    fn get_references(&self) -> HashSet<SymbolRef> {
+        self.r#ref.clone()
+    }
+}
+
+impl EnumSymbol {
+    pub fn new(name: String, start: Position, end: Position, owner: SymbolRef) -> Self {
+        Self {
+            id: None,
+            name,
+            start,
+            end,
+            sema_info: SymbolSemanticInfo::default(),
+            owner,
+            members: IndexMap::default(),
+            r#ref: HashSet::default(),
+        }
+    }
+}
+#[allow(unused)]
+#[derive(Debug, Clone)]
 pub struct RuleSymbol {
     pub(crate) id: Option<SymbolRef>,
     pub(crate) name: String,
@@ -2003,6 +2224,9 @@ pub struct UnresolvedSymbol {
     pub(crate) owner: Option<SymbolRef>,
     pub(crate) sema_info: SymbolSemanticInfo,
     pub(crate) is_type: bool,
+    /// Whether the reference type is narrowed in a branch e.g., `x` in the body
+    /// of `if x != None:`, and the narrowed type is stored in the `sema_info`.
+    pub(crate) is_narrowed: bool,
     pub(crate) r#ref: HashSet<SymbolRef>,
 }
 
@@ -2042,6 +2266,8 @@ impl Symbol for UnresolvedSymbol {
     ) -> Option<SymbolRef> {
         if self.is_type() {
             None
+        } else if self.is_narrowed {
+            data.get_type_attribute(self.sema_info.ty.as_ref()?, name, module_info)
         } else {
             data.get_symbol(self.def?)?
                 .get_attribute(name, data, module_info)
@@ -2053,7 +2279,15 @@ impl Symbol for UnresolvedSymbol {
         data: &Self::SymbolData,
         module_info: Option<&ModuleInfo>,
     ) -> Vec<SymbolRef> {
-        if !self.is_type() {
+        if self.is_narrowed {
+            if let Some(ty) = self.sema_info.ty.as_ref() {
+                if let Some(symbol_ref) = data.get_type_symbol(ty, module_info) {
+                    if let Some(symbol) = data.get_symbol(symbol_ref) {
+                        return symbol.get_all_attributes(data, module_info);
+                    }
+                }
+            }
+        } else if !self.is_type() {
             if let Some(def) = self.def {
                 if let Some(def_symbol) = data.get_symbol(def) {
                     return def_symbol.get_all_attributes(data, module_info);
@@ -2130,6 +2364,7 @@ impl UnresolvedSymbol {
             sema_info: SymbolSemanticInfo::default(),
             owner,
             is_type,
+            is_narrowed: false,
             r#ref: HashSet::default(),
         }
     }
@@ -2153,6 +2388,10 @@ impl UnresolvedSymbol {
     pub fn is_type(&self) -> bool {
         self.is_type
     }
+
+    pub fn is_narrowed(&self) -> bool {
+        self.is_narrowed
+    }
 }
 
 #[derive(Debug, Clone)]
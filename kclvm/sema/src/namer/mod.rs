@@ -117,7 +117,13 @@ impl<'ctx> Namer<'ctx> {
             .get_packages_mut()
             .add_package(namer.ctx.current_package_info.take().unwrap());
 
-        for (name, modules) in namer.ctx.program.pkgs.iter() {
+        for (name, modules) in namer
+            .ctx
+            .program
+            .pkgs
+            .iter()
+            .chain(namer.ctx.program.plugin_decls.iter())
+        {
             namer.walk_pkg(name, modules);
         }
 
@@ -1,6 +1,7 @@
 use crate::core::package::ImportInfo;
 use crate::core::symbol::{
-    AttributeSymbol, RuleSymbol, SchemaSymbol, SymbolKind, SymbolRef, TypeAliasSymbol, ValueSymbol,
+    AttributeSymbol, EnumSymbol, RuleSymbol, SchemaSymbol, SymbolKind, SymbolRef, TypeAliasSymbol,
+    ValueSymbol,
 };
 
 use super::Namer;
@@ -202,6 +203,10 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Namer<'_> {
         None
     }
 
+    fn walk_export_stmt(&mut self, _export_stmt: &'ctx ast::ExportStmt) -> Self::Result {
+        None
+    }
+
     fn walk_schema_stmt(&mut self, schema_stmt: &'ctx ast::SchemaStmt) -> Self::Result {
         let (start_pos, end_pos): Range = schema_stmt.name.get_span_pos();
         let owner = self.ctx.owner_symbols.last().unwrap();
@@ -264,6 +269,53 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Namer<'_> {
         Some(vec![rule_ref])
     }
 
+    fn walk_enum_stmt(&mut self, enum_stmt: &'ctx ast::EnumStmt) -> Self::Result {
+        let (start_pos, end_pos): Range = enum_stmt.name.get_span_pos();
+        let owner = self.ctx.owner_symbols.last().unwrap().clone();
+        let pkg_name = self
+            .ctx
+            .current_package_info
+            .clone()
+            .unwrap()
+            .fully_qualified_name;
+        let enum_ref = self.gs.get_symbols_mut().alloc_enum_symbol(
+            EnumSymbol::new(enum_stmt.name.node.clone(), start_pos, end_pos, owner),
+            self.ctx.get_node_key(&enum_stmt.name.id),
+            pkg_name.clone(),
+        );
+        for member in enum_stmt.members.iter() {
+            let name = member.node.name.node.clone();
+            let (start_pos, end_pos): Range = member.node.name.get_span_pos();
+            // The default value of the enum member is its name string.
+            let default_value = match &member.node.value {
+                Some(value) => print_ast_node(ASTNode::Expr(value)),
+                None => format!("\"{}\"", name),
+            };
+            let member_ref = self.gs.get_symbols_mut().alloc_attribute_symbol(
+                AttributeSymbol::new(
+                    name.clone(),
+                    start_pos,
+                    end_pos,
+                    enum_ref,
+                    false,
+                    Some(default_value),
+                ),
+                self.ctx.get_node_key(&member.node.name.id),
+                pkg_name.clone(),
+            );
+            let enum_symbol = self
+                .gs
+                .get_symbols_mut()
+                .enums
+                .get_mut(enum_ref.get_id())
+                .unwrap();
+            if !enum_symbol.members.contains_key(&name) {
+                enum_symbol.members.insert(name, member_ref);
+            }
+        }
+        Some(vec![enum_ref])
+    }
+
     fn walk_quant_expr(&mut self, _quant_expr: &'ctx ast::QuantExpr) -> Self::Result {
         None
     }
@@ -373,6 +425,10 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Namer<'_> {
         None
     }
 
+    fn walk_match_expr(&mut self, _match_expr: &'ctx ast::MatchExpr) -> Self::Result {
+        None
+    }
+
     fn walk_keyword(&mut self, _keyword: &'ctx ast::Keyword) -> Self::Result {
         None
     }
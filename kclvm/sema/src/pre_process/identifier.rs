@@ -128,6 +128,17 @@ impl<'ctx> MutSelfMutWalker<'ctx> for QualifiedIdentifierTransformer {
         walk_if_mut!(self, walk_expr, quant_expr.if_cond);
         self.local_vars.clear();
     }
+    fn walk_match_expr(&mut self, match_expr: &'ctx mut ast::MatchExpr) {
+        self.walk_expr(&mut match_expr.subject.node);
+        for case in match_expr.cases.iter_mut() {
+            self.local_vars
+                .extend(case.node.pattern.node.get_capture_names());
+            self.walk_pattern(&mut case.node.pattern.node);
+            walk_if_mut!(self, walk_expr, case.node.guard);
+            self.walk_expr(&mut case.node.body.node);
+            self.local_vars.clear();
+        }
+    }
     fn walk_identifier(&mut self, identifier: &'ctx mut ast::Identifier) {
         if identifier.names.len() >= 2 {
             // skip global name and generator local variables in list/dict comp and quant expression
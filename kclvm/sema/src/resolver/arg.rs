@@ -1,5 +1,5 @@
 use crate::resolver::Resolver;
-use crate::ty::FunctionType;
+use crate::ty::{infer_type_bindings, FunctionType, TypeBindings, TypeInferMethods};
 use compiler_base_error::unit_type::{TypeWithUnit, UnitUsize};
 use kclvm_ast::ast;
 use kclvm_primitives::IndexSet;
@@ -26,26 +26,29 @@ impl<'ctx> Resolver<'_> {
         }
     }
 
-    /// Do schema/function/decorator argument type check.
+    /// Do schema/function/decorator argument type check, and return the type
+    /// parameter bindings inferred from the argument types.
     pub fn do_arguments_type_check(
         &mut self,
         func: &ast::NodeRef<ast::Expr>,
         args: &'ctx [ast::NodeRef<ast::Expr>],
         kwargs: &'ctx [ast::NodeRef<ast::Keyword>],
         func_ty: &FunctionType,
-    ) {
+    ) -> TypeBindings {
         let func_name = self.get_func_name(&func.node);
+        let mut bindings = TypeBindings::default();
         let mut check_table: IndexSet<String> = IndexSet::default();
         let mut prev_kw_pos = None;
         for (i, arg) in args.iter().enumerate() {
             match func_ty.params.get(i) {
                 Some(param) => {
-                    self.upgrade_type_for_expr(
+                    let arg_ty = self.upgrade_type_for_expr(
                         param.ty.clone(),
                         arg,
                         arg.get_span_pos(),
                         Some(param.range.clone()),
                     );
+                    self.infer_arg_type_bindings(&param.ty, arg_ty, &mut bindings);
                 }
                 None => {
                     // If the parameter has not a expected type, just check the argument type
@@ -122,6 +125,7 @@ impl<'ctx> Resolver<'_> {
                         self.node_ty_map
                             .borrow_mut()
                             .insert(self.get_node_key(kw.id.clone()), arg_value_type.clone());
+                        self.infer_arg_type_bindings(expected_ty, arg_value_type, &mut bindings);
                     }
                 }
             } else {
@@ -155,6 +159,21 @@ impl<'ctx> Resolver<'_> {
                 );
             }
         }
+        bindings
+    }
+
+    /// Infer the type parameter bindings from the argument type of the generic
+    /// function or schema, the literal types are inferred to their variable types.
+    pub fn infer_arg_type_bindings(
+        &self,
+        param_ty: &TypeRef,
+        arg_ty: TypeRef,
+        bindings: &mut TypeBindings,
+    ) {
+        if param_ty.has_type_param() {
+            let arg_ty = self.ctx.ty_ctx.infer_to_variable_type(arg_ty);
+            infer_type_bindings(param_ty, &arg_ty, bindings);
+        }
     }
 
     /// Generate suggestions for keyword argument errors.
@@ -34,7 +34,7 @@ impl<'ctx> Resolver<'_> {
 
     pub fn load_attr(&mut self, obj: TypeRef, attr: &str, range: Range) -> ResolvedResult {
         let (result, return_ty) = match &obj.kind {
-            TypeKind::Any => (true, self.any_ty()),
+            TypeKind::Any | TypeKind::TypeParam(_) => (true, self.any_ty()),
             TypeKind::None
             | TypeKind::Bool
             | TypeKind::BoolLit(_)
@@ -65,6 +65,13 @@ impl<'ctx> Resolver<'_> {
             // union type load attr based the type guard. e.g, a: str|int; if a is str: xxx; if a is int: xxx;
             // return sup([self.load_attr_type(t, attr, filename, line, column) for t in obj.types])
             TypeKind::Union(_) => (true, self.any_ty()),
+            // The enum instance is the union of its member literal types.
+            TypeKind::Enum(enum_ty) if enum_ty.is_instance => (true, self.any_ty()),
+            // The enum member e.g., `Protocol.TCP` is its literal value type.
+            TypeKind::Enum(enum_ty) => match enum_ty.members.get(attr) {
+                Some(member) => (true, member.ty.clone()),
+                None => (false, self.any_ty()),
+            },
             TypeKind::Schema(schema_ty) => {
                 let (result, schema_attr_ty) = self.schema_load_attr(schema_ty, attr);
                 if result {
@@ -118,7 +125,20 @@ impl<'ctx> Resolver<'_> {
                             )
                         }
                     }
-                    ModuleKind::Plugin => (true, self.any_ty()),
+                    ModuleKind::Plugin => {
+                        // Plugin members are typed by the plugin declaration if exists.
+                        if self.program.plugin_decls.contains_key(&module_ty.pkgpath) {
+                            match self.scope_map.get(&module_ty.pkgpath) {
+                                Some(scope) => match scope.borrow().elems.get(attr) {
+                                    Some(v) => (true, v.borrow().ty.clone()),
+                                    None => (false, self.any_ty()),
+                                },
+                                None => (true, self.any_ty()),
+                            }
+                        } else {
+                            (true, self.any_ty())
+                        }
+                    }
                 }
             }
         };
@@ -144,6 +164,12 @@ impl<'ctx> Resolver<'_> {
                     if suggs.len() > 0 {
                         suggestion = format!(", did you mean '{:?}'?", suggs);
                     }
+                } else if let TypeKind::Enum(enum_ty) = &obj.kind {
+                    let members = enum_ty.members.keys().cloned().collect::<Vec<String>>();
+                    let suggs = suggestions::provide_suggestions(attr, &members);
+                    if suggs.len() > 0 {
+                        suggestion = format!(", did you mean '{:?}'?", suggs);
+                    }
                 }
                 (attr, suggestion)
             };
@@ -262,6 +262,7 @@ impl<'ctx> Resolver<'ctx> {
     /// list/config/schema  # lexicographical         [1] == [2]
     /// iterable            # 1 in [1, 2, 3], "s" in "ss", "key" in Schema
     /// relation            # a is True, b is Undefined
+    /// instance            # a is Person, b is not Person
     pub fn compare(
         &mut self,
         left: TypeRef,
@@ -307,6 +308,12 @@ impl<'ctx> Resolver<'ctx> {
         if matches!(op, ast::CmpOp::In | ast::CmpOp::NotIn) && t2.is_iterable() {
             return self.bool_ty();
         }
+        // Schema instance check e.g., `x is Person`.
+        if matches!(op, ast::CmpOp::Is | ast::CmpOp::IsNot)
+            && matches!(&t2.kind, TypeKind::Schema(schema_ty) if !schema_ty.is_instance)
+        {
+            return self.bool_ty();
+        }
         if (t1.is_none() || t2.is_none())
             && matches!(
                 op,
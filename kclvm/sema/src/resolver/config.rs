@@ -313,10 +313,41 @@ impl<'ctx> Resolver<'_> {
             if let Some(Some(obj)) = self.ctx.config_expr_context.last() {
                 let ty = obj.ty.clone();
                 self.must_check_config_attr(name, &ty, &key.get_span_pos(), None);
+                self.check_config_attr_final(name, &ty, &key.get_span_pos());
             }
         }
     }
 
+    /// Check the config attr does not override the final schema attribute.
+    pub(crate) fn check_config_attr_final(&mut self, attr: &str, ty: &TypeRef, range: &Range) {
+        if let TypeKind::Schema(schema_ty) = &ty.kind {
+            if self.is_final_schema_attr(schema_ty, attr) {
+                self.handler.add_compile_error(
+                    &format!(
+                        "can't override the final attribute '{}' of the schema '{}'",
+                        attr, schema_ty.name
+                    ),
+                    range.clone(),
+                );
+            }
+        }
+    }
+
+    /// Whether the schema attribute is a final attribute, the schema type in the schema
+    /// mapping is preferred because it has the complete attributes.
+    pub(crate) fn is_final_schema_attr(&self, schema_ty: &SchemaType, attr: &str) -> bool {
+        let runtime_type = kclvm_runtime::schema_runtime_type(&schema_ty.name, &schema_ty.pkgpath);
+        match self.ctx.schema_mapping.get(&runtime_type) {
+            Some(schema_mapping_ty) => schema_mapping_ty
+                .borrow()
+                .get_obj_of_attr(attr)
+                .map_or(false, |attr| attr.is_final),
+            None => schema_ty
+                .get_obj_of_attr(attr)
+                .map_or(false, |attr| attr.is_final),
+        }
+    }
+
     fn check_config_value_recursively(&mut self, value_ty: &TypeRef, value_span: &Range) {
         match &value_ty.kind {
             TypeKind::Dict(DictType {
@@ -797,7 +828,7 @@ impl<'ctx> Resolver<'_> {
                 None => {
                     let val_ty = value_ty.unwrap_or_else(|| self.expr(value));
                     match &val_ty.kind {
-                        TypeKind::None | TypeKind::Any => {
+                        TypeKind::None | TypeKind::Any | TypeKind::TypeParam(_) => {
                             val_types.push(val_ty.clone());
                         }
                         TypeKind::Dict(DictType {
@@ -4,11 +4,13 @@ use std::sync::Arc;
 use crate::info::is_private_field;
 use crate::resolver::Resolver;
 use crate::ty::{
-    full_ty_str, is_upper_bound, DecoratorTarget, FunctionType, Parameter, SchemaAttr,
-    SchemaIndexSignature, SchemaType, Type, TypeKind, RESERVED_TYPE_IDENTIFIERS,
+    full_ty_str, is_upper_bound, DecoratorTarget, EnumMemberType, EnumType, FunctionType,
+    Parameter, SchemaAttr, SchemaIndexSignature, SchemaType, Type, TypeKind, TypeRef,
+    RESERVED_TYPE_IDENTIFIERS,
 };
 use kclvm_ast::ast;
 use kclvm_ast_pretty::{print_ast_node, print_schema_expr, ASTNode};
+use kclvm_error::diagnostic::Range;
 use kclvm_error::*;
 use kclvm_primitives::IndexMap;
 
@@ -20,6 +22,40 @@ const MAX_SCOPE_SCAN_COUNT: usize = 3;
 pub const MIXIN_SUFFIX: &str = "Mixin";
 pub const PROTOCOL_SUFFIX: &str = "Protocol";
 
+/// Get the literal type of the enum member value e.g., `"tcp"`, `1`, `-1.0` and `True`.
+fn enum_member_literal_ty(value: &ast::Expr) -> Option<TypeRef> {
+    let ty = match value {
+        ast::Expr::StringLit(string_lit) => Type::str_lit(&string_lit.value),
+        ast::Expr::NumberLit(ast::NumberLit {
+            binary_suffix: None,
+            value,
+        }) => match value {
+            ast::NumberLitValue::Int(v) => Type::int_lit(*v),
+            ast::NumberLitValue::Float(v) => Type::float_lit(*v),
+        },
+        ast::Expr::NameConstantLit(name_constant_lit) => match name_constant_lit.value {
+            ast::NameConstant::True => Type::bool_lit(true),
+            ast::NameConstant::False => Type::bool_lit(false),
+            _ => return None,
+        },
+        ast::Expr::Unary(ast::UnaryExpr {
+            op: ast::UnaryOp::USub,
+            operand,
+        }) => match &operand.node {
+            ast::Expr::NumberLit(ast::NumberLit {
+                binary_suffix: None,
+                value,
+            }) => match value {
+                ast::NumberLitValue::Int(v) => Type::int_lit(-*v),
+                ast::NumberLitValue::Float(v) => Type::float_lit(-*v),
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(Arc::new(ty))
+}
+
 impl<'ctx> Resolver<'_> {
     /// Init global types including top-level global variable types and
     /// schema types. Because the schema allows backward references,
@@ -32,6 +68,7 @@ impl<'ctx> Resolver<'_> {
             .pkgs
             .get(pkgpath)
             .or(self.program.pkgs_not_imported.get(pkgpath))
+            .or(self.program.plugin_decls.get(pkgpath))
         {
             Some(modules) => {
                 // 1. Scan all schema and rule type symbol
@@ -46,35 +83,46 @@ impl<'ctx> Resolver<'_> {
                     self.change_package_context(pkgpath, filename);
                     for stmt in &module.body {
                         let (start, end) = stmt.get_span_pos();
-                        let (name, doc, is_mixin, is_protocol, is_rule) = match &stmt.node {
-                            ast::Stmt::Schema(schema_stmt) => (
-                                &schema_stmt.name.node,
-                                {
-                                    if let Some(doc) = &schema_stmt.doc {
-                                        doc.node.clone()
-                                    } else {
-                                        "".to_string()
-                                    }
-                                },
-                                schema_stmt.is_mixin,
-                                schema_stmt.is_protocol,
-                                false,
-                            ),
-                            ast::Stmt::Rule(rule_stmt) => (
-                                &rule_stmt.name.node,
-                                {
-                                    if let Some(doc) = &rule_stmt.doc {
-                                        doc.node.clone()
-                                    } else {
-                                        "".to_string()
-                                    }
-                                },
-                                false,
-                                false,
-                                true,
-                            ),
-                            _ => continue,
-                        };
+                        if let ast::Stmt::Enum(enum_stmt) = &stmt.node {
+                            self.init_enum_type(enum_stmt, stmt.get_span_pos());
+                            continue;
+                        }
+                        let (name, doc, is_mixin, is_protocol, is_rule, type_params) =
+                            match &stmt.node {
+                                ast::Stmt::Schema(schema_stmt) => (
+                                    &schema_stmt.name.node,
+                                    {
+                                        if let Some(doc) = &schema_stmt.doc {
+                                            doc.node.clone()
+                                        } else {
+                                            "".to_string()
+                                        }
+                                    },
+                                    schema_stmt.is_mixin,
+                                    schema_stmt.is_protocol,
+                                    false,
+                                    schema_stmt
+                                        .type_params
+                                        .iter()
+                                        .map(|param| param.node.clone())
+                                        .collect(),
+                                ),
+                                ast::Stmt::Rule(rule_stmt) => (
+                                    &rule_stmt.name.node,
+                                    {
+                                        if let Some(doc) = &rule_stmt.doc {
+                                            doc.node.clone()
+                                        } else {
+                                            "".to_string()
+                                        }
+                                    },
+                                    false,
+                                    false,
+                                    true,
+                                    vec![],
+                                ),
+                                _ => continue,
+                            };
                         if self.contains_object(name) {
                             self.handler.add_error(
                                 ErrorKind::UniqueKeyError,
@@ -113,6 +161,8 @@ impl<'ctx> Resolver<'_> {
                             }),
                             index_signature: None,
                             decorators: vec![],
+                            type_params,
+                            type_args: vec![],
                         };
                         self.insert_object(
                             name,
@@ -184,10 +234,113 @@ impl<'ctx> Resolver<'_> {
         };
     }
 
+    /// Init the enum type and insert it into the global scope. Enum members
+    /// can only be literal values, and the default value of a member is its
+    /// name string.
+    fn init_enum_type(&mut self, enum_stmt: &ast::EnumStmt, range: Range) {
+        let name = &enum_stmt.name.node;
+        if self.contains_object(name) {
+            self.handler.add_error(
+                ErrorKind::UniqueKeyError,
+                &[Message {
+                    range,
+                    style: Style::LineAndColumn,
+                    message: format!("Unique key error name '{}'", name),
+                    note: None,
+                    suggested_replacement: None,
+                }],
+            );
+            return;
+        }
+        if RESERVED_TYPE_IDENTIFIERS.contains(&name.as_str()) {
+            self.handler.add_type_error(
+                &format!(
+                    "enum '{}' cannot be the same as the built-in types ({:?})",
+                    name, RESERVED_TYPE_IDENTIFIERS
+                ),
+                enum_stmt.name.get_span_pos(),
+            );
+        }
+        let mut members = IndexMap::default();
+        for member in &enum_stmt.members {
+            let member_name = &member.node.name.node;
+            if members.contains_key(member_name) {
+                self.handler.add_error(
+                    ErrorKind::UniqueKeyError,
+                    &[Message {
+                        range: member.node.name.get_span_pos(),
+                        style: Style::LineAndColumn,
+                        message: format!("enum '{}' has duplicate member '{}'", name, member_name),
+                        note: None,
+                        suggested_replacement: None,
+                    }],
+                );
+                continue;
+            }
+            let ty = match &member.node.value {
+                Some(value) => match enum_member_literal_ty(&value.node) {
+                    Some(ty) => ty,
+                    None => {
+                        self.handler.add_type_error(
+                            &format!(
+                                "enum member '{}.{}' value must be a str, int, float or bool literal",
+                                name, member_name
+                            ),
+                            value.get_span_pos(),
+                        );
+                        self.any_ty()
+                    }
+                },
+                None => Arc::new(Type::str_lit(member_name)),
+            };
+            members.insert(
+                member_name.to_string(),
+                EnumMemberType {
+                    ty,
+                    range: member.get_span_pos(),
+                    doc: member
+                        .node
+                        .doc
+                        .as_ref()
+                        .map(|doc| parse_schema_doc_string(&doc.node).summary),
+                },
+            );
+        }
+        let doc = enum_stmt
+            .doc
+            .as_ref()
+            .map(|doc| parse_schema_doc_string(&doc.node).summary)
+            .unwrap_or_default();
+        let enum_ty = EnumType {
+            name: name.to_string(),
+            pkgpath: self.ctx.pkgpath.clone(),
+            filename: self.ctx.filename.clone(),
+            doc: doc.clone(),
+            members,
+            is_instance: false,
+        };
+        self.insert_object(
+            name,
+            ScopeObject {
+                name: name.to_string(),
+                start: range.0,
+                end: range.1,
+                ty: Arc::new(Type::enumeration(enum_ty)),
+                kind: ScopeObjectKind::Definition,
+                doc: Some(doc),
+            },
+        )
+    }
+
     /// Init global var types.
     pub(crate) fn init_global_var_types(&mut self, unique_check: bool) {
         let pkgpath = &self.ctx.pkgpath;
-        match self.program.pkgs.get(pkgpath) {
+        match self
+            .program
+            .pkgs
+            .get(pkgpath)
+            .or(self.program.plugin_decls.get(pkgpath))
+        {
             Some(modules) => {
                 // 1. Scan all schema and rule type symbol
                 for module in modules {
@@ -482,6 +635,13 @@ impl<'ctx> Resolver<'_> {
         should_add_schema_ref: bool,
     ) -> SchemaType {
         let name = &schema_stmt.name.node;
+        // Bring the type parameters into the type scope to resolve the attribute types.
+        let type_params: Vec<String> = schema_stmt
+            .type_params
+            .iter()
+            .map(|param| param.node.clone())
+            .collect();
+        let type_params_depth = self.push_type_params(&type_params);
         if RESERVED_TYPE_IDENTIFIERS.contains(&name.as_str()) {
             self.handler.add_compile_error_with_suggestions(
                 &format!(
@@ -586,7 +746,7 @@ impl<'ctx> Resolver<'_> {
                 .unwrap_or_default(),
         );
         for stmt in &schema_stmt.body {
-            let (name, ty, is_optional, default, decorators, range) = match &stmt.node {
+            let (name, ty, is_optional, is_final, default, decorators, range) = match &stmt.node {
                 ast::Stmt::Unification(unification_stmt) => {
                     let name = unification_stmt.value.node.name.node.get_name();
                     let ty = self.parse_ty_str_with_scope(&name, stmt.get_span_pos());
@@ -600,6 +760,7 @@ impl<'ctx> Resolver<'_> {
                         unification_stmt.target.node.get_name(),
                         ty,
                         is_optional,
+                        false,
                         Some(default),
                         vec![],
                         stmt.get_span_pos(),
@@ -627,6 +788,7 @@ impl<'ctx> Resolver<'_> {
                         name,
                         ty,
                         is_optional,
+                        schema_attr.is_final,
                         default,
                         decorators,
                         stmt.get_span_pos(),
@@ -638,6 +800,12 @@ impl<'ctx> Resolver<'_> {
                 Some(ref ty) => ty.get_type_of_attr(&name).map_or(self.any_ty(), |ty| ty),
                 None => self.any_ty(),
             };
+            if is_final && default.is_none() {
+                self.handler.add_compile_error(
+                    &format!("the final attribute '{}' must have a default value", name),
+                    stmt.get_span_pos(),
+                );
+            }
             if !attr_obj_map.contains_key(&name) {
                 let existed_attr = parent_ty.as_ref().and_then(|ty| ty.get_obj_of_attr(&name));
                 let doc_str = parsed_doc.attrs.iter().find_map(|attr| {
@@ -651,6 +819,7 @@ impl<'ctx> Resolver<'_> {
                     name.clone(),
                     SchemaAttr {
                         is_optional: existed_attr.map_or(is_optional, |attr| attr.is_optional),
+                        is_final: is_final || existed_attr.map_or(false, |attr| attr.is_final),
                         has_default: default.is_some(),
                         default,
                         ty: ty.clone(),
@@ -849,7 +1018,10 @@ impl<'ctx> Resolver<'_> {
             }),
             index_signature,
             decorators,
+            type_params,
+            type_args: vec![],
         };
+        self.pop_type_params(type_params_depth);
         let schema_runtime_ty = kclvm_runtime::schema_runtime_type(name, &self.ctx.pkgpath);
         self.ctx
             .schema_mapping
@@ -1001,6 +1173,8 @@ impl<'ctx> Resolver<'_> {
             }),
             index_signature,
             decorators,
+            type_params: vec![],
+            type_args: vec![],
         }
     }
 }
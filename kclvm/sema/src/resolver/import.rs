@@ -7,6 +7,7 @@ use crate::{
     ty::{Type, TypeKind},
 };
 use kclvm_ast::ast;
+use kclvm_error::diagnostic::Range;
 use kclvm_error::*;
 use kclvm_primitives::IndexMap;
 use std::rc::Rc;
@@ -120,6 +121,63 @@ impl<'ctx> Resolver<'ctx> {
         self.init_import_list();
     }
 
+    /// Check the export statement at the top level of the module, all the exported
+    /// names must be defined in the current package.
+    pub(crate) fn check_export_stmt(&mut self, export_stmt: &ast::ExportStmt) {
+        let scope = match self.scope_map.get(&self.ctx.pkgpath) {
+            Some(scope) => scope.clone(),
+            None => return,
+        };
+        for name in &export_stmt.names {
+            let obj = scope.borrow().elems.get(&name.node).cloned();
+            match obj {
+                Some(obj) if !obj.borrow().ty.is_module() => {}
+                _ => self.handler.add_error(
+                    ErrorKind::CompileError,
+                    &[Message {
+                        range: name.get_span_pos(),
+                        style: Style::LineAndColumn,
+                        message: format!(
+                            "the exported name '{}' is not defined in the package '{}'",
+                            name.node, self.ctx.pkgpath
+                        ),
+                        note: None,
+                        suggested_replacement: None,
+                    }],
+                ),
+            }
+        }
+    }
+
+    /// Check whether the member of the user module can be accessed from the current
+    /// package. A package without export statements exports all its members.
+    pub(crate) fn check_exported_member(&mut self, pkgpath: &str, name: &str, range: &Range) {
+        if pkgpath == self.ctx.pkgpath {
+            return;
+        }
+        if !self.ctx.pkg_exports.contains_key(pkgpath) {
+            let exports = self.program.get_pkg_exports(pkgpath);
+            self.ctx.pkg_exports.insert(pkgpath.to_string(), exports);
+        }
+        if let Some(Some(exports)) = self.ctx.pkg_exports.get(pkgpath) {
+            if !exports.contains(name) {
+                self.handler.add_error(
+                    ErrorKind::AttributeError,
+                    &[Message {
+                        range: range.clone(),
+                        style: Style::LineAndColumn,
+                        message: format!(
+                            "the member '{}' is not exported by the module '{}'",
+                            name, pkgpath
+                        ),
+                        note: None,
+                        suggested_replacement: None,
+                    }],
+                );
+            }
+        }
+    }
+
     /// Init import list and store the module scope object into the scope map.
     fn init_import_list(&mut self) {
         let modules = self.program.pkgs.get(&self.ctx.pkgpath);
@@ -243,7 +301,13 @@ impl<'ctx> Resolver<'ctx> {
                                         matches!(kind, ModuleKind::User)
                                     }
                                 };
-                                if !is_user_module {
+                                // Plugin modules with declarations are checked as user modules.
+                                if !is_user_module
+                                    && !self
+                                        .program
+                                        .plugin_decls
+                                        .contains_key(&import_stmt.path.node)
+                                {
                                     continue;
                                 }
                             }
@@ -7,12 +7,15 @@ mod format;
 pub mod global;
 mod import;
 mod r#loop;
+mod narrow;
 mod node;
 mod para;
+mod pattern;
 mod schema;
 pub mod scope;
 pub(crate) mod ty;
 mod ty_alias;
+mod ty_enum;
 mod ty_erasure;
 mod var;
 
@@ -21,17 +24,20 @@ mod tests;
 
 use kclvm_error::diagnostic::Range;
 use kclvm_primitives::{IndexMap, IndexSet};
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::{cell::RefCell, rc::Rc};
 
 use crate::lint::{CombinedLintPass, Linter};
 use crate::pre_process::pre_process_program;
+use crate::resolver::narrow::NarrowedTypes;
 use crate::resolver::scope::ScopeObject;
 use crate::resolver::ty_alias::type_alias_pass;
+use crate::resolver::ty_enum::enum_member_pass;
 use crate::resolver::ty_erasure::type_func_erasure_pass;
 use crate::ty::TypeContext;
 use crate::{resolver::scope::Scope, ty::SchemaType};
-use kclvm_ast::ast::Program;
+use kclvm_ast::ast::{self, Program};
 use kclvm_error::*;
 
 use self::scope::{builtin_scope, KCLScopeCache, NodeTyMap, ProgramScope};
@@ -78,6 +84,7 @@ impl<'ctx> Resolver<'ctx> {
             .pkgs
             .get(pkgpath)
             .or(self.program.pkgs_not_imported.get(pkgpath))
+            .or(self.program.plugin_decls.get(pkgpath))
         {
             Some(modules) => {
                 for module in modules {
@@ -91,7 +98,12 @@ impl<'ctx> Resolver<'ctx> {
                         files.insert(module.filename.to_string());
                     }
                     for stmt in &module.body {
-                        self.stmt(&stmt);
+                        match &stmt.node {
+                            ast::Stmt::Export(export_stmt) => self.check_export_stmt(export_stmt),
+                            _ => {
+                                self.stmt(&stmt);
+                            }
+                        }
                     }
                     if self.options.lint_check {
                         self.lint_check_module(&module);
@@ -160,8 +172,14 @@ pub struct Context {
     pub config_expr_context: Vec<Option<ScopeObject>>,
     /// Type context.
     pub ty_ctx: TypeContext,
+    /// Type parameters of the generic schemas and lambdas in the current scope.
+    pub type_params: Vec<String>,
+    /// Flow-sensitive narrowed variable types of the current branches.
+    pub narrowed_types: Vec<NarrowedTypes>,
     /// Type alias mapping
     pub type_alias_mapping: IndexMap<String, IndexMap<String, String>>,
+    /// Exported member names of the user packages, `None` denotes all the members are exported.
+    pub pkg_exports: IndexMap<String, Option<HashSet<String>>>,
     /// invalid pkg scope, remove when after resolve
     pub invalid_pkg_scope: IndexSet<String>,
 }
@@ -244,6 +262,8 @@ pub fn resolve_program_with_opts(
     }
     if opts.type_erasure {
         let type_alias_mapping = resolver.ctx.type_alias_mapping.clone();
+        // Replace all the enum members with their literal values
+        enum_member_pass(program, &scope.node_ty_map.borrow());
         // Erase all the function type to a named type "function"
         type_func_erasure_pass(program);
         // Erase types with their type alias
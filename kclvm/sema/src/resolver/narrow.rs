@@ -0,0 +1,299 @@
+use std::sync::Arc;
+
+use crate::resolver::Resolver;
+use crate::ty::{
+    subsume, sup, Type, TypeKind, TypeRef, BOOL_TYPE_STR, DICT_TYPE_STR, FLOAT_TYPE_STR,
+    FUNCTION_TYPE_STR, INT_TYPE_STR, LIST_TYPE_STR, NONE_TYPE_STR, STR_TYPE_STR,
+};
+use kclvm_ast::ast;
+use kclvm_primitives::IndexMap;
+
+/// The flow-sensitive narrowed variable types in a branch, where the key is the
+/// variable name e.g., `x` or `x.y`, and a `None` value denotes the variable is
+/// shadowed by a local variable e.g., a loop variable or a lambda parameter and
+/// it is not narrowed.
+pub type NarrowedTypes = IndexMap<String, Option<TypeRef>>;
+
+const TYPEOF_FUNC_NAME: &str = "typeof";
+
+impl<'ctx> Resolver<'ctx> {
+    /// Resolve the expression with the narrowed variable types.
+    pub(crate) fn expr_with_narrowed_types(
+        &mut self,
+        expr: &'ctx ast::NodeRef<ast::Expr>,
+        narrowed_types: NarrowedTypes,
+    ) -> TypeRef {
+        self.ctx.narrowed_types.push(narrowed_types);
+        let ty = self.expr(expr);
+        self.ctx.narrowed_types.pop();
+        ty
+    }
+
+    /// Resolve the statements with the narrowed variable types.
+    pub(crate) fn stmts_with_narrowed_types(
+        &mut self,
+        stmts: &'ctx [ast::NodeRef<ast::Stmt>],
+        narrowed_types: NarrowedTypes,
+    ) -> TypeRef {
+        self.ctx.narrowed_types.push(narrowed_types);
+        let ty = self.stmts(stmts);
+        self.ctx.narrowed_types.pop();
+        ty
+    }
+
+    /// Resolve the comprehension guards, the latter guards and the comprehension
+    /// element are narrowed by the former guards. The narrowed types are cleared
+    /// by the comprehension when leaving its scope.
+    pub(crate) fn comp_guards_with_narrowed_types(
+        &mut self,
+        guards: &'ctx [ast::NodeRef<ast::Expr>],
+    ) {
+        for guard in guards {
+            self.expr(guard);
+            self.ctx.narrowed_types.push(self.narrow_cond(guard, true));
+        }
+    }
+
+    /// Shadow the narrowed types of the outer variables by the local variables
+    /// e.g., loop variables and lambda parameters.
+    pub(crate) fn shadow_narrowed_types<'a>(&mut self, names: impl Iterator<Item = &'a str>) {
+        if !self.ctx.narrowed_types.is_empty() {
+            self.ctx
+                .narrowed_types
+                .push(names.map(|name| (name.to_string(), None)).collect());
+        }
+    }
+
+    /// Lookup the narrowed type of the variable name from the inner branch to
+    /// the outer branch.
+    pub(crate) fn lookup_narrowed_type(&self, name: &str) -> Option<TypeRef> {
+        for narrowed_types in self.ctx.narrowed_types.iter().rev() {
+            if let Some(ty) = narrowed_types.get(name) {
+                return ty.clone();
+            }
+        }
+        None
+    }
+
+    /// Clear the narrowed types of the variable and its attributes when the
+    /// variable is assigned.
+    pub(crate) fn clear_narrowed_type(&mut self, name: &str) {
+        let prefix = format!("{}.", name);
+        for narrowed_types in self.ctx.narrowed_types.iter_mut() {
+            narrowed_types
+                .retain(|key, ty| ty.is_none() || (key != name && !key.starts_with(&prefix)));
+        }
+    }
+
+    /// Narrow the variable types by the condition expression which has been
+    /// resolved, `truthy` denotes whether the condition is true in the branch.
+    ///
+    /// Supported conditions:
+    /// - `x`, `x != None`, `x is not None` and `x == None`, `x is None`.
+    /// - `typeof(x) == "str"` and `typeof(x) != "str"`.
+    /// - `x is Schema` and `x is not Schema`.
+    /// - `x == "literal"` and `x != "literal"`.
+    /// - `not cond`, `cond1 and cond2` and `cond1 or cond2`.
+    pub(crate) fn narrow_cond(
+        &self,
+        cond: &'ctx ast::NodeRef<ast::Expr>,
+        truthy: bool,
+    ) -> NarrowedTypes {
+        let mut narrowed_types = NarrowedTypes::default();
+        match &cond.node {
+            ast::Expr::Paren(paren_expr) => return self.narrow_cond(&paren_expr.expr, truthy),
+            ast::Expr::Unary(unary_expr) if matches!(unary_expr.op, ast::UnaryOp::Not) => {
+                return self.narrow_cond(&unary_expr.operand, !truthy)
+            }
+            ast::Expr::Binary(binary_expr) => match binary_expr.op {
+                // Both operands are true when `a and b` is true, and both operands
+                // are false when `a or b` is false.
+                ast::BinOp::And | ast::BinOp::Or
+                    if truthy == matches!(binary_expr.op, ast::BinOp::And) =>
+                {
+                    narrowed_types.extend(self.narrow_cond(&binary_expr.left, truthy));
+                    narrowed_types.extend(self.narrow_cond(&binary_expr.right, truthy));
+                }
+                _ => {}
+            },
+            ast::Expr::Identifier(_) if truthy => {
+                if let Some((name, ty)) = self.narrowing_target(cond) {
+                    if let Some(ty) = filter_union_ty(&ty, |ty| !ty.is_none()) {
+                        narrowed_types.insert(name, Some(ty));
+                    }
+                }
+            }
+            ast::Expr::Compare(compare) if compare.ops.len() == 1 => {
+                let positive = match &compare.ops[0] {
+                    ast::CmpOp::Eq | ast::CmpOp::Is => truthy,
+                    ast::CmpOp::NotEq | ast::CmpOp::IsNot => !truthy,
+                    _ => return narrowed_types,
+                };
+                let is_identity = matches!(compare.ops[0], ast::CmpOp::Is | ast::CmpOp::IsNot);
+                let right = &compare.comparators[0];
+                let narrowed = self
+                    .narrow_compare(&compare.left, right, is_identity, positive)
+                    .or_else(|| self.narrow_compare(right, &compare.left, is_identity, positive));
+                if let Some((name, ty)) = narrowed {
+                    narrowed_types.insert(name, Some(ty));
+                }
+            }
+            _ => {}
+        }
+        narrowed_types
+    }
+
+    /// Narrow the variable type in the `target` expression compared with the
+    /// `other` expression, `positive` denotes whether they are equal.
+    fn narrow_compare(
+        &self,
+        target: &'ctx ast::NodeRef<ast::Expr>,
+        other: &'ctx ast::NodeRef<ast::Expr>,
+        is_identity: bool,
+        positive: bool,
+    ) -> Option<(String, TypeRef)> {
+        // typeof(x) == "str"
+        if let (ast::Expr::Call(call_expr), ast::Expr::StringLit(string_lit)) =
+            (&target.node, &other.node)
+        {
+            let is_typeof_call = matches!(
+                &call_expr.func.node,
+                ast::Expr::Identifier(identifier) if identifier.get_name() == TYPEOF_FUNC_NAME
+            ) && call_expr.args.len() == 1
+                && call_expr.keywords.is_empty();
+            if !is_typeof_call {
+                return None;
+            }
+            let (name, ty) = self.narrowing_target(&call_expr.args[0])?;
+            let type_name = string_lit.value.as_str();
+            let ty = if positive {
+                if ty.is_any() {
+                    builtin_ty_of_typeof(type_name)
+                } else {
+                    filter_union_ty(&ty, |ty| typeof_str(ty).map_or(true, |s| s == type_name))
+                }
+            } else {
+                filter_union_ty(&ty, |ty| typeof_str(ty).map_or(true, |s| s != type_name))
+            }?;
+            return Some((name, ty));
+        }
+        let (name, ty) = self.narrowing_target(target)?;
+        let other_ty = self.node_ty(other)?;
+        let ty = match &other_ty.kind {
+            // x == None
+            TypeKind::None => {
+                if positive {
+                    filter_union_ty(&ty, |ty| ty.is_none())
+                } else {
+                    filter_union_ty(&ty, |ty| !ty.is_none())
+                }
+            }
+            // x is Schema
+            TypeKind::Schema(schema_ty) if is_identity && !schema_ty.is_instance => {
+                let is_instance_of = |ty: &TypeRef| match &ty.kind {
+                    TypeKind::Schema(ty) => {
+                        ty.is_instance && ty.full_ty_str() == schema_ty.full_ty_str()
+                    }
+                    _ => false,
+                };
+                if positive {
+                    if ty.is_any() {
+                        let mut schema_ty = schema_ty.clone();
+                        schema_ty.is_instance = true;
+                        Some(Arc::new(Type::schema(schema_ty)))
+                    } else {
+                        filter_union_ty(&ty, is_instance_of)
+                    }
+                } else {
+                    filter_union_ty(&ty, |ty| !is_instance_of(ty))
+                }
+            }
+            // x == "literal"
+            _ if !is_identity && other_ty.is_literal() => {
+                if positive {
+                    let types = match &ty.kind {
+                        TypeKind::Union(types) => types.clone(),
+                        _ => vec![ty.clone()],
+                    };
+                    types
+                        .iter()
+                        .any(|ty| subsume(other_ty.clone(), ty.clone(), false))
+                        .then(|| other_ty.clone())
+                } else {
+                    filter_union_ty(&ty, |ty| ty.ty_str() != other_ty.ty_str())
+                }
+            }
+            _ => None,
+        }?;
+        Some((name, ty))
+    }
+
+    /// Get the variable name and its resolved type of the narrowing target expression.
+    pub(crate) fn narrowing_target(
+        &self,
+        expr: &'ctx ast::NodeRef<ast::Expr>,
+    ) -> Option<(String, TypeRef)> {
+        match &expr.node {
+            ast::Expr::Identifier(identifier) if identifier.pkgpath.is_empty() => {
+                Some((identifier.get_name(), self.node_ty(expr)?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the resolved type of the expression.
+    #[inline]
+    pub(crate) fn node_ty(&self, expr: &'ctx ast::NodeRef<ast::Expr>) -> Option<TypeRef> {
+        self.node_ty_map
+            .borrow()
+            .get(&self.get_node_key(expr.id.clone()))
+            .cloned()
+    }
+}
+
+/// Keep the members of the union type matched by the predicate, returns `None`
+/// when the type is not a union type or no member is removed or kept.
+pub(crate) fn filter_union_ty(ty: &TypeRef, f: impl Fn(&TypeRef) -> bool) -> Option<TypeRef> {
+    match &ty.kind {
+        TypeKind::Union(types) => {
+            let filtered_types: Vec<TypeRef> = types.iter().filter(|ty| f(ty)).cloned().collect();
+            if filtered_types.is_empty() || filtered_types.len() == types.len() {
+                None
+            } else {
+                Some(sup(&filtered_types))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Get the `typeof` result string of the value with the type, returns `None`
+/// when it is unknown at compile time.
+fn typeof_str(ty: &TypeRef) -> Option<&str> {
+    match &ty.kind {
+        TypeKind::Bool | TypeKind::BoolLit(_) => Some(BOOL_TYPE_STR),
+        TypeKind::Int | TypeKind::IntLit(_) => Some(INT_TYPE_STR),
+        TypeKind::Float | TypeKind::FloatLit(_) => Some(FLOAT_TYPE_STR),
+        TypeKind::Str | TypeKind::StrLit(_) => Some(STR_TYPE_STR),
+        TypeKind::List(_) => Some(LIST_TYPE_STR),
+        TypeKind::Dict(_) => Some(DICT_TYPE_STR),
+        TypeKind::None => Some(NONE_TYPE_STR),
+        TypeKind::Function(_) => Some(FUNCTION_TYPE_STR),
+        TypeKind::Schema(schema_ty) if schema_ty.is_instance => Some(&schema_ty.name),
+        _ => None,
+    }
+}
+
+/// Get the built-in type from the `typeof` result string.
+fn builtin_ty_of_typeof(type_name: &str) -> Option<TypeRef> {
+    match type_name {
+        BOOL_TYPE_STR => Some(Type::bool_ref()),
+        INT_TYPE_STR => Some(Type::int_ref()),
+        FLOAT_TYPE_STR => Some(Type::float_ref()),
+        STR_TYPE_STR => Some(Type::str_ref()),
+        LIST_TYPE_STR => Some(Type::list_ref(Type::any_ref())),
+        DICT_TYPE_STR => Some(Type::dict_ref(Type::any_ref(), Type::any_ref())),
+        NONE_TYPE_STR => Some(Arc::new(Type::NONE)),
+        _ => None,
+    }
+}
@@ -8,12 +8,13 @@ use std::sync::Arc;
 
 use crate::info::is_private_field;
 use crate::ty::{
-    sup, DictType, FunctionType, Parameter, Type, TypeInferMethods, TypeKind, TypeRef,
-    RESERVED_TYPE_IDENTIFIERS,
+    sup, DictType, FunctionType, Parameter, Type, TypeBindings, TypeInferMethods, TypeKind,
+    TypeRef, RESERVED_TYPE_IDENTIFIERS,
 };
 
 use super::doc::extract_doc_from_body;
 use super::format::VALID_FORMAT_SPEC_SET;
+use super::narrow::NarrowedTypes;
 use super::scope::{ScopeKind, ScopeObject, ScopeObjectKind};
 use super::ty::ty_str_replace_pkgpath;
 use super::Resolver;
@@ -297,8 +298,10 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
 
     fn walk_if_stmt(&mut self, if_stmt: &'ctx ast::IfStmt) -> Self::Result {
         self.expr(&if_stmt.cond);
-        let if_ty = self.stmts(&if_stmt.body);
-        let orelse_ty = self.stmts(&if_stmt.orelse);
+        let if_ty =
+            self.stmts_with_narrowed_types(&if_stmt.body, self.narrow_cond(&if_stmt.cond, true));
+        let orelse_ty =
+            self.stmts_with_narrowed_types(&if_stmt.orelse, self.narrow_cond(&if_stmt.cond, false));
         sup(&[if_ty, orelse_ty])
     }
 
@@ -307,6 +310,16 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         self.any_ty()
     }
 
+    fn walk_export_stmt(&mut self, _export_stmt: &'ctx ast::ExportStmt) -> Self::Result {
+        // The export statements at the top level of the module are checked in the
+        // package checking, thus it must be a nested one here.
+        self.handler.add_compile_error(
+            "the export statement is only allowed at the top level of the module",
+            (self.ctx.start_pos.clone(), self.ctx.end_pos.clone()),
+        );
+        self.any_ty()
+    }
+
     fn walk_schema_stmt(&mut self, schema_stmt: &'ctx ast::SchemaStmt) -> Self::Result {
         self.resolve_schema_stmt(schema_stmt)
     }
@@ -315,6 +328,28 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         self.resolve_rule_stmt(rule_stmt)
     }
 
+    fn walk_enum_stmt(&mut self, enum_stmt: &'ctx ast::EnumStmt) -> Self::Result {
+        // The enum type has been built in the global scope.
+        let ty = match self.find_type_in_scope(&enum_stmt.name.node) {
+            Some(ty) if ty.is_enum() => ty,
+            _ => return self.any_ty(),
+        };
+        self.node_ty_map
+            .borrow_mut()
+            .insert(self.get_node_key(enum_stmt.name.id.clone()), ty.clone());
+        if let TypeKind::Enum(enum_ty) = &ty.kind {
+            for member in &enum_stmt.members {
+                if let Some(member_ty) = enum_ty.members.get(&member.node.name.node) {
+                    self.node_ty_map.borrow_mut().insert(
+                        self.get_node_key(member.node.name.id.clone()),
+                        member_ty.ty.clone(),
+                    );
+                }
+            }
+        }
+        self.any_ty()
+    }
+
     fn walk_quant_expr(&mut self, quant_expr: &'ctx ast::QuantExpr) -> Self::Result {
         let iter_ty = self.expr(&quant_expr.target);
         let (start, mut end) = quant_expr.test.get_span_pos();
@@ -322,6 +357,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             end = if_cond.get_end_pos();
         }
         self.enter_scope(start, end, ScopeKind::Loop);
+        let narrowed_depth = self.ctx.narrowed_types.len();
         let (mut key_name, mut val_name) = (None, None);
         for (i, target) in quant_expr.variables.iter().enumerate() {
             if target.node.names.is_empty() {
@@ -362,6 +398,12 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 },
             );
         }
+        self.shadow_narrowed_types(
+            [key_name, val_name]
+                .iter()
+                .flatten()
+                .map(|name| name.node.as_str()),
+        );
         self.do_loop_type_check(
             key_name,
             val_name,
@@ -369,7 +411,13 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             quant_expr.target.get_span_pos(),
         );
         self.expr_or_any_type(&quant_expr.if_cond);
+        if let Some(if_cond) = &quant_expr.if_cond {
+            self.ctx
+                .narrowed_types
+                .push(self.narrow_cond(if_cond, true));
+        }
         let item_ty = self.expr(&quant_expr.test);
+        self.ctx.narrowed_types.truncate(narrowed_depth);
         self.leave_scope();
         match &quant_expr.op {
             ast::QuantOperation::All | ast::QuantOperation::Any => self.bool_ty(),
@@ -472,8 +520,10 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
     /// <body> if <cond> else <orelse> -> sup([body, orelse])
     fn walk_if_expr(&mut self, if_expr: &'ctx ast::IfExpr) -> Self::Result {
         self.expr(&if_expr.cond);
-        let body_ty = self.expr(&if_expr.body);
-        let orelse_ty = self.expr(&if_expr.orelse);
+        let body_ty =
+            self.expr_with_narrowed_types(&if_expr.body, self.narrow_cond(&if_expr.cond, true));
+        let orelse_ty =
+            self.expr_with_narrowed_types(&if_expr.orelse, self.narrow_cond(&if_expr.cond, false));
         sup(&[body_ty, orelse_ty])
     }
 
@@ -488,7 +538,15 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
 
     fn walk_binary_expr(&mut self, binary_expr: &'ctx ast::BinaryExpr) -> Self::Result {
         let left_ty = self.expr(&binary_expr.left);
-        let mut right_ty = self.expr(&binary_expr.right);
+        let mut right_ty = match &binary_expr.op {
+            // The right operand of `a and b` is evaluated only when `a` is true,
+            // and the right operand of `a or b` is evaluated only when `a` is false.
+            ast::BinOp::And | ast::BinOp::Or => self.expr_with_narrowed_types(
+                &binary_expr.right,
+                self.narrow_cond(&binary_expr.left, matches!(binary_expr.op, ast::BinOp::And)),
+            ),
+            _ => self.expr(&binary_expr.right),
+        };
         let range = (binary_expr.left.get_pos(), binary_expr.right.get_end_pos());
         match &binary_expr.op {
             ast::BinOp::As => {
@@ -579,13 +637,24 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             );
             self.any_ty()
         } else if let TypeKind::Function(func_ty) = &call_ty.kind {
-            self.do_arguments_type_check(
+            let bindings = self.do_arguments_type_check(
                 &call_expr.func,
                 &call_expr.args,
                 &call_expr.keywords,
                 &func_ty,
             );
-            func_ty.return_ty.clone()
+            if func_ty.return_ty.has_type_param() {
+                // Instantiate the return type of the generic function, and the
+                // unbound type parameters are treated as the any type.
+                let return_ty = func_ty.return_ty.substitute(&bindings);
+                if return_ty.has_type_param() {
+                    self.any_ty()
+                } else {
+                    return_ty
+                }
+            } else {
+                func_ty.return_ty.clone()
+            }
         } else if let TypeKind::Schema(schema_ty) = &call_ty.kind {
             if schema_ty.is_instance {
                 self.handler.add_compile_error(
@@ -645,6 +714,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             None => list_comp.elt.get_end_pos(),
         };
         self.enter_scope(start.clone(), end, ScopeKind::Loop);
+        let narrowed_depth = self.ctx.narrowed_types.len();
         for comp_clause in &list_comp.generators {
             self.walk_comp_clause(&comp_clause.node);
         }
@@ -655,6 +725,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             );
         }
         let item_ty = self.expr(&list_comp.elt);
+        self.ctx.narrowed_types.truncate(narrowed_depth);
         self.leave_scope();
         self.clear_config_expr_context(stack_depth, false);
         Type::list_ref(item_ty)
@@ -672,13 +743,14 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 None => dict_comp.entry.value.get_end_pos(),
             };
             self.enter_scope(start.clone(), end, ScopeKind::Loop);
+            let narrowed_depth = self.ctx.narrowed_types.len();
             for comp_clause in &dict_comp.generators {
                 self.walk_comp_clause(&comp_clause.node);
             }
             let stack_depth = self.switch_config_expr_context_by_key(&dict_comp.entry.key);
             let val_ty = self.expr(&dict_comp.entry.value);
             let key_ty = match &val_ty.kind {
-                TypeKind::None | TypeKind::Any => val_ty.clone(),
+                TypeKind::None | TypeKind::Any | TypeKind::TypeParam(_) => val_ty.clone(),
                 TypeKind::Dict(DictType { key_ty, .. }) => key_ty.clone(),
                 TypeKind::Schema(schema_ty) => schema_ty.key_ty().clone(),
                 TypeKind::Union(types)
@@ -704,6 +776,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 }
             };
             self.clear_config_expr_context(stack_depth, false);
+            self.ctx.narrowed_types.truncate(narrowed_depth);
             self.leave_scope();
             Type::dict_ref(key_ty, val_ty)
         } else {
@@ -714,6 +787,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             };
             let start = key.get_pos();
             self.enter_scope(start.clone(), end, ScopeKind::Loop);
+            let narrowed_depth = self.ctx.narrowed_types.len();
             for comp_clause in &dict_comp.generators {
                 self.walk_comp_clause(&comp_clause.node);
             }
@@ -722,6 +796,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             let stack_depth = self.switch_config_expr_context_by_key(&dict_comp.entry.key);
             let val_ty = self.expr(&dict_comp.entry.value);
             self.clear_config_expr_context(stack_depth, false);
+            self.ctx.narrowed_types.truncate(narrowed_depth);
             self.leave_scope();
             Type::dict_ref(key_ty, val_ty)
         }
@@ -732,12 +807,20 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         list_if_item_expr: &'ctx ast::ListIfItemExpr,
     ) -> Self::Result {
         self.expr(&list_if_item_expr.if_cond);
+        self.ctx
+            .narrowed_types
+            .push(self.narrow_cond(&list_if_item_expr.if_cond, false));
         let mut or_else_ty = self.expr_or_any_type(&list_if_item_expr.orelse);
+        self.ctx.narrowed_types.pop();
         // `orelse` node maybe a list unpack node, use its item type instead.
         if let TypeKind::List(item_ty) = &or_else_ty.kind {
             or_else_ty = item_ty.clone();
         }
+        self.ctx
+            .narrowed_types
+            .push(self.narrow_cond(&list_if_item_expr.if_cond, true));
         let exprs_ty = sup(&self.exprs(&list_if_item_expr.exprs).to_vec());
+        self.ctx.narrowed_types.pop();
         sup(&[or_else_ty, exprs_ty])
     }
 
@@ -745,7 +828,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         let value_ty = self.expr(&starred_expr.value);
         fn starred_ty_walk_fn(ty: &TypeRef) -> (TypeRef, bool) {
             match &ty.kind {
-                TypeKind::None | TypeKind::Any => (ty.clone(), true),
+                TypeKind::None | TypeKind::Any | TypeKind::TypeParam(_) => (ty.clone(), true),
                 TypeKind::List(item_ty) => (item_ty.clone(), true),
                 TypeKind::Dict(DictType { key_ty, .. }) => (key_ty.clone(), true),
                 TypeKind::Schema(schema_ty) => (schema_ty.key_ty(), true),
@@ -784,9 +867,16 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         config_if_entry_expr: &'ctx ast::ConfigIfEntryExpr,
     ) -> Self::Result {
         self.expr(&config_if_entry_expr.if_cond);
+        self.ctx
+            .narrowed_types
+            .push(self.narrow_cond(&config_if_entry_expr.if_cond, true));
         let dict_ty = self.walk_config_entries(&config_if_entry_expr.items);
+        self.ctx.narrowed_types.pop();
         if let Some(orelse) = &config_if_entry_expr.orelse {
-            let or_else_ty = self.expr(orelse);
+            let or_else_ty = self.expr_with_narrowed_types(
+                orelse,
+                self.narrow_cond(&config_if_entry_expr.if_cond, false),
+            );
             sup(&[dict_ty, or_else_ty])
         } else {
             dict_ty
@@ -843,12 +933,18 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             );
             prev_target_pos = Some(target.get_end_pos());
         }
+        self.shadow_narrowed_types(
+            [key_name, val_name]
+                .iter()
+                .flatten()
+                .map(|name| name.node.as_str()),
+        );
         if iter_ty.is_any() {
-            self.exprs(&comp_clause.ifs);
+            self.comp_guards_with_narrowed_types(&comp_clause.ifs);
             iter_ty
         } else {
             self.do_loop_type_check(key_name, val_name, iter_ty, comp_clause.iter.get_span_pos());
-            self.exprs(&comp_clause.ifs);
+            self.comp_guards_with_narrowed_types(&comp_clause.ifs);
             self.any_ty()
         }
     }
@@ -862,6 +958,8 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             );
         }
         let mut range = schema_expr.name.get_span_pos();
+        // The type parameter bindings of the generic schema.
+        let mut bindings = TypeBindings::default();
         let ret_ty = match &def_ty.kind {
             TypeKind::Dict(DictType { .. }) => {
                 let obj = self.new_config_expr_context_item(
@@ -893,7 +991,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                     Position::dummy_pos(),
                 );
                 let init_stack_depth = self.switch_config_expr_context(Some(obj));
-                self.expr(&schema_expr.config);
+                let config_ty = self.expr(&schema_expr.config);
                 self.node_ty_map.borrow_mut().insert(
                     self.get_node_key(schema_expr.config.id.clone()),
                     def_ty.clone(),
@@ -911,16 +1009,24 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                         ast::Expr::Identifier(schema_expr.name.node.clone()),
                         schema_expr.name.pos(),
                     ));
-                    self.do_arguments_type_check(
+                    bindings = self.do_arguments_type_check(
                         &func,
                         &schema_expr.args,
                         &schema_expr.kwargs,
                         &schema_ty.func,
                     );
+                    // Infer the type arguments of the generic schema from the config values.
+                    if let TypeKind::Dict(DictType { attrs, .. }) = &config_ty.kind {
+                        for (name, attr) in attrs {
+                            if let Some(ty) = schema_ty.get_type_of_attr(name) {
+                                self.infer_arg_type_bindings(&ty, attr.ty.clone(), &mut bindings);
+                            }
+                        }
+                    }
                 }
                 self.any_ty()
             }
-            TypeKind::Any => {
+            TypeKind::Any | TypeKind::TypeParam(_) => {
                 return self.any_ty();
             }
             _ => {
@@ -936,6 +1042,14 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         let mut def_ty_clone = def_ty.as_ref().clone();
         if let TypeKind::Schema(schema_ty) = &mut def_ty_clone.kind {
             schema_ty.is_instance = true;
+            if !bindings.is_empty() && schema_ty.type_args.is_empty() {
+                let type_args: Vec<TypeRef> = schema_ty
+                    .type_params
+                    .iter()
+                    .map(|param| bindings.get(param).cloned().unwrap_or_else(Type::any_ref))
+                    .collect();
+                *schema_ty = schema_ty.instantiate(&type_args);
+            }
         }
         if def_ty_clone.is_schema() {
             Arc::new(def_ty_clone)
@@ -960,6 +1074,23 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
     fn walk_lambda_expr(&mut self, lambda_expr: &'ctx ast::LambdaExpr) -> Self::Result {
         let mut ret_ty = self.any_ty();
         let mut params = vec![];
+        self.check_type_params(&lambda_expr.type_params);
+        let type_params: Vec<String> = lambda_expr
+            .type_params
+            .iter()
+            .map(|param| param.node.clone())
+            .collect();
+        let type_params_depth = self.push_type_params(&type_params);
+        let narrowed_depth = self.ctx.narrowed_types.len();
+        if let Some(args) = &lambda_expr.args {
+            self.shadow_narrowed_types(
+                args.node
+                    .args
+                    .iter()
+                    .flat_map(|arg| arg.node.names.first())
+                    .map(|name| name.node.as_str()),
+            );
+        }
         self.do_parameters_check(&lambda_expr.args);
         if let Some(args) = &lambda_expr.args {
             for (i, arg) in args.node.args.iter().enumerate() {
@@ -1067,6 +1198,8 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         // Leave the lambda scope.
         self.leave_scope();
         self.ctx.in_lambda_expr.pop();
+        self.pop_type_params(type_params_depth);
+        self.ctx.narrowed_types.truncate(narrowed_depth);
 
         if !real_ret_ty.is_any() && ret_ty.is_any() && lambda_expr.return_ty.is_none() {
             ret_ty = real_ret_ty;
@@ -1082,6 +1215,41 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         ))
     }
 
+    fn walk_match_expr(&mut self, match_expr: &'ctx ast::MatchExpr) -> Self::Result {
+        let subject_ty = self.expr(&match_expr.subject);
+        let subject_name = self
+            .narrowing_target(&match_expr.subject)
+            .map(|(name, _)| name);
+        let mut body_tys = vec![];
+        for case in &match_expr.cases {
+            let (start, end) = case.get_span_pos();
+            self.enter_scope(start, end, ScopeKind::Loop);
+            let narrowed_depth = self.ctx.narrowed_types.len();
+            let mut names = vec![];
+            let narrowed_ty = self.pattern(&case.node.pattern, subject_ty.clone(), &mut names);
+            // The subject variable is narrowed by the pattern in the case guard and body.
+            if let Some(subject_name) = &subject_name {
+                let mut narrowed_types = NarrowedTypes::default();
+                narrowed_types.insert(subject_name.clone(), Some(narrowed_ty));
+                self.ctx.narrowed_types.push(narrowed_types);
+            }
+            self.shadow_narrowed_types(names.iter().map(|name| name.as_str()));
+            if let Some(guard) = &case.node.guard {
+                self.expr(guard);
+                self.ctx.narrowed_types.push(self.narrow_cond(guard, true));
+            }
+            body_tys.push(self.expr(&case.node.body));
+            self.ctx.narrowed_types.truncate(narrowed_depth);
+            self.leave_scope();
+        }
+        self.check_match_exhaustiveness(match_expr, &subject_ty, match_expr.subject.get_span_pos());
+        if body_tys.is_empty() {
+            self.any_ty()
+        } else {
+            sup(&body_tys)
+        }
+    }
+
     fn walk_keyword(&mut self, keyword: &'ctx ast::Keyword) -> Self::Result {
         self.walk_identifier_expr(&keyword.arg);
         self.expr_or_any_type(&keyword.value)
@@ -1139,6 +1307,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
     }
 
     fn walk_target(&mut self, target: &'ctx ast::Target) -> Self::Result {
+        self.clear_narrowed_type(&target.name.node);
         let tys = self.resolve_target(
             &target,
             (self.ctx.start_pos.clone(), self.ctx.end_pos.clone()),
@@ -1318,6 +1487,17 @@ impl<'ctx> Resolver<'_> {
         self.node_ty_map
             .borrow_mut()
             .insert(self.get_node_key(identifier.id.clone()), ident_ty.clone());
+        // The enum has no runtime value, and only its members can be used in the expression.
+        if ident_ty.is_enum_def() {
+            self.handler.add_compile_error(
+                &format!(
+                    "enum '{}' cannot be used as a value, use its members e.g., '{}.<member>' instead",
+                    ident_ty.ty_str(),
+                    identifier.node.get_name()
+                ),
+                identifier.get_span_pos(),
+            );
+        }
 
         ident_ty
     }
@@ -0,0 +1,318 @@
+use std::sync::Arc;
+
+use crate::resolver::Resolver;
+use crate::ty::{subsume, sup, Type, TypeKind, TypeRef};
+use kclvm_ast::ast;
+use kclvm_ast::pos::GetPos;
+use kclvm_error::diagnostic::Range;
+
+use super::narrow::filter_union_ty;
+use super::scope::{ScopeObject, ScopeObjectKind};
+
+impl<'ctx> Resolver<'ctx> {
+    /// Resolve the match case pattern with the subject type, define the capture
+    /// variables in the current scope and return the subject type narrowed by the
+    /// pattern. `names` records the capture variable names of the whole pattern.
+    pub(crate) fn pattern(
+        &mut self,
+        pattern: &'ctx ast::NodeRef<ast::Pattern>,
+        ty: TypeRef,
+        names: &mut Vec<String>,
+    ) -> TypeRef {
+        match &pattern.node {
+            ast::Pattern::Wildcard => ty,
+            ast::Pattern::Value(value) => {
+                let value_ty = self.expr(value);
+                if !self.may_match(&value_ty, &ty) {
+                    self.add_never_match_error(&value_ty, &ty, pattern.get_span_pos());
+                }
+                value_ty
+            }
+            ast::Pattern::Capture(name) => {
+                self.define_capture(name, ty.clone(), names);
+                ty
+            }
+            ast::Pattern::Schema(schema_pattern) => {
+                let schema_def_ty = self.walk_identifier_expr(&schema_pattern.name);
+                let instance_ty = match &schema_def_ty.kind {
+                    TypeKind::Schema(schema_ty) if !schema_ty.is_instance => {
+                        let mut schema_ty = schema_ty.clone();
+                        schema_ty.is_instance = true;
+                        Arc::new(Type::schema(schema_ty))
+                    }
+                    _ => {
+                        if !schema_def_ty.is_any() {
+                            self.handler.add_compile_error(
+                                &format!(
+                                    "'{}' is not a schema and can not be used in the pattern",
+                                    schema_pattern.name.node.get_name()
+                                ),
+                                schema_pattern.name.get_span_pos(),
+                            );
+                        }
+                        self.any_ty()
+                    }
+                };
+                if !self.may_match(&instance_ty, &ty) {
+                    self.add_never_match_error(&instance_ty, &ty, pattern.get_span_pos());
+                }
+                for entry in &schema_pattern.entries {
+                    let attr_ty = self.load_attr(
+                        instance_ty.clone(),
+                        &entry.node.key.node,
+                        entry.node.key.get_span_pos(),
+                    );
+                    self.pattern(&entry.node.value, attr_ty, names);
+                }
+                instance_ty
+            }
+            ast::Pattern::List(list_pattern) => {
+                let list_ty = if ty.is_any() {
+                    Type::list_ref(self.any_ty())
+                } else if ty.is_list() {
+                    ty.clone()
+                } else {
+                    match filter_union_ty(&ty, |ty| ty.is_list()) {
+                        Some(list_ty) => list_ty,
+                        None => {
+                            let pattern_ty = Type::list_ref(self.any_ty());
+                            self.add_never_match_error(&pattern_ty, &ty, pattern.get_span_pos());
+                            pattern_ty
+                        }
+                    }
+                };
+                let item_ty = match &list_ty.kind {
+                    TypeKind::List(item_ty) => item_ty.clone(),
+                    TypeKind::Union(types) => sup(&types
+                        .iter()
+                        .map(|ty| ty.list_item_ty())
+                        .collect::<Vec<TypeRef>>()),
+                    _ => self.any_ty(),
+                };
+                for elt in &list_pattern.elts {
+                    self.pattern(elt, item_ty.clone(), names);
+                }
+                if let Some(rest) = &list_pattern.rest {
+                    self.define_capture(rest, Type::list_ref(item_ty), names);
+                }
+                list_ty
+            }
+            ast::Pattern::Dict(dict_pattern) => {
+                let mapping_ty = if ty.is_any() || ty.is_dict_or_schema() {
+                    ty.clone()
+                } else {
+                    match filter_union_ty(&ty, |ty| ty.is_dict_or_schema()) {
+                        Some(mapping_ty) => mapping_ty,
+                        None => {
+                            let pattern_ty = Type::dict_ref(self.str_ty(), self.any_ty());
+                            self.add_never_match_error(&pattern_ty, &ty, pattern.get_span_pos());
+                            pattern_ty
+                        }
+                    }
+                };
+                for entry in &dict_pattern.entries {
+                    let val_ty = match &mapping_ty.kind {
+                        TypeKind::Dict(_) | TypeKind::Schema(_) => self.load_attr(
+                            mapping_ty.clone(),
+                            &entry.node.key.node,
+                            entry.node.key.get_span_pos(),
+                        ),
+                        _ => self.any_ty(),
+                    };
+                    self.pattern(&entry.node.value, val_ty, names);
+                }
+                if let Some(rest) = &dict_pattern.rest {
+                    let rest_val_ty = match &mapping_ty.kind {
+                        TypeKind::Dict(dict_ty) => dict_ty.val_ty.clone(),
+                        _ => self.any_ty(),
+                    };
+                    self.define_capture(rest, Type::dict_ref(self.str_ty(), rest_val_ty), names);
+                }
+                mapping_ty
+            }
+            ast::Pattern::Or(alternatives) => {
+                let mut types = vec![];
+                for alternative in alternatives {
+                    let mut alternative_names = vec![];
+                    types.push(self.pattern(alternative, ty.clone(), &mut alternative_names));
+                    if !alternative_names.is_empty() {
+                        self.handler.add_compile_error(
+                            "the alternative patterns can not bind names",
+                            alternative.get_span_pos(),
+                        );
+                    }
+                }
+                sup(&types)
+            }
+            ast::Pattern::As(as_pattern) => {
+                let narrowed_ty = self.pattern(&as_pattern.pattern, ty, names);
+                self.define_capture(&as_pattern.name, narrowed_ty.clone(), names);
+                narrowed_ty
+            }
+        }
+    }
+
+    /// Check whether all the literal values of the match subject type are matched by
+    /// the match cases without guards. The check is skipped when the subject type is
+    /// not a union of literal types e.g., `str`.
+    pub(crate) fn check_match_exhaustiveness(
+        &mut self,
+        match_expr: &'ctx ast::MatchExpr,
+        subject_ty: &TypeRef,
+        range: Range,
+    ) {
+        let members = match literal_members(subject_ty) {
+            Some(members) => members,
+            None => return,
+        };
+        let mut matched = vec![];
+        for case in match_expr
+            .cases
+            .iter()
+            .filter(|case| case.node.guard.is_none())
+        {
+            if is_irrefutable(&case.node.pattern.node) {
+                return;
+            }
+            self.matched_literals(&case.node.pattern.node, &mut matched);
+        }
+        let missing: Vec<String> = members
+            .iter()
+            .filter(|ty| !matched.contains(&ty.ty_str()))
+            .map(|ty| ty.into_type_annotation_str())
+            .collect();
+        if !missing.is_empty() {
+            self.handler.add_compile_error(
+                &format!(
+                    "the match expression is not exhaustive, missing cases: {}",
+                    missing.join(", ")
+                ),
+                range,
+            );
+        }
+    }
+
+    /// Collect the literal type strings matched by the pattern.
+    fn matched_literals(&self, pattern: &'ctx ast::Pattern, matched: &mut Vec<String>) {
+        match pattern {
+            ast::Pattern::Value(value) => {
+                if let Some(ty) = self.node_ty(value) {
+                    if ty.is_literal() || ty.is_none() {
+                        matched.push(ty.ty_str());
+                    }
+                }
+            }
+            ast::Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    self.matched_literals(&alternative.node, matched);
+                }
+            }
+            ast::Pattern::As(as_pattern) => {
+                self.matched_literals(&as_pattern.pattern.node, matched)
+            }
+            _ => {}
+        }
+    }
+
+    /// Define the capture variable of the pattern in the current scope.
+    fn define_capture(
+        &mut self,
+        name: &'ctx ast::NodeRef<ast::Identifier>,
+        ty: TypeRef,
+        names: &mut Vec<String>,
+    ) {
+        let var_name = name.node.get_name();
+        if var_name == "_" {
+            return;
+        }
+        if names.contains(&var_name) {
+            self.handler.add_compile_error(
+                &format!("multiple assignments to name '{}' in the pattern", var_name),
+                name.get_span_pos(),
+            );
+        }
+        names.push(var_name.clone());
+        self.ctx.local_vars.push(var_name.clone());
+        let (start, end) = name.get_span_pos();
+        self.insert_object(
+            &var_name,
+            ScopeObject {
+                name: var_name.clone(),
+                start,
+                end,
+                ty: ty.clone(),
+                kind: ScopeObjectKind::Variable,
+                doc: None,
+            },
+        );
+        for name in &name.node.names {
+            self.node_ty_map
+                .borrow_mut()
+                .insert(self.get_node_key(name.id.clone()), ty.clone());
+        }
+        self.node_ty_map
+            .borrow_mut()
+            .insert(self.get_node_key(name.id.clone()), ty);
+    }
+
+    /// Whether the value of the subject type may be matched by the pattern type.
+    #[inline]
+    fn may_match(&self, pattern_ty: &TypeRef, ty: &TypeRef) -> bool {
+        ty.is_any()
+            || pattern_ty.is_any()
+            || subsume(pattern_ty.clone(), ty.clone(), false)
+            || subsume(ty.clone(), pattern_ty.clone(), false)
+    }
+
+    fn add_never_match_error(&mut self, pattern_ty: &TypeRef, ty: &TypeRef, range: Range) {
+        self.handler.add_compile_error(
+            &format!(
+                "the pattern of type '{}' can never match the value of type '{}'",
+                pattern_ty.ty_str(),
+                ty.ty_str()
+            ),
+            range,
+        );
+    }
+}
+
+/// Get all the literal values of the type e.g., `"tcp" | "udp"`, returns `None`
+/// when the type has infinite values.
+fn literal_members(ty: &TypeRef) -> Option<Vec<TypeRef>> {
+    match &ty.kind {
+        TypeKind::None | TypeKind::BoolLit(_) | TypeKind::IntLit(_) | TypeKind::StrLit(_) => {
+            Some(vec![ty.clone()])
+        }
+        TypeKind::Bool => Some(vec![
+            Arc::new(Type::bool_lit(true)),
+            Arc::new(Type::bool_lit(false)),
+        ]),
+        TypeKind::Enum(enum_ty) if enum_ty.is_instance => {
+            literal_members(&enum_ty.member_union_ty())
+        }
+        TypeKind::Union(types) => {
+            let mut members: Vec<TypeRef> = vec![];
+            for ty in types {
+                for member in literal_members(ty)? {
+                    if !members.iter().any(|ty| ty.ty_str() == member.ty_str()) {
+                        members.push(member);
+                    }
+                }
+            }
+            Some(members)
+        }
+        _ => None,
+    }
+}
+
+/// Whether the pattern matches all the values.
+fn is_irrefutable(pattern: &ast::Pattern) -> bool {
+    match pattern {
+        ast::Pattern::Wildcard | ast::Pattern::Capture(_) => true,
+        ast::Pattern::Or(alternatives) => alternatives
+            .iter()
+            .any(|alternative| is_irrefutable(&alternative.node)),
+        ast::Pattern::As(as_pattern) => is_irrefutable(&as_pattern.pattern.node),
+        _ => false,
+    }
+}
@@ -43,6 +43,8 @@ impl<'ctx> Resolver<'_> {
             return ty;
         };
         self.ctx.schema = Some(Rc::new(RefCell::new(scope_ty.clone())));
+        self.check_type_params(&schema_stmt.type_params);
+        let type_params_depth = self.push_type_params(&scope_ty.type_params);
         if let Some(args) = &schema_stmt.args {
             for (i, arg) in args.node.args.iter().enumerate() {
                 let ty = args.node.get_arg_type_node(i);
@@ -120,6 +122,27 @@ impl<'ctx> Resolver<'_> {
         }
         let schema_attr_names = schema_stmt.get_left_identifier_list();
         for (line, column, name) in schema_attr_names {
+            // The final attributes of the base schema can't be overridden in the sub schema.
+            if let Some(base) = &scope_ty.base {
+                if self.is_final_schema_attr(base, &name) {
+                    let start = Position {
+                        filename: self.ctx.filename.clone(),
+                        line,
+                        column: Some(column),
+                    };
+                    let end = Position {
+                        column: Some(column + name.chars().count() as u64),
+                        ..start.clone()
+                    };
+                    self.handler.add_compile_error(
+                        &format!(
+                            "can't override the final attribute '{}' of the base schema '{}'",
+                            name, base.name
+                        ),
+                        (start, end),
+                    );
+                }
+            }
             if !self.contains_object(&name) {
                 self.insert_object(
                     &name,
@@ -145,6 +168,7 @@ impl<'ctx> Resolver<'_> {
             self.walk_check_expr(&check_expr.node);
         }
         self.leave_scope();
+        self.pop_type_params(type_params_depth);
         self.ctx.schema = None;
         ty
     }
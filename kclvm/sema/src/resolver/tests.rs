@@ -32,6 +32,7 @@ pub fn parse_program(filename: &str) -> Result<ast::Program> {
         modules: HashMap::new(),
         pkgs_not_imported: HashMap::new(),
         modules_not_imported: HashMap::new(),
+        plugin_decls: HashMap::new(),
     };
 
     let mut module = parse_file_force_errors(abspath.to_str().unwrap(), None)?;
@@ -1164,3 +1165,416 @@ fn clear_cache_test() {
         second_scope.schema_mapping.len()
     );
 }
+
+#[test]
+fn test_generic_schema_and_lambda() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/generic.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 0);
+    let main_scope = scope.main_scope().unwrap().borrow();
+    let ty_str = |name: &str| main_scope.lookup(name).unwrap().borrow().ty.ty_str();
+    assert_eq!(ty_str("page"), "Page[int]");
+    assert_eq!(ty_str("pair"), "Pair[str, Page[int]]");
+    assert_eq!(ty_str("item"), "str");
+}
+
+#[test]
+fn test_generic_schema_invalid() {
+    let test_cases = [
+        (
+            "./src/resolver/test_fail_data/generic/type_arg_mismatch.k",
+            "expected Page[str], got Page[int]",
+        ),
+        (
+            "./src/resolver/test_fail_data/generic/type_args_count.k",
+            "expected 1 type arguments for the generic schema 'Page', found 2",
+        ),
+        (
+            "./src/resolver/test_fail_data/generic/not_generic.k",
+            "'Page' is not a generic schema type",
+        ),
+        (
+            "./src/resolver/test_fail_data/generic/duplicate_type_param.k",
+            "duplicate type parameter 'T'",
+        ),
+    ];
+
+    for (file, expected_message) in &test_cases {
+        let sess = Arc::new(ParseSession::default());
+        let mut program = load_program(sess.clone(), &[file], None, None)
+            .unwrap()
+            .program;
+        let scope = resolve_program(&mut program);
+        let diags = scope.handler.diagnostics;
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].messages[0].message, *expected_message);
+    }
+}
+
+#[test]
+fn test_flow_sensitive_narrowing() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/narrowing.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 0);
+    let main_scope = scope.main_scope().unwrap().borrow();
+    let ty_str = |name: &str| main_scope.lookup(name).unwrap().borrow().ty.ty_str();
+    assert_eq!(ty_str("name"), "str");
+    assert_eq!(ty_str("label"), "str");
+    assert_eq!(ty_str("count"), "int");
+    assert_eq!(ty_str("names"), "[str]");
+    assert_eq!(ty_str("kinds"), "[str]");
+    assert_eq!(ty_str("valid"), "bool");
+}
+
+#[test]
+fn test_flow_sensitive_narrowing_invalid() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_fail_data/narrowing.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    let diags = scope.handler.diagnostics;
+    assert_eq!(diags.len(), 2);
+    assert_eq!(
+        diags[0].messages[0].message,
+        "attribute 'kind' not found in 'Person'"
+    );
+    assert_eq!(
+        diags[1].messages[0].message,
+        "attribute 'name' not found in 'Pet'"
+    );
+}
+
+#[test]
+fn test_refinement_types() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/refinement.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 0);
+    let main_scope = scope.main_scope().unwrap().borrow();
+    let ty_str = |name: &str| main_scope.lookup(name).unwrap().borrow().ty.ty_str();
+    assert_eq!(ty_str("port"), "int(1..65535)");
+    assert_eq!(ty_str("ports"), "[int(1..65535)]");
+}
+
+#[test]
+fn test_refinement_types_invalid() {
+    let test_cases = [
+        (
+            "./src/resolver/test_fail_data/refinement/out_of_range.k",
+            "expected int(1..65535), got int(70000)",
+        ),
+        (
+            "./src/resolver/test_fail_data/refinement/pattern_mismatch.k",
+            r#"expected str("^[a-z0-9-]+$"), got str(KCL)"#,
+        ),
+        (
+            "./src/resolver/test_fail_data/refinement/invalid_range.k",
+            "the range minimum is greater than the maximum in '10..1'",
+        ),
+        (
+            "./src/resolver/test_fail_data/refinement/float_bound.k",
+            "the range bounds of the 'int' type must be integers",
+        ),
+    ];
+
+    for (file, expected_message) in &test_cases {
+        let sess = Arc::new(ParseSession::default());
+        let mut program = load_program(sess.clone(), &[file], None, None)
+            .unwrap()
+            .program;
+        let scope = resolve_program(&mut program);
+        let diags = scope.handler.diagnostics;
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].messages[0].message, *expected_message);
+    }
+}
+
+#[test]
+fn test_enum_types() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/enum.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 0);
+    let main_scope = scope.main_scope().unwrap().borrow();
+    let ty_str = |name: &str| main_scope.lookup(name).unwrap().borrow().ty.ty_str();
+    assert_eq!(ty_str("protocol"), "Protocol");
+    assert_eq!(ty_str("name"), "str");
+    assert_eq!(ty_str("tcp"), "str");
+    assert_eq!(ty_str("level"), "int");
+}
+
+#[test]
+fn test_enum_types_invalid() {
+    let test_cases = [
+        (
+            "./src/resolver/test_fail_data/enum/duplicate_member.k",
+            "enum 'Protocol' has duplicate member 'TCP'",
+        ),
+        (
+            "./src/resolver/test_fail_data/enum/invalid_value.k",
+            "enum member 'Protocol.UDP' value must be a str, int, float or bool literal",
+        ),
+        (
+            "./src/resolver/test_fail_data/enum/enum_as_value.k",
+            "enum 'Protocol' cannot be used as a value, use its members e.g., 'Protocol.<member>' instead",
+        ),
+        (
+            "./src/resolver/test_fail_data/enum/member_mismatch.k",
+            "expected Protocol, got str(sctp)",
+        ),
+    ];
+
+    for (file, expected_message) in &test_cases {
+        let sess = Arc::new(ParseSession::default());
+        let mut program = load_program(sess.clone(), &[file], None, None)
+            .unwrap()
+            .program;
+        let scope = resolve_program(&mut program);
+        let diags = scope.handler.diagnostics;
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].messages[0].message, *expected_message);
+    }
+}
+
+#[test]
+fn test_match_expr() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/match.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 0);
+    let main_scope = scope.main_scope().unwrap().borrow();
+    let ty_str = |name: &str| main_scope.lookup(name).unwrap().borrow().ty.ty_str();
+    assert_eq!(ty_str("message"), "str");
+    assert_eq!(ty_str("first"), "int");
+    assert_eq!(ty_str("kind"), "str");
+}
+
+#[test]
+fn test_match_expr_invalid() {
+    let test_cases = [
+        (
+            "./src/resolver/test_fail_data/match/non_exhaustive.k",
+            "the match expression is not exhaustive, missing cases: \"sctp\"",
+        ),
+        (
+            "./src/resolver/test_fail_data/match/never_match.k",
+            "the pattern of type 'int(1)' can never match the value of type 'str(tcp) | str(udp)'",
+        ),
+        (
+            "./src/resolver/test_fail_data/match/or_pattern_binding.k",
+            "the alternative patterns can not bind names",
+        ),
+        (
+            "./src/resolver/test_fail_data/match/multiple_assignments.k",
+            "multiple assignments to name 'x' in the pattern",
+        ),
+    ];
+
+    for (file, expected_message) in &test_cases {
+        let sess = Arc::new(ParseSession::default());
+        let mut program = load_program(sess.clone(), &[file], None, None)
+            .unwrap()
+            .program;
+        let scope = resolve_program(&mut program);
+        let diags = scope.handler.diagnostics;
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].messages[0].message, *expected_message);
+    }
+}
+
+#[test]
+fn test_plugin_decls() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/plugin/main.k"],
+        Some(LoadProgramOptions {
+            load_plugins: true,
+            plugin_paths: vec!["./src/resolver/test_data/plugin/plugins".to_string()],
+            ..Default::default()
+        }),
+        None,
+    )
+    .unwrap()
+    .program;
+    assert_eq!(program.plugin_decls.len(), 1);
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 0);
+    let main_scope = scope.main_scope().unwrap().borrow();
+    let ty_str = |name: &str| main_scope.lookup(name).unwrap().borrow().ty.ty_str();
+    assert_eq!(ty_str("sum"), "int");
+    assert_eq!(ty_str("message"), "str");
+    assert_eq!(ty_str("person"), "Person");
+}
+
+#[test]
+fn test_plugin_decls_invalid() {
+    let test_cases = [
+        (
+            "./src/resolver/test_fail_data/plugin/arg_type.k",
+            "expected int, got str(1)",
+        ),
+        (
+            "./src/resolver/test_fail_data/plugin/member_not_found.k",
+            "attribute 'say_hi' not found in 'module 'kcl_plugin.hello''",
+        ),
+    ];
+
+    for (file, expected_message) in &test_cases {
+        let sess = Arc::new(ParseSession::default());
+        let mut program = load_program(
+            sess.clone(),
+            &[file],
+            Some(LoadProgramOptions {
+                load_plugins: true,
+                plugin_paths: vec!["./src/resolver/test_data/plugin/plugins".to_string()],
+                ..Default::default()
+            }),
+            None,
+        )
+        .unwrap()
+        .program;
+        let scope = resolve_program(&mut program);
+        let diags = scope.handler.diagnostics;
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].messages[0].message, *expected_message);
+    }
+}
+
+#[test]
+fn test_export_stmt() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/visibility/main.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 0);
+    let main_scope = scope.main_scope().unwrap().borrow();
+    let ty_str = |name: &str| main_scope.lookup(name).unwrap().borrow().ty.ty_str();
+    assert_eq!(ty_str("person"), "Person");
+    assert_eq!(ty_str("name"), "str");
+}
+
+#[test]
+fn test_export_stmt_invalid() {
+    let test_cases = [
+        (
+            "./src/resolver/test_fail_data/visibility/not_exported.k",
+            "the member 'suffix' is not exported by the module 'pkg'",
+        ),
+        (
+            "./src/resolver/test_fail_data/visibility/undefined_export.k",
+            "the exported name 'Person' is not defined in the package '__main__'",
+        ),
+        (
+            "./src/resolver/test_fail_data/visibility/nested_export.k",
+            "the export statement is only allowed at the top level of the module",
+        ),
+    ];
+
+    for (file, expected_message) in &test_cases {
+        let sess = Arc::new(ParseSession::default());
+        let mut program = load_program(sess.clone(), &[file], None, None)
+            .unwrap()
+            .program;
+        let scope = resolve_program(&mut program);
+        let diags = scope.handler.diagnostics;
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].messages[0].message, *expected_message);
+    }
+}
+
+#[test]
+fn test_final_schema_attr() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/final.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 0);
+    let main_scope = scope.main_scope().unwrap().borrow();
+    let labels = main_scope.lookup("labels").unwrap();
+    assert_eq!(labels.borrow().ty.ty_str(), "{str:str}");
+}
+
+#[test]
+fn test_final_schema_attr_invalid() {
+    let test_cases = [
+        (
+            "./src/resolver/test_fail_data/final/config_override.k",
+            "can't override the final attribute 'labels' of the schema 'App'",
+        ),
+        (
+            "./src/resolver/test_fail_data/final/subclass_override.k",
+            "can't override the final attribute 'labels' of the base schema 'App'",
+        ),
+        (
+            "./src/resolver/test_fail_data/final/no_default.k",
+            "the final attribute 'labels' must have a default value",
+        ),
+    ];
+
+    for (file, expected_message) in &test_cases {
+        let sess = Arc::new(ParseSession::default());
+        let mut program = load_program(sess.clone(), &[file], None, None)
+            .unwrap()
+            .program;
+        let scope = resolve_program(&mut program);
+        let diags = scope.handler.diagnostics;
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].messages[0].message, *expected_message);
+    }
+}
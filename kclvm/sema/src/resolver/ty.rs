@@ -4,7 +4,8 @@ use super::node::ResolvedResult;
 use crate::resolver::Resolver;
 use crate::ty::parser::parse_type_str;
 use crate::ty::{
-    assignable_to, is_upper_bound, Attr, DictType, Parameter, SchemaType, Type, TypeKind, TypeRef,
+    assignable_to, is_upper_bound, range_bound_value, Attr, DictType, Parameter, SchemaType, Type,
+    TypeKind, TypeRef, RESERVED_TYPE_IDENTIFIERS,
 };
 use kclvm_ast::ast;
 use kclvm_ast::pos::GetPos;
@@ -461,6 +462,17 @@ impl<'ctx> Resolver<'_> {
         range: &Range,
         ty_node: Option<&ast::Node<ast::Type>>,
     ) -> ResolvedResult {
+        if let Some(ty_node) = ty_node {
+            match &ty_node.node {
+                ast::Type::Generic(generic_ty) => {
+                    return self.upgrade_generic_ty_with_scope(ty, range, ty_node, generic_ty);
+                }
+                ast::Type::Refined(refined_ty) => {
+                    self.check_refined_ty(refined_ty, ty_node.get_span_pos())
+                }
+                _ => {}
+            }
+        }
         match &ty.kind {
             TypeKind::List(item_ty) => {
                 let mut inner_node = None;
@@ -536,6 +548,23 @@ impl<'ctx> Resolver<'_> {
                 }
                 let mut pkgpath = "".to_string();
                 let name = names[0];
+                // The type parameter of the generic schema or lambda.
+                if names.len() == 1 && self.ctx.type_params.iter().any(|param| param == name) {
+                    let ty = Arc::new(Type::type_param(name));
+                    if let Some(ty_node) = ty_node {
+                        if let ast::Type::Named(identifier) = &ty_node.node {
+                            for name in &identifier.names {
+                                self.node_ty_map
+                                    .borrow_mut()
+                                    .insert(self.get_node_key(name.id.clone()), ty.clone());
+                            }
+                        }
+                        self.node_ty_map
+                            .borrow_mut()
+                            .insert(self.get_node_key(ty_node.id.clone()), ty.clone());
+                    }
+                    return ty;
+                }
                 if names.len() > 1 && !self.ctx.local_vars.contains(&name.to_string()) {
                     if let Some(mapping) = self.ctx.import_names.get(&self.ctx.filename) {
                         pkgpath = mapping
@@ -544,11 +573,17 @@ impl<'ctx> Resolver<'_> {
                     }
                 }
                 self.ctx.l_value = false;
-                let tys = self.resolve_var(
+                let mut tys = self.resolve_var(
                     &names.iter().map(|n| n.to_string()).collect::<Vec<String>>(),
                     &pkgpath,
                     range.clone(),
                 );
+                // The enum in the type annotation denotes an enum instance.
+                if let Some(TypeKind::Enum(enum_ty)) = tys.last().map(|ty| &ty.kind) {
+                    let mut enum_ty = enum_ty.clone();
+                    enum_ty.is_instance = true;
+                    *tys.last_mut().unwrap() = Arc::new(Type::enumeration(enum_ty));
+                }
 
                 if let Some(ty_node) = ty_node {
                     if let ast::Type::Named(identifier) = &ty_node.node {
@@ -612,6 +647,133 @@ impl<'ctx> Resolver<'_> {
         }
     }
 
+    /// Resolve the generic schema type with its type arguments e.g., `Page[int]`.
+    fn upgrade_generic_ty_with_scope(
+        &mut self,
+        ty: TypeRef,
+        range: &Range,
+        ty_node: &ast::Node<ast::Type>,
+        generic_ty: &ast::GenericType,
+    ) -> ResolvedResult {
+        let named_ty_node = ast::Node {
+            node: ast::Type::Named(generic_ty.name.clone()),
+            ..ty_node.clone()
+        };
+        let schema_ty = self.upgrade_named_ty_with_scope(ty, range, Some(&named_ty_node));
+        let type_args: Vec<TypeRef> = generic_ty
+            .type_args
+            .iter()
+            .map(|type_arg| self.parse_ty_with_scope(Some(type_arg), type_arg.get_span_pos()))
+            .collect();
+        let ty = match &schema_ty.kind {
+            TypeKind::Schema(schema_ty) if !schema_ty.type_params.is_empty() => {
+                if schema_ty.type_params.len() == type_args.len() {
+                    Arc::new(Type::schema(schema_ty.instantiate(&type_args)))
+                } else {
+                    self.handler.add_compile_error(
+                        &format!(
+                            "expected {} type arguments for the generic schema '{}', found {}",
+                            schema_ty.type_params.len(),
+                            schema_ty.name,
+                            type_args.len()
+                        ),
+                        ty_node.get_span_pos(),
+                    );
+                    Arc::new(Type::schema(schema_ty.clone()))
+                }
+            }
+            // The error has been reported when the type is not found.
+            TypeKind::Any => schema_ty.clone(),
+            _ => {
+                self.handler.add_compile_error(
+                    &format!("'{}' is not a generic schema type", schema_ty.ty_str()),
+                    ty_node.get_span_pos(),
+                );
+                schema_ty.clone()
+            }
+        };
+        self.node_ty_map
+            .borrow_mut()
+            .insert(self.get_node_key(ty_node.id.clone()), ty.clone());
+        ty
+    }
+
+    /// Bring the type parameters of the generic schema or lambda into the type
+    /// scope, and return the previous depth of the type scope.
+    pub(crate) fn push_type_params(&mut self, type_params: &[String]) -> usize {
+        let depth = self.ctx.type_params.len();
+        self.ctx.type_params.extend(type_params.iter().cloned());
+        depth
+    }
+
+    /// Leave the type parameters scope with the previous depth.
+    #[inline]
+    pub(crate) fn pop_type_params(&mut self, depth: usize) {
+        self.ctx.type_params.truncate(depth);
+    }
+
+    /// Check the type parameter declarations of the generic schema or lambda.
+    pub(crate) fn check_type_params(&mut self, type_params: &[ast::NodeRef<String>]) {
+        for (i, param) in type_params.iter().enumerate() {
+            if RESERVED_TYPE_IDENTIFIERS.contains(&param.node.as_str()) {
+                self.handler.add_compile_error(
+                    &format!(
+                        "type parameter name '{}' cannot be the same as the built-in types ({:?})",
+                        param.node, RESERVED_TYPE_IDENTIFIERS
+                    ),
+                    param.get_span_pos(),
+                );
+            } else if type_params[..i].iter().any(|p| p.node == param.node) {
+                self.handler.add_compile_error(
+                    &format!("duplicate type parameter '{}'", param.node),
+                    param.get_span_pos(),
+                );
+            }
+        }
+    }
+
+    /// Check the value constraint of the refined type e.g., the range bounds of
+    /// `int(1..65535)` and the regular expression pattern of `str(r"^[a-z]+$")`.
+    fn check_refined_ty(&mut self, refined_ty: &ast::RefinedType, range: Range) {
+        match &refined_ty.constraint {
+            ast::TypeConstraint::Range(range_constraint) => {
+                match (&range_constraint.min, &range_constraint.max) {
+                    (None, None) => self.handler.add_compile_error(
+                        "the range constraint requires the minimum or the maximum",
+                        range.clone(),
+                    ),
+                    (Some(min), Some(max)) if range_bound_value(min) > range_bound_value(max) => {
+                        self.handler.add_compile_error(
+                            &format!(
+                                "the range minimum is greater than the maximum in '{}'",
+                                refined_ty.constraint.to_string()
+                            ),
+                            range.clone(),
+                        )
+                    }
+                    _ => {}
+                }
+                let has_float_bound = [&range_constraint.min, &range_constraint.max]
+                    .iter()
+                    .any(|bound| matches!(bound, Some(ast::NumberLitValue::Float(_))));
+                if matches!(refined_ty.base, ast::BasicType::Int) && has_float_bound {
+                    self.handler.add_compile_error(
+                        "the range bounds of the 'int' type must be integers",
+                        range,
+                    );
+                }
+            }
+            ast::TypeConstraint::Pattern(pattern) => {
+                if let Err(err) = fancy_regex::Regex::new(pattern) {
+                    self.handler.add_compile_error(
+                        &format!("invalid regular expression pattern '{}': {}", pattern, err),
+                        range,
+                    );
+                }
+            }
+        }
+    }
+
     pub fn add_type_alias(&mut self, name: &str, alias: &str) {
         if alias.starts_with('@') {
             if name == &alias[1..] {
@@ -0,0 +1,157 @@
+use kclvm_ast::ast;
+use kclvm_ast::walker::MutSelfMutWalker;
+
+use crate::ty::TypeKind;
+
+use super::scope::{NodeKey, NodeTyMap};
+
+struct EnumMemberTransformer<'a> {
+    pkgpath: String,
+    node_ty_map: &'a NodeTyMap,
+}
+
+impl<'a> EnumMemberTransformer<'a> {
+    /// Get the literal expression of the enum member in the identifier e.g., `"tcp"` in
+    /// `Protocol.TCP` and `pkg.Protocol.TCP`, and the remaining names are converted to
+    /// the selector expressions e.g., `"tcp".upper` in `Protocol.TCP.upper`.
+    fn enum_member_expr(&self, identifier: &ast::Identifier) -> Option<ast::Expr> {
+        let (index, member_ty) = identifier
+            .names
+            .iter()
+            .zip(identifier.names.iter().skip(1))
+            .enumerate()
+            .find_map(|(index, (name, member))| {
+                let ty = self.node_ty_map.get(&NodeKey {
+                    pkgpath: self.pkgpath.clone(),
+                    id: name.id.clone(),
+                })?;
+                match &ty.kind {
+                    TypeKind::Enum(enum_ty) if !enum_ty.is_instance => enum_ty
+                        .members
+                        .get(&member.node)
+                        .map(|member| (index + 1, member.ty.clone())),
+                    _ => None,
+                }
+            })?;
+        let mut expr = match &member_ty.kind {
+            TypeKind::StrLit(v) => ast::Expr::StringLit(ast::StringLit {
+                is_long_string: false,
+                raw_value: member_ty.into_type_annotation_str(),
+                value: v.clone(),
+            }),
+            TypeKind::IntLit(v) => ast::Expr::NumberLit(ast::NumberLit {
+                binary_suffix: None,
+                value: ast::NumberLitValue::Int(*v),
+            }),
+            TypeKind::FloatLit(v) => ast::Expr::NumberLit(ast::NumberLit {
+                binary_suffix: None,
+                value: ast::NumberLitValue::Float(*v),
+            }),
+            TypeKind::BoolLit(v) => ast::Expr::NameConstantLit(ast::NameConstantLit {
+                value: if *v {
+                    ast::NameConstant::True
+                } else {
+                    ast::NameConstant::False
+                },
+            }),
+            _ => return None,
+        };
+        let first = &identifier.names[0];
+        for (i, attr) in identifier.names.iter().enumerate().skip(index + 1) {
+            let prev = &identifier.names[i - 1];
+            expr = ast::Expr::Selector(ast::SelectorExpr {
+                value: Box::new(ast::Node::new(
+                    expr,
+                    first.filename.clone(),
+                    first.line,
+                    first.column,
+                    prev.end_line,
+                    prev.end_column,
+                )),
+                attr: Box::new(ast::Node::new(
+                    ast::Identifier {
+                        names: vec![attr.clone()],
+                        pkgpath: "".to_string(),
+                        ctx: ast::ExprContext::Load,
+                    },
+                    attr.filename.clone(),
+                    attr.line,
+                    attr.column,
+                    attr.end_line,
+                    attr.end_column,
+                )),
+                ctx: ast::ExprContext::Load,
+                has_question: false,
+            });
+        }
+        Some(expr)
+    }
+}
+
+impl<'ctx> MutSelfMutWalker<'ctx> for EnumMemberTransformer<'_> {
+    fn walk_expr(&mut self, expr: &'ctx mut ast::Expr) {
+        match expr {
+            ast::Expr::Identifier(identifier) => {
+                if let Some(member_expr) = self.enum_member_expr(identifier) {
+                    *expr = member_expr;
+                }
+            }
+            ast::Expr::Target(target) => self.walk_target(target),
+            ast::Expr::Unary(unary_expr) => self.walk_unary_expr(unary_expr),
+            ast::Expr::Binary(binary_expr) => self.walk_binary_expr(binary_expr),
+            ast::Expr::If(if_expr) => self.walk_if_expr(if_expr),
+            ast::Expr::Selector(selector_expr) => self.walk_selector_expr(selector_expr),
+            ast::Expr::Call(call_expr) => self.walk_call_expr(call_expr),
+            ast::Expr::Paren(paren_expr) => self.walk_paren_expr(paren_expr),
+            ast::Expr::Quant(quant_expr) => self.walk_quant_expr(quant_expr),
+            ast::Expr::List(list_expr) => self.walk_list_expr(list_expr),
+            ast::Expr::ListIfItem(list_if_item_expr) => {
+                self.walk_list_if_item_expr(list_if_item_expr)
+            }
+            ast::Expr::ListComp(list_comp) => self.walk_list_comp(list_comp),
+            ast::Expr::Starred(starred_expr) => self.walk_starred_expr(starred_expr),
+            ast::Expr::DictComp(dict_comp) => self.walk_dict_comp(dict_comp),
+            ast::Expr::ConfigIfEntry(config_if_entry_expr) => {
+                self.walk_config_if_entry_expr(config_if_entry_expr)
+            }
+            ast::Expr::CompClause(comp_clause) => self.walk_comp_clause(comp_clause),
+            ast::Expr::Schema(schema_expr) => self.walk_schema_expr(schema_expr),
+            ast::Expr::Config(config_expr) => self.walk_config_expr(config_expr),
+            ast::Expr::Check(check) => self.walk_check_expr(check),
+            ast::Expr::Lambda(lambda) => self.walk_lambda_expr(lambda),
+            ast::Expr::Match(match_expr) => self.walk_match_expr(match_expr),
+            ast::Expr::Subscript(subscript) => self.walk_subscript(subscript),
+            ast::Expr::Keyword(keyword) => self.walk_keyword(keyword),
+            ast::Expr::Arguments(arguments) => self.walk_arguments(arguments),
+            ast::Expr::Compare(compare) => self.walk_compare(compare),
+            ast::Expr::NumberLit(number_lit) => self.walk_number_lit(number_lit),
+            ast::Expr::StringLit(string_lit) => self.walk_string_lit(string_lit),
+            ast::Expr::NameConstantLit(name_constant_lit) => {
+                self.walk_name_constant_lit(name_constant_lit)
+            }
+            ast::Expr::JoinedString(joined_string) => self.walk_joined_string(joined_string),
+            ast::Expr::FormattedValue(formatted_value) => {
+                self.walk_formatted_value(formatted_value)
+            }
+            ast::Expr::Missing(missing_expr) => self.walk_missing_expr(missing_expr),
+        }
+    }
+}
+
+/// Run a pass on AST and replace all the enum members with their literal values
+/// e.g., `Protocol.TCP` to `"tcp"`, because enums have no runtime values.
+pub fn enum_member_pass(program: &mut ast::Program, node_ty_map: &NodeTyMap) {
+    for (pkgpath, modules) in program.pkgs.iter() {
+        for module in modules.iter() {
+            let mut module = program
+                .get_module_mut(module)
+                .expect("Failed to acquire module lock")
+                .expect(&format!("module {:?} not found in program", module));
+            EnumMemberTransformer {
+                pkgpath: pkgpath.clone(),
+                node_ty_map,
+            }
+            .walk_module(&mut module);
+        }
+    }
+}
@@ -2,37 +2,92 @@ use kclvm_ast::walker::MutSelfMutWalker;
 use kclvm_ast::{ast, walk_if_mut, walk_list_mut};
 
 #[derive(Default)]
-struct TypeErasureTransformer;
+struct TypeErasureTransformer {
+    /// Type parameters of the generic schemas and lambdas in the current scope.
+    type_params: Vec<String>,
+}
 const FUNCTION: &str = "function";
 
+impl TypeErasureTransformer {
+    /// Erase the function type to the `function` type and the generic types.
+    fn erase_ty(&self, ty: &mut ast::Type) {
+        if let ast::Type::Function(_) = ty {
+            *ty = FUNCTION.to_string().into();
+        } else {
+            self.erase_generic_ty(ty);
+        }
+    }
+
+    /// Erase the type parameters to the `any` type and the generic schema types
+    /// to their raw schema types, e.g., `[T]` to `[any]` and `Page[int]` to `Page`.
+    fn erase_generic_ty(&self, ty: &mut ast::Type) {
+        match ty {
+            ast::Type::Named(identifier)
+                if identifier.names.len() == 1
+                    && self.type_params.contains(&identifier.names[0].node) =>
+            {
+                *ty = ast::Type::Any;
+            }
+            ast::Type::Generic(generic_ty) => {
+                *ty = ast::Type::Named(generic_ty.name.clone());
+            }
+            ast::Type::List(list_ty) => {
+                if let Some(inner_ty) = list_ty.inner_type.as_mut() {
+                    self.erase_generic_ty(&mut inner_ty.node);
+                }
+            }
+            ast::Type::Dict(dict_ty) => {
+                if let Some(key_ty) = dict_ty.key_type.as_mut() {
+                    self.erase_generic_ty(&mut key_ty.node);
+                }
+                if let Some(value_ty) = dict_ty.value_type.as_mut() {
+                    self.erase_generic_ty(&mut value_ty.node);
+                }
+            }
+            ast::Type::Union(union_ty) => {
+                for elem_ty in union_ty.type_elements.iter_mut() {
+                    self.erase_generic_ty(&mut elem_ty.node);
+                }
+            }
+            ast::Type::Function(func_ty) => {
+                for param_ty in func_ty.params_ty.iter_mut().flatten() {
+                    self.erase_generic_ty(&mut param_ty.node);
+                }
+                if let Some(ret_ty) = func_ty.ret_ty.as_mut() {
+                    self.erase_generic_ty(&mut ret_ty.node);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 impl<'ctx> MutSelfMutWalker<'ctx> for TypeErasureTransformer {
     fn walk_schema_stmt(&mut self, schema_stmt: &'ctx mut ast::SchemaStmt) {
+        let type_params_depth = self.type_params.len();
+        self.type_params.extend(
+            schema_stmt
+                .type_params
+                .iter()
+                .map(|param| param.node.clone()),
+        );
         if let Some(schema_index_signature) = schema_stmt.index_signature.as_deref_mut() {
-            if let kclvm_ast::ast::Type::Function(_) =
-                &mut schema_index_signature.node.value_ty.node
-            {
-                schema_index_signature.node.value_ty.node = FUNCTION.to_string().into();
-            }
+            self.erase_ty(&mut schema_index_signature.node.value_ty.node);
         }
         walk_if_mut!(self, walk_arguments, schema_stmt.args);
         walk_list_mut!(self, walk_call_expr, schema_stmt.decorators);
         walk_list_mut!(self, walk_check_expr, schema_stmt.checks);
         walk_list_mut!(self, walk_stmt, schema_stmt.body);
+        self.type_params.truncate(type_params_depth);
     }
     fn walk_schema_attr(&mut self, schema_attr: &'ctx mut ast::SchemaAttr) {
         walk_list_mut!(self, walk_call_expr, schema_attr.decorators);
         walk_if_mut!(self, walk_expr, schema_attr.value);
-        if let kclvm_ast::ast::Type::Function(_) = schema_attr.ty.as_ref().node {
-            schema_attr.ty.node = FUNCTION.to_string().into();
-        }
+        self.erase_ty(&mut schema_attr.ty.node);
     }
     fn walk_assign_stmt(&mut self, assign_stmt: &'ctx mut ast::AssignStmt) {
         if let Some(ty) = &mut assign_stmt.ty {
-            if let kclvm_ast::ast::Type::Function(_) = ty.as_ref().node {
-                if let Some(ty_anno) = &mut assign_stmt.ty {
-                    ty_anno.node = FUNCTION.to_string().into();
-                }
-            }
+            self.erase_ty(&mut ty.node);
         }
         self.walk_expr(&mut assign_stmt.value.node);
     }
@@ -43,9 +98,7 @@ impl<'ctx> MutSelfMutWalker<'ctx> for TypeErasureTransformer {
     }
     fn walk_arguments(&mut self, arguments: &'ctx mut ast::Arguments) {
         for ty in (&mut arguments.ty_list.iter_mut()).flatten() {
-            if let kclvm_ast::ast::Type::Function(_) = ty.as_ref().node {
-                ty.node = FUNCTION.to_string().into();
-            }
+            self.erase_ty(&mut ty.node);
         }
         for default in arguments.defaults.iter_mut() {
             if let Some(d) = default.as_deref_mut() {
@@ -54,17 +107,25 @@ impl<'ctx> MutSelfMutWalker<'ctx> for TypeErasureTransformer {
         }
     }
     fn walk_lambda_expr(&mut self, lambda_expr: &'ctx mut ast::LambdaExpr) {
+        let type_params_depth = self.type_params.len();
+        self.type_params.extend(
+            lambda_expr
+                .type_params
+                .iter()
+                .map(|param| param.node.clone()),
+        );
         walk_if_mut!(self, walk_arguments, lambda_expr.args);
         walk_list_mut!(self, walk_stmt, lambda_expr.body);
         if let Some(ty) = lambda_expr.return_ty.as_mut() {
-            if let kclvm_ast::ast::Type::Function(_) = ty.as_ref().node {
-                ty.node = FUNCTION.to_string().into();
-            }
+            self.erase_ty(&mut ty.node);
         }
+        self.type_params.truncate(type_params_depth);
     }
 }
 
-/// Run a pass on AST and change the function type to the `Named("function")` type
+/// Run a pass on AST and change the function type to the `Named("function")` type,
+/// the type parameters to the `any` type and the generic schema types to their raw
+/// schema types.
 pub fn type_func_erasure_pass<'ctx>(program: &'ctx mut ast::Program) {
     for (_, modules) in program.pkgs.iter() {
         for module in modules.iter() {
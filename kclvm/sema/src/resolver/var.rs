@@ -1,4 +1,5 @@
 use crate::resolver::Resolver;
+use crate::ty::{ModuleKind, TypeKind};
 use kclvm_ast::ast;
 use kclvm_ast::pos::GetPos;
 use kclvm_error::diagnostic::Range;
@@ -22,6 +23,12 @@ impl<'ctx> Resolver<'_> {
                 range.clone(),
             );
         }
+        // Load the narrowed variable type in the branch e.g., `x` in the body of `if x != None:`.
+        if names.len() == 1 && pkgpath.is_empty() && !self.ctx.l_value {
+            if let Some(ty) = self.lookup_narrowed_type(&names[0]) {
+                return vec![ty];
+            }
+        }
         if names.len() == 1 {
             let name = &names[0];
             let scope_schema_ty = self.ctx.schema.clone();
@@ -127,12 +134,22 @@ impl<'ctx> Resolver<'_> {
             let mut tys = self.resolve_var(&[names[0].clone()], pkgpath, range.clone());
             let mut ty = tys[0].clone();
 
-            for name in &names[1..] {
+            for (i, name) in names.iter().enumerate().skip(1) {
                 // Store and config attr check
                 if self.ctx.l_value {
                     self.must_check_config_attr(name, &ty, &range, None);
                 }
+                if let TypeKind::Module(module_ty) = &ty.kind {
+                    if module_ty.kind == ModuleKind::User {
+                        self.check_exported_member(&module_ty.pkgpath, name, &range);
+                    }
+                }
                 ty = self.load_attr(ty, name, range.clone());
+                if !self.ctx.l_value {
+                    if let Some(narrowed_ty) = self.lookup_narrowed_type(&names[..=i].join(".")) {
+                        ty = narrowed_ty;
+                    }
+                }
                 tys.push(ty.clone());
             }
             tys
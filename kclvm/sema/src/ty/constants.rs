@@ -95,41 +95,48 @@ impl Type {
         kind: TypeKind::Void,
         flags: TypeFlags::VOID,
         is_type_alias: false,
+        constraint: None,
     };
     /// Type constant `int`.
     pub const INT: Type = Type {
         kind: TypeKind::Int,
         flags: TypeFlags::INT,
         is_type_alias: false,
+        constraint: None,
     };
     /// Type constant `float`.
     pub const FLOAT: Type = Type {
         kind: TypeKind::Float,
         flags: TypeFlags::FLOAT,
         is_type_alias: false,
+        constraint: None,
     };
     /// Type constant `str`.
     pub const STR: Type = Type {
         kind: TypeKind::Str,
         flags: TypeFlags::STR,
         is_type_alias: false,
+        constraint: None,
     };
     /// Type constant `bool`.
     pub const BOOL: Type = Type {
         kind: TypeKind::Bool,
         flags: TypeFlags::BOOL,
         is_type_alias: false,
+        constraint: None,
     };
     /// Type constant `any`.
     pub const ANY: Type = Type {
         kind: TypeKind::Any,
         flags: TypeFlags::ANY,
         is_type_alias: false,
+        constraint: None,
     };
     /// Type constant `NoneType` including the name constants `None` and `Undefined`.
     pub const NONE: Type = Type {
         kind: TypeKind::None,
         flags: TypeFlags::NONE,
         is_type_alias: false,
+        constraint: None,
     };
 }
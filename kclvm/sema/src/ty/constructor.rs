@@ -33,6 +33,7 @@ impl Type {
             kind: TypeKind::Union(types.to_owned()),
             flags: TypeFlags::UNION,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct an union type reference.
@@ -47,6 +48,7 @@ impl Type {
             kind: TypeKind::List(item_ty),
             flags: TypeFlags::LIST,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a list type ref
@@ -65,6 +67,7 @@ impl Type {
             }),
             flags: TypeFlags::DICT,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a dict type ref
@@ -87,6 +90,7 @@ impl Type {
             }),
             flags: TypeFlags::DICT,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a dict type reference with attrs
@@ -105,6 +109,7 @@ impl Type {
             kind: TypeKind::BoolLit(val),
             flags: TypeFlags::BOOL | TypeFlags::LITERAL,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a int literal type.
@@ -114,6 +119,7 @@ impl Type {
             kind: TypeKind::IntLit(num),
             flags: TypeFlags::INT | TypeFlags::LITERAL,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a float literal type.
@@ -123,6 +129,7 @@ impl Type {
             kind: TypeKind::FloatLit(num),
             flags: TypeFlags::FLOAT | TypeFlags::LITERAL,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a float literal type.
@@ -132,6 +139,7 @@ impl Type {
             kind: TypeKind::StrLit(val.to_string()),
             flags: TypeFlags::STR | TypeFlags::LITERAL,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a named type.
@@ -141,6 +149,17 @@ impl Type {
             kind: TypeKind::Named(val.to_string()),
             flags: TypeFlags::NAMED,
             is_type_alias: false,
+            constraint: None,
+        }
+    }
+    /// Construct a type parameter type, which is also an any type.
+    #[inline]
+    pub fn type_param(name: &str) -> Type {
+        Type {
+            kind: TypeKind::TypeParam(name.to_string()),
+            flags: TypeFlags::ANY | TypeFlags::TYPE_PARAM,
+            is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a number multiplier type.
@@ -155,6 +174,7 @@ impl Type {
             }),
             flags: TypeFlags::NUMBER_MULTIPLIER,
             is_type_alias: false,
+            constraint: None,
         }
     }
     #[inline]
@@ -168,6 +188,7 @@ impl Type {
             }),
             flags: TypeFlags::NUMBER_MULTIPLIER,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a function type.
@@ -191,6 +212,7 @@ impl Type {
             }),
             flags: TypeFlags::FUNCTION,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a module type.
@@ -203,6 +225,7 @@ impl Type {
             }),
             flags: TypeFlags::MODULE,
             is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a schema type.
@@ -211,6 +234,16 @@ impl Type {
             kind: TypeKind::Schema(schema_ty),
             flags: TypeFlags::SCHEMA,
             is_type_alias: false,
+            constraint: None,
+        }
+    }
+    /// Construct an enum type.
+    pub fn enumeration(enum_ty: EnumType) -> Type {
+        Type {
+            kind: TypeKind::Enum(enum_ty),
+            flags: TypeFlags::ENUM,
+            is_type_alias: false,
+            constraint: None,
         }
     }
     /// Construct a iterable type
@@ -346,6 +379,23 @@ impl Type {
     pub fn is_module(&self) -> bool {
         self.flags.contains(TypeFlags::MODULE)
     }
+    /// Whether is an enum type.
+    #[inline]
+    pub fn is_enum(&self) -> bool {
+        self.flags.contains(TypeFlags::ENUM)
+    }
+    #[inline]
+    pub fn is_enum_def(&self) -> bool {
+        match &self.kind {
+            TypeKind::Enum(enum_ty) => !enum_ty.is_instance,
+            _ => false,
+        }
+    }
+    /// Whether is a type parameter type.
+    #[inline]
+    pub fn is_type_param(&self) -> bool {
+        self.flags.contains(TypeFlags::TYPE_PARAM)
+    }
     /// Whether is an assignable type.
     #[inline]
     pub fn is_assignable_type(&self) -> bool {
@@ -365,7 +415,9 @@ impl Type {
             | TypeKind::Union(_)
             | TypeKind::Schema(_)
             | TypeKind::NumberMultiplier(_)
-            | TypeKind::Function(_) => true,
+            | TypeKind::Function(_)
+            | TypeKind::TypeParam(_)
+            | TypeKind::Enum(_) => true,
             TypeKind::Void | TypeKind::Module(_) | TypeKind::Named(_) => false,
         }
     }
@@ -0,0 +1,180 @@
+//! Instantiation and inference of the generic schema and lambda types.
+//!
+//! The type parameters are erased at runtime, thus they are only used
+//! to check and infer the types in the resolver.
+
+use super::*;
+
+/// The type parameter bindings from the type parameter names to the type arguments.
+pub type TypeBindings = IndexMap<String, TypeRef>;
+
+impl Type {
+    /// Whether the type contains any type parameter.
+    pub fn has_type_param(&self) -> bool {
+        match &self.kind {
+            TypeKind::TypeParam(_) => true,
+            TypeKind::List(item_ty) => item_ty.has_type_param(),
+            TypeKind::Dict(DictType { key_ty, val_ty, .. }) => {
+                key_ty.has_type_param() || val_ty.has_type_param()
+            }
+            TypeKind::Union(types) => types.iter().any(|ty| ty.has_type_param()),
+            TypeKind::Schema(schema_ty) => schema_ty.type_args.iter().any(|ty| ty.has_type_param()),
+            TypeKind::Function(func_ty) => {
+                func_ty.params.iter().any(|param| param.ty.has_type_param())
+                    || func_ty.return_ty.has_type_param()
+            }
+            _ => false,
+        }
+    }
+
+    /// Substitute the type parameters in the type with the type bindings,
+    /// the unbound type parameters are kept.
+    pub fn substitute(&self, bindings: &TypeBindings) -> TypeRef {
+        match &self.kind {
+            TypeKind::TypeParam(name) => match bindings.get(name) {
+                Some(ty) => ty.clone(),
+                None => Arc::new(self.clone()),
+            },
+            TypeKind::List(item_ty) => Type::list_ref(item_ty.substitute(bindings)),
+            TypeKind::Dict(DictType {
+                key_ty,
+                val_ty,
+                attrs,
+            }) => Type::dict_ref_with_attrs(
+                key_ty.substitute(bindings),
+                val_ty.substitute(bindings),
+                attrs
+                    .iter()
+                    .map(|(key, attr)| {
+                        (
+                            key.to_string(),
+                            Attr {
+                                ty: attr.ty.substitute(bindings),
+                                range: attr.range.clone(),
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            TypeKind::Union(types) => Type::union_ref(
+                &types
+                    .iter()
+                    .map(|ty| ty.substitute(bindings))
+                    .collect::<Vec<TypeRef>>(),
+            ),
+            TypeKind::Schema(schema_ty) if !schema_ty.type_args.is_empty() => {
+                Arc::new(Type::schema(schema_ty.substitute(bindings)))
+            }
+            TypeKind::Function(func_ty) => Arc::new(Type {
+                kind: TypeKind::Function(func_ty.substitute(bindings)),
+                flags: self.flags,
+                is_type_alias: self.is_type_alias,
+                constraint: self.constraint.clone(),
+            }),
+            _ => Arc::new(self.clone()),
+        }
+    }
+}
+
+impl SchemaType {
+    /// Instantiate the generic schema type with the type arguments e.g.,
+    /// `Page[int]` for `schema Page[T][]`.
+    pub fn instantiate(&self, type_args: &[TypeRef]) -> SchemaType {
+        let bindings: TypeBindings = self
+            .type_params
+            .iter()
+            .cloned()
+            .zip(type_args.iter().cloned())
+            .collect();
+        let mut schema_ty = self.substitute(&bindings);
+        schema_ty.type_args = type_args.to_vec();
+        schema_ty
+    }
+
+    /// Substitute the type parameters in the schema attributes, index
+    /// signature, arguments and type arguments with the type bindings.
+    pub fn substitute(&self, bindings: &TypeBindings) -> SchemaType {
+        let mut schema_ty = self.clone();
+        for attr in schema_ty.attrs.values_mut() {
+            attr.ty = attr.ty.substitute(bindings);
+        }
+        if let Some(index_signature) = schema_ty.index_signature.as_mut() {
+            index_signature.key_ty = index_signature.key_ty.substitute(bindings);
+            index_signature.val_ty = index_signature.val_ty.substitute(bindings);
+        }
+        schema_ty.func = Box::new(schema_ty.func.substitute(bindings));
+        schema_ty.type_args = schema_ty
+            .type_args
+            .iter()
+            .map(|ty| ty.substitute(bindings))
+            .collect();
+        schema_ty
+    }
+}
+
+impl FunctionType {
+    /// Substitute the type parameters in the parameter and return types
+    /// with the type bindings.
+    pub fn substitute(&self, bindings: &TypeBindings) -> FunctionType {
+        FunctionType {
+            params: self
+                .params
+                .iter()
+                .map(|param| Parameter {
+                    ty: param.ty.substitute(bindings),
+                    ..param.clone()
+                })
+                .collect(),
+            return_ty: self.return_ty.substitute(bindings),
+            ..self.clone()
+        }
+    }
+}
+
+/// Infer the type parameter bindings by matching the declared type containing
+/// the type parameters with the actual type, e.g., `T` is bound to `int` when
+/// matching `[T]` with `[int]`. A type parameter matched more than once is
+/// bound to the supremum of the actual types.
+pub fn infer_type_bindings(ty: &TypeRef, actual_ty: &TypeRef, bindings: &mut TypeBindings) {
+    match (&ty.kind, &actual_ty.kind) {
+        (TypeKind::TypeParam(name), _) => {
+            let bound_ty = match bindings.get(name) {
+                Some(bound_ty) => sup(&[bound_ty.clone(), actual_ty.clone()]),
+                None => actual_ty.clone(),
+            };
+            bindings.insert(name.to_string(), bound_ty);
+        }
+        (TypeKind::List(item_ty), TypeKind::List(actual_item_ty)) => {
+            infer_type_bindings(item_ty, actual_item_ty, bindings)
+        }
+        (
+            TypeKind::Dict(DictType { key_ty, val_ty, .. }),
+            TypeKind::Dict(DictType {
+                key_ty: actual_key_ty,
+                val_ty: actual_val_ty,
+                ..
+            }),
+        ) => {
+            infer_type_bindings(key_ty, actual_key_ty, bindings);
+            infer_type_bindings(val_ty, actual_val_ty, bindings);
+        }
+        (TypeKind::Schema(schema_ty), TypeKind::Schema(actual_schema_ty))
+            if schema_ty.full_ty_str() == actual_schema_ty.full_ty_str() =>
+        {
+            for (ty, actual_ty) in schema_ty
+                .type_args
+                .iter()
+                .zip(actual_schema_ty.type_args.iter())
+            {
+                infer_type_bindings(ty, actual_ty, bindings);
+            }
+        }
+        (TypeKind::Function(func_ty), TypeKind::Function(actual_func_ty)) => {
+            for (param, actual_param) in func_ty.params.iter().zip(actual_func_ty.params.iter()) {
+                infer_type_bindings(&param.ty, &actual_param.ty, bindings);
+            }
+            infer_type_bindings(&func_ty.return_ty, &actual_func_ty.return_ty, bindings);
+        }
+        _ => {}
+    }
+}
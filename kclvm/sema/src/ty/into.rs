@@ -116,6 +116,8 @@ impl Type {
                 }
             }
             TypeKind::Function(fn_ty) => fn_ty.ty_str(),
+            // Enum types are erased to the literal union of its member types.
+            TypeKind::Enum(enum_ty) => enum_ty.member_union_ty().into_type_annotation_str(),
             _ => self.ty_str(),
         }
     }
@@ -131,7 +133,13 @@ impl From<ast::Type> for Type {
                 ast::BasicType::Float => Type::FLOAT,
                 ast::BasicType::Str => Type::STR,
             },
+            ast::Type::Refined(refined_ty) => Type::refined(
+                ast::Type::Basic(refined_ty.base).into(),
+                refined_ty.constraint,
+            ),
             ast::Type::Named(identifier) => Type::named(&identifier.get_name()),
+            // The type arguments of the generic type are resolved with the scope.
+            ast::Type::Generic(generic_ty) => Type::named(&generic_ty.name.get_name()),
             ast::Type::List(list_ty) => Type::list(
                 list_ty
                     .inner_type
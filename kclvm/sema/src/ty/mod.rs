@@ -1,8 +1,10 @@
 mod constants;
 mod constructor;
 mod context;
+mod generic;
 mod into;
 pub mod parser;
+mod refinement;
 mod unify;
 mod walker;
 
@@ -11,11 +13,13 @@ use std::sync::Arc;
 
 pub use constants::*;
 pub use context::{TypeContext, TypeInferMethods};
+pub use generic::*;
 use kclvm_ast::ast;
 use kclvm_ast::MAIN_PKG;
 use kclvm_error::diagnostic::Range;
 use kclvm_error::Position;
 use kclvm_primitives::IndexMap;
+pub use refinement::*;
 pub use unify::*;
 pub use walker::walk_type;
 
@@ -38,6 +42,10 @@ pub struct Type {
     /// This field provides fast access to information that is
     /// also contained in `kind`.
     flags: TypeFlags,
+    /// The value constraint of the refined type e.g., `1..65535` in
+    /// `int(1..65535)`, the refined type is the same as its basic type
+    /// except that the literal values are checked by the constraint.
+    pub constraint: Option<ast::TypeConstraint>,
 }
 
 unsafe impl Send for Type {}
@@ -50,6 +58,14 @@ impl Type {
     }
     /// Returns the type string used for the error handler.
     pub fn ty_str(&self) -> String {
+        match &self.constraint {
+            Some(constraint) => format!("{}({})", self.kind_ty_str(), constraint.to_string()),
+            None => self.kind_ty_str(),
+        }
+    }
+
+    /// Returns the type string of the type kind without the value constraint.
+    pub fn kind_ty_str(&self) -> String {
         match &self.kind {
             TypeKind::None => NONE_TYPE_STR.to_string(),
             TypeKind::Any => ANY_TYPE_STR.to_string(),
@@ -80,12 +96,14 @@ impl Type {
                 .map(|ty| ty.ty_str())
                 .collect::<Vec<String>>()
                 .join(" | "),
-            TypeKind::Schema(schema_ty) => schema_ty.name.to_string(),
+            TypeKind::Schema(schema_ty) => schema_ty.ty_str(),
             TypeKind::NumberMultiplier(number_multiplier) => number_multiplier.ty_str(),
             TypeKind::Function(func_ty) => func_ty.ty_str(),
             TypeKind::Void => VOID_TYPE_STR.to_string(),
             TypeKind::Module(module_ty) => format!("{} '{}'", MODULE_TYPE_STR, module_ty.pkgpath),
             TypeKind::Named(name) => name.to_string(),
+            TypeKind::TypeParam(name) => name.to_string(),
+            TypeKind::Enum(enum_ty) => enum_ty.ty_str(),
         }
     }
 
@@ -120,6 +138,7 @@ impl Type {
                 .collect::<Vec<String>>()
                 .join(" | "),
             TypeKind::Schema(schema_ty) => schema_ty.full_ty_str(),
+            TypeKind::Enum(enum_ty) => enum_ty.full_ty_str(),
             _ => self.ty_str(),
         }
     }
@@ -128,6 +147,7 @@ impl Type {
         match &self.kind {
             TypeKind::Schema(schema) => Some(schema.doc.clone()),
             TypeKind::Function(func) => Some(func.doc.clone()),
+            TypeKind::Enum(enum_ty) => Some(enum_ty.doc.clone()),
             _ => None,
         }
     }
@@ -176,12 +196,18 @@ pub enum TypeKind {
     Module(ModuleType),
     /// A named type alias.
     Named(String),
+    /// An enum type, which is a named union of the literal member types.
+    Enum(EnumType),
+    /// A type parameter of the generic schema or lambda e.g., `T` in
+    /// `schema Page[T][]`, which is treated as the `any` type until it
+    /// is bound by the type argument.
+    TypeParam(String),
 }
 
 bitflags::bitflags! {
     /// TypeFlags provides fast access to information that is also contained
     /// in `kind`.
-    pub struct TypeFlags: u16 {
+    pub struct TypeFlags: u32 {
         const VOID = 1 << 0;
         const INT = 1 << 1;
         const FLOAT = 1 << 2;
@@ -198,6 +224,8 @@ bitflags::bitflags! {
         const FUNCTION = 1 << 13;
         const MODULE = 1 << 14;
         const NAMED = 1 << 15;
+        const TYPE_PARAM = 1 << 16;
+        const ENUM = 1 << 17;
     }
 }
 
@@ -264,6 +292,11 @@ pub struct SchemaType {
     pub index_signature: Option<Box<SchemaIndexSignature>>,
     /// Schema decorators including self and attribute decorators.
     pub decorators: Vec<Decorator>,
+    /// The type parameter names of the generic schema.
+    pub type_params: Vec<String>,
+    /// The type arguments of the generic schema instantiation e.g.,
+    /// `int` in `Page[int]`, which is empty for the raw generic schema.
+    pub type_args: Vec<TypeRef>,
 }
 
 impl SchemaType {
@@ -275,6 +308,22 @@ impl SchemaType {
             format!("@{}.{}", self.pkgpath, self.name)
         }
     }
+    /// Get the type string with the type arguments e.g., `Page[int]`.
+    pub fn ty_str(&self) -> String {
+        if self.type_args.is_empty() {
+            self.name.clone()
+        } else {
+            format!(
+                "{}[{}]",
+                self.name,
+                self.type_args
+                    .iter()
+                    .map(|ty| ty.ty_str())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        }
+    }
     /// Get the object type string.
     pub fn full_ty_str(&self) -> String {
         full_ty_str(&self.pkgpath, &self.name)
@@ -290,6 +339,7 @@ impl SchemaType {
             None => {
                 let schema_attr = SchemaAttr {
                     is_optional: true,
+                    is_final: false,
                     has_default: false,
                     default: None,
                     ty,
@@ -400,6 +450,8 @@ pub fn full_ty_str(pkgpath: &str, name: &str) -> String {
 #[derive(Debug, Clone, PartialEq)]
 pub struct SchemaAttr {
     pub is_optional: bool,
+    /// Final attributes can't be overridden by the configs and the sub schemas.
+    pub is_final: bool,
     pub has_default: bool,
     /// `default` denotes the schema attribute optional value string. For example,
     /// for the schema attribute definition `name?: str = "Alice"`, the value of
@@ -434,6 +486,61 @@ impl SchemaIndexSignature {
     }
 }
 
+/// The enum type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumType {
+    /// The enum name.
+    pub name: String,
+    /// The enum definition package path.
+    pub pkgpath: String,
+    /// The enum definition file path.
+    pub filename: String,
+    /// The enum definition document string.
+    pub doc: String,
+    /// Enum members in the definition order.
+    pub members: IndexMap<String, EnumMemberType>,
+    /// Indicates whether the enum is a type of a instance or the enum
+    /// definition itself used in the value expression, such as the
+    /// following code:
+    /// ```no_check
+    /// enum Protocol:
+    ///     TCP = "tcp"
+    ///     UDP = "udp"
+    ///
+    /// # `Protocol` in `protocol: Protocol` is an enum instance type, and
+    /// # `Protocol` in `Protocol.TCP` is the enum definition.
+    /// protocol: Protocol = Protocol.TCP
+    /// ```
+    pub is_instance: bool,
+}
+
+impl EnumType {
+    /// Get the enum type string with the package path.
+    #[inline]
+    pub fn full_ty_str(&self) -> String {
+        full_ty_str(&self.pkgpath, &self.name)
+    }
+    /// Get the enum type string.
+    #[inline]
+    pub fn ty_str(&self) -> String {
+        self.name.clone()
+    }
+    /// Get the union type of all member literal types, which is the
+    /// type of an enum instance.
+    pub fn member_union_ty(&self) -> TypeRef {
+        let types: Vec<TypeRef> = self.members.values().map(|m| m.ty.clone()).collect();
+        Type::union_ref(&types)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumMemberType {
+    /// The literal type of the member value.
+    pub ty: TypeRef,
+    pub range: Range,
+    pub doc: Option<String>,
+}
+
 /// The module type.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ModuleType {
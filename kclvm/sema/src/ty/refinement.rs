@@ -0,0 +1,71 @@
+//! Refinement types constrain the values of the basic types e.g.,
+//! `int(1..65535)` and `str(r"^[a-z0-9-]+$")`.
+//!
+//! A refined type is the same as its basic type in the resolver except that
+//! the literal values are checked against the constraint, and the other values
+//! are checked at runtime.
+
+use super::*;
+
+impl Type {
+    /// Construct a refined type with the basic type and the value constraint.
+    #[inline]
+    pub fn refined(base: Type, constraint: ast::TypeConstraint) -> Type {
+        Type {
+            constraint: Some(constraint),
+            ..base
+        }
+    }
+    /// Whether is a refined type.
+    #[inline]
+    pub fn is_refined(&self) -> bool {
+        self.constraint.is_some()
+    }
+    /// Get the basic type of the refined type without the value constraint.
+    #[inline]
+    pub fn base_ty(&self) -> TypeRef {
+        Arc::new(Type {
+            constraint: None,
+            ..self.clone()
+        })
+    }
+}
+
+/// Whether the literal type satisfies the value constraint e.g., `int(80)`
+/// satisfies `1..65535`. The non-literal types are always satisfied because
+/// their values are unknown at compile time.
+pub fn satisfy_constraint(ty: &Type, constraint: &ast::TypeConstraint) -> bool {
+    match (&ty.kind, constraint) {
+        (TypeKind::IntLit(v), ast::TypeConstraint::Range(range)) => in_range(*v as f64, range),
+        (TypeKind::FloatLit(v), ast::TypeConstraint::Range(range)) => in_range(*v, range),
+        (TypeKind::StrLit(v), ast::TypeConstraint::Pattern(pattern)) => {
+            match fancy_regex::Regex::new(pattern) {
+                Ok(re) => re.is_match(v).unwrap_or(true),
+                // The invalid pattern is reported when resolving the type annotation.
+                Err(_) => true,
+            }
+        }
+        _ => true,
+    }
+}
+
+/// Whether the number value is in the inclusive range.
+fn in_range(value: f64, range: &ast::RangeConstraint) -> bool {
+    range
+        .min
+        .as_ref()
+        .map_or(true, |min| value >= range_bound_value(min))
+        && range
+            .max
+            .as_ref()
+            .map_or(true, |max| value <= range_bound_value(max))
+}
+
+/// Get the float value of the number range bound.
+#[inline]
+pub fn range_bound_value(value: &ast::NumberLitValue) -> f64 {
+    match value {
+        ast::NumberLitValue::Int(v) => *v as f64,
+        ast::NumberLitValue::Float(v) => *v,
+    }
+}
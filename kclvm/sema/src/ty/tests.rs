@@ -115,3 +115,52 @@ fn test_type_walker() {
         );
     }
 }
+
+#[test]
+fn test_enum_subsume() {
+    let mut members = IndexMap::default();
+    for (name, value) in [("TCP", "tcp"), ("UDP", "udp")] {
+        members.insert(
+            name.to_string(),
+            EnumMemberType {
+                ty: Arc::new(Type::str_lit(value)),
+                range: Default::default(),
+                doc: None,
+            },
+        );
+    }
+    let enum_ty = EnumType {
+        name: "Protocol".to_string(),
+        pkgpath: MAIN_PKG.to_string(),
+        filename: "".to_string(),
+        doc: "".to_string(),
+        members,
+        is_instance: true,
+    };
+    let protocol = Arc::new(Type::enumeration(enum_ty.clone()));
+    let protocol_def = Arc::new(Type::enumeration(EnumType {
+        is_instance: false,
+        ..enum_ty
+    }));
+    assert!(subsume(
+        Arc::new(Type::str_lit("tcp")),
+        protocol.clone(),
+        false
+    ));
+    assert!(!subsume(
+        Arc::new(Type::str_lit("sctp")),
+        protocol.clone(),
+        false
+    ));
+    assert!(!subsume(Arc::new(Type::STR), protocol.clone(), false));
+    assert!(subsume(protocol.clone(), Arc::new(Type::STR), false));
+    assert!(subsume(protocol.clone(), protocol.clone(), false));
+    assert!(!subsume(protocol_def.clone(), Arc::new(Type::STR), false));
+    assert!(!subsume(
+        Arc::new(Type::str_lit("tcp")),
+        protocol_def,
+        false
+    ));
+    assert_eq!(protocol.ty_str(), "Protocol");
+    assert_eq!(protocol.into_type_annotation_str(), r#""tcp" | "udp""#);
+}
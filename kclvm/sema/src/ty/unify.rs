@@ -2,7 +2,7 @@ use std::{collections::HashSet, sync::Arc};
 
 use kclvm_primitives::IndexMap;
 
-use super::{SchemaType, Type, TypeKind, TypeRef};
+use super::{satisfy_constraint, SchemaType, Type, TypeKind, TypeRef};
 
 /// The type can be assigned to the expected type.
 ///
@@ -21,6 +21,25 @@ pub fn subsume(ty_lhs: TypeRef, ty_rhs: TypeRef, check_left_any: bool) -> bool {
         types
             .iter()
             .any(|ty| subsume(ty_lhs.clone(), ty.clone(), false))
+    } else if let Some(constraint) = &ty_rhs.constraint {
+        // The literal value must satisfy the value constraint of the refined type
+        // e.g., `80` for `int(1..65535)`, and other values are checked at runtime.
+        satisfy_constraint(&ty_lhs, constraint) && subsume(ty_lhs, ty_rhs.base_ty(), check_left_any)
+    } else if let TypeKind::Enum(ty_lhs_enum) = &ty_lhs.kind {
+        match &ty_rhs.kind {
+            TypeKind::Enum(ty_rhs_enum) => {
+                ty_lhs_enum.is_instance == ty_rhs_enum.is_instance
+                    && ty_lhs_enum.full_ty_str() == ty_rhs_enum.full_ty_str()
+            }
+            // The enum instance can be assigned to the type of all its members e.g., `Protocol` -> `str`.
+            _ if ty_lhs_enum.is_instance => {
+                subsume(ty_lhs_enum.member_union_ty(), ty_rhs, check_left_any)
+            }
+            _ => false,
+        }
+    } else if let TypeKind::Enum(ty_rhs_enum) = &ty_rhs.kind {
+        // The member literal value can be assigned to the enum e.g., `"tcp"` -> `Protocol`.
+        ty_rhs_enum.is_instance && subsume(ty_lhs, ty_rhs_enum.member_union_ty(), check_left_any)
     } else if ty_lhs.is_schema() {
         match &ty_rhs.kind {
             TypeKind::Schema(ty_rhs_schema) => {
@@ -103,7 +122,14 @@ pub fn equal(ty_lhs: TypeRef, ty_rhs: TypeRef) -> bool {
 /// Whether the schema is sub schema of another schema.
 pub fn is_sub_schema_of(schema_ty_lhs: &SchemaType, schema_ty_rhs: &SchemaType) -> bool {
     if schema_ty_lhs.full_ty_str() == schema_ty_rhs.full_ty_str() {
-        true
+        // The type arguments of the generic schema are covariant, and the raw
+        // generic schema without type arguments matches all its instantiations.
+        schema_ty_lhs.type_args.len() != schema_ty_rhs.type_args.len()
+            || schema_ty_lhs
+                .type_args
+                .iter()
+                .zip(schema_ty_rhs.type_args.iter())
+                .all(|(ty_lhs, ty_rhs)| subsume(ty_lhs.clone(), ty_rhs.clone(), false))
     } else {
         match &schema_ty_lhs.base {
             Some(base) => is_sub_schema_of(base, schema_ty_rhs),
@@ -38,6 +38,10 @@ symbols! {
         Filter:          "filter",
         Map:             "map",
         Type:            "type",
+        Enum:            "enum",
+        Match:           "match",
+        Export:          "export",
+        Final:           "final",
         True:            "True",
         False:           "False",
         None:            "None",
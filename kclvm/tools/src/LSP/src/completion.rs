@@ -25,6 +25,7 @@ use kclvm_error::diagnostic::Range;
 use kclvm_parser::get_kcl_files;
 use kclvm_primitives::{DefaultHashBuilder, IndexMap, IndexSet};
 use kclvm_sema::core::global_state::GlobalState;
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::{fs, path::Path};
 
@@ -48,6 +49,8 @@ pub enum KCLCompletionItemKind {
     Dir,
     Schema,
     SchemaAttr,
+    Enum,
+    EnumMember,
     Module,
     Doc,
 }
@@ -60,6 +63,8 @@ impl From<KCLCompletionItemKind> for CompletionItemKind {
             KCLCompletionItemKind::File => CompletionItemKind::FILE,
             KCLCompletionItemKind::Schema => CompletionItemKind::CLASS,
             KCLCompletionItemKind::SchemaAttr => CompletionItemKind::FIELD,
+            KCLCompletionItemKind::Enum => CompletionItemKind::ENUM,
+            KCLCompletionItemKind::EnumMember => CompletionItemKind::ENUM_MEMBER,
             KCLCompletionItemKind::Module => CompletionItemKind::MODULE,
             KCLCompletionItemKind::Dir => CompletionItemKind::FOLDER,
             KCLCompletionItemKind::Doc => CompletionItemKind::SNIPPET,
@@ -167,7 +172,12 @@ pub fn completion(
                         }));
                         // Complete all schema def in gs if in main pkg
                         if program.get_main_files().contains(&pos.filename) {
-                            completions.extend(unimport_schemas(&pos.filename, gs, schema_map));
+                            completions.extend(unimport_schemas(
+                                &pos.filename,
+                                program,
+                                gs,
+                                schema_map,
+                            ));
                         }
                     }
                 }
@@ -273,15 +283,22 @@ fn completion_dot(
     }
     let def = match symbol {
         Some(symbol_ref) => {
+            let mut is_narrowed = false;
             if let SymbolKind::Unresolved = symbol_ref.get_kind() {
                 let unresolved_symbol = gs.get_symbols().get_unresolved_symbol(symbol_ref).unwrap();
                 if unresolved_symbol.is_type() {
                     return Some(into_completion_items(&items).into());
                 }
+                is_narrowed = unresolved_symbol.is_narrowed();
             }
-            match gs.get_symbols().get_symbol(symbol_ref) {
-                Some(symbol) => symbol.get_definition(),
-                None => None,
+            if is_narrowed {
+                // Complete the attributes of the narrowed type in the branch.
+                Some(symbol_ref)
+            } else {
+                match gs.get_symbols().get_symbol(symbol_ref) {
+                    Some(symbol) => symbol.get_definition(),
+                    None => None,
+                }
             }
         }
         None => None,
@@ -292,11 +309,19 @@ fn completion_dot(
             if let Some(def) = gs.get_symbols().get_symbol(def_ref) {
                 let module_info = gs.get_packages().get_module_info(&pos.filename);
                 let attrs = def.get_all_attributes(gs.get_symbols(), module_info);
+                // The members not exported by the package are hidden.
+                let exports = match def_ref.get_kind() {
+                    SymbolKind::Package => program.get_pkg_exports(&def.get_name()),
+                    _ => None,
+                };
                 for attr in attrs {
                     let attr_def = gs.get_symbols().get_symbol(attr);
                     if let Some(attr_def) = attr_def {
                         let sema_info = attr_def.get_sema_info();
                         let name = attr_def.get_name();
+                        if matches!(&exports, Some(exports) if !exports.contains(&name)) {
+                            continue;
+                        }
                         match &sema_info.ty {
                             Some(attr_ty) => {
                                 let label: String = match &attr_ty.kind {
@@ -316,6 +341,9 @@ fn completion_dot(
                                         TypeKind::Schema(_) => {
                                             Some(KCLCompletionItemKind::SchemaAttr)
                                         }
+                                        TypeKind::Enum(_) => {
+                                            Some(KCLCompletionItemKind::EnumMember)
+                                        }
                                         _ => type_to_item_kind(attr_ty),
                                     },
                                     None => type_to_item_kind(attr_ty),
@@ -844,6 +872,23 @@ fn ty_complete_label_and_inser_text(
                 "{$1}".to_string(), // `$1`` is used to determine the cursor position after completion
             )]
         }
+        TypeKind::Enum(enum_ty) => {
+            let prefix = if enum_ty.pkgpath.is_empty() || enum_ty.pkgpath == MAIN_PKG {
+                "".to_string()
+            } else if let Some(m) = module {
+                format!("{}.", pkg_real_name(&enum_ty.pkgpath, m))
+            } else {
+                format!("{}.", enum_ty.pkgpath.split('.').last().unwrap())
+            };
+            enum_ty
+                .members
+                .keys()
+                .map(|name| {
+                    let member = format!("{}{}.{}", prefix, enum_ty.name, name);
+                    (member.clone(), member)
+                })
+                .collect()
+        }
         _ => vec![],
     }
 }
@@ -892,9 +937,10 @@ fn type_to_item_kind(ty: &Type) -> Option<KCLCompletionItemKind> {
         | TypeKind::NumberMultiplier(_)
         | TypeKind::Named(_) => Some(KCLCompletionItemKind::Variable),
         TypeKind::Schema(_) => Some(KCLCompletionItemKind::Schema),
+        TypeKind::Enum(_) => Some(KCLCompletionItemKind::Enum),
         TypeKind::Function(_) => Some(KCLCompletionItemKind::Function),
         TypeKind::Module(_) => Some(KCLCompletionItemKind::Module),
-        TypeKind::Void | TypeKind::None | TypeKind::Any => None,
+        TypeKind::Void | TypeKind::None | TypeKind::Any | TypeKind::TypeParam(_) => None,
     }
 }
 
@@ -944,13 +990,21 @@ pub(crate) fn into_completion_items(items: &IndexSet<KCLCompletionItem>) -> Vec<
 
 fn unimport_schemas(
     filename: &str,
+    program: &Program,
     gs: &GlobalState,
     schema_map: &IndexMap<String, Vec<SchemaType>>,
 ) -> IndexSet<KCLCompletionItem> {
     let module = gs.get_packages().get_module_info(filename);
     let mut completions: IndexSet<KCLCompletionItem> = Default::default();
+    let mut pkg_exports: HashMap<String, Option<HashSet<String>>> = HashMap::new();
     for (_, schemas) in schema_map {
         for schema in schemas {
+            let exports = pkg_exports
+                .entry(schema.pkgpath.clone())
+                .or_insert_with(|| program.get_pkg_exports(&schema.pkgpath));
+            if matches!(exports, Some(exports) if !exports.contains(&schema.name)) {
+                continue;
+            }
             let has_import = match module {
                 Some(m) => m
                     .get_imports()
@@ -1434,6 +1488,11 @@ mod tests {
                 "template",
                 "runtime",
                 "base32",
+                "csv",
+                "ini",
+                "properties",
+                "xml",
+                "hcl",
             ]
             .iter()
             .map(|name| KCLCompletionItem {
@@ -2314,6 +2373,22 @@ mod tests {
         Some('.')
     );
 
+    completion_label_test_snapshot!(
+        enum_member_dot,
+        "src/test_data/completion_test/dot/enum/enum.k",
+        6,
+        13,
+        Some('.')
+    );
+
+    completion_label_test_snapshot!(
+        plugin_member_dot,
+        "src/test_data/completion_test/dot/plugin/plugin.k",
+        3,
+        10,
+        Some('.')
+    );
+
     completion_label_without_builtin_func_test_snapshot!(
         complete_unimport_schemas,
         "src/test_data/completion_test/unimport/unimport/main.k",
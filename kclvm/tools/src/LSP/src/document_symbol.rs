@@ -28,7 +28,7 @@ pub fn document_symbol(file: &str, gs: &GlobalState) -> Option<lsp_types::Docume
                                 // filter current file symbols
                                 if symbol_range.0.filename == file {
                                     match def.get_kind() {
-                                        KCLSymbolKind::Schema => {
+                                        KCLSymbolKind::Schema | KCLSymbolKind::Enum => {
                                             match &mut symbol_to_document_symbol(symbol) {
                                                 Some(schema_symbol) => {
                                                     let module_info = gs
@@ -57,8 +57,18 @@ pub fn document_symbol(file: &str, gs: &GlobalState) -> Option<lsp_types::Docume
                                                     }
 
                                                     schema_symbol.children = Some(children);
-                                                    schema_symbol.name =
-                                                        format!("schema {}", schema_symbol.name);
+                                                    let keyword = if matches!(
+                                                        def.get_kind(),
+                                                        KCLSymbolKind::Enum
+                                                    ) {
+                                                        "enum"
+                                                    } else {
+                                                        "schema"
+                                                    };
+                                                    schema_symbol.name = format!(
+                                                        "{} {}",
+                                                        keyword, schema_symbol.name
+                                                    );
                                                     document_symbols.push(schema_symbol.clone());
                                                 }
                                                 None => {}
@@ -123,6 +133,7 @@ fn symbol_kind_to_document_symbol_kind(kind: KCLSymbolKind) -> Option<SymbolKind
         KCLSymbolKind::Function => Some(SymbolKind::FUNCTION),
         KCLSymbolKind::Package => Some(SymbolKind::PACKAGE),
         KCLSymbolKind::TypeAlias => Some(SymbolKind::TYPE_PARAMETER),
+        KCLSymbolKind::Enum => Some(SymbolKind::ENUM),
         KCLSymbolKind::Unresolved => Some(SymbolKind::NULL),
         KCLSymbolKind::Rule => Some(SymbolKind::FUNCTION),
         KCLSymbolKind::Expression => None,
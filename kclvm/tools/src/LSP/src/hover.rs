@@ -6,7 +6,7 @@ use kclvm_sema::{
 };
 use lsp_types::{Hover, HoverContents, MarkedString};
 
-use crate::goto_def::find_def;
+use crate::goto_def::{find_def, find_symbol};
 
 enum MarkedStringType {
     String,
@@ -18,6 +18,23 @@ enum MarkedStringType {
 pub fn hover(kcl_pos: &KCLPos, gs: &GlobalState) -> Option<lsp_types::Hover> {
     let mut docs: Vec<(String, MarkedStringType)> = vec![];
 
+    // Show the narrowed type of the variable reference in a branch.
+    if let Some(symbol_ref) = find_symbol(kcl_pos, gs, true) {
+        let is_narrowed = gs
+            .get_symbols()
+            .get_unresolved_symbol(symbol_ref)
+            .map_or(false, |symbol| symbol.is_narrowed());
+        if let (true, Some(symbol)) = (is_narrowed, gs.get_symbols().get_symbol(symbol_ref)) {
+            if let Some(ty) = &symbol.get_sema_info().ty {
+                docs.push((
+                    format!("{}: {}", &symbol.get_name(), ty.ty_str()),
+                    MarkedStringType::LanguageString,
+                ));
+                return docs_to_hover(docs);
+            }
+        }
+    }
+
     let def = find_def(kcl_pos, gs, true);
     match def {
         Some(def_ref) => match gs.get_symbols().get_symbol(def_ref) {
@@ -92,6 +109,44 @@ pub fn hover(kcl_pos: &KCLPos, gs: &GlobalState) -> Option<lsp_types::Hover> {
                     }
                     _ => {}
                 },
+                kclvm_sema::core::symbol::SymbolKind::Enum => match &obj.get_sema_info().ty {
+                    Some(ty) => {
+                        // Build hover content for enum definition
+                        // ```
+                        // pkg
+                        // ----------------
+                        // enum Foo:
+                        //     Member1 = value1
+                        //     Member2 = value2
+                        // -----------------
+                        // doc
+                        // ```
+                        if let kclvm_sema::ty::TypeKind::Enum(enum_ty) = &ty.kind {
+                            if !enum_ty.pkgpath.is_empty() {
+                                docs.push((enum_ty.pkgpath.clone(), MarkedStringType::String));
+                            }
+                            let members = enum_ty
+                                .members
+                                .iter()
+                                .map(|(name, member)| {
+                                    format!(
+                                        "    {} = {}",
+                                        name,
+                                        member.ty.into_type_annotation_str()
+                                    )
+                                })
+                                .collect::<Vec<String>>();
+                            docs.push((
+                                format!("enum {}:\n{}", enum_ty.name, members.join("\n")),
+                                MarkedStringType::LanguageString,
+                            ));
+                            if !enum_ty.doc.is_empty() {
+                                docs.push((enum_ty.doc.clone(), MarkedStringType::String));
+                            }
+                        }
+                    }
+                    _ => {}
+                },
                 kclvm_sema::core::symbol::SymbolKind::Attribute => {
                     let sema_info = obj.get_sema_info();
                     let attr_symbol = gs.get_symbols().get_attr_symbol(def_ref).unwrap();
@@ -582,6 +637,109 @@ mod tests {
         }
     }
 
+    #[test]
+    #[bench_test]
+    fn narrowed_var_hover() {
+        let (file, _program, _, gs, _) = compile_test_file("src/test_data/hover_test/narrowing.k");
+        let pos = KCLPos {
+            filename: file.clone(),
+            line: 8,
+            column: Some(8),
+        };
+        let got = hover(&pos, &gs).unwrap();
+        match got.contents {
+            lsp_types::HoverContents::Scalar(marked_string) => {
+                if let MarkedString::LanguageString(s) = marked_string {
+                    assert_eq!(s.value, "animal: Person");
+                }
+            }
+            _ => unreachable!("test error"),
+        }
+    }
+
+    #[test]
+    #[bench_test]
+    fn enum_hover() {
+        let (file, _program, _, gs, _) = compile_test_file("src/test_data/hover_test/enum.k");
+        // test hover of enum definition: protocol: Protocol
+        let pos = KCLPos {
+            filename: file.clone(),
+            line: 7,
+            column: Some(12),
+        };
+        let got = hover(&pos, &gs).unwrap();
+        match got.contents {
+            lsp_types::HoverContents::Array(vec) => {
+                assert_eq!(vec.len(), 3);
+                if let MarkedString::String(s) = vec[0].clone() {
+                    assert_eq!(s, "__main__");
+                }
+                if let MarkedString::LanguageString(s) = vec[1].clone() {
+                    assert_eq!(
+                        s.value,
+                        "enum Protocol:\n    TCP = \"tcp\"\n    UDP = \"udp\""
+                    );
+                }
+                if let MarkedString::String(s) = vec[2].clone() {
+                    assert_eq!(s, "Network protocol");
+                }
+            }
+            _ => unreachable!("test error"),
+        }
+        // test hover of enum member: Protocol.TCP
+        let pos = KCLPos {
+            filename: file.clone(),
+            line: 7,
+            column: Some(31),
+        };
+        let got = hover(&pos, &gs).unwrap();
+        match got.contents {
+            lsp_types::HoverContents::Array(vec) => {
+                assert_eq!(vec.len(), 2);
+                if let MarkedString::LanguageString(s) = vec[0].clone() {
+                    assert_eq!(s.value, "TCP: \"tcp\" = \"tcp\"");
+                }
+                if let MarkedString::String(s) = vec[1].clone() {
+                    assert_eq!(s, "Transmission Control Protocol");
+                }
+            }
+            _ => unreachable!("test error"),
+        }
+    }
+
+    #[test]
+    #[bench_test]
+    fn plugin_func_hover() {
+        let (file, _program, _, gs, _) =
+            compile_test_file("src/test_data/hover_test/plugin/plugin.k");
+        // test hover of the plugin function declared in plugins/hello.k: hello.add
+        let pos = KCLPos {
+            filename: file.clone(),
+            line: 3,
+            column: Some(13),
+        };
+        let got = hover(&pos, &gs).unwrap();
+        let expected =
+            lsp_types::HoverContents::Scalar(MarkedString::LanguageString(LanguageString {
+                language: "KCL".to_string(),
+                value: "function add(x: int, y: int) -> int".to_string(),
+            }));
+        assert_eq!(got.contents, expected);
+
+        let pos = KCLPos {
+            filename: file.clone(),
+            line: 4,
+            column: Some(17),
+        };
+        let got = hover(&pos, &gs).unwrap();
+        let expected =
+            lsp_types::HoverContents::Scalar(MarkedString::LanguageString(LanguageString {
+                language: "KCL".to_string(),
+                value: "function say_hello(msg: str) -> str".to_string(),
+            }));
+        assert_eq!(got.contents, expected);
+    }
+
     #[test]
     #[bench_test]
     fn str_var_func_hover() {
@@ -214,18 +214,25 @@ fn apply_rename_changes(
 
         let mut to_removed = HashSet::new();
 
+        // Apply the edits from the end of the file, thus the edits on the same line
+        // e.g., `a: Protocol = Protocol.TCP` do not shift the positions of each other.
+        let mut edits: Vec<&TextEdit> = edits.iter().collect();
+        edits.sort_by(|a, b| {
+            (b.range.start.line, b.range.start.character)
+                .cmp(&(a.range.start.line, a.range.start.character))
+        });
         for edit in edits {
             let start_line = edit.range.start.line as usize;
             let end_line = edit.range.end.line as usize;
 
             if start_line == end_line {
                 // the text edit belongs to a single line
-                let line = &file_content_lines[start_line];
+                let line = &updated_lines[start_line];
                 let updated_line = apply_text_edit(edit, line);
                 updated_lines[start_line] = updated_line;
             } else {
-                let start_line_text = &file_content_lines[start_line];
-                let end_line_text = &file_content_lines[end_line];
+                let start_line_text = updated_lines[start_line].clone();
+                let end_line_text = updated_lines[end_line].clone();
                 let start_line_edit = TextEdit {
                     range: Range {
                         start: edit.range.start,
@@ -246,8 +253,8 @@ fn apply_rename_changes(
                     },
                     new_text: String::new(),
                 };
-                let updated_start_line = apply_text_edit(&start_line_edit, start_line_text);
-                let updated_end_line = apply_text_edit(&end_line_edit, end_line_text);
+                let updated_start_line = apply_text_edit(&start_line_edit, &start_line_text);
+                let updated_end_line = apply_text_edit(&end_line_edit, &end_line_text);
                 updated_lines[start_line] = format!("{}{}", updated_start_line, updated_end_line);
 
                 for line_num in (start_line + 1)..end_line + 1 {
@@ -733,26 +740,65 @@ Bob = vars.Person {
             expected: String,
         }
 
-        let test_cases = vec![TestCase {
-            changes: HashMap::from([(
-                path.clone(),
-                vec![TextEdit {
-                    range: Range {
-                        start: Position {
-                            line: 2,
-                            character: 11,
+        let test_cases = vec![
+            TestCase {
+                changes: HashMap::from([(
+                    path.clone(),
+                    vec![TextEdit {
+                        range: Range {
+                            start: Position {
+                                line: 2,
+                                character: 11,
+                            },
+                            end: Position {
+                                line: 2,
+                                character: 17,
+                            },
                         },
-                        end: Position {
-                            line: 2,
-                            character: 17,
+                        new_text: "Person2".to_string(),
+                    }],
+                )]),
+                expected:
+                    "import .pkg.vars\n\nBob = vars.Person2 {\n    name: \"Bob\"\n    age: 30\n}"
+                        .to_string(),
+            },
+            TestCase {
+                changes: HashMap::from([(
+                    path.clone(),
+                    vec![
+                        TextEdit {
+                            range: Range {
+                                start: Position {
+                                    line: 2,
+                                    character: 0,
+                                },
+                                end: Position {
+                                    line: 2,
+                                    character: 3,
+                                },
+                            },
+                            new_text: "Alice".to_string(),
                         },
-                    },
-                    new_text: "Person2".to_string(),
-                }],
-            )]),
-            expected: "import .pkg.vars\n\nBob = vars.Person2 {\n    name: \"Bob\"\n    age: 30\n}"
-                .to_string(),
-        }];
+                        TextEdit {
+                            range: Range {
+                                start: Position {
+                                    line: 2,
+                                    character: 11,
+                                },
+                                end: Position {
+                                    line: 2,
+                                    character: 17,
+                                },
+                            },
+                            new_text: "Person2".to_string(),
+                        },
+                    ],
+                )]),
+                expected:
+                    "import .pkg.vars\n\nAlice = vars.Person2 {\n    name: \"Bob\"\n    age: 30\n}"
+                        .to_string(),
+            },
+        ];
 
         for test_case in test_cases {
             let result = apply_rename_changes(&test_case.changes, source_codes.clone());
@@ -904,4 +950,60 @@ a = base.NewPerson {
 }"#
         );
     }
+
+    #[test]
+    fn test_rename_enum_on_code() {
+        let main_path = "/mock_root/main.k".to_string();
+        let source_code = r#"enum Protocol:
+    TCP = "tcp"
+    UDP = "udp"
+
+schema Service:
+    protocol: Protocol = Protocol.TCP
+
+service = Service {protocol = Protocol.UDP}
+tcp = Protocol.TCP"#;
+
+        // Rename the enum name.
+        let result = rename_symbol_on_code(
+            "/mock_root",
+            "Protocol",
+            hashmap! {main_path.clone() => source_code.to_string()},
+            "Transport".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            result.get(&main_path).unwrap(),
+            r#"enum Transport:
+    TCP = "tcp"
+    UDP = "udp"
+
+schema Service:
+    protocol: Transport = Transport.TCP
+
+service = Service {protocol = Transport.UDP}
+tcp = Transport.TCP"#
+        );
+
+        // Rename the enum member.
+        let result = rename_symbol_on_code(
+            "/mock_root",
+            "Protocol.TCP",
+            hashmap! {main_path.clone() => source_code.to_string()},
+            "Tcp".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            result.get(&main_path).unwrap(),
+            r#"enum Protocol:
+    Tcp = "tcp"
+    UDP = "udp"
+
+schema Service:
+    protocol: Protocol = Protocol.Tcp
+
+service = Service {protocol = Protocol.UDP}
+tcp = Protocol.Tcp"#
+        );
+    }
 }
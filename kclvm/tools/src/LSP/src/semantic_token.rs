@@ -18,6 +18,7 @@ pub const LEGEND_TYPE: &[SemanticTokenType] = &[
     SemanticTokenType::COMMENT,
     SemanticTokenType::PARAMETER,
     SemanticTokenType::FUNCTION,
+    SemanticTokenType::ENUM,
 ];
 
 pub(crate) struct KCLSemanticToken {
@@ -64,6 +65,7 @@ pub(crate) fn get_kind(symbol_ref: SymbolRef, symbol: &KCLSymbol, gs: &GlobalSta
         SymbolKind::Attribute => Some(type_index(SemanticTokenType::PROPERTY)),
         SymbolKind::Package => Some(type_index(SemanticTokenType::NAMESPACE)),
         SymbolKind::TypeAlias => Some(type_index(SemanticTokenType::TYPE)),
+        SymbolKind::Enum => Some(type_index(SemanticTokenType::ENUM)),
         SymbolKind::Value => {
             if let Some(ty) = &symbol.get_sema_info().ty {
                 match ty.kind {
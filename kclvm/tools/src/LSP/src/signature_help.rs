@@ -229,4 +229,20 @@ mod tests {
         11,
         Some(",".to_string())
     );
+
+    signature_help_test_snapshot!(
+        plugin_function_signature_help_test_0,
+        "src/test_data/signature_help/plugin_function_signature_help/plugin_function_signature_help.k",
+        3,
+        10,
+        Some("(".to_string())
+    );
+
+    signature_help_test_snapshot!(
+        plugin_function_signature_help_test_1,
+        "src/test_data/signature_help/plugin_function_signature_help/plugin_function_signature_help.k",
+        4,
+        12,
+        Some(",".to_string())
+    );
 }
@@ -105,6 +105,9 @@ pub(crate) struct LanguageServerState {
     /// Process files that are not in any defined workspace and delete the workspace when closing the file
     pub temporary_workspace: Arc<RwLock<HashMap<FileId, Option<WorkSpaceKind>>>>,
     pub workspace_folders: Option<Vec<WorkspaceFolder>>,
+    /// The plugin declaration paths in the `pluginPaths` initialization option,
+    /// which are used to type the `kcl_plugin.*` calls.
+    pub plugin_paths: Vec<String>,
     /// Actively monitor file system changes. These changes will not be notified through lsp,
     /// e.g., execute `kcl mod add xxx`, `kcl fmt xxx`
     pub fs_event_watcher: Option<
@@ -190,6 +193,12 @@ impl LanguageServerState {
             workspace_config_cache: KCLWorkSpaceConfigCache::default(),
             temporary_workspace: Arc::new(RwLock::new(HashMap::new())),
             workspace_folders: initialize_params.workspace_folders.clone(),
+            plugin_paths: initialize_params
+                .initialization_options
+                .as_ref()
+                .and_then(|options| options.get("pluginPaths"))
+                .and_then(|paths| serde_json::from_value(paths.clone()).ok())
+                .unwrap_or_default(),
             fs_event_watcher,
         };
 
@@ -624,6 +633,16 @@ impl LanguageServerState {
             None => None,
         };
 
+        let mut opts = opts;
+        if !self.plugin_paths.is_empty() {
+            let load_opts = opts.1.get_or_insert_with(Default::default);
+            for path in &self.plugin_paths {
+                if !load_opts.plugin_paths.contains(path) {
+                    load_opts.plugin_paths.push(path.clone());
+                }
+            }
+        }
+
         let mut workspace_config_cache = self.workspace_config_cache.write();
         workspace_config_cache.insert(workspace.clone(), opts.clone());
         drop(workspace_config_cache);
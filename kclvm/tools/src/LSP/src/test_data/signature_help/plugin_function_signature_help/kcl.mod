@@ -0,0 +1,7 @@
+[package]
+name = "plugin"
+edition = "0.0.1"
+version = "0.0.1"
+
+[profile]
+plugin_paths = ["plugins"]
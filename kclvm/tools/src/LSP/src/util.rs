@@ -1,6 +1,6 @@
 use kclvm_ast::ast::{
-    ConfigEntry, Expr, Identifier, MemberOrIndex, Node, NodeRef, PosTuple, Program, SchemaStmt,
-    Stmt, Type,
+    ConfigEntry, Expr, Identifier, MemberOrIndex, Node, NodeRef, Pattern, PosTuple, Program,
+    SchemaStmt, Stmt, Type,
 };
 use kclvm_ast::node_ref;
 use kclvm_ast::pos::ContainsPos;
@@ -341,7 +341,13 @@ pub(crate) fn inner_most_expr_in_stmt(
             }
             (None, schema_def)
         }
-        Stmt::Import(_) => (None, schema_def),
+        Stmt::Enum(enum_stmt) => {
+            for member in &enum_stmt.members {
+                walk_option_if_contains!(member.node.value, pos, schema_def);
+            }
+            (None, schema_def)
+        }
+        Stmt::Import(_) | Stmt::Export(_) => (None, schema_def),
     }
 }
 
@@ -495,6 +501,17 @@ pub(crate) fn inner_most_expr(
 
             (Some(expr.clone()), schema_def)
         }
+        Expr::Match(match_expr) => {
+            walk_if_contains!(match_expr.subject, pos, schema_def);
+            for case in &match_expr.cases {
+                if case.node.pattern.contains_pos(pos) {
+                    return inner_most_expr_in_pattern(&case.node.pattern, pos, schema_def);
+                }
+                walk_option_if_contains!(case.node.guard, pos, schema_def);
+                walk_if_contains!(case.node.body, pos, schema_def);
+            }
+            (Some(expr.clone()), schema_def)
+        }
         Expr::Subscript(subscript_expr) => {
             walk_if_contains!(subscript_expr.value, pos, schema_def);
             walk_option_if_contains!(subscript_expr.index, pos, schema_def);
@@ -548,6 +565,62 @@ pub(crate) fn inner_most_expr(
     }
 }
 
+fn inner_most_expr_in_pattern(
+    pattern: &Node<Pattern>,
+    pos: &KCLPos,
+    schema_def: Option<Node<Expr>>,
+) -> (Option<Node<Expr>>, Option<Node<Expr>>) {
+    match &pattern.node {
+        Pattern::Wildcard => {}
+        Pattern::Value(value) => walk_if_contains!(value, pos, schema_def),
+        Pattern::Capture(name) => {
+            walk_if_contains_with_new_expr!(name, pos, schema_def, Expr::Identifier)
+        }
+        Pattern::Schema(schema_pattern) => {
+            walk_if_contains_with_new_expr!(schema_pattern.name, pos, schema_def, Expr::Identifier);
+            for entry in &schema_pattern.entries {
+                if entry.node.value.contains_pos(pos) {
+                    return inner_most_expr_in_pattern(&entry.node.value, pos, schema_def);
+                }
+            }
+        }
+        Pattern::List(list_pattern) => {
+            for elt in &list_pattern.elts {
+                if elt.contains_pos(pos) {
+                    return inner_most_expr_in_pattern(elt, pos, schema_def);
+                }
+            }
+            if let Some(rest) = &list_pattern.rest {
+                walk_if_contains_with_new_expr!(rest, pos, schema_def, Expr::Identifier);
+            }
+        }
+        Pattern::Dict(dict_pattern) => {
+            for entry in &dict_pattern.entries {
+                if entry.node.value.contains_pos(pos) {
+                    return inner_most_expr_in_pattern(&entry.node.value, pos, schema_def);
+                }
+            }
+            if let Some(rest) = &dict_pattern.rest {
+                walk_if_contains_with_new_expr!(rest, pos, schema_def, Expr::Identifier);
+            }
+        }
+        Pattern::Or(patterns) => {
+            for pattern in patterns {
+                if pattern.contains_pos(pos) {
+                    return inner_most_expr_in_pattern(pattern, pos, schema_def);
+                }
+            }
+        }
+        Pattern::As(as_pattern) => {
+            if as_pattern.pattern.contains_pos(pos) {
+                return inner_most_expr_in_pattern(&as_pattern.pattern, pos, schema_def);
+            }
+            walk_if_contains_with_new_expr!(as_pattern.name, pos, schema_def, Expr::Identifier);
+        }
+    }
+    (None, schema_def)
+}
+
 fn inner_most_expr_in_config_entry(
     config_entry: &Node<ConfigEntry>,
     pos: &KCLPos,
@@ -631,7 +704,7 @@ fn build_identifier_from_ty_string(ty: &NodeRef<Type>, pos: &KCLPos) -> Option<N
                 ty.end_column,
             ),
         )),
-        Type::Basic(_) => None,
+        Type::Basic(_) | Type::Refined(_) => None,
         Type::List(list_ty) => {
             if let Some(inner) = &list_ty.inner_type {
                 if inner.contains_pos(pos) {
@@ -663,5 +736,22 @@ fn build_identifier_from_ty_string(ty: &NodeRef<Type>, pos: &KCLPos) -> Option<N
         }
         Type::Literal(_) => None,
         Type::Function(_) => None,
+        Type::Generic(generic_ty) => {
+            for ty in &generic_ty.type_args {
+                if ty.contains_pos(pos) {
+                    return build_identifier_from_ty_string(ty, pos);
+                }
+            }
+            Some(Node::node_with_pos(
+                Expr::Identifier(generic_ty.name.clone()),
+                (
+                    ty.filename.clone(),
+                    ty.line,
+                    ty.column,
+                    ty.end_line,
+                    ty.end_column,
+                ),
+            ))
+        }
     }
 }